@@ -144,6 +144,31 @@ impl Client for HttpClient {
         })
     }
 
+    fn cancel_action(&self, id: &Uuid, span: Option<SpanContext>) -> Result<()> {
+        let endpoint = self.endpoint(format!("/api/unstable/actions/cancel/{}", id));
+        let request = self.client.post(&endpoint);
+        let span = match (self.tracer.as_ref(), span) {
+            (Some(tracer), Some(parent)) => {
+                let options = StartOptions::default().child_of(parent);
+                let mut span = tracer
+                    .span_with_options("agent.client.http.actions.cancel", options)
+                    .auto_finish();
+                span.tag("action.id", id.to_string());
+                Some(span)
+            }
+            _ => None,
+        };
+        let context = span.as_ref().map(|span| span.context().clone());
+        // To ignore the response from the agent we need to pass a catch all type
+        // or the operation will fail trying to JSON-decode the response into the unit type.
+        self.perform::<serde_json::Value>(request, context)
+            .map_err(|error| match span {
+                None => error,
+                Some(mut span) => fail_span(error, span.as_mut()),
+            })?;
+        Ok(())
+    }
+
     fn datastore_info(&self, span: Option<SpanContext>) -> Result<DatastoreInfo> {
         let endpoint = self.endpoint("/api/unstable/info/datastore");
         let request = self.client.get(&endpoint);
@@ -383,6 +408,8 @@ mod tests {
     use slog::Logger;
 
     use replicante_models_core::scope::Namespace;
+    use replicante_models_core::scope::NsActions;
+    use replicante_models_core::scope::NsHealth;
     use replicante_models_core::scope::NsHttpsTransport;
 
     use super::HttpClient;
@@ -391,6 +418,8 @@ mod tests {
     fn enpoint_concat() {
         let ns = Namespace {
             ns_id: "test".into(),
+            actions: NsActions::default(),
+            health: NsHealth::default(),
             https_transport: NsHttpsTransport::default(),
         };
         let logger = Logger::root(Discard, o!());
@@ -409,6 +438,8 @@ mod tests {
     fn enpoint_trim_root() {
         let ns = Namespace {
             ns_id: "test".into(),
+            actions: NsActions::default(),
+            health: NsHealth::default(),
             https_transport: NsHttpsTransport::default(),
         };
         let logger = Logger::root(Discard, o!());
@@ -427,6 +458,8 @@ mod tests {
     fn enpoint_trim_path_prefix() {
         let ns = Namespace {
             ns_id: "test".into(),
+            actions: NsActions::default(),
+            health: NsHealth::default(),
             https_transport: NsHttpsTransport::default(),
         };
         let logger = Logger::root(Discard, o!());
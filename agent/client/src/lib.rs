@@ -13,6 +13,7 @@ use replicante_models_agent::info::Shards;
 mod error;
 mod http;
 mod metrics;
+mod pool;
 
 #[cfg(any(test, feature = "with_test_support"))]
 pub mod mock;
@@ -22,6 +23,7 @@ pub use self::error::ErrorKind;
 pub use self::error::Result;
 pub use self::http::HttpClient;
 pub use self::metrics::register_metrics;
+pub use self::pool::ClientPool;
 
 /// Interface to interact with (remote) agents.
 ///
@@ -40,6 +42,9 @@ pub trait Client {
     /// Return general agent information.
     fn agent_info(&self, span: Option<SpanContext>) -> Result<AgentInfo>;
 
+    /// Request the agent to cancel an action.
+    fn cancel_action(&self, id: &Uuid, span: Option<SpanContext>) -> Result<()>;
+
     /// Return general datastore information.
     fn datastore_info(&self, span: Option<SpanContext>) -> Result<DatastoreInfo>;
 
@@ -1,4 +1,5 @@
 use lazy_static::lazy_static;
+use prometheus::Counter;
 use prometheus::CounterVec;
 use prometheus::HistogramOpts;
 use prometheus::HistogramVec;
@@ -40,6 +41,16 @@ lazy_static! {
         &["endpoint"]
     )
     .expect("Failed to create CLIENT_OPS_DURATION histogram");
+    pub static ref CLIENT_POOL_BUILDS: Counter = Counter::new(
+        "replicore_agentclient_pool_builds",
+        "Number of agent clients built (or rebuilt) by the client pool"
+    )
+    .expect("Failed to create CLIENT_POOL_BUILDS counter");
+    pub static ref CLIENT_POOL_EVICTIONS: Counter = Counter::new(
+        "replicore_agentclient_pool_evictions",
+        "Number of idle agent clients evicted from the client pool"
+    )
+    .expect("Failed to create CLIENT_POOL_EVICTIONS counter");
     pub static ref CLIENT_TIMEOUT: CounterVec = CounterVec::new(
         Opts::new(
             "replicore_agentclient_timeout",
@@ -68,6 +79,12 @@ pub fn register_metrics(logger: &Logger, registry: &Registry) {
     if let Err(error) = registry.register(Box::new(CLIENT_OPS_DURATION.clone())) {
         debug!(logger, "Failed to register CLIENT_OPS_DURATION"; "error" => ?error);
     }
+    if let Err(error) = registry.register(Box::new(CLIENT_POOL_BUILDS.clone())) {
+        debug!(logger, "Failed to register CLIENT_POOL_BUILDS"; "error" => ?error);
+    }
+    if let Err(error) = registry.register(Box::new(CLIENT_POOL_EVICTIONS.clone())) {
+        debug!(logger, "Failed to register CLIENT_POOL_EVICTIONS"; "error" => ?error);
+    }
     if let Err(error) = registry.register(Box::new(CLIENT_TIMEOUT.clone())) {
         debug!(logger, "Failed to register CLIENT_TIMEOUT"; "error" => ?error);
     }
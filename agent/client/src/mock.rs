@@ -57,6 +57,11 @@ where
         (self.agent_info)()
     }
 
+    fn cancel_action(&self, _: &Uuid, _: Option<SpanContext>) -> Result<()> {
+        // To be implemented when needed.
+        Ok(())
+    }
+
     fn datastore_info(&self, _: Option<SpanContext>) -> Result<DatastoreInfo> {
         (self.datastore_info)()
     }
@@ -0,0 +1,241 @@
+use std::collections::HashMap;
+use std::fs;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+use std::time::Instant;
+use std::time::SystemTime;
+
+use opentracingrust::Tracer;
+use slog::debug;
+use slog::Logger;
+
+use replicante_models_core::scope::Namespace;
+use replicante_models_core::scope::NsHttpsTransport;
+
+use super::metrics::CLIENT_POOL_BUILDS;
+use super::metrics::CLIENT_POOL_EVICTIONS;
+use super::HttpClient;
+use super::Result;
+
+/// Identify a pooled client by agent and transport settings.
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+struct PoolKey {
+    ns_id: String,
+    target: String,
+    transport: NsHttpsTransport,
+}
+
+impl PoolKey {
+    fn new(ns: &Namespace, target: &str) -> PoolKey {
+        PoolKey {
+            ns_id: ns.ns_id.clone(),
+            target: target.to_string(),
+            transport: ns.https_transport.clone(),
+        }
+    }
+
+    /// Modification times of the files the transport settings refer to.
+    fn files_version(&self) -> Vec<Option<SystemTime>> {
+        vec![
+            modified(self.transport.ca_bundle.as_ref()),
+            modified(self.transport.client_key_id.as_ref()),
+        ]
+    }
+}
+
+/// A pooled client along with information to reload and evict it.
+struct PoolEntry {
+    client: Arc<HttpClient>,
+    files_version: Vec<Option<SystemTime>>,
+    last_used: Instant,
+}
+
+/// Shared cache of `HttpClient`s to reuse across cluster refreshes.
+///
+/// Building an `HttpClient` loads certificates and keys from disk and sets up TLS.
+/// The pool keeps clients for each agent and namespace transport settings so this
+/// cost is paid only once for each agent:
+///
+///   * Clients not used for longer than the idle timeout are evicted.
+///   * Clients are rebuilt when the certificate or key files change on disk.
+#[derive(Clone)]
+pub struct ClientPool {
+    clients: Arc<Mutex<HashMap<PoolKey, PoolEntry>>>,
+    idle_timeout: Duration,
+    last_eviction: Arc<Mutex<Instant>>,
+    logger: Logger,
+    timeout: Duration,
+    tracer: Option<Arc<Tracer>>,
+}
+
+impl ClientPool {
+    pub fn new<T>(logger: Logger, timeout: Duration, idle_timeout: Duration, tracer: T) -> Self
+    where
+        T: Into<Option<Arc<Tracer>>>,
+    {
+        ClientPool {
+            clients: Arc::new(Mutex::new(HashMap::new())),
+            idle_timeout,
+            last_eviction: Arc::new(Mutex::new(Instant::now())),
+            logger,
+            timeout,
+            tracer: tracer.into(),
+        }
+    }
+
+    /// Get a client for the agent at `target`, creating it if needed.
+    pub fn get(&self, ns: &Namespace, target: &str) -> Result<Arc<HttpClient>> {
+        self.evict_idle();
+        let key = PoolKey::new(ns, target);
+        let files_version = key.files_version();
+        {
+            let mut clients = self.clients.lock().expect("ClientPool lock poisoned");
+            if let Some(entry) = clients.get_mut(&key) {
+                if entry.files_version == files_version {
+                    entry.last_used = Instant::now();
+                    return Ok(Arc::clone(&entry.client));
+                }
+                debug!(
+                    self.logger,
+                    "Transport files changed, reloading agent client";
+                    "namespace" => &key.ns_id,
+                    "target" => &key.target,
+                );
+            }
+        }
+
+        // Build clients without holding the lock as it involves disk I/O.
+        let client = HttpClient::new(
+            ns,
+            target,
+            self.timeout,
+            self.logger.clone(),
+            self.tracer.clone(),
+        )?;
+        let client = Arc::new(client);
+        CLIENT_POOL_BUILDS.inc();
+        let entry = PoolEntry {
+            client: Arc::clone(&client),
+            files_version,
+            last_used: Instant::now(),
+        };
+        self.clients
+            .lock()
+            .expect("ClientPool lock poisoned")
+            .insert(key, entry);
+        Ok(client)
+    }
+
+    /// Number of clients currently in the pool.
+    pub fn len(&self) -> usize {
+        self.clients.lock().expect("ClientPool lock poisoned").len()
+    }
+
+    /// Check if the pool has no clients.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl ClientPool {
+    /// Remove clients that have not been used for longer than the idle timeout.
+    ///
+    /// To avoid scanning the pool on every request, eviction runs at most
+    /// once every idle timeout.
+    fn evict_idle(&self) {
+        {
+            let mut last_eviction = self
+                .last_eviction
+                .lock()
+                .expect("ClientPool eviction lock poisoned");
+            if last_eviction.elapsed() < self.idle_timeout {
+                return;
+            }
+            *last_eviction = Instant::now();
+        }
+        let idle_timeout = self.idle_timeout;
+        let mut clients = self.clients.lock().expect("ClientPool lock poisoned");
+        let before = clients.len();
+        clients.retain(|_, entry| entry.last_used.elapsed() < idle_timeout);
+        let evicted = before - clients.len();
+        if evicted > 0 {
+            CLIENT_POOL_EVICTIONS.inc_by(evicted as f64);
+            debug!(self.logger, "Evicted idle agent clients"; "count" => evicted);
+        }
+    }
+}
+
+/// Modification time of an optional file, if it can be determined.
+fn modified(path: Option<&String>) -> Option<SystemTime> {
+    path.and_then(|path| fs::metadata(path).ok())
+        .and_then(|metadata| metadata.modified().ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use slog::o;
+    use slog::Discard;
+    use slog::Logger;
+
+    use replicante_models_core::scope::Namespace;
+    use replicante_models_core::scope::NsActions;
+    use replicante_models_core::scope::NsHealth;
+    use replicante_models_core::scope::NsHttpsTransport;
+
+    use super::ClientPool;
+
+    fn namespace(ns_id: &str) -> Namespace {
+        Namespace {
+            ns_id: ns_id.into(),
+            actions: NsActions::default(),
+            health: NsHealth::default(),
+            https_transport: NsHttpsTransport::default(),
+        }
+    }
+
+    fn pool(idle_timeout: Duration) -> ClientPool {
+        let logger = Logger::root(Discard, o!());
+        ClientPool::new(logger, Duration::from_secs(15), idle_timeout, None)
+    }
+
+    #[test]
+    fn reuse_clients() {
+        let pool = pool(Duration::from_secs(300));
+        let ns = namespace("test");
+        let first = pool.get(&ns, "http://host:port").unwrap();
+        let second = pool.get(&ns, "http://host:port").unwrap();
+        assert!(Arc::ptr_eq(&first, &second));
+        assert_eq!(pool.len(), 1);
+    }
+
+    #[test]
+    fn clients_by_namespace() {
+        let pool = pool(Duration::from_secs(300));
+        let first = pool.get(&namespace("ns1"), "http://host:port").unwrap();
+        let second = pool.get(&namespace("ns2"), "http://host:port").unwrap();
+        assert!(!Arc::ptr_eq(&first, &second));
+        assert_eq!(pool.len(), 2);
+    }
+
+    #[test]
+    fn clients_by_target() {
+        let pool = pool(Duration::from_secs(300));
+        let ns = namespace("test");
+        pool.get(&ns, "http://host1:port").unwrap();
+        pool.get(&ns, "http://host2:port").unwrap();
+        assert_eq!(pool.len(), 2);
+    }
+
+    #[test]
+    fn evict_idle_clients() {
+        let pool = pool(Duration::from_millis(0));
+        let ns = namespace("test");
+        pool.get(&ns, "http://host1:port").unwrap();
+        pool.get(&ns, "http://host2:port").unwrap();
+        assert_eq!(pool.len(), 1);
+    }
+}
@@ -1,3 +1,4 @@
+use chrono::Duration;
 use chrono::Utc;
 use failure::ResultExt;
 use serde_json::Value;
@@ -63,6 +64,15 @@ pub fn replicante_io_v0(args: ApplierArgs) -> Result<Value> {
             "Action approval attribute must be a string",
         ),
     }
+    match object.metadata.get("timeout") {
+        None => (),
+        Some(timeout) if timeout.as_i64().map(|t| t > 0).unwrap_or(false) => (),
+        Some(_) => errors.collect(
+            "InvalidAttribute",
+            "metadata.timeout",
+            "Action timeout must be a positive number of seconds",
+        ),
+    }
     match object.attributes.get("spec") {
         Some(spec) if spec.is_object() => match spec.get("action") {
             Some(kind) if kind.is_string() => (),
@@ -132,11 +142,21 @@ pub fn replicante_io_v0(args: ApplierArgs) -> Result<Value> {
     };
 
     let now = Utc::now();
+    let deadline_ts = object
+        .metadata
+        .get("timeout")
+        .map(|timeout| {
+            timeout
+                .as_i64()
+                .expect("validation should have caught this")
+        })
+        .map(|timeout| now + Duration::seconds(timeout));
     let action = Action {
         action_id: Uuid::new_v4(),
         args: action_args,
         cluster_id: cluster.to_string(),
         created_ts: now,
+        deadline_ts,
         finished_ts: None,
         headers: args.headers,
         kind: kind.to_string(),
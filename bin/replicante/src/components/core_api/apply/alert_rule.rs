@@ -0,0 +1,61 @@
+use failure::ResultExt;
+use serde_json::Value;
+
+use replicante_models_core::alerts::AlertRule;
+use replicante_models_core::api::apply::SCOPE_NS;
+use replicante_models_core::api::objects::AlertRule as AlertRuleObject;
+use replicante_models_core::events::Event;
+use replicante_stream_events::EmitMessage;
+
+use super::appliers::ApplierArgs;
+use super::validate;
+use crate::ErrorKind;
+use crate::Result;
+
+/// Validate an AlertRule object and add it to the DB.
+pub fn replicante_io_v0(args: ApplierArgs) -> Result<Value> {
+    // Valiate request.
+    let object = &args.object;
+    validate::alert_rule(object)?;
+
+    // Convert ApplierArgs into a usable structures.
+    let ns = object
+        .metadata
+        .get(SCOPE_NS)
+        .expect("validation should have caught this")
+        .as_str()
+        .expect("validation should have caught this")
+        .to_string();
+    let name = object
+        .metadata
+        .get("name")
+        .expect("validation should have caught this")
+        .as_str()
+        .expect("validation should have caught this")
+        .to_string();
+    let rule = object
+        .attributes
+        .get("spec")
+        .expect("validation should have caught this")
+        .clone();
+    let rule: AlertRuleObject =
+        serde_json::from_value(rule).expect("validation should have caught this");
+    let rule = AlertRule::from_object(ns, name, rule);
+
+    // Emit the apply event and persist the rule to the DB.
+    let span = args.span.map(|span| span.context().clone());
+    let event = Event::builder().alert().rule_apply(rule.clone());
+    let code = event.code();
+    let stream_key = event.stream_key();
+    let event = EmitMessage::with(stream_key, event)
+        .with_context(|_| ErrorKind::EventsStreamEmit(code))?
+        .trace(span.clone());
+    args.events
+        .emit(event)
+        .with_context(|_| ErrorKind::EventsStreamEmit(code))?;
+    args.store
+        .persist()
+        .alert_rule(rule, span)
+        .with_context(|_| ErrorKind::PrimaryStorePersist("AlertRule"))?;
+    Ok(serde_json::json!(null))
+}
@@ -8,12 +8,18 @@ use replicante_store_primary::store::Store;
 use replicante_stream_events::Stream;
 
 use super::agent_action;
+use super::alert_rule;
+use super::cluster_settings;
 use super::discovery_settings;
+use super::namespace;
 use crate::Result;
 
 const APIV_REPLI_V0: &str = "replicante.io/v0";
 const KIND_AGENT_ACTION: &str = "AgentAction";
+const KIND_ALERT_RULE: &str = "AlertRule";
+const KIND_CLUSTER_SETTINGS: &str = "ClusterSettings";
 const KIND_DISCOVERY_SETTING: &str = "DiscoverySettings";
+const KIND_NAMESPACE: &str = "Namespace";
 
 /// Type of closure that handles a specific `kind` for a specific `apiVersion`.
 pub type Applier = Box<dyn Fn(ApplierArgs) -> Result<Value>>;
@@ -33,9 +39,14 @@ pub fn find(object: &ApplyObject) -> Option<Applier> {
     let kind = object.kind.as_str();
     match (api_version, kind) {
         (APIV_REPLI_V0, KIND_AGENT_ACTION) => Some(Box::new(agent_action::replicante_io_v0)),
+        (APIV_REPLI_V0, KIND_ALERT_RULE) => Some(Box::new(alert_rule::replicante_io_v0)),
+        (APIV_REPLI_V0, KIND_CLUSTER_SETTINGS) => {
+            Some(Box::new(cluster_settings::replicante_io_v0))
+        }
         (APIV_REPLI_V0, KIND_DISCOVERY_SETTING) => {
             Some(Box::new(discovery_settings::replicante_io_v0))
         }
+        (APIV_REPLI_V0, KIND_NAMESPACE) => Some(Box::new(namespace::replicante_io_v0)),
         _ => None,
     }
 }
@@ -0,0 +1,62 @@
+use failure::ResultExt;
+use serde_json::Value;
+
+use replicante_models_core::api::apply::SCOPE_CLUSTER;
+use replicante_models_core::api::apply::SCOPE_NS;
+use replicante_models_core::api::objects::ClusterSettings as ClusterSettingsObject;
+use replicante_models_core::cluster::ClusterSettings;
+use replicante_models_core::events::Event;
+use replicante_stream_events::EmitMessage;
+
+use super::appliers::ApplierArgs;
+use super::validate;
+use crate::ErrorKind;
+use crate::Result;
+
+/// Validate a ClusterSettings object and add it to the DB.
+pub fn replicante_io_v0(args: ApplierArgs) -> Result<Value> {
+    // Valiate request.
+    let object = &args.object;
+    validate::cluster_settings(object)?;
+
+    // Convert ApplierArgs into a usable structures.
+    let ns = object
+        .metadata
+        .get(SCOPE_NS)
+        .expect("validation should have caught this")
+        .as_str()
+        .expect("validation should have caught this")
+        .to_string();
+    let cluster_id = object
+        .metadata
+        .get(SCOPE_CLUSTER)
+        .expect("validation should have caught this")
+        .as_str()
+        .expect("validation should have caught this")
+        .to_string();
+    let settings = object
+        .attributes
+        .get("spec")
+        .expect("validation should have caught this")
+        .clone();
+    let settings: ClusterSettingsObject =
+        serde_json::from_value(settings).expect("validation should have caught this");
+
+    // Persist the settings to the DB and emit relevant events.
+    let settings = ClusterSettings::from_object(ns, cluster_id, settings);
+    let span = args.span.map(|span| span.context().clone());
+    let event = Event::builder().cluster().apply_settings(settings.clone());
+    let code = event.code();
+    let stream_key = event.stream_key();
+    let event = EmitMessage::with(stream_key, event)
+        .with_context(|_| ErrorKind::EventsStreamEmit(code))?
+        .trace(span.clone());
+    args.events
+        .emit(event)
+        .with_context(|_| ErrorKind::EventsStreamEmit("ClusterSettings"))?;
+    args.store
+        .persist()
+        .cluster_settings(settings, span)
+        .with_context(|_| ErrorKind::PrimaryStorePersist("ClusterSettings"))?;
+    Ok(serde_json::json!(null))
+}
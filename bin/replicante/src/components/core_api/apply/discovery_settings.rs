@@ -40,12 +40,20 @@ pub fn replicante_io_v0(args: ApplierArgs) -> Result<Value> {
         Some(spec) => {
             let spec: std::result::Result<DiscoverySettingsObject, _> =
                 serde_json::from_value(spec.clone());
-            if let Err(error) = spec {
-                errors.collect(
+            match spec {
+                Err(error) => errors.collect(
                     "InvalidAttribute",
                     "spec",
                     format!("Invalid specification: {}", error),
-                );
+                ),
+                Ok(spec) => match spec.grace_period {
+                    Some(grace_period) if grace_period <= 0 => errors.collect(
+                        "InvalidAttribute",
+                        "spec.grace_period",
+                        "The removal grace period must be a positive number of seconds",
+                    ),
+                    _ => (),
+                },
             }
         }
     }
@@ -75,11 +83,16 @@ pub fn replicante_io_v0(args: ApplierArgs) -> Result<Value> {
         serde_json::from_value(settings).expect("validation should have caught this");
 
     // Persist the settings to the DB and emit relevant events.
-    let settings = DiscoverySettings::from_object(ns, name, settings);
     let span = args.span.map(|span| span.context().clone());
+    let current = args
+        .store
+        .discovery_settings(ns.clone())
+        .get(&name, span.clone())
+        .with_context(|_| ErrorKind::PrimaryStoreQuery("DiscoverySettings"))?;
+    let settings = DiscoverySettings::from_object(ns, name, settings).revise(current.as_ref());
     let event = Event::builder()
         .discovery_settings()
-        .apply(settings.clone());
+        .apply(current, settings.clone());
     let code = event.code();
     let stream_key = event.stream_key();
     let event = EmitMessage::with(stream_key, event)
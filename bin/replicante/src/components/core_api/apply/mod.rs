@@ -23,9 +23,12 @@ use crate::interfaces::Interfaces;
 use crate::Result;
 
 mod agent_action;
+mod alert_rule;
 mod appliers;
+mod cluster_settings;
 mod discovery_settings;
 mod metrics;
+mod namespace;
 mod validate;
 
 pub use metrics::register_metrics;
@@ -0,0 +1,53 @@
+use failure::ResultExt;
+use serde_json::Value;
+
+use replicante_models_core::api::objects::Namespace as NamespaceObject;
+use replicante_models_core::events::Event;
+use replicante_models_core::scope::Namespace;
+use replicante_stream_events::EmitMessage;
+
+use super::appliers::ApplierArgs;
+use super::validate;
+use crate::ErrorKind;
+use crate::Result;
+
+/// Validate a Namespace object and add it to the DB.
+pub fn replicante_io_v0(args: ApplierArgs) -> Result<Value> {
+    // Valiate request.
+    let object = &args.object;
+    validate::namespace(object)?;
+
+    // Convert ApplierArgs into a usable structures.
+    let ns_id = object
+        .metadata
+        .get("name")
+        .expect("validation should have caught this")
+        .as_str()
+        .expect("validation should have caught this")
+        .to_string();
+    let namespace = object
+        .attributes
+        .get("spec")
+        .expect("validation should have caught this")
+        .clone();
+    let namespace: NamespaceObject =
+        serde_json::from_value(namespace).expect("validation should have caught this");
+
+    // Persist the namespace to the DB and emit relevant events.
+    let namespace = Namespace::from_object(ns_id, namespace);
+    let span = args.span.map(|span| span.context().clone());
+    let event = Event::builder().namespace().apply(namespace.clone());
+    let code = event.code();
+    let stream_key = event.stream_key();
+    let event = EmitMessage::with(stream_key, event)
+        .with_context(|_| ErrorKind::EventsStreamEmit(code))?
+        .trace(span.clone());
+    args.events
+        .emit(event)
+        .with_context(|_| ErrorKind::EventsStreamEmit("Namespace"))?;
+    args.store
+        .persist()
+        .namespace(namespace, span)
+        .with_context(|_| ErrorKind::PrimaryStorePersist("Namespace"))?;
+    Ok(serde_json::json!(null))
+}
@@ -1,6 +1,13 @@
 use serde_json::Value;
 
+use replicante_models_core::alerts::AlertCondition;
 use replicante_models_core::api::apply::ApplyObject;
+use replicante_models_core::api::apply::SCOPE_CLUSTER;
+use replicante_models_core::api::apply::SCOPE_NS;
+use replicante_models_core::api::objects::AlertRule as AlertRuleObject;
+use replicante_models_core::api::objects::ClusterSettings as ClusterSettingsObject;
+use replicante_models_core::api::objects::Namespace as NamespaceObject;
+use replicante_models_core::api::validate::ErrorsCollection;
 
 use crate::Error;
 use crate::ErrorKind;
@@ -12,3 +19,302 @@ pub fn required_attributes(object: Value) -> Result<ApplyObject> {
         .map_err(ErrorKind::ValidateFailed)
         .map_err(Error::from)
 }
+
+/// Validate a `replicante.io/v0` `AlertRule` object.
+pub fn alert_rule(object: &ApplyObject) -> Result<()> {
+    let mut errors = ErrorsCollection::new();
+    match object.metadata.get(SCOPE_NS) {
+        None => errors.collect(
+            "MissingAttribute",
+            format!("metadata.{}", SCOPE_NS),
+            "A namespace id must be attached to the request",
+        ),
+        Some(ns) if !ns.is_string() => errors.collect(
+            "TypeError",
+            format!("metadata.{}", SCOPE_NS),
+            format!("metadata.{} must be a string", SCOPE_NS),
+        ),
+        Some(_) => (),
+    }
+    match object.metadata.get("name") {
+        None => errors.collect(
+            "MissingAttribute",
+            "metadata.name",
+            "An AlertRule name must be attached to the request",
+        ),
+        Some(name) if !name.is_string() => errors.collect(
+            "TypeError",
+            "metadata.name",
+            "metadata.name must be a string",
+        ),
+        Some(_) => (),
+    }
+    match object.attributes.get("spec") {
+        None => errors.collect(
+            "MissingAttribute",
+            "spec",
+            "An AlertRule object must have a spec definition",
+        ),
+        Some(spec) => {
+            let spec: std::result::Result<AlertRuleObject, _> =
+                serde_json::from_value(spec.clone());
+            match spec {
+                Err(error) => errors.collect(
+                    "InvalidAttribute",
+                    "spec",
+                    format!("Invalid specification: {}", error),
+                ),
+                Ok(spec) => alert_condition(&spec.condition, &mut errors),
+            }
+        }
+    }
+    errors.into_result(ErrorKind::ValidateFailed)?;
+    Ok(())
+}
+
+/// Validate `AlertRule` condition specific attributes.
+fn alert_condition(condition: &AlertCondition, errors: &mut ErrorsCollection) {
+    match condition {
+        AlertCondition::ActionFailed { kind } if kind.is_empty() => errors.collect(
+            "InvalidAttribute",
+            "spec.condition.kind",
+            "The action kind to alert on must not be empty",
+        ),
+        AlertCondition::EventCount { event, window, .. } => {
+            if event.is_empty() {
+                errors.collect(
+                    "InvalidAttribute",
+                    "spec.condition.event",
+                    "The event code to count must not be empty",
+                );
+            }
+            if *window <= 0 {
+                errors.collect(
+                    "InvalidAttribute",
+                    "spec.condition.window",
+                    "The events window must be a positive number of seconds",
+                );
+            }
+        }
+        _ => (),
+    }
+}
+
+/// Validate a `replicante.io/v0` `ClusterSettings` object.
+pub fn cluster_settings(object: &ApplyObject) -> Result<()> {
+    let mut errors = ErrorsCollection::new();
+    match object.metadata.get(SCOPE_NS) {
+        None => errors.collect(
+            "MissingAttribute",
+            format!("metadata.{}", SCOPE_NS),
+            "A namespace id must be attached to the request",
+        ),
+        Some(ns) if !ns.is_string() => errors.collect(
+            "TypeError",
+            format!("metadata.{}", SCOPE_NS),
+            format!("metadata.{} must be a string", SCOPE_NS),
+        ),
+        Some(_) => (),
+    }
+    match object.metadata.get(SCOPE_CLUSTER) {
+        None => errors.collect(
+            "MissingAttribute",
+            format!("metadata.{}", SCOPE_CLUSTER),
+            "A cluster id must be attached to the request",
+        ),
+        Some(cluster) if !cluster.is_string() => errors.collect(
+            "TypeError",
+            format!("metadata.{}", SCOPE_CLUSTER),
+            format!("metadata.{} must be a string", SCOPE_CLUSTER),
+        ),
+        Some(_) => (),
+    }
+    match object.attributes.get("spec") {
+        None => errors.collect(
+            "MissingAttribute",
+            "spec",
+            "A ClusterSettings object must have a spec definition",
+        ),
+        Some(spec) => {
+            let spec: std::result::Result<ClusterSettingsObject, _> =
+                serde_json::from_value(spec.clone());
+            match spec {
+                Err(error) => errors.collect(
+                    "InvalidAttribute",
+                    "spec",
+                    format!("Invalid specification: {}", error),
+                ),
+                Ok(spec) => match spec.interval {
+                    Some(interval) if interval <= 0 => errors.collect(
+                        "InvalidAttribute",
+                        "spec.interval",
+                        "The orchestration interval must be a positive number of seconds",
+                    ),
+                    _ => (),
+                },
+            }
+        }
+    }
+    errors.into_result(ErrorKind::ValidateFailed)?;
+    Ok(())
+}
+
+/// Validate a `replicante.io/v0` `Namespace` object.
+pub fn namespace(object: &ApplyObject) -> Result<()> {
+    let mut errors = ErrorsCollection::new();
+    match object.metadata.get("name") {
+        None => errors.collect(
+            "MissingAttribute",
+            "metadata.name",
+            "A Namespace name must be attached to the request",
+        ),
+        Some(name) if !name.is_string() => errors.collect(
+            "TypeError",
+            "metadata.name",
+            "metadata.name must be a string",
+        ),
+        Some(_) => (),
+    }
+    match object.metadata.get(SCOPE_NS) {
+        Some(ns) if !ns.is_string() => errors.collect(
+            "TypeError",
+            format!("metadata.{}", SCOPE_NS),
+            format!("metadata.{} must be a string", SCOPE_NS),
+        ),
+        Some(ns) if Some(ns) != object.metadata.get("name") => errors.collect(
+            "InvalidAttribute",
+            format!("metadata.{}", SCOPE_NS),
+            format!("metadata.{} must match the Namespace name", SCOPE_NS),
+        ),
+        _ => (),
+    }
+    match object.attributes.get("spec") {
+        None => errors.collect(
+            "MissingAttribute",
+            "spec",
+            "A Namespace object must have a spec definition",
+        ),
+        Some(spec) => {
+            let spec: std::result::Result<NamespaceObject, _> =
+                serde_json::from_value(spec.clone());
+            match spec {
+                Err(error) => errors.collect(
+                    "InvalidAttribute",
+                    "spec",
+                    format!("Invalid specification: {}", error),
+                ),
+                Ok(spec) if spec.actions.max_schedule_attempts <= 0 => errors.collect(
+                    "InvalidAttribute",
+                    "spec.actions.max_schedule_attempts",
+                    "The maximum number of schedule attempts must be a positive number",
+                ),
+                Ok(spec) if spec.actions.timeout.map(|t| t <= 0).unwrap_or(false) => errors
+                    .collect(
+                        "InvalidAttribute",
+                        "spec.actions.timeout",
+                        "The actions timeout must be a positive number of seconds",
+                    ),
+                Ok(spec) if spec.health.degraded_lag <= 0 => errors.collect(
+                    "InvalidAttribute",
+                    "spec.health.degraded_lag",
+                    "The degraded replication lag must be a positive number of seconds",
+                ),
+                Ok(_) => (),
+            }
+        }
+    }
+    errors.into_result(ErrorKind::ValidateFailed)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use replicante_models_core::api::apply::ApplyObject;
+
+    use super::alert_rule;
+    use super::cluster_settings;
+    use super::namespace;
+
+    fn alert_rule_object(metadata: serde_json::Value) -> ApplyObject {
+        let object = json!({
+            "apiVersion": "replicante.io/v0",
+            "kind": "AlertRule",
+            "metadata": metadata,
+            "spec": {"condition": {"type": "action_failed", "kind": "test.action"}},
+        });
+        serde_json::from_value(object).unwrap()
+    }
+
+    fn namespace_object(metadata: serde_json::Value) -> ApplyObject {
+        let object = json!({
+            "apiVersion": "replicante.io/v0",
+            "kind": "Namespace",
+            "metadata": metadata,
+            "spec": {"actions": {"max_schedule_attempts": 10}},
+        });
+        serde_json::from_value(object).unwrap()
+    }
+
+    // Decode objects directly to check validation does not rely on `ApplyObject::from_raw`.
+    fn settings_object(metadata: serde_json::Value) -> ApplyObject {
+        let object = json!({
+            "apiVersion": "replicante.io/v0",
+            "kind": "ClusterSettings",
+            "metadata": metadata,
+            "spec": {"enabled": true},
+        });
+        serde_json::from_value(object).unwrap()
+    }
+
+    #[test]
+    fn alert_rule_ids_must_be_strings() {
+        let object = alert_rule_object(json!({"namespace": "default", "name": 42}));
+        assert!(alert_rule(&object).is_err());
+        let object = alert_rule_object(json!({"namespace": 42, "name": "rule"}));
+        assert!(alert_rule(&object).is_err());
+    }
+
+    #[test]
+    fn alert_rule_valid() {
+        let object = alert_rule_object(json!({"namespace": "default", "name": "rule"}));
+        assert!(alert_rule(&object).is_ok());
+    }
+
+    #[test]
+    fn cluster_settings_ids_must_be_strings() {
+        let object = settings_object(json!({"namespace": "default", "cluster": 42}));
+        assert!(cluster_settings(&object).is_err());
+        let object = settings_object(json!({"namespace": 42, "cluster": "cluster1"}));
+        assert!(cluster_settings(&object).is_err());
+    }
+
+    #[test]
+    fn cluster_settings_valid() {
+        let object = settings_object(json!({"namespace": "default", "cluster": "cluster1"}));
+        assert!(cluster_settings(&object).is_ok());
+    }
+
+    #[test]
+    fn namespace_ids_must_be_strings() {
+        let object = namespace_object(json!({"name": 42}));
+        assert!(namespace(&object).is_err());
+        let object = namespace_object(json!({"name": "default", "namespace": 42}));
+        assert!(namespace(&object).is_err());
+    }
+
+    #[test]
+    fn namespace_scope_must_match_name() {
+        let object = namespace_object(json!({"name": "default", "namespace": "other"}));
+        assert!(namespace(&object).is_err());
+        let object = namespace_object(json!({"name": "default", "namespace": "default"}));
+        assert!(namespace(&object).is_ok());
+    }
+
+    #[test]
+    fn namespace_valid() {
+        let object = namespace_object(json!({"name": "default"}));
+        assert!(namespace(&object).is_ok());
+    }
+}
@@ -0,0 +1,292 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use actix_web::dev::HttpServiceFactory;
+use actix_web::web;
+use actix_web::HttpRequest;
+use actix_web::HttpResponse;
+use actix_web::Responder;
+use failure::ResultExt;
+use opentracingrust::SpanContext;
+use serde_json::json;
+use slog::debug;
+use slog::Logger;
+use uuid::Uuid;
+
+use replicante_agent_client::Client;
+use replicante_agent_client::ClientPool;
+use replicante_models_core::actions::Action;
+use replicante_models_core::actions::ActionState;
+use replicante_models_core::events::Event;
+use replicante_models_core::scope::Namespace;
+use replicante_store_primary::store::Store;
+use replicante_stream_events::EmitMessage;
+use replicante_stream_events::Stream as EventsStream;
+use replicante_util_actixweb::with_request_span;
+use replicante_util_actixweb::TracingMiddleware;
+
+use crate::interfaces::Interfaces;
+use crate::Config;
+use crate::ErrorKind;
+use crate::Result;
+
+pub struct Cancel {
+    data: CancelData,
+    tracer: Arc<opentracingrust::Tracer>,
+}
+
+impl Cancel {
+    pub fn new(config: &Config, logger: &Logger, interfaces: &mut Interfaces) -> Cancel {
+        let clients = ClientPool::new(
+            logger.clone(),
+            Duration::from_secs(config.timeouts.agents_api),
+            Duration::from_secs(config.cluster_refresh.client_idle_timeout),
+            interfaces.tracing.tracer(),
+        );
+        let data = CancelData {
+            clients,
+            default_namespace: config.tmp_namespace_settings.clone().into(),
+            events: interfaces.streams.events.clone(),
+            logger: logger.clone(),
+            store: interfaces.stores.primary.clone(),
+        };
+        Cancel {
+            data,
+            tracer: interfaces.tracing.tracer(),
+        }
+    }
+
+    pub fn resource(&self) -> impl HttpServiceFactory {
+        let logger = self.data.logger.clone();
+        let tracer = Arc::clone(&self.tracer);
+        let tracer = TracingMiddleware::with_name(
+            logger,
+            tracer,
+            "/cluster/{cluster_id}/action/{action_id}/cancel",
+        );
+        web::resource("/action/{action_id}/cancel")
+            .data(self.data.clone())
+            .wrap(tracer)
+            .route(web::post().to(responder))
+    }
+}
+
+async fn responder(data: web::Data<CancelData>, request: HttpRequest) -> Result<impl Responder> {
+    let path = request.match_info();
+    let cluster_id = path
+        .get("cluster_id")
+        .ok_or(ErrorKind::APIRequestParameterNotFound("cluster_id"))?
+        .to_string();
+    let action_id = path
+        .get("action_id")
+        .ok_or(ErrorKind::APIRequestParameterNotFound("action_id"))?;
+    let action_id = Uuid::parse_str(action_id)
+        .with_context(|_| ErrorKind::APIRequestParameterInvalid("action_id"))?;
+
+    let mut request = request;
+    with_request_span(&mut request, |span| -> Result<()> {
+        let span = span.map(|span| span.context().clone());
+        let action = data
+            .store
+            .actions(cluster_id.clone())
+            .action(action_id, span.clone())
+            .with_context(|_| ErrorKind::PrimaryStoreQuery("action"))?
+            .ok_or_else(|| ErrorKind::ModelNotFound("action", action_id.to_string()))?;
+        if action.finished_ts.is_some() {
+            return Err(ErrorKind::ActionAlreadyFinished(action_id.to_string()).into());
+        }
+        match action.state {
+            ActionState::PendingApprove | ActionState::PendingSchedule => {
+                cancel_pending(&data, action, span)
+            }
+            _ => cancel_on_agent(&data, action, span),
+        }
+    })?;
+
+    debug!(
+        data.logger,
+        "Cancelled action";
+        "cluster" => cluster_id,
+        "action" => %action_id,
+    );
+    let response = HttpResponse::Ok().json(json!({}));
+    Ok(response)
+}
+
+/// Cancel an action that was not sent to the agent yet.
+///
+/// The action is only cancelled if it is still pending when the store is updated
+/// so actions scheduled in the meantime are not reported as cancelled.
+fn cancel_pending(data: &CancelData, mut action: Action, span: Option<SpanContext>) -> Result<()> {
+    let action_id = action.action_id;
+    let cancelled = data
+        .store
+        .actions(action.cluster_id.clone())
+        .cancel(action_id, span.clone())
+        .with_context(|_| ErrorKind::PrimaryStorePersist("action cancellation"))?;
+    if !cancelled {
+        return Err(ErrorKind::ActionNotPending(action_id.to_string()).into());
+    }
+    action.finish(ActionState::Cancelled);
+    let event = Event::builder().action().cancelled(action);
+    emit(data, event, span)
+}
+
+/// Request the agent running an action to cancel it.
+///
+/// The action record is updated once the agent reports the action as cancelled.
+fn cancel_on_agent(data: &CancelData, action: Action, span: Option<SpanContext>) -> Result<()> {
+    let ns = namespace(data, &action.cluster_id, span.clone())?;
+    let client = data
+        .clients
+        .get(&ns, &action.node_id)
+        .with_context(|_| ErrorKind::AgentActionCancel(action.node_id.clone()))?;
+    client
+        .cancel_action(&action.action_id, span.clone())
+        .with_context(|_| ErrorKind::AgentActionCancel(action.node_id.clone()))?;
+    let event = Event::builder().action().cancel_requested(action);
+    emit(data, event, span)
+}
+
+/// Emit an action event to record the user request.
+fn emit(data: &CancelData, event: Event, span: Option<SpanContext>) -> Result<()> {
+    let code = event.code();
+    let stream_key = event.stream_key();
+    let event = EmitMessage::with(stream_key, event)
+        .with_context(|_| ErrorKind::EventsStreamEmit(code))?
+        .trace(span);
+    data.events
+        .emit(event)
+        .with_context(|_| ErrorKind::EventsStreamEmit(code))?;
+    Ok(())
+}
+
+/// Resolve the namespace settings used to reach the cluster's agents.
+fn namespace(data: &CancelData, cluster_id: &str, span: Option<SpanContext>) -> Result<Namespace> {
+    let discovery = data
+        .store
+        .cluster("TODO_NS".to_string(), cluster_id.to_string())
+        .discovery(span.clone())
+        .with_context(|_| ErrorKind::PrimaryStoreQuery("cluster_discovery"))?
+        .ok_or_else(|| ErrorKind::ModelNotFound("cluster_discovery", cluster_id.to_string()))?;
+    let ns_id = discovery.namespace;
+    let ns = data
+        .store
+        .namespace(ns_id.clone())
+        .get(span)
+        .with_context(|_| ErrorKind::PrimaryStoreQuery("namespace"))?;
+    let ns = ns.unwrap_or_else(|| {
+        let mut ns = data.default_namespace.clone();
+        ns.ns_id = ns_id;
+        ns
+    });
+    Ok(ns)
+}
+
+#[derive(Clone)]
+struct CancelData {
+    clients: ClientPool,
+    default_namespace: Namespace,
+    events: EventsStream,
+    logger: Logger,
+    store: Store,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use actix_web::http::StatusCode;
+    use actix_web::test::call_service;
+    use actix_web::test::init_service;
+    use actix_web::test::TestRequest;
+    use actix_web::web;
+    use actix_web::App;
+    use chrono::Utc;
+    use serde_json::json;
+    use uuid::Uuid;
+
+    use replicante_models_core::actions::Action;
+    use replicante_models_core::actions::ActionRequester;
+    use replicante_models_core::actions::ActionState;
+
+    use super::Cancel;
+    use crate::interfaces::test_support::MockInterfaces;
+    use crate::Config;
+
+    fn mock_action(mocks: &MockInterfaces, state: ActionState) -> Uuid {
+        let action = Action {
+            action_id: Uuid::new_v4(),
+            args: json!({}),
+            cluster_id: "c1".into(),
+            created_ts: Utc::now(),
+            deadline_ts: None,
+            finished_ts: None,
+            headers: HashMap::new(),
+            kind: "test.action".into(),
+            node_id: "n1".into(),
+            refresh_id: 0,
+            requester: ActionRequester::CoreApi,
+            schedule_attempt: 0,
+            scheduled_ts: None,
+            state,
+            state_payload: None,
+        };
+        let action_id = action.action_id;
+        let key = ("c1".to_string(), "n1".to_string(), action_id);
+        let mut state = mocks.stores.primary.state.lock().unwrap();
+        state.actions.insert(key, action);
+        action_id
+    }
+
+    fn stored_action(mocks: &MockInterfaces, action_id: Uuid) -> Action {
+        let key = ("c1".to_string(), "n1".to_string(), action_id);
+        let state = mocks.stores.primary.state.lock().unwrap();
+        state.actions.get(&key).unwrap().clone()
+    }
+
+    async fn cancel(mocks: &MockInterfaces, action_id: Uuid) -> StatusCode {
+        let config = Config::mock();
+        let mut interfaces = mocks.interfaces();
+        let cancel = Cancel::new(&config, &mocks.logger, &mut interfaces);
+        let app =
+            App::new().service(web::scope("/cluster/{cluster_id}").service(cancel.resource()));
+        let mut app = init_service(app).await;
+        let uri = format!("/cluster/c1/action/{}/cancel", action_id);
+        let request = TestRequest::post().uri(&uri).to_request();
+        call_service(&mut app, request).await.status()
+    }
+
+    #[actix_rt::test]
+    async fn cancel_pending_action() {
+        let mocks = MockInterfaces::mock_quietly();
+        let action_id = mock_action(&mocks, ActionState::PendingApprove);
+        let status = cancel(&mocks, action_id).await;
+        assert_eq!(status, StatusCode::OK);
+        let action = stored_action(&mocks, action_id);
+        assert_eq!(action.state, ActionState::Cancelled);
+        assert!(action.finished_ts.is_some());
+    }
+
+    #[actix_rt::test]
+    async fn cancel_finished_action_conflicts() {
+        let mocks = MockInterfaces::mock_quietly();
+        let action_id = mock_action(&mocks, ActionState::Done);
+        {
+            let key = ("c1".to_string(), "n1".to_string(), action_id);
+            let mut state = mocks.stores.primary.state.lock().unwrap();
+            state.actions.get_mut(&key).unwrap().finished_ts = Some(Utc::now());
+        }
+        let status = cancel(&mocks, action_id).await;
+        assert_eq!(status, StatusCode::CONFLICT);
+        let action = stored_action(&mocks, action_id);
+        assert_eq!(action.state, ActionState::Done);
+    }
+
+    #[actix_rt::test]
+    async fn cancel_missing_action() {
+        let mocks = MockInterfaces::mock_quietly();
+        let status = cancel(&mocks, Uuid::new_v4()).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+}
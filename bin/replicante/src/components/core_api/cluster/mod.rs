@@ -5,20 +5,28 @@ use replicante_util_actixweb::RootDescriptor;
 use crate::interfaces::api::APIRoot;
 use crate::interfaces::api::AppConfigContext;
 use crate::interfaces::Interfaces;
+use crate::Config;
 
 mod action_approve;
+mod action_cancel;
 mod action_disapprove;
 mod refresh;
 
 /// Return an `AppConfig` callback to configure cluster endpoints.
-pub fn configure(logger: &Logger, interfaces: &mut Interfaces) -> impl Fn(&mut AppConfigContext) {
+pub fn configure(
+    config: &Config,
+    logger: &Logger,
+    interfaces: &mut Interfaces,
+) -> impl Fn(&mut AppConfigContext) {
     let approve = self::action_approve::Approve::new(logger, interfaces);
+    let cancel = self::action_cancel::Cancel::new(config, logger, interfaces);
     let disapprove = self::action_disapprove::Disapprove::new(logger, interfaces);
     let refresh = self::refresh::Refresh::new(logger, interfaces);
     move |conf| {
         APIRoot::UnstableCoreApi.and_then(&conf.context.flags, |root| {
             let scope = actix_web::web::scope("/cluster/{cluster_id}")
                 .service(approve.resource())
+                .service(cancel.resource())
                 .service(disapprove.resource())
                 .service(refresh.resource());
             conf.scoped_service(root.prefix(), scope);
@@ -58,18 +58,28 @@ async fn responder(data: web::Data<RefreshData>, request: HttpRequest) -> Result
         .to_string();
 
     let mut request = request;
-    let cluster = with_request_span(&mut request, |span| -> Result<_> {
+    let (cluster, namespace) = with_request_span(&mut request, |span| -> Result<_> {
         let span = span.map(|span| span.context().clone());
+        let namespace = data
+            .store
+            .cluster("TODO_NS".to_string(), cluster_id.clone())
+            .namespace(span.clone())
+            .with_context(|_| ErrorKind::PrimaryStoreQuery("cluster_namespace"))?;
         let cluster = data
             .store
             .cluster("TODO_NS".to_string(), cluster_id.clone())
             .discovery(span)
             .with_context(|_| ErrorKind::PrimaryStoreQuery("cluster_discovery"))?
             .ok_or_else(|| ErrorKind::ModelNotFound("cluster_discovery", cluster_id.clone()))?;
-        Ok(cluster)
+        Ok((cluster, namespace))
     })?;
 
+    // Refresh the cluster with the settings of its namespace, like scheduled refreshes do.
     let payload = ClusterRefreshPayload::new(cluster, false);
+    let payload = match namespace {
+        None => payload,
+        Some(namespace) => payload.with_namespace(namespace),
+    };
     let mut task = TaskRequest::new(ReplicanteQueues::ClusterRefresh);
     with_request_span(&mut request, |span| {
         let span = span.map(|span| span.context());
@@ -116,3 +126,77 @@ struct RefreshData {
 struct RefreshResponse {
     task_id: String,
 }
+
+#[cfg(test)]
+mod tests {
+    use actix_web::http::StatusCode;
+    use actix_web::test::call_service;
+    use actix_web::test::init_service;
+    use actix_web::test::TestRequest;
+    use actix_web::web;
+    use actix_web::App;
+
+    use replicante_models_core::cluster::discovery::ClusterDiscovery;
+    use replicante_models_core::cluster::ClusterSettings;
+    use replicore_models_tasks::payload::ClusterRefreshPayload;
+
+    use super::Refresh;
+    use crate::interfaces::test_support::MockInterfaces;
+
+    fn mock_cluster(mocks: &MockInterfaces, namespace: Option<&str>) {
+        let mut state = mocks.stores.primary.state.lock().unwrap();
+        let discovery = ClusterDiscovery::new("c1", vec!["n1".into()]);
+        state.discoveries.insert("c1".into(), discovery);
+        if let Some(namespace) = namespace {
+            let settings = ClusterSettings::new(namespace, "c1", true);
+            state
+                .cluster_settings
+                .insert((namespace.into(), "c1".into()), settings);
+        }
+    }
+
+    async fn refresh(mocks: &MockInterfaces) -> StatusCode {
+        let mut interfaces = mocks.interfaces();
+        let refresh = Refresh::new(&mocks.logger, &mut interfaces);
+        let app =
+            App::new().service(web::scope("/cluster/{cluster_id}").service(refresh.resource()));
+        let mut app = init_service(app).await;
+        let request = TestRequest::post().uri("/cluster/c1/refresh").to_request();
+        call_service(&mut app, request).await.status()
+    }
+
+    fn requested_payload(mocks: &MockInterfaces) -> ClusterRefreshPayload {
+        let requests = mocks.tasks.requests.lock().unwrap();
+        assert_eq!(requests.len(), 1);
+        serde_json::from_value(requests[0].1.clone()).unwrap()
+    }
+
+    #[actix_rt::test]
+    async fn refresh_cluster_in_namespace() {
+        let mocks = MockInterfaces::mock_quietly();
+        mock_cluster(&mocks, Some("team-a"));
+        let status = refresh(&mocks).await;
+        assert_eq!(status, StatusCode::OK);
+        let payload = requested_payload(&mocks);
+        assert_eq!(payload.cluster.cluster_id, "c1");
+        assert_eq!(payload.namespace, Some("team-a".to_string()));
+    }
+
+    #[actix_rt::test]
+    async fn refresh_cluster_without_settings() {
+        let mocks = MockInterfaces::mock_quietly();
+        mock_cluster(&mocks, None);
+        let status = refresh(&mocks).await;
+        assert_eq!(status, StatusCode::OK);
+        let payload = requested_payload(&mocks);
+        assert_eq!(payload.namespace, None);
+    }
+
+    #[actix_rt::test]
+    async fn refresh_unknown_cluster() {
+        let mocks = MockInterfaces::mock_quietly();
+        let status = refresh(&mocks).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert!(mocks.tasks.requests.lock().unwrap().is_empty());
+    }
+}
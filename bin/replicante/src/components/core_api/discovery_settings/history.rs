@@ -0,0 +1,88 @@
+use std::sync::Arc;
+
+use actix_web::dev::HttpServiceFactory;
+use actix_web::web;
+use actix_web::HttpRequest;
+use actix_web::HttpResponse;
+use actix_web::Responder;
+use failure::ResultExt;
+use slog::Logger;
+
+use replicante_models_core::api::discovery_settings::DiscoverySettingsHistoryResponse;
+use replicante_store_primary::store::Store;
+use replicante_util_actixweb::with_request_span;
+use replicante_util_actixweb::TracingMiddleware;
+
+use crate::interfaces::Interfaces;
+use crate::ErrorKind;
+use crate::Result;
+
+pub struct History {
+    data: HistoryData,
+    logger: Logger,
+    tracer: Arc<opentracingrust::Tracer>,
+}
+
+impl History {
+    pub fn new(logger: &Logger, interfaces: &mut Interfaces) -> History {
+        let data = HistoryData {
+            store: interfaces.stores.primary.clone(),
+        };
+        History {
+            data,
+            logger: logger.clone(),
+            tracer: interfaces.tracing.tracer(),
+        }
+    }
+
+    pub fn resource(&self) -> impl HttpServiceFactory {
+        let logger = self.logger.clone();
+        let tracer = Arc::clone(&self.tracer);
+        let tracer = TracingMiddleware::with_name(
+            logger,
+            tracer,
+            "/discoverysettings/{namespace}/{name}/history",
+        );
+        web::resource("/{name}/history")
+            .data(self.data.clone())
+            .wrap(tracer)
+            .route(web::get().to(responder))
+    }
+}
+
+async fn responder(data: web::Data<HistoryData>, request: HttpRequest) -> Result<impl Responder> {
+    let path = request.match_info();
+    let namespace = path
+        .get("namespace")
+        .ok_or(ErrorKind::APIRequestParameterNotFound("namespace"))?
+        .to_string();
+    let name = path
+        .get("name")
+        .ok_or(ErrorKind::APIRequestParameterNotFound("name"))?
+        .to_string();
+
+    let mut request = request;
+    let cursor = with_request_span(&mut request, |span| {
+        let span = span.map(|span| span.context().clone());
+        data.store
+            .discovery_settings(namespace)
+            .history(&name, span)
+            .with_context(|_| ErrorKind::PrimaryStoreQuery("discovery settings history"))
+    })?;
+
+    let mut revisions = vec![];
+    for revision in cursor {
+        let revision = revision
+            .with_context(|_| ErrorKind::PrimaryStoreQuery("discovery settings history"))?;
+        revisions.push(revision);
+    }
+
+    let response = DiscoverySettingsHistoryResponse { revisions };
+    let response = HttpResponse::Ok().json(response);
+    Ok(response)
+}
+
+#[derive(Clone)]
+struct HistoryData {
+    store: Store,
+}
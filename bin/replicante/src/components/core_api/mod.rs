@@ -4,11 +4,13 @@ use replicante_util_upkeep::Upkeep;
 
 use super::Component;
 use crate::interfaces::Interfaces;
+use crate::Config;
 use crate::Result;
 
 mod apply;
 mod cluster;
 mod discovery_settings;
+mod namespace;
 
 pub use apply::register_metrics;
 
@@ -16,13 +18,15 @@ pub use apply::register_metrics;
 pub struct CoreAPI {}
 
 impl CoreAPI {
-    pub fn new(logger: Logger, interfaces: &mut Interfaces) -> CoreAPI {
+    pub fn new(config: &Config, logger: Logger, interfaces: &mut Interfaces) -> CoreAPI {
         let apply = self::apply::configure(&logger, interfaces);
-        let cluster = self::cluster::configure(&logger, interfaces);
+        let cluster = self::cluster::configure(config, &logger, interfaces);
         let discovery_settings = self::discovery_settings::configure(&logger, interfaces);
+        let namespace = self::namespace::configure(&logger, interfaces);
         interfaces.api.configure(apply);
         interfaces.api.configure(cluster);
         interfaces.api.configure(discovery_settings);
+        interfaces.api.configure(namespace);
         CoreAPI {}
     }
 }
@@ -0,0 +1,160 @@
+use std::sync::Arc;
+
+use actix_web::dev::HttpServiceFactory;
+use actix_web::web;
+use actix_web::HttpRequest;
+use actix_web::HttpResponse;
+use actix_web::Responder;
+use failure::ResultExt;
+use serde_json::json;
+use slog::Logger;
+
+use replicante_models_core::events::Event;
+use replicante_store_primary::store::Store;
+use replicante_stream_events::EmitMessage;
+use replicante_stream_events::Stream;
+use replicante_util_actixweb::with_request_span;
+use replicante_util_actixweb::TracingMiddleware;
+
+use crate::interfaces::Interfaces;
+use crate::ErrorKind;
+use crate::Result;
+
+pub struct Delete {
+    data: DeleteData,
+    logger: Logger,
+    tracer: Arc<opentracingrust::Tracer>,
+}
+
+impl Delete {
+    pub fn new(logger: &Logger, interfaces: &mut Interfaces) -> Delete {
+        let data = DeleteData {
+            events: interfaces.streams.events.clone(),
+            store: interfaces.stores.primary.clone(),
+        };
+        Delete {
+            data,
+            logger: logger.clone(),
+            tracer: interfaces.tracing.tracer(),
+        }
+    }
+
+    pub fn resource(&self) -> impl HttpServiceFactory {
+        let logger = self.logger.clone();
+        let tracer = Arc::clone(&self.tracer);
+        let tracer = TracingMiddleware::with_name(logger, tracer, "/namespace/{namespace}/delete");
+        web::resource("/{namespace}/delete")
+            .data(self.data.clone())
+            .wrap(tracer)
+            .route(web::delete().to(responder))
+    }
+}
+
+async fn responder(data: web::Data<DeleteData>, request: HttpRequest) -> Result<impl Responder> {
+    let path = request.match_info();
+    let namespace = path
+        .get("namespace")
+        .ok_or(ErrorKind::APIRequestParameterNotFound("namespace"))?
+        .to_string();
+
+    // Namespaces with clusters are not deleted to avoid orphaning their settings.
+    let mut request = request;
+    with_request_span(&mut request, |span| -> Result<()> {
+        let span = span.map(|span| span.context().clone());
+        let cluster = data
+            .store
+            .namespace(namespace.clone())
+            .iter_clusters(span.clone())
+            .with_context(|_| ErrorKind::PrimaryStoreQuery("namespace_clusters"))?
+            .next()
+            .transpose()
+            .with_context(|_| ErrorKind::PrimaryStoreQuery("namespace_clusters"))?;
+        if let Some(cluster_id) = cluster {
+            return Err(ErrorKind::NamespaceInUse(namespace.clone(), cluster_id).into());
+        }
+
+        let event = Event::builder().namespace().delete(namespace.clone());
+        let code = event.code();
+        let stream_key = event.stream_key();
+        let event = EmitMessage::with(stream_key, event)
+            .with_context(|_| ErrorKind::EventsStreamEmit(code))?
+            .trace(span.clone());
+        data.events
+            .emit(event)
+            .with_context(|_| ErrorKind::EventsStreamEmit("Namespace"))?;
+        data.store
+            .namespace(namespace)
+            .delete(span)
+            .with_context(|_| ErrorKind::PrimaryStoreDelete("namespace"))?;
+        Ok(())
+    })?;
+
+    let response = HttpResponse::Ok().json(json!({}));
+    Ok(response)
+}
+
+#[derive(Clone)]
+struct DeleteData {
+    events: Stream,
+    store: Store,
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::http::StatusCode;
+    use actix_web::test::call_service;
+    use actix_web::test::init_service;
+    use actix_web::test::TestRequest;
+    use actix_web::web;
+    use actix_web::App;
+    use serde_json::json;
+
+    use replicante_models_core::cluster::ClusterSettings;
+
+    use super::Delete;
+    use crate::interfaces::test_support::MockInterfaces;
+
+    fn mock_namespace(mocks: &MockInterfaces) {
+        let namespace = serde_json::from_value(json!({"ns_id": "team-a"})).unwrap();
+        let mut state = mocks.stores.primary.state.lock().unwrap();
+        state.namespaces.insert("team-a".into(), namespace);
+    }
+
+    async fn delete(mocks: &MockInterfaces) -> StatusCode {
+        let mut interfaces = mocks.interfaces();
+        let delete = Delete::new(&mocks.logger, &mut interfaces);
+        let app = App::new().service(web::scope("/namespace").service(delete.resource()));
+        let mut app = init_service(app).await;
+        let request = TestRequest::delete()
+            .uri("/namespace/team-a/delete")
+            .to_request();
+        call_service(&mut app, request).await.status()
+    }
+
+    #[actix_rt::test]
+    async fn delete_empty_namespace() {
+        let mocks = MockInterfaces::mock_quietly();
+        mock_namespace(&mocks);
+        let status = delete(&mocks).await;
+        assert_eq!(status, StatusCode::OK);
+        let state = mocks.stores.primary.state.lock().unwrap();
+        assert!(!state.namespaces.contains_key("team-a"));
+    }
+
+    #[actix_rt::test]
+    async fn delete_namespace_with_clusters_conflicts() {
+        let mocks = MockInterfaces::mock_quietly();
+        mock_namespace(&mocks);
+        {
+            let settings = ClusterSettings::new("team-a", "c1", true);
+            let mut state = mocks.stores.primary.state.lock().unwrap();
+            state
+                .cluster_settings
+                .insert(("team-a".into(), "c1".into()), settings);
+        }
+        let status = delete(&mocks).await;
+        assert_eq!(status, StatusCode::CONFLICT);
+        let state = mocks.stores.primary.state.lock().unwrap();
+        assert!(state.namespaces.contains_key("team-a"));
+    }
+}
@@ -12,14 +12,18 @@ use serde_json::json;
 
 use replicante_models_core::events::action::ActionEvent;
 use replicante_models_core::events::agent::AgentEvent;
+use replicante_models_core::events::alert::AlertEvent;
 use replicante_models_core::events::cluster::ClusterEvent;
 use replicante_models_core::events::discovery_settings::DiscoverySettingsEvent;
+use replicante_models_core::events::namespace::NamespaceEvent;
 use replicante_models_core::events::node::NodeEvent;
 use replicante_models_core::events::shard::ShardEvent;
 use replicante_models_core::events::Event;
 use replicante_models_core::events::Payload;
+use replicante_store_view::store::events::EventCodeMatch;
 use replicante_store_view::store::events::EventsFilters;
 use replicante_store_view::store::events::EventsOptions;
+use replicante_store_view::store::events::SYSTEM_EVENTS_TAG;
 use replicante_store_view::store::Store;
 use replicante_util_actixweb::with_request_span;
 
@@ -48,13 +52,23 @@ impl Annotations {
     fn tags(event: &Event) -> Vec<String> {
         let mut tags = Vec::new();
         tags.push(event.code().into());
-        tags.push(String::from(event.cluster_id().unwrap_or("System")));
+        tags.push(String::from(
+            event.cluster_id().unwrap_or(SYSTEM_EVENTS_TAG),
+        ));
         tags
     }
 
     fn text(event: &Event) -> String {
         match &event.payload {
             Payload::Action(action) => match action {
+                ActionEvent::Cancelled(action) => format!(
+                    "Action with ID {} on {} was cancelled",
+                    &action.action_id, &action.cluster_id,
+                ),
+                ActionEvent::CancelRequested(action) => format!(
+                    "Cancellation of action with ID {} on {} was requested",
+                    &action.action_id, &action.cluster_id,
+                ),
                 ActionEvent::Changed(change) => format!(
                     "Details about action with ID {} on {} changed",
                     &change.current.action_id, &change.cluster_id,
@@ -86,16 +100,50 @@ impl Annotations {
                 AgentEvent::New(agent) => {
                     format!("A new agent was detected on host {}", &agent.host)
                 }
+                AgentEvent::Removed(agent) => {
+                    format!(
+                        "Agent on host {} is no longer part of the cluster",
+                        &agent.host
+                    )
+                }
                 AgentEvent::Up(change) => format!("Agent {} is now up", &change.host),
                 // TODO: for when #[non_exhaustive] is usable
                 //_ => event.code().to_string(),
             },
+            Payload::Alert(alert) => match alert {
+                AlertEvent::Firing(alert) => format!(
+                    "Alert {}.{} is firing for cluster {}",
+                    &alert.namespace, &alert.rule, &alert.cluster_id,
+                ),
+                AlertEvent::Resolved(alert) => format!(
+                    "Alert {}.{} resolved for cluster {}",
+                    &alert.namespace, &alert.rule, &alert.cluster_id,
+                ),
+                AlertEvent::RuleApply(rule) => format!(
+                    "An AlertRule object named {} was applied in {}",
+                    &rule.name, &rule.namespace,
+                ),
+                // TODO: for when #[non_exhaustive] is usable
+                //_ => event.code().to_string(),
+            },
             Payload::Cluster(cluster) => match cluster {
                 ClusterEvent::Changed(_) => String::from(concat!(
                     "Cluster discovery record changed (most commonly, this",
                     " indicates a membership change)",
                 )),
+                ClusterEvent::Disappeared(discovery) => format!(
+                    "Cluster {} is no longer reported by discovery and was retired",
+                    &discovery.cluster_id,
+                ),
+                ClusterEvent::HealthChanged(change) => format!(
+                    "Health of cluster {} is now {:?}",
+                    &change.cluster_id, change.after.health,
+                ),
                 ClusterEvent::New(_) => "Cluster discovered for the first time".into(),
+                ClusterEvent::SettingsApply(settings) => format!(
+                    "A ClusterSettings object was applied for cluster {}.{}",
+                    settings.namespace, settings.cluster_id,
+                ),
                 ClusterEvent::SettingsSynthetic(settings) => format!(
                     "A synthetic ClusterSettings record was created for cluster {}.{}",
                     settings.namespace, settings.cluster_id,
@@ -104,9 +152,9 @@ impl Annotations {
                 //_ => event.code().to_string(),
             },
             Payload::DiscoverySettings(settings) => match settings {
-                DiscoverySettingsEvent::Apply(settings) => format!(
-                    "A DiscoverySettings object named {} was applied in {}",
-                    &settings.name, &settings.namespace,
+                DiscoverySettingsEvent::Apply(apply) => format!(
+                    "A DiscoverySettings object named {} was applied in {} (revision {})",
+                    &apply.after.name, &apply.after.namespace, apply.after.revision,
                 ),
                 DiscoverySettingsEvent::Delete(id) => format!(
                     "A DiscoverySettings object named {} was delete from {}",
@@ -115,6 +163,12 @@ impl Annotations {
                 // TODO: for when #[non_exhaustive] is usable
                 //_ => event.code().to_string(),
             },
+            Payload::Namespace(namespace) => match namespace {
+                NamespaceEvent::Apply(ns) => format!("Namespace object {} was applied", &ns.ns_id),
+                NamespaceEvent::Delete(id) => format!("Namespace object {} was deleted", &id.ns_id),
+                // TODO: for when #[non_exhaustive] is usable
+                //_ => event.code().to_string(),
+            },
             Payload::Node(node) => match node {
                 NodeEvent::Changed(change) => {
                     format!("Details about datastore node {} changed", &change.node_id)
@@ -124,6 +178,10 @@ impl Annotations {
                     &state.host,
                 ),
                 NodeEvent::New(_) => "A new datastore node was detected".into(),
+                NodeEvent::Removed(node) => format!(
+                    "Datastore node {} is no longer part of the cluster",
+                    &node.node_id,
+                ),
                 NodeEvent::Up(change) => format!("Datastore node {} is now up", &change.host),
                 // TODO: for when #[non_exhaustive] is usable
                 //_ => event.code().to_string(),
@@ -137,6 +195,14 @@ impl Annotations {
                     "Shard {} found on node {} for the first time",
                     &shard.shard_id, &shard.node_id
                 ),
+                ShardEvent::AllocationRemoved(shard) => format!(
+                    "Shard {} is no longer found on node {}",
+                    &shard.shard_id, &shard.node_id
+                ),
+                ShardEvent::LagSample(shard) => format!(
+                    "Commit offset or lag of shard {} on node {} changed",
+                    &shard.shard_id, &shard.node_id
+                ),
                 // TODO: for when #[non_exhaustive] is usable
                 //_ => event.code().to_string(),
             },
@@ -148,6 +214,8 @@ impl Annotations {
     fn title(event: &Event) -> String {
         match &event.payload {
             Payload::Action(action) => match action {
+                ActionEvent::Cancelled(_) => "Action cancelled".into(),
+                ActionEvent::CancelRequested(_) => "Action cancellation requested".into(),
                 ActionEvent::Changed(_) => "Action details changed".into(),
                 ActionEvent::Finished(_) => "Action finished executing".into(),
                 ActionEvent::Lost(_) => "Unfinished action is no longer reported".into(),
@@ -159,13 +227,24 @@ impl Annotations {
                 AgentEvent::InfoChanged(_) => "Agent details changed".into(),
                 AgentEvent::InfoNew(_) => "New agent detected".into(),
                 AgentEvent::New(_) => "New agent detected".into(),
+                AgentEvent::Removed(_) => "Agent removed".into(),
                 AgentEvent::Up(_) => "Agent is up".into(),
                 // TODO: for when #[non_exhaustive] is usable
                 //_ => event.code().to_string(),
             },
+            Payload::Alert(alert) => match alert {
+                AlertEvent::Firing(_) => "Alert firing".into(),
+                AlertEvent::Resolved(_) => "Alert resolved".into(),
+                AlertEvent::RuleApply(_) => "AlertRule applied".into(),
+                // TODO: for when #[non_exhaustive] is usable
+                //_ => event.code().to_string(),
+            },
             Payload::Cluster(cluster) => match cluster {
                 ClusterEvent::Changed(_) => "Cluster changed".into(),
+                ClusterEvent::Disappeared(_) => "Cluster disappeared".into(),
+                ClusterEvent::HealthChanged(_) => "Cluster health changed".into(),
                 ClusterEvent::New(_) => "New cluster detected".into(),
+                ClusterEvent::SettingsApply(_) => "ClusterSettings applied".into(),
                 ClusterEvent::SettingsSynthetic(_) => "Synthetic ClusterSettings created".into(),
                 // TODO: for when #[non_exhaustive] is usable
                 //_ => event.code().to_string(),
@@ -176,10 +255,17 @@ impl Annotations {
                 // TODO: for when #[non_exhaustive] is usable
                 //_ => event.code().to_string(),
             },
+            Payload::Namespace(namespace) => match namespace {
+                NamespaceEvent::Apply(_) => "Namespace applied".into(),
+                NamespaceEvent::Delete(_) => "Namespace deleted".into(),
+                // TODO: for when #[non_exhaustive] is usable
+                //_ => event.code().to_string(),
+            },
             Payload::Node(node) => match node {
                 NodeEvent::Changed(_) => "Datastore node details changed".into(),
                 NodeEvent::Down(_) => "Datastore node is down".into(),
                 NodeEvent::New(_) => "New datastore node detected".into(),
+                NodeEvent::Removed(_) => "Datastore node removed".into(),
                 NodeEvent::Up(_) => "Datastore node is up".into(),
                 // TODO: for when #[non_exhaustive] is usable
                 //_ => event.code().to_string(),
@@ -187,6 +273,8 @@ impl Annotations {
             Payload::Shard(shard) => match shard {
                 ShardEvent::AllocationChanged(_) => "Shard status on node changed".into(),
                 ShardEvent::AllocationNew(_) => "Shard found on node".into(),
+                ShardEvent::AllocationRemoved(_) => "Shard removed from node".into(),
+                ShardEvent::LagSample(_) => "Shard lag changed".into(),
                 // TODO: for when #[non_exhaustive] is usable
                 //_ => event.code().to_string(),
             },
@@ -202,14 +290,32 @@ struct AnnotationData {
 }
 
 /// Advanced query parameters passed as JSON blob in the annotation.query field.
+///
+/// Events matching any of the clusters and any of the event filters are returned.
 #[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Serialize, Deserialize)]
 struct AdvancedQuery {
+    /// Event categories (event code prefixes such as `ACTION` or `SHARD`) to return.
+    #[serde(default)]
+    categories: Vec<String>,
+
     #[serde(default)]
     cluster_id: Option<String>,
 
+    /// Return events for any of the listed clusters.
+    #[serde(default)]
+    clusters: Vec<String>,
+
     #[serde(default)]
     event: Option<String>,
 
+    /// Event codes or glob patterns (`*` and `?` wildcards) to return.
+    #[serde(default)]
+    events: Vec<String>,
+
+    /// Regular expressions matching event codes to return.
+    #[serde(default)]
+    events_regex: Vec<String>,
+
     #[serde(default = "AdvancedQuery::default_exclude_snapshots")]
     exclude_snapshots: bool,
 
@@ -223,8 +329,12 @@ struct AdvancedQuery {
 impl Default for AdvancedQuery {
     fn default() -> Self {
         Self {
+            categories: Vec::new(),
             cluster_id: None,
+            clusters: Vec::new(),
             event: None,
+            events: Vec::new(),
+            events_regex: Vec::new(),
             exclude_snapshots: Self::default_exclude_snapshots(),
             exclude_system_events: Self::default_exclude_system_events(),
             limit: Self::default_limit(),
@@ -242,6 +352,39 @@ impl AdvancedQuery {
     fn default_limit() -> i64 {
         1000
     }
+
+    /// Cluster IDs to return events for.
+    fn cluster_ids(&self) -> Vec<String> {
+        let mut clusters: Vec<String> = self.cluster_id.iter().cloned().collect();
+        clusters.extend(self.clusters.iter().cloned());
+        clusters
+    }
+
+    /// Event code filters to return events for.
+    ///
+    /// Regular expressions are validated by the store that runs them.
+    fn event_matches(&self) -> Vec<EventCodeMatch> {
+        let mut matches: Vec<EventCodeMatch> = self
+            .event
+            .iter()
+            .cloned()
+            .map(EventCodeMatch::Code)
+            .collect();
+        for event in &self.events {
+            if event.contains('*') || event.contains('?') {
+                matches.push(EventCodeMatch::Glob(event.clone()));
+            } else {
+                matches.push(EventCodeMatch::Code(event.clone()));
+            }
+        }
+        for regex in &self.events_regex {
+            matches.push(EventCodeMatch::Regex(regex.clone()));
+        }
+        for category in &self.categories {
+            matches.push(EventCodeMatch::category(category));
+        }
+        matches
+    }
 }
 
 /// Response annotation, a list of which is our response to SimpleJson.
@@ -269,6 +412,10 @@ struct AnnotationQuery {
     icon_color: String,
     name: String,
     query: Option<String>,
+
+    /// Only return events with all of these tags.
+    #[serde(default)]
+    tags: Vec<String>,
 }
 
 /// Time-range for the annotation query.
@@ -298,23 +445,38 @@ async fn responder(
         }
     };
     let mut filters = EventsFilters::most();
-    filters.cluster_id = query.cluster_id;
-    filters.event = query.event;
+    filters.cluster_ids = query.cluster_ids();
+    filters.events = query.event_matches();
     filters.exclude_snapshots = query.exclude_snapshots;
     filters.exclude_system_events = query.exclude_system_events;
     filters.start_from = Some(body.range.from);
     filters.stop_at = Some(body.range.to);
+    filters.tags = body.annotation.tags.clone();
     let mut options = EventsOptions::default();
     options.limit = Some(query.limit);
 
     // Fetch and format annotations.
     let mut request = request;
-    let events = with_request_span(&mut request, |span| {
+    let events = with_request_span(&mut request, |span| -> Result<_> {
         let span = span.map(|span| span.context().clone());
-        data.store
+        for event in &filters.events {
+            if let EventCodeMatch::Regex(regex) = event {
+                let valid = data
+                    .store
+                    .events()
+                    .valid_code_regex(regex.clone(), span.clone())
+                    .with_context(|_| ErrorKind::ViewStoreQuery("events regex"))?;
+                if !valid {
+                    return Err(ErrorKind::APIRequestBodyInvalid.into());
+                }
+            }
+        }
+        let events = data
+            .store
             .events()
             .range(filters, options, span)
-            .with_context(|_| ErrorKind::ViewStoreQuery("events"))
+            .with_context(|_| ErrorKind::ViewStoreQuery("events"))?;
+        Ok(events)
     })?;
     let mut annotations: Vec<Annotation> = Vec::new();
     for event in events {
@@ -335,3 +497,51 @@ async fn responder(
     let response = HttpResponse::Ok().json(annotations);
     Ok(response)
 }
+
+#[cfg(test)]
+mod tests {
+    use replicante_store_view::store::events::EventCodeMatch;
+
+    use super::AdvancedQuery;
+
+    #[test]
+    fn advanced_query_defaults() {
+        let query: AdvancedQuery = serde_json::from_str("{}").unwrap();
+        assert_eq!(query, AdvancedQuery::default());
+        assert!(query.cluster_ids().is_empty());
+        assert!(query.event_matches().is_empty());
+    }
+
+    #[test]
+    fn advanced_query_multi_filters() {
+        let payload = r#"{
+            "categories": ["shard"],
+            "cluster_id": "c1",
+            "clusters": ["c2", "c3"],
+            "events": ["NODE_DOWN", "AGENT_*"],
+            "events_regex": ["^CLUSTER_(NEW|CHANGED)$"]
+        }"#;
+        let query: AdvancedQuery = serde_json::from_str(payload).unwrap();
+        assert_eq!(query.cluster_ids(), vec!["c1", "c2", "c3"]);
+        assert_eq!(
+            query.event_matches(),
+            vec![
+                EventCodeMatch::Code("NODE_DOWN".into()),
+                EventCodeMatch::Glob("AGENT_*".into()),
+                EventCodeMatch::Regex("^CLUSTER_(NEW|CHANGED)$".into()),
+                EventCodeMatch::Glob("SHARD_*".into()),
+            ],
+        );
+    }
+
+    // Expressions are validated by the store as the syntax may differ from the regex crate.
+    #[test]
+    fn advanced_query_regex_passed_to_store() {
+        let payload = r#"{"events_regex": ["^NODE_(?!DOWN)"]}"#;
+        let query: AdvancedQuery = serde_json::from_str(payload).unwrap();
+        assert_eq!(
+            query.event_matches(),
+            vec![EventCodeMatch::Regex("^NODE_(?!DOWN)".into())],
+        );
+    }
+}
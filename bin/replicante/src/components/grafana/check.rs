@@ -3,5 +3,5 @@ use actix_web::Responder;
 
 #[get("/")]
 pub async fn check() -> impl Responder {
-    "Grafana SimpleJson Annotations API endpoints".to_string()
+    "Grafana SimpleJson API endpoints".to_string()
 }
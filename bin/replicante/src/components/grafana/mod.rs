@@ -8,21 +8,26 @@ use crate::Result;
 
 mod annotations;
 mod check;
+mod query;
+mod search;
 
 /// Component to mount grafana endpoints.
 ///
-/// These endpoints are designed to provide an Annotations backend for the Grafana
-/// [SimpleJson](https://grafana.com/plugins/grafana-simple-json-datasource) plugin.
+/// These endpoints are designed to provide an Annotations and time series backend for the
+/// Grafana [SimpleJson](https://grafana.com/plugins/grafana-simple-json-datasource) plugin.
 pub struct Grafana {}
 
 impl Grafana {
     pub fn new(interfaces: &mut Interfaces) -> Grafana {
         let annotations = self::annotations::Annotations::new(interfaces);
+        let query = self::query::Query::new(interfaces);
         interfaces.api.configure(move |conf| {
             APIRoot::UnstableApi.and_then(&conf.context.flags, |root| {
                 let scope = actix_web::web::scope("/grafana")
                     .service(self::check::check)
-                    .service(annotations.resource());
+                    .service(annotations.resource())
+                    .service(query.resource())
+                    .service(self::search::search);
                 conf.scoped_service(root.prefix(), scope);
             });
         });
@@ -0,0 +1,457 @@
+use std::collections::BTreeMap;
+
+use actix_web::dev::HttpServiceFactory;
+use actix_web::web;
+use actix_web::HttpRequest;
+use actix_web::HttpResponse;
+use actix_web::Responder;
+use chrono::DateTime;
+use chrono::Utc;
+use failure::ResultExt;
+use opentracingrust::SpanContext;
+use serde_derive::Deserialize;
+use serde_derive::Serialize;
+
+use replicante_models_core::cluster::ClusterMeta;
+use replicante_store_primary::store::Store as PrimaryStore;
+use replicante_store_view::store::actions::SearchFilters;
+use replicante_store_view::store::events::EventsFilters;
+use replicante_store_view::store::IntervalCount;
+use replicante_store_view::store::Store as ViewStore;
+use replicante_util_actixweb::with_request_span;
+
+use crate::ErrorKind;
+use crate::Interfaces;
+use crate::Result;
+
+/// Maximum number of datapoints returned for each series.
+const MAX_DATA_POINTS: i64 = 10000;
+
+/// Smallest interval, in milliseconds, datapoints are grouped by.
+const MIN_INTERVAL_MS: i64 = 1000;
+
+/// Metrics that can be queried by SimpleJson.
+///
+/// Each metric returns one time series for every cluster, action state or event code.
+pub const TARGETS: &[&str] = &[
+    "actions.by_state",
+    "cluster.agents_down",
+    "cluster.nodes",
+    "cluster.nodes_down",
+    "cluster.shards_count",
+    "cluster.shards_primaries",
+    "cluster.shards_without_primary",
+    "events.by_code",
+];
+
+pub struct Query {
+    data: QueryData,
+}
+
+impl Query {
+    pub fn new(interfaces: &mut Interfaces) -> Query {
+        let data = QueryData {
+            primary_store: interfaces.stores.primary.clone(),
+            view_store: interfaces.stores.view.clone(),
+        };
+        Query { data }
+    }
+
+    pub fn resource(&self) -> impl HttpServiceFactory {
+        web::resource("/query")
+            .data(self.data.clone())
+            .route(web::post().to(responder))
+    }
+}
+
+#[derive(Clone)]
+struct QueryData {
+    primary_store: PrimaryStore,
+    view_store: ViewStore,
+}
+
+/// Request data sent to us by SimpleJson.
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
+struct QueryRequest {
+    #[serde(default, rename = "intervalMs")]
+    interval_ms: Option<i64>,
+    #[serde(default, rename = "maxDataPoints")]
+    max_data_points: Option<i64>,
+    range: QueryRequestRange,
+    targets: Vec<QueryTarget>,
+}
+
+impl QueryRequest {
+    /// Interval, in milliseconds, to group datapoints by.
+    ///
+    /// The interval requested by Grafana is increased if needed to stay within the
+    /// maximum number of datapoints requested.
+    fn interval_ms(&self) -> i64 {
+        let range_ms = (self.range.to - self.range.from).num_milliseconds().max(0);
+        let by_points = self
+            .max_data_points
+            .filter(|points| *points > 0)
+            .map(|points| range_ms / points)
+            .unwrap_or(0);
+        self.interval_ms
+            .unwrap_or(0)
+            .max(by_points)
+            .max(MIN_INTERVAL_MS)
+    }
+}
+
+/// Time-range for the query.
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
+struct QueryRequestRange {
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+}
+
+/// Metric requested by SimpleJson along with optional filters.
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
+struct QueryTarget {
+    target: String,
+
+    /// Additional JSON data attached to the target in the Grafana query editor.
+    #[serde(default)]
+    data: Option<QueryTargetData>,
+}
+
+/// Filters attached to targets as additional JSON data.
+#[derive(Clone, Default, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
+struct QueryTargetData {
+    /// Only return series for the given cluster instead of for the top clusters.
+    #[serde(default)]
+    cluster_id: Option<String>,
+}
+
+/// Response time series, a list of which is our response to SimpleJson.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+struct Series {
+    target: String,
+    datapoints: Vec<(f64, i64)>,
+}
+
+/// Count occurrences of keys in fixed intervals over a time range.
+struct Buckets {
+    counts: BTreeMap<String, BTreeMap<i64, u64>>,
+    from_ms: i64,
+    interval_ms: i64,
+    to_ms: i64,
+}
+
+impl Buckets {
+    /// Prepare buckets over a time range.
+    ///
+    /// The interval is increased if needed so no more than `MAX_DATA_POINTS` buckets are used.
+    fn new(from: DateTime<Utc>, to: DateTime<Utc>, interval_ms: i64) -> Buckets {
+        let from_ms = from.timestamp_millis();
+        let to_ms = to.timestamp_millis();
+        let range_ms = (to_ms - from_ms).max(0);
+        let min_interval_ms = range_ms / MAX_DATA_POINTS + 1;
+        Buckets {
+            counts: BTreeMap::new(),
+            from_ms,
+            interval_ms: interval_ms.max(min_interval_ms),
+            to_ms,
+        }
+    }
+
+    /// Count `count` occurrences of `key` at the given time.
+    fn count(&mut self, key: String, time: DateTime<Utc>, count: u64) {
+        let time_ms = time.timestamp_millis();
+        if time_ms < self.from_ms || time_ms > self.to_ms {
+            return;
+        }
+        let bucket = self.from_ms + (time_ms - self.from_ms) / self.interval_ms * self.interval_ms;
+        *self
+            .counts
+            .entry(key)
+            .or_insert_with(BTreeMap::new)
+            .entry(bucket)
+            .or_insert(0) += count;
+    }
+
+    /// Add counts aggregated by the store over the same intervals.
+    fn extend(&mut self, counts: Vec<IntervalCount>) {
+        for count in counts {
+            self.count(count.key, count.interval, count.count);
+        }
+    }
+
+    /// Convert counts into series, with zeros for intervals without occurrences.
+    fn into_series(self) -> Vec<Series> {
+        let from_ms = self.from_ms;
+        let interval_ms = self.interval_ms;
+        let to_ms = self.to_ms;
+        self.counts
+            .into_iter()
+            .map(|(target, counts)| {
+                let mut datapoints = Vec::new();
+                let mut bucket = from_ms;
+                while bucket <= to_ms {
+                    let count = counts.get(&bucket).cloned().unwrap_or(0);
+                    datapoints.push((count as f64, bucket));
+                    bucket += interval_ms;
+                }
+                Series { target, datapoints }
+            })
+            .collect()
+    }
+}
+
+/// Extract a metric from `ClusterMeta` records.
+fn cluster_metric(target: &str, meta: &ClusterMeta) -> Option<i32> {
+    let value = match target {
+        "cluster.agents_down" => meta.agents_down,
+        "cluster.nodes" => meta.nodes,
+        "cluster.nodes_down" => meta.nodes_down,
+        "cluster.shards_count" => meta.shards_count,
+        "cluster.shards_primaries" => meta.shards_primaries,
+        "cluster.shards_without_primary" => meta.shards_without_primary,
+        _ => return None,
+    };
+    Some(value)
+}
+
+/// Fetch cluster metadata for the requested cluster or for the top clusters.
+fn clusters(
+    data: &QueryData,
+    filters: &QueryTargetData,
+    span: Option<SpanContext>,
+) -> Result<Vec<ClusterMeta>> {
+    let legacy = data.primary_store.legacy();
+    if let Some(cluster_id) = &filters.cluster_id {
+        let meta = legacy
+            .cluster_meta(cluster_id.clone(), span)
+            .with_context(|_| ErrorKind::PrimaryStoreQuery("cluster_meta"))?;
+        return Ok(meta.into_iter().collect());
+    }
+    let mut clusters = Vec::new();
+    let metas = legacy
+        .top_clusters(span)
+        .with_context(|_| ErrorKind::PrimaryStoreQuery("top_clusters"))?;
+    for meta in metas {
+        let meta = meta.with_context(|_| ErrorKind::PrimaryStoreQuery("top_clusters"))?;
+        clusters.push(meta);
+    }
+    Ok(clusters)
+}
+
+/// Current value of a cluster metric, one series per cluster.
+///
+/// Cluster metadata is not versioned so only the latest value is known.
+fn query_cluster(
+    data: &QueryData,
+    request: &QueryRequest,
+    target: &str,
+    filters: &QueryTargetData,
+    span: Option<SpanContext>,
+) -> Result<Vec<Series>> {
+    let time = request.range.to.min(Utc::now()).timestamp_millis();
+    let series = clusters(data, filters, span)?
+        .into_iter()
+        .filter_map(|meta| {
+            cluster_metric(target, &meta).map(|value| Series {
+                target: meta.cluster_id,
+                datapoints: vec![(f64::from(value), time)],
+            })
+        })
+        .collect();
+    Ok(series)
+}
+
+/// Number of actions created over time, one series per action state.
+fn query_actions(
+    data: &QueryData,
+    request: &QueryRequest,
+    filters: &QueryTargetData,
+    span: Option<SpanContext>,
+) -> Result<Vec<Series>> {
+    let mut buckets = Buckets::new(request.range.from, request.range.to, request.interval_ms());
+    for meta in clusters(data, filters, span.clone())? {
+        let search = SearchFilters {
+            action_kind: None,
+            action_state: None,
+            from: request.range.from,
+            node_id: None,
+            until: request.range.to,
+        };
+        let counts = data
+            .view_store
+            .actions(meta.cluster_id)
+            .count_by_state(search, buckets.interval_ms, span.clone())
+            .with_context(|_| ErrorKind::ViewStoreQuery("actions"))?;
+        buckets.extend(counts);
+    }
+    Ok(buckets.into_series())
+}
+
+/// Number of events emitted over time, one series per event code.
+fn query_events(
+    data: &QueryData,
+    request: &QueryRequest,
+    filters: &QueryTargetData,
+    span: Option<SpanContext>,
+) -> Result<Vec<Series>> {
+    let mut events_filters = EventsFilters::most();
+    events_filters.cluster_ids = filters.cluster_id.iter().cloned().collect();
+    events_filters.start_from = Some(request.range.from);
+    events_filters.stop_at = Some(request.range.to);
+    let mut buckets = Buckets::new(request.range.from, request.range.to, request.interval_ms());
+    let counts = data
+        .view_store
+        .events()
+        .count_by_code(events_filters, buckets.interval_ms, span)
+        .with_context(|_| ErrorKind::ViewStoreQuery("events"))?;
+    buckets.extend(counts);
+    Ok(buckets.into_series())
+}
+
+async fn responder(
+    body: web::Json<QueryRequest>,
+    data: web::Data<QueryData>,
+    request: HttpRequest,
+) -> Result<impl Responder> {
+    let body = body.into_inner();
+    let mut request = request;
+    let series = with_request_span(&mut request, |span| -> Result<Vec<Series>> {
+        let span = span.map(|span| span.context().clone());
+        let mut series = Vec::new();
+        for target in &body.targets {
+            let filters = target.data.clone().unwrap_or_default();
+            let name = target.target.as_str();
+            if !TARGETS.contains(&name) {
+                return Err(ErrorKind::APIRequestParameterInvalid("target").into());
+            }
+            let mut results = match name {
+                "actions.by_state" => query_actions(&data, &body, &filters, span.clone())?,
+                "events.by_code" => query_events(&data, &body, &filters, span.clone())?,
+                name => query_cluster(&data, &body, name, &filters, span.clone())?,
+            };
+            series.append(&mut results);
+        }
+        Ok(series)
+    })?;
+    let response = HttpResponse::Ok().json(series);
+    Ok(response)
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::http::StatusCode;
+    use actix_web::test::call_service;
+    use actix_web::test::init_service;
+    use actix_web::test::TestRequest;
+    use actix_web::App;
+    use chrono::TimeZone;
+    use chrono::Utc;
+    use serde_json::json;
+
+    use replicante_store_view::store::IntervalCount;
+
+    use super::Buckets;
+    use super::QueryRequest;
+    use super::MAX_DATA_POINTS;
+    use crate::interfaces::test_support::MockInterfaces;
+
+    #[test]
+    fn buckets_fill_gaps() {
+        let from = Utc.timestamp(0, 0);
+        let to = Utc.timestamp(3, 0);
+        let mut buckets = Buckets::new(from, to, 1000);
+        buckets.count("A".into(), Utc.timestamp_millis(500), 1);
+        buckets.count("A".into(), Utc.timestamp_millis(700), 1);
+        buckets.count("A".into(), Utc.timestamp_millis(2100), 1);
+        buckets.count("B".into(), Utc.timestamp_millis(3000), 1);
+        buckets.count("B".into(), Utc.timestamp_millis(5000), 1);
+        let series = buckets.into_series();
+        assert_eq!(series.len(), 2);
+        assert_eq!(series[0].target, "A");
+        assert_eq!(
+            series[0].datapoints,
+            vec![(2.0, 0), (0.0, 1000), (1.0, 2000), (0.0, 3000)],
+        );
+        assert_eq!(series[1].target, "B");
+        assert_eq!(
+            series[1].datapoints,
+            vec![(0.0, 0), (0.0, 1000), (0.0, 2000), (1.0, 3000)],
+        );
+    }
+
+    #[test]
+    fn buckets_limited() {
+        let from = Utc.ymd(2020, 1, 1).and_hms(0, 0, 0);
+        let to = Utc.ymd(2021, 1, 1).and_hms(0, 0, 0);
+        let mut buckets = Buckets::new(from, to, 1000);
+        buckets.count("A".into(), from, 1);
+        let series = buckets.into_series();
+        assert!(series[0].datapoints.len() as i64 <= MAX_DATA_POINTS);
+    }
+
+    #[test]
+    fn buckets_from_store_counts() {
+        let from = Utc.timestamp(0, 0);
+        let to = Utc.timestamp(2, 0);
+        let mut buckets = Buckets::new(from, to, 1000);
+        buckets.extend(vec![
+            IntervalCount {
+                count: 3,
+                interval: Utc.timestamp(1, 0),
+                key: "A".into(),
+            },
+            IntervalCount {
+                count: 2,
+                interval: Utc.timestamp(0, 0),
+                key: "A".into(),
+            },
+        ]);
+        let series = buckets.into_series();
+        assert_eq!(
+            series[0].datapoints,
+            vec![(2.0, 0), (3.0, 1000), (0.0, 2000)]
+        );
+    }
+
+    #[actix_rt::test]
+    async fn unknown_cluster_target() {
+        let mocks = MockInterfaces::mock_quietly();
+        let mut interfaces = mocks.interfaces();
+        let query = super::Query::new(&mut interfaces);
+        let mut app = init_service(App::new().service(query.resource())).await;
+        let body = json!({
+            "range": {"from": "2020-01-01T00:00:00Z", "to": "2020-01-01T01:00:00Z"},
+            "targets": [{"target": "cluster.not_a_metric"}],
+        });
+        let request = TestRequest::post()
+            .uri("/query")
+            .set_json(&body)
+            .to_request();
+        let response = call_service(&mut app, request).await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[test]
+    fn interval_limited_by_max_points() {
+        let payload = r#"{
+            "intervalMs": 1000,
+            "maxDataPoints": 10,
+            "range": {"from": "2020-01-01T00:00:00Z", "to": "2020-01-01T01:00:00Z"},
+            "targets": [{"target": "events.by_code"}]
+        }"#;
+        let request: QueryRequest = serde_json::from_str(payload).unwrap();
+        assert_eq!(request.interval_ms(), 360_000);
+    }
+
+    #[test]
+    fn target_data() {
+        let payload = r#"{
+            "range": {"from": "2020-01-01T00:00:00Z", "to": "2020-01-01T01:00:00Z"},
+            "targets": [{"target": "cluster.nodes", "data": {"cluster_id": "test"}}]
+        }"#;
+        let request: QueryRequest = serde_json::from_str(payload).unwrap();
+        let data = request.targets[0].data.clone().unwrap();
+        assert_eq!(data.cluster_id, Some("test".to_string()));
+        assert_eq!(request.interval_ms(), 1000);
+    }
+}
@@ -0,0 +1,27 @@
+use actix_web::post;
+use actix_web::web;
+use actix_web::HttpResponse;
+use actix_web::Responder;
+use serde_derive::Deserialize;
+use serde_derive::Serialize;
+
+use super::query::TARGETS;
+
+/// Request data sent to us by SimpleJson.
+#[derive(Clone, Default, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
+struct SearchRequest {
+    #[serde(default)]
+    target: Option<String>,
+}
+
+/// List metrics available to the `/query` endpoint matching the requested target.
+#[post("/search")]
+pub async fn search(body: web::Json<SearchRequest>) -> impl Responder {
+    let target = body.into_inner().target.unwrap_or_default();
+    let targets: Vec<&str> = TARGETS
+        .iter()
+        .filter(|name| name.contains(target.as_str()))
+        .cloned()
+        .collect();
+    HttpResponse::Ok().json(targets)
+}
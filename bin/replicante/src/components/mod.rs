@@ -5,8 +5,10 @@ use slog::Logger;
 
 use replicante_util_upkeep::Upkeep;
 
+use replicore_component_alerts::Config as AlertsConfig;
 use replicore_component_discovery_scheduler::Config as DiscoveryConfig;
 use replicore_component_orchestrator_scheduler::Config as OrchestratorConfig;
+use replicore_component_webhooks::Config as WebhooksConfig;
 
 use super::metrics::COMPONENTS_ENABLED;
 use super::Config;
@@ -47,6 +49,19 @@ macro_rules! impl_component {
     };
 }
 
+impl_component!(Alerts, replicore_component_alerts::Alerts);
+impl Alerts {
+    fn new(config: AlertsConfig, interfaces: &Interfaces) -> Alerts {
+        let events = interfaces.streams.events.clone();
+        let logger = interfaces.logger.clone();
+        let store = interfaces.stores.primary.clone();
+        let tracer = interfaces.tracing.tracer();
+        let component =
+            replicore_component_alerts::Alerts::new(config, events, logger, store, tracer);
+        Alerts(component)
+    }
+}
+
 impl_component!(
     Discovery,
     replicore_component_discovery_scheduler::Discovery
@@ -75,10 +90,17 @@ impl_component!(
 );
 impl OrchestratorScheduler {
     fn new(config: OrchestratorConfig, interfaces: &Interfaces) -> OrchestratorScheduler {
+        let logger = interfaces.logger.clone();
+        let store = interfaces.stores.primary.clone();
+        let tasks = interfaces.tasks.clone();
+        let tracer = interfaces.tracing.tracer();
         let component = replicore_component_orchestrator_scheduler::OrchestratorScheduler::new(
             interfaces.coordinator.clone(),
             config,
-            interfaces.logger.clone(),
+            logger,
+            store,
+            tasks,
+            tracer,
         );
         OrchestratorScheduler(component)
     }
@@ -97,6 +119,19 @@ impl ViewUpdater {
     }
 }
 
+impl_component!(Webhooks, replicore_component_webhooks::Webhooks);
+impl Webhooks {
+    fn new(config: WebhooksConfig, interfaces: &Interfaces) -> Webhooks {
+        let events = interfaces.streams.events.clone();
+        let logger = interfaces.logger.clone();
+        let store = interfaces.stores.primary.clone();
+        let tracer = interfaces.tracing.tracer();
+        let component =
+            replicore_component_webhooks::Webhooks::new(config, events, logger, store, tracer);
+        Webhooks(component)
+    }
+}
+
 /// Helper function to keep `Components::new` simpler in the presence of optional components.
 macro_rules! init_components {
     {
@@ -165,9 +200,13 @@ impl Components {
     pub fn new(config: &Config, logger: Logger, interfaces: &mut Interfaces) -> Result<Components> {
         let components = init_components! {
             let logger = &logger;
+            component("alerts", "required") {
+                let enabled = config.components.alerts();
+                Alerts::new(config.alerts.clone(), interfaces)
+            }
             component("core_api", "required") {
                 let enabled = config.components.core_api();
-                CoreAPI::new(logger.clone(), interfaces)
+                CoreAPI::new(config, logger.clone(), interfaces)
             }
             component("discovery", "required") {
                 let enabled = config.components.discovery();
@@ -195,6 +234,10 @@ impl Components {
                 let enabled = config.components.viewupdater();
                 ViewUpdater::new(interfaces)
             }
+            component("webhooks", "optional") {
+                let enabled = config.components.webhooks();
+                Webhooks::new(config.webhooks.clone(), interfaces)
+            }
             component("webui", "optional") {
                 let enabled = config.components.webui();
                 WebUI::new(interfaces)
@@ -212,7 +255,10 @@ impl Components {
     /// Metrics that fail to register are logged and ignored.
     pub fn register_metrics(logger: &Logger, registry: &Registry) {
         self::core_api::register_metrics(logger, registry);
+        replicore_component_alerts::register_metrics(logger, registry);
         replicore_component_discovery_scheduler::register_metrics(logger, registry);
+        replicore_component_orchestrator_scheduler::register_metrics(logger, registry);
+        replicore_component_webhooks::register_metrics(logger, registry);
         self::workers::register_metrics(logger, registry);
     }
 
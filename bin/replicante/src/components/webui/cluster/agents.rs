@@ -12,6 +12,7 @@ use serde_derive::Serialize;
 use slog::Logger;
 
 use replicante_models_core::agent::Agent;
+use replicante_models_core::agent::AgentCircuit;
 use replicante_models_core::agent::AgentInfo;
 use replicante_models_core::agent::AgentStatus;
 use replicante_store_primary::store::Store;
@@ -108,6 +109,7 @@ async fn responder(data: web::Data<AgentsData>, request: HttpRequest) -> Result<
 /// Agent details returned by the API.
 #[derive(Clone, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
 struct AgentDetails {
+    pub circuit: Option<AgentCircuit>,
     pub host: String,
     pub status: AgentStatus,
     pub version_checkout: Option<String>,
@@ -118,6 +120,7 @@ struct AgentDetails {
 impl AgentDetails {
     pub fn combine(agent: Agent, details: Option<AgentInfo>) -> AgentDetails {
         let mut record = AgentDetails {
+            circuit: agent.circuit,
             host: agent.host,
             status: agent.status,
             version_checkout: None,
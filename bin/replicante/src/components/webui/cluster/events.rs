@@ -61,7 +61,7 @@ async fn responder(data: web::Data<EventsData>, request: HttpRequest) -> Result<
         .to_string();
 
     let mut filters = EventsFilters::all();
-    filters.cluster_id = Some(cluster_id);
+    filters.cluster_ids = vec![cluster_id];
     let mut options = EventsOptions::default();
     options.limit = Some(RECENT_EVENTS_LIMIT);
     options.reverse = true;
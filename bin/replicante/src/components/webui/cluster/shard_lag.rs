@@ -0,0 +1,189 @@
+use std::sync::Arc;
+
+use actix_web::dev::HttpServiceFactory;
+use actix_web::web;
+use actix_web::HttpRequest;
+use actix_web::HttpResponse;
+use actix_web::Responder;
+use chrono::DateTime;
+use chrono::Duration;
+use chrono::Utc;
+use failure::ResultExt;
+use serde_derive::Deserialize;
+use slog::Logger;
+
+use replicante_models_core::agent::ShardLagSample;
+use replicante_store_view::store::Store;
+use replicante_util_actixweb::with_request_span;
+use replicante_util_actixweb::TracingMiddleware;
+
+use super::super::constants::SHARD_LAG_DEFAULT_RANGE_MINUTES;
+use crate::ErrorKind;
+use crate::Interfaces;
+use crate::Result;
+
+pub struct ShardLag {
+    data: ShardLagData,
+    logger: Logger,
+    tracer: Arc<opentracingrust::Tracer>,
+}
+
+impl ShardLag {
+    pub fn new(interfaces: &mut Interfaces) -> ShardLag {
+        let data = ShardLagData {
+            store: interfaces.stores.view.clone(),
+        };
+        ShardLag {
+            data,
+            logger: interfaces.logger.clone(),
+            tracer: interfaces.tracing.tracer(),
+        }
+    }
+
+    pub fn resource(&self) -> impl HttpServiceFactory {
+        let logger = self.logger.clone();
+        let tracer = Arc::clone(&self.tracer);
+        let tracer = TracingMiddleware::with_name(
+            logger,
+            tracer,
+            "/cluster/{cluster_id}/shard/{shard_id}/lag",
+        );
+        web::resource("/shard/{shard_id}/lag")
+            .data(self.data.clone())
+            .wrap(tracer)
+            .route(web::get().to(responder))
+    }
+}
+
+#[derive(Clone)]
+struct ShardLagData {
+    store: Store,
+}
+
+/// Time range to return lag samples for.
+///
+/// Defaults to the last `SHARD_LAG_DEFAULT_RANGE_MINUTES` minutes.
+#[derive(Clone, Debug, Deserialize)]
+struct ShardLagQuery {
+    from: Option<DateTime<Utc>>,
+    until: Option<DateTime<Utc>>,
+}
+
+async fn responder(
+    query: web::Query<ShardLagQuery>,
+    data: web::Data<ShardLagData>,
+    request: HttpRequest,
+) -> Result<impl Responder> {
+    let path = request.match_info();
+    let cluster_id = path
+        .get("cluster_id")
+        .ok_or(ErrorKind::APIRequestParameterNotFound("cluster_id"))?
+        .to_string();
+    let shard_id = path
+        .get("shard_id")
+        .ok_or(ErrorKind::APIRequestParameterNotFound("shard_id"))?
+        .to_string();
+    let query = query.into_inner();
+    let until = query.until.unwrap_or_else(Utc::now);
+    let from = query
+        .from
+        .unwrap_or_else(|| until - Duration::minutes(SHARD_LAG_DEFAULT_RANGE_MINUTES));
+
+    let mut request = request;
+    let cursor = with_request_span(&mut request, |span| {
+        let span = span.map(|span| span.context().clone());
+        data.store
+            .shards(cluster_id)
+            .lag(shard_id, from, until, span)
+            .with_context(|_| ErrorKind::ViewStoreQuery("shard lag"))
+    })?;
+    let mut samples: Vec<ShardLagSample> = Vec::new();
+    for sample in cursor {
+        let sample = sample
+            .with_context(|_| ErrorKind::Deserialize("shard lag sample", "ShardLagSample"))?;
+        samples.push(sample);
+    }
+
+    let response = HttpResponse::Ok().json(samples);
+    Ok(response)
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::test::call_service;
+    use actix_web::test::init_service;
+    use actix_web::test::read_response_json;
+    use actix_web::test::TestRequest;
+    use actix_web::web;
+    use actix_web::App;
+    use chrono::TimeZone;
+    use chrono::Utc;
+
+    use replicante_models_core::agent::CommitOffset;
+    use replicante_models_core::agent::ShardLagSample;
+
+    use crate::interfaces::test_support::MockInterfaces;
+
+    fn sample(shard_id: &str, minute: u32) -> ShardLagSample {
+        ShardLagSample {
+            cluster_id: "c1".into(),
+            commit_offset: Some(CommitOffset::seconds(i64::from(minute))),
+            lag: Some(CommitOffset::seconds(2)),
+            node_id: "n1".into(),
+            shard_id: shard_id.into(),
+            timestamp: Utc.ymd(2020, 6, 1).and_hms(12, minute, 0),
+        }
+    }
+
+    fn mock_samples(mocks: &MockInterfaces, samples: Vec<ShardLagSample>) {
+        mocks
+            .stores
+            .view
+            .state
+            .lock()
+            .unwrap()
+            .shards_lag
+            .extend(samples);
+    }
+
+    #[actix_rt::test]
+    async fn get_lag_in_range() {
+        let mocks = MockInterfaces::mock_quietly();
+        mock_samples(
+            &mocks,
+            vec![
+                sample("s1", 20),
+                sample("s1", 5),
+                sample("s2", 10),
+                sample("s1", 10),
+                sample("s1", 50),
+            ],
+        );
+
+        let mut interfaces = mocks.interfaces();
+        let lag = super::ShardLag::new(&mut interfaces);
+        let app = App::new().service(web::scope("/cluster/{cluster_id}").service(lag.resource()));
+        let mut app = init_service(app).await;
+
+        let request = TestRequest::get()
+            .uri("/cluster/c1/shard/s1/lag?from=2020-06-01T12:10:00Z&until=2020-06-01T12:30:00Z")
+            .to_request();
+        let response: Vec<ShardLagSample> = read_response_json(&mut app, request).await;
+        assert_eq!(response, vec![sample("s1", 10), sample("s1", 20)]);
+    }
+
+    #[actix_rt::test]
+    async fn invalid_range_is_rejected() {
+        let mocks = MockInterfaces::mock_quietly();
+        let mut interfaces = mocks.interfaces();
+        let lag = super::ShardLag::new(&mut interfaces);
+        let app = App::new().service(web::scope("/cluster/{cluster_id}").service(lag.resource()));
+        let mut app = init_service(app).await;
+
+        let request = TestRequest::get()
+            .uri("/cluster/c1/shard/s1/lag?from=yesterday")
+            .to_request();
+        let response = call_service(&mut app, request).await;
+        assert!(response.status().is_client_error());
+    }
+}
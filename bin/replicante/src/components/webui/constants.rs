@@ -1,2 +1,3 @@
 pub const FIND_CLUSTERS_LIMIT: u8 = 25;
 pub const RECENT_EVENTS_LIMIT: i64 = 100;
+pub const SHARD_LAG_DEFAULT_RANGE_MINUTES: i64 = 60;
@@ -107,6 +107,7 @@ impl Workers {
                     interfaces.streams.events.clone(),
                     logger.clone(),
                     interfaces.stores.primary.clone(),
+                    interfaces.tasks.clone(),
                     interfaces.tracing.tracer(),
                 )
             },
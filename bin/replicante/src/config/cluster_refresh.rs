@@ -0,0 +1,97 @@
+use std::time::Duration;
+
+use serde_derive::Deserialize;
+use serde_derive::Serialize;
+
+use replicante_cluster_fetcher::CircuitBreaker;
+
+/// Cluster refresh configuration options.
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
+pub struct ClusterRefreshConfig {
+    /// Circuit breaker for agents that consecutively fail to respond.
+    #[serde(default)]
+    pub agents_circuit: AgentsCircuitConfig,
+
+    /// Seconds an agent client can go unused before it is removed from the client pool.
+    #[serde(default = "ClusterRefreshConfig::default_client_idle_timeout")]
+    pub client_idle_timeout: u64,
+
+    /// Maximum number of nodes in a cluster to fetch state from at the same time.
+    #[serde(default = "ClusterRefreshConfig::default_fetch_parallelism")]
+    pub fetch_parallelism: usize,
+}
+
+impl Default for ClusterRefreshConfig {
+    fn default() -> ClusterRefreshConfig {
+        ClusterRefreshConfig {
+            agents_circuit: AgentsCircuitConfig::default(),
+            client_idle_timeout: Self::default_client_idle_timeout(),
+            fetch_parallelism: Self::default_fetch_parallelism(),
+        }
+    }
+}
+
+impl ClusterRefreshConfig {
+    /// Default value for `client_idle_timeout` used by serde.
+    fn default_client_idle_timeout() -> u64 {
+        600
+    }
+
+    /// Default value for `fetch_parallelism` used by serde.
+    fn default_fetch_parallelism() -> usize {
+        8
+    }
+}
+
+/// Circuit breaker configuration for agents that consecutively fail to respond.
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
+pub struct AgentsCircuitConfig {
+    /// Seconds to wait before probing an agent once the circuit opens.
+    #[serde(default = "AgentsCircuitConfig::default_backoff_initial")]
+    pub backoff_initial: u64,
+
+    /// Maximum number of seconds to wait between probes of an agent.
+    #[serde(default = "AgentsCircuitConfig::default_backoff_max")]
+    pub backoff_max: u64,
+
+    /// Number of consecutive failures after which the circuit opens.
+    #[serde(default = "AgentsCircuitConfig::default_threshold")]
+    pub threshold: i32,
+}
+
+impl Default for AgentsCircuitConfig {
+    fn default() -> AgentsCircuitConfig {
+        AgentsCircuitConfig {
+            backoff_initial: Self::default_backoff_initial(),
+            backoff_max: Self::default_backoff_max(),
+            threshold: Self::default_threshold(),
+        }
+    }
+}
+
+impl AgentsCircuitConfig {
+    /// Default value for `backoff_initial` used by serde.
+    fn default_backoff_initial() -> u64 {
+        30
+    }
+
+    /// Default value for `backoff_max` used by serde.
+    fn default_backoff_max() -> u64 {
+        900
+    }
+
+    /// Default value for `threshold` used by serde.
+    fn default_threshold() -> i32 {
+        3
+    }
+}
+
+impl From<AgentsCircuitConfig> for CircuitBreaker {
+    fn from(config: AgentsCircuitConfig) -> CircuitBreaker {
+        CircuitBreaker {
+            backoff_initial: Duration::from_secs(config.backoff_initial),
+            backoff_max: Duration::from_secs(config.backoff_max),
+            threshold: config.threshold,
+        }
+    }
+}
@@ -8,6 +8,10 @@ pub struct ComponentsConfig {
     #[serde(default = "ComponentsConfig::default_default", rename = "_default")]
     default: bool,
 
+    /// Enable evaluation of alert rules against the events stream.
+    #[serde(default)]
+    alerts: Option<bool>,
+
     /// Enable Replicante Core API endpoints.
     #[serde(default)]
     core_api: Option<bool>,
@@ -31,6 +35,10 @@ pub struct ComponentsConfig {
     /// Enable the view DB updater.
     viewupdater: Option<bool>,
 
+    /// Enable forwarding of events to HTTP webhook endpoints (optional).
+    #[serde(default = "ComponentsConfig::default_false")]
+    webhooks: bool,
+
     /// Enable the WebUI API endpoints (optional).
     #[serde(default)]
     webui: Option<bool>,
@@ -44,12 +52,14 @@ impl Default for ComponentsConfig {
     fn default() -> Self {
         Self {
             default: Self::default_default(),
+            alerts: None,
             core_api: None,
             discovery: None,
             grafana: None,
             orchestrator: None,
             update_checker: Self::default_false(),
             viewupdater: None,
+            webhooks: Self::default_false(),
             webui: None,
             workers: None,
         }
@@ -67,6 +77,11 @@ impl ComponentsConfig {
 }
 
 impl ComponentsConfig {
+    /// Check if the alerts component is enabled.
+    pub fn alerts(&self) -> bool {
+        self.alerts.unwrap_or(self.default)
+    }
+
     /// Check if the core API component is enabled.
     pub fn core_api(&self) -> bool {
         self.core_api.unwrap_or(self.default)
@@ -97,6 +112,11 @@ impl ComponentsConfig {
         self.viewupdater.unwrap_or(self.default)
     }
 
+    /// Check if the webhooks component is enabled.
+    pub fn webhooks(&self) -> bool {
+        self.webhooks
+    }
+
     /// Check if the WebUI endpoints component is enabled.
     pub fn webui(&self) -> bool {
         self.webui.unwrap_or(self.default)
@@ -9,24 +9,30 @@ use serde_derive::Serialize;
 use replicante_logging::Config as LoggingConfig;
 use replicante_logging::LoggingLevel;
 use replicante_models_core::scope::Namespace;
+use replicante_models_core::scope::NsActions;
+use replicante_models_core::scope::NsHealth;
 use replicante_models_core::scope::NsHttpsTransport;
 use replicante_service_coordinator::Config as CoordinatorConfig;
 use replicante_service_tasks::Config as TasksConfig;
 use replicante_stream::StreamConfig;
 use replicante_util_tracing::Config as TracingConfig;
+use replicore_component_alerts::Config as AlertsConfig;
 use replicore_component_discovery_scheduler::Config as DiscoveryConfig;
 use replicore_component_orchestrator_scheduler::Config as OrchestratorConfig;
+use replicore_component_webhooks::Config as WebhooksConfig;
 
 use crate::interfaces::api::Config as APIConfig;
 use crate::ErrorKind;
 use crate::Result;
 
+mod cluster_refresh;
 mod components;
 mod sentry;
 mod storage;
 mod task_workers;
 mod timeouts;
 
+pub use self::cluster_refresh::ClusterRefreshConfig;
 pub use self::components::ComponentsConfig;
 pub use self::sentry::SentryCaptureApi;
 pub use self::sentry::SentryConfig;
@@ -49,6 +55,14 @@ pub struct Config {
     #[serde(default)]
     pub api: APIConfig,
 
+    /// Cluster refresh configuration options.
+    #[serde(default)]
+    pub cluster_refresh: ClusterRefreshConfig,
+
+    /// Alert rules evaluation options.
+    #[serde(default)]
+    pub alerts: AlertsConfig,
+
     /// Components enabling configuration.
     #[serde(default)]
     pub components: ComponentsConfig,
@@ -94,7 +108,11 @@ pub struct Config {
     #[serde(default)]
     pub tracing: TracingConfig,
 
-    /// Settings that will move to the DB once namespaces are fully introduced.
+    /// Events forwarding to HTTP webhook endpoints.
+    #[serde(default)]
+    pub webhooks: WebhooksConfig,
+
+    /// Default settings for namespaces that are not stored in the primary store.
     #[serde(default)]
     pub tmp_namespace_settings: TmpNsSettings,
 }
@@ -142,7 +160,7 @@ impl Config {
     }
 }
 
-/// Settings that will move to the DB once namespaces are fully introduced.
+/// Default settings for namespaces that are not stored in the primary store.
 #[derive(Clone, Default, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
 pub struct TmpNsSettings {
     /// HTTPS configuration for agent transport.
@@ -154,6 +172,8 @@ impl From<TmpNsSettings> for Namespace {
     fn from(config: TmpNsSettings) -> Namespace {
         Namespace {
             ns_id: "tmp_global_namespace".into(),
+            actions: NsActions::default(),
+            health: NsHealth::default(),
             https_transport: NsHttpsTransport {
                 ca_bundle: config.https_transport.ca_bundle,
                 client_key_id: config.https_transport.client_key,
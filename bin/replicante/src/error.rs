@@ -70,6 +70,18 @@ impl ResponseError for Error {
 /// Exhaustive list of possible errors emitted by this crate.
 #[derive(Debug, Fail)]
 pub enum ErrorKind {
+    #[fail(display = "action with ID {} has already finished", _0)]
+    ActionAlreadyFinished(String),
+
+    #[fail(
+        display = "action with ID {} is no longer pending and can't be cancelled",
+        _0
+    )]
+    ActionNotPending(String),
+
+    #[fail(display = "could not cancel action on agent {}", _0)]
+    AgentActionCancel(String),
+
     #[fail(display = "the request body is not valid")]
     APIRequestBodyInvalid,
 
@@ -121,6 +133,12 @@ pub enum ErrorKind {
     #[fail(display = "could not find model {} with ID {}", _0, _1)]
     ModelNotFound(&'static str, String),
 
+    #[fail(
+        display = "namespace {} can't be deleted while it has clusters (such as {})",
+        _0, _1
+    )]
+    NamespaceInUse(String, String),
+
     #[fail(display = "could not delete {} from the primary store", _0)]
     PrimaryStoreDelete(&'static str),
 
@@ -152,11 +170,14 @@ pub enum ErrorKind {
 impl ErrorKind {
     fn http_status(&self) -> StatusCode {
         match self {
+            Self::ActionAlreadyFinished(_) => StatusCode::CONFLICT,
+            Self::ActionNotPending(_) => StatusCode::CONFLICT,
             Self::APIRequestBodyInvalid => StatusCode::BAD_REQUEST,
             Self::APIRequestBodyNotFound => StatusCode::BAD_REQUEST,
             Self::APIRequestParameterInvalid(_) => StatusCode::BAD_REQUEST,
             Self::APIRequestParameterNotFound(_) => StatusCode::BAD_REQUEST,
             Self::ModelNotFound(_, _) => StatusCode::NOT_FOUND,
+            Self::NamespaceInUse(_, _) => StatusCode::CONFLICT,
             Self::ValidateFailed(_) => StatusCode::BAD_REQUEST,
             _ => StatusCode::INTERNAL_SERVER_ERROR,
         }
@@ -164,6 +185,9 @@ impl ErrorKind {
 
     fn kind_name(&self) -> Option<&str> {
         let name = match self {
+            ErrorKind::ActionAlreadyFinished(_) => "ActionAlreadyFinished",
+            ErrorKind::ActionNotPending(_) => "ActionNotPending",
+            ErrorKind::AgentActionCancel(_) => "AgentActionCancel",
             ErrorKind::APIRequestBodyInvalid => "APIRequestBodyInvalid",
             ErrorKind::APIRequestBodyNotFound => "APIRequestBodyNotFound",
             ErrorKind::APIRequestParameterInvalid(_) => "APIRequestParameterInvalid",
@@ -181,6 +205,7 @@ impl ErrorKind {
             ErrorKind::InterfaceAlreadyRunning(_) => "InterfaceAlreadyRunning",
             ErrorKind::InterfaceInit(_) => "InterfaceInit",
             ErrorKind::ModelNotFound(_, _) => "ModelNotFound",
+            ErrorKind::NamespaceInUse(_, _) => "NamespaceInUse",
             ErrorKind::PrimaryStoreQuery(_) => "PrimaryStoreQuery",
             ErrorKind::PrimaryStoreDelete(_) => "PrimaryStoreDelete",
             ErrorKind::PrimaryStorePersist(_) => "PrimaryStorePersist",
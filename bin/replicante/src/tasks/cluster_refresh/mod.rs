@@ -11,12 +11,17 @@ use opentracingrust::Span;
 use slog::info;
 use slog::Logger;
 
+use replicante_agent_client::ClientPool;
 use replicante_cluster_aggregator::Aggregator;
 use replicante_cluster_fetcher::Fetcher;
+use replicante_models_core::cluster::discovery::ClusterDiscovery;
+use replicante_models_core::cluster::OrchestrateReport;
 use replicante_models_core::scope::Namespace;
 use replicante_service_coordinator::Coordinator;
 use replicante_service_coordinator::ErrorKind as CoordinatorErrorKind;
+use replicante_service_coordinator::NonBlockingLock;
 use replicante_service_tasks::TaskHandler;
+use replicante_store_primary::store::Store;
 use replicante_util_failure::capture_fail;
 use replicante_util_failure::failure_info;
 use replicante_util_tracing::fail_span;
@@ -43,10 +48,11 @@ pub struct Handler {
     coordinator: Coordinator,
     fetcher: Fetcher,
     logger: Logger,
+    store: Store,
     tracing: Tracing,
 
-    // TODO: remove when namespaces are done properly from the primary store.
-    tmp_global_namespace: Namespace,
+    /// Settings for clusters in namespaces that are not stored.
+    default_namespace: Namespace,
 }
 
 impl Handler {
@@ -57,24 +63,37 @@ impl Handler {
         agents_timeout: Duration,
     ) -> Handler {
         let primary_store = interfaces.stores.primary.clone();
-        let aggregator = Aggregator::new(logger.clone(), primary_store.clone());
+        let aggregator = Aggregator::new(
+            logger.clone(),
+            interfaces.streams.events.clone(),
+            primary_store.clone(),
+        );
         let coordinator = interfaces.coordinator.clone();
+        let clients = ClientPool::new(
+            logger.clone(),
+            agents_timeout,
+            Duration::from_secs(config.cluster_refresh.client_idle_timeout),
+            interfaces.tracing.tracer(),
+        );
         let fetcher = Fetcher::new(
             logger.clone(),
             interfaces.streams.events.clone(),
-            primary_store,
-            agents_timeout,
+            primary_store.clone(),
+            clients,
+            config.cluster_refresh.agents_circuit.clone().into(),
+            config.cluster_refresh.fetch_parallelism,
             interfaces.tracing.tracer(),
         );
         let tracing = interfaces.tracing.clone();
-        let tmp_global_namespace = config.tmp_namespace_settings.clone().into();
+        let default_namespace = config.tmp_namespace_settings.clone().into();
         Handler {
             aggregator,
             coordinator,
             fetcher,
             logger,
+            store: primary_store,
             tracing,
-            tmp_global_namespace,
+            default_namespace,
         }
     }
 
@@ -84,6 +103,7 @@ impl Handler {
             .with_context(|_| ErrorKind::Deserialize("task payload", "ClusterRefreshPayload"))?;
         let discovery = payload.cluster;
         let snapshot = payload.snapshot;
+        let ns_id = payload.namespace;
         span.tag("cluster.id", discovery.cluster_id.clone());
         span.tag("emit.snapshot", snapshot);
 
@@ -110,19 +130,37 @@ impl Handler {
         };
 
         // Fetch cluster's namespace model.
-        // TODO: replace with store access when namespaces are done properly.
-        let ns = self.tmp_global_namespace.clone();
+        let ns = self.namespace(ns_id, span)?;
 
         // Refresh cluster state.
         let cluster_id = discovery.cluster_id.clone();
-        let refresh_id = Utc::now().timestamp();
+        let ns_id = ns.ns_id.clone();
+        let start_time = Utc::now();
+        let refresh_id = start_time.timestamp();
         let timer = REFRESH_DURATION.start_timer();
-        self.fetcher
-            .fetch(ns, discovery.clone(), refresh_id, lock.watch(), span)
-            .with_context(|_| ErrorKind::ClusterRefresh)?;
-        self.aggregator
-            .aggregate(discovery, lock.watch(), span)
-            .with_context(|_| ErrorKind::ClusterAggregation)?;
+        let result = self.refresh(ns, discovery, refresh_id, &lock, span);
+
+        // Record a summary of the orchestration run with the cluster settings.
+        let report = match &result {
+            Ok(()) => OrchestrateReport::success(ns_id, cluster_id.clone(), start_time),
+            Err(error) => {
+                OrchestrateReport::failed(ns_id, cluster_id.clone(), start_time, error.to_string())
+            }
+        };
+        if let Err(error) = self
+            .store
+            .persist()
+            .cluster_orchestrate_report(report, span.context().clone())
+        {
+            capture_fail!(
+                &error,
+                self.logger,
+                "Unable to persist cluster orchestration report";
+                "cluster_id" => &cluster_id,
+                failure_info(&error),
+            );
+        }
+        result?;
 
         // Done.
         timer.observe_duration();
@@ -131,6 +169,51 @@ impl Handler {
         info!(self.logger, "Cluster state refresh completed"; "cluster_id" => cluster_id);
         Ok(())
     }
+
+    /// Resolve the namespace settings to refresh a cluster with.
+    ///
+    /// Namespaces that are not stored use the default settings from the configuration.
+    fn namespace(&self, ns_id: Option<String>, span: &mut Span) -> Result<Namespace> {
+        let ns_id = match ns_id {
+            None => return Ok(self.default_namespace.clone()),
+            Some(ns_id) => ns_id,
+        };
+        let ns = self
+            .store
+            .namespace(ns_id.clone())
+            .get(span.context().clone())
+            .with_context(|_| ErrorKind::PrimaryStoreQuery("namespace"))?;
+        let ns = ns.unwrap_or_else(|| {
+            let mut ns = self.default_namespace.clone();
+            ns.ns_id = ns_id;
+            ns
+        });
+        Ok(ns)
+    }
+
+    /// Fetch the cluster state and aggregate it into cluster-level models.
+    fn refresh(
+        &self,
+        ns: Namespace,
+        discovery: ClusterDiscovery,
+        refresh_id: i64,
+        lock: &NonBlockingLock,
+        span: &mut Span,
+    ) -> Result<()> {
+        self.fetcher
+            .fetch(
+                ns.clone(),
+                discovery.clone(),
+                refresh_id,
+                lock.watch(),
+                span,
+            )
+            .with_context(|_| ErrorKind::ClusterRefresh)?;
+        self.aggregator
+            .aggregate(ns, discovery, lock.watch(), span)
+            .with_context(|_| ErrorKind::ClusterAggregation)?;
+        Ok(())
+    }
 }
 
 impl TaskHandler<ReplicanteQueues> for Handler {
@@ -15,6 +15,7 @@ const ENDPOINT_APPLY: &str = "/api/unstable/core/apply";
 const ENDPOINT_CLUSTER: &str = "/api/unstable/core/cluster";
 const ENDPOINT_CLUSTER_ACTION: &str = "action";
 const ENDPOINT_CLUSTER_ACTION_APPROVE: &str = "approve";
+const ENDPOINT_CLUSTER_ACTION_CANCEL: &str = "cancel";
 const ENDPOINT_CLUSTER_ACTION_DISAPPROVE: &str = "disapprove";
 const ENDPOINT_CLUSTER_REFRESH: &str = "refresh";
 const ENDPOINT_DISCOVERY_SETTINGS: &str = "/api/unstable/core/discoverysettings";
@@ -53,6 +54,31 @@ impl RepliClient {
         Ok(())
     }
 
+    /// Cancel an action that has not finished yet.
+    pub async fn action_cancel(&self, cluster: &str, action: Uuid) -> Result<()> {
+        debug!(
+            self.logger, "About to POST action cancel request";
+            "action" => %action,
+            "cluster" => cluster,
+        );
+        let uri = format!(
+            "{}/{}/{}/{}/{}",
+            ENDPOINT_CLUSTER,
+            cluster,
+            ENDPOINT_CLUSTER_ACTION,
+            action,
+            ENDPOINT_CLUSTER_ACTION_CANCEL,
+        );
+        let request = self.client.post(&uri);
+        let response = self
+            .client
+            .send(request)
+            .await
+            .context("Unable to cancel action")?;
+        response.check_status()?;
+        Ok(())
+    }
+
     /// Dispprove a PENDING_APPROVE action so it will not be scheduled.
     pub async fn action_disapprove(&self, cluster: &str, action: Uuid) -> Result<()> {
         debug!(
@@ -4,6 +4,7 @@ use structopt::StructOpt;
 use uuid::Uuid;
 
 mod approve;
+mod cancel;
 mod disapprove;
 
 // Command line options common to all action commands.
@@ -28,6 +29,9 @@ pub enum Opt {
     /// Approve an action that is pending approval.
     Approve(CommonOpt),
 
+    /// Cancel an action that has not finished yet.
+    Cancel(CommonOpt),
+
     /// Disapprove (reject) an action that is pending approval.
     Disapprove(CommonOpt),
 }
@@ -36,6 +40,7 @@ pub enum Opt {
 pub async fn execute(logger: &Logger, opt: &crate::Opt, action_cmd: &Opt) -> Result<i32> {
     match &action_cmd {
         Opt::Approve(approve_opt) => approve::execute(logger, opt, approve_opt).await,
+        Opt::Cancel(cancel_opt) => cancel::execute(logger, opt, cancel_opt).await,
         Opt::Disapprove(disapprove_opt) => disapprove::execute(logger, opt, disapprove_opt).await,
     }
 }
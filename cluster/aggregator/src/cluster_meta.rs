@@ -1,10 +1,15 @@
+use std::collections::HashMap;
 use std::collections::HashSet;
 
 use failure::ResultExt;
 use opentracingrust::Span;
 
+use replicante_models_core::agent::CommitUnit;
+use replicante_models_core::agent::ShardRole;
 use replicante_models_core::cluster::discovery::ClusterDiscovery;
+use replicante_models_core::cluster::ClusterHealth;
 use replicante_models_core::cluster::ClusterMeta;
+use replicante_models_core::scope::NsHealth;
 use replicante_store_primary::store::Store;
 
 use super::ErrorKind;
@@ -14,11 +19,16 @@ pub(crate) struct ClusterMetaAggregator {
     agents_down: i32,
     cluster_display_name: Option<String>,
     cluster_id: String,
+    degraded_lag: i64,
     kinds: HashSet<String>,
+    max_lag_seconds: Option<i64>,
     nodes: i32,
     nodes_down: i32,
     shards_count: i32,
+    shards_ids: i32,
     shards_primaries: i32,
+    shards_without_primary: i32,
+    versions: HashSet<String>,
 }
 
 impl ClusterMetaAggregator {
@@ -46,6 +56,38 @@ impl ClusterMetaAggregator {
             .with_context(|_| ErrorKind::StoreRead("shards counts"))?;
         self.shards_count = counts.shards;
         self.shards_primaries = counts.primaries;
+
+        // Collect datastore versions to detect version skew.
+        let nodes = store
+            .nodes(self.cluster_id.clone())
+            .iter(span.context().clone())
+            .with_context(|_| ErrorKind::StoreRead("nodes"))?;
+        for node in nodes {
+            let node = node.with_context(|_| ErrorKind::StoreRead("node"))?;
+            self.versions.insert(node.version);
+        }
+
+        // Look for shards without a primary and the max replication lag.
+        let shards = store
+            .shards(self.cluster_id.clone())
+            .iter(span.context().clone())
+            .with_context(|_| ErrorKind::StoreRead("shards"))?;
+        let mut primaries: HashMap<String, bool> = HashMap::new();
+        for shard in shards {
+            let shard = shard.with_context(|_| ErrorKind::StoreRead("shard"))?;
+            let has_primary = primaries.entry(shard.shard_id).or_insert(false);
+            *has_primary = *has_primary || shard.role == ShardRole::Primary;
+            match shard.lag {
+                Some(ref lag) if lag.unit == CommitUnit::Seconds => {
+                    let max = self.max_lag_seconds.unwrap_or(lag.value).max(lag.value);
+                    self.max_lag_seconds = Some(max);
+                }
+                _ => (),
+            }
+        }
+        self.shards_ids = primaries.len() as i32;
+        self.shards_without_primary =
+            primaries.values().filter(|primary| !**primary).count() as i32;
         Ok(())
     }
 
@@ -68,19 +110,137 @@ impl ClusterMetaAggregator {
         meta.nodes_down = self.nodes_down;
         meta.shards_count = self.shards_count;
         meta.shards_primaries = self.shards_primaries;
+        meta.health = self.health();
+        meta.max_lag_seconds = self.max_lag_seconds;
+        meta.shards_without_primary = self.shards_without_primary;
+        let mut versions: Vec<String> = self.versions.into_iter().collect();
+        versions.sort();
+        meta.versions = versions;
         meta
     }
 
-    pub(crate) fn new(discovery: &ClusterDiscovery) -> ClusterMetaAggregator {
+    pub(crate) fn new(discovery: &ClusterDiscovery, health: &NsHealth) -> ClusterMetaAggregator {
         ClusterMetaAggregator {
             agents_down: 0,
             cluster_display_name: discovery.display_name.clone(),
             cluster_id: discovery.cluster_id.clone(),
+            degraded_lag: health.degraded_lag,
             kinds: HashSet::new(),
+            max_lag_seconds: None,
             nodes: 0,
             nodes_down: 0,
             shards_count: 0,
+            shards_ids: 0,
             shards_primaries: 0,
+            shards_without_primary: 0,
+            versions: HashSet::new(),
         }
     }
 }
+
+impl ClusterMetaAggregator {
+    /// Classify the cluster health based on the aggregated data.
+    ///
+    ///   * `UNAVAILABLE`: all nodes are down or no shard has a primary.
+    ///   * `DEGRADED`: some agents or nodes are down, some shards have no primary,
+    ///     replication lag is above the namespace `degraded_lag` or nodes run
+    ///     different datastore versions.
+    ///   * `HEALTHY`: none of the above.
+    fn health(&self) -> ClusterHealth {
+        let down = self.agents_down + self.nodes_down;
+        if self.nodes == 0 || down >= self.nodes {
+            return ClusterHealth::Unavailable;
+        }
+        if self.shards_ids > 0 && self.shards_without_primary >= self.shards_ids {
+            return ClusterHealth::Unavailable;
+        }
+        let lagging = self
+            .max_lag_seconds
+            .map(|lag| lag > self.degraded_lag)
+            .unwrap_or(false);
+        if down > 0 || self.shards_without_primary > 0 || lagging || self.versions.len() > 1 {
+            return ClusterHealth::Degraded;
+        }
+        ClusterHealth::Healthy
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use replicante_models_core::cluster::discovery::ClusterDiscovery;
+    use replicante_models_core::cluster::ClusterHealth;
+    use replicante_models_core::scope::NsHealth;
+
+    use super::ClusterMetaAggregator;
+
+    fn aggregator() -> ClusterMetaAggregator {
+        let discovery = ClusterDiscovery::new("test", vec![]);
+        let mut aggregator = ClusterMetaAggregator::new(&discovery, &NsHealth::default());
+        aggregator.nodes = 3;
+        aggregator.shards_ids = 2;
+        aggregator.versions.insert("1.2.3".into());
+        aggregator
+    }
+
+    #[test]
+    fn healthy() {
+        let mut aggregator = aggregator();
+        aggregator.max_lag_seconds = Some(2);
+        assert_eq!(aggregator.health(), ClusterHealth::Healthy);
+    }
+
+    #[test]
+    fn degraded_agents_down() {
+        let mut aggregator = aggregator();
+        aggregator.agents_down = 1;
+        assert_eq!(aggregator.health(), ClusterHealth::Degraded);
+    }
+
+    #[test]
+    fn degraded_lag() {
+        let mut aggregator = aggregator();
+        aggregator.max_lag_seconds = Some(120);
+        assert_eq!(aggregator.health(), ClusterHealth::Degraded);
+    }
+
+    #[test]
+    fn degraded_lag_from_namespace() {
+        let discovery = ClusterDiscovery::new("test", vec![]);
+        let health = NsHealth { degraded_lag: 300 };
+        let mut aggregator = ClusterMetaAggregator::new(&discovery, &health);
+        aggregator.nodes = 3;
+        aggregator.max_lag_seconds = Some(120);
+        assert_eq!(aggregator.health(), ClusterHealth::Healthy);
+        aggregator.max_lag_seconds = Some(301);
+        assert_eq!(aggregator.health(), ClusterHealth::Degraded);
+    }
+
+    #[test]
+    fn degraded_shard_without_primary() {
+        let mut aggregator = aggregator();
+        aggregator.shards_without_primary = 1;
+        assert_eq!(aggregator.health(), ClusterHealth::Degraded);
+    }
+
+    #[test]
+    fn degraded_version_skew() {
+        let mut aggregator = aggregator();
+        aggregator.versions.insert("1.3.0".into());
+        assert_eq!(aggregator.health(), ClusterHealth::Degraded);
+    }
+
+    #[test]
+    fn unavailable_all_down() {
+        let mut aggregator = aggregator();
+        aggregator.agents_down = 1;
+        aggregator.nodes_down = 2;
+        assert_eq!(aggregator.health(), ClusterHealth::Unavailable);
+    }
+
+    #[test]
+    fn unavailable_no_primaries() {
+        let mut aggregator = aggregator();
+        aggregator.shards_without_primary = 2;
+        assert_eq!(aggregator.health(), ClusterHealth::Unavailable);
+    }
+}
@@ -52,6 +52,9 @@ pub enum ErrorKind {
     #[fail(display = "aggregator lock for cluster '{}' was lost", _0)]
     ClusterLockLost(String),
 
+    #[fail(display = "error emitting {} event", _0)]
+    EventEmit(&'static str),
+
     #[fail(display = "missing cluster metadata attribute '{}'", _0)]
     MissingMetadata(&'static str),
 
@@ -66,6 +69,7 @@ impl ErrorKind {
     fn kind_name(&self) -> Option<&str> {
         let name = match self {
             ErrorKind::ClusterLockLost(_) => "ClusterLockLost",
+            ErrorKind::EventEmit(_) => "EventEmit",
             ErrorKind::MissingMetadata(_) => "MissingMetadata",
             ErrorKind::StoreRead(_) => "StoreRead",
             ErrorKind::StoreWrite(_) => "StoreWrite",
@@ -5,8 +5,13 @@ use slog::debug;
 use slog::Logger;
 
 use replicante_models_core::cluster::discovery::ClusterDiscovery;
+use replicante_models_core::cluster::ClusterMeta;
+use replicante_models_core::events::Event;
+use replicante_models_core::scope::Namespace;
 use replicante_service_coordinator::NonBlockingLockWatcher;
 use replicante_store_primary::store::Store;
+use replicante_stream_events::EmitMessage;
+use replicante_stream_events::Stream as EventsStream;
 use replicante_util_tracing::fail_span;
 
 mod cluster_meta;
@@ -24,13 +29,18 @@ pub use self::metrics::register_metrics;
 
 /// Node (agent and datastore) status aggregator logic.
 pub struct Aggregator {
+    events: EventsStream,
     logger: Logger,
     store: Store,
 }
 
 impl Aggregator {
-    pub fn new(logger: Logger, store: Store) -> Aggregator {
-        Aggregator { logger, store }
+    pub fn new(logger: Logger, events: EventsStream, store: Store) -> Aggregator {
+        Aggregator {
+            events,
+            logger,
+            store,
+        }
     }
 
     /// Process aggregations for a cluster.
@@ -39,16 +49,18 @@ impl Aggregator {
     /// internal logic, ...) the process is aborted and the error propagated.
     pub fn aggregate(
         &self,
+        ns: Namespace,
         discovery: ClusterDiscovery,
         lock: NonBlockingLockWatcher,
         span: &mut Span,
     ) -> Result<()> {
         span.log(Log::new().log("stage", "aggregate"));
         let _timer = AGGREGATE_DURATION.start_timer();
-        self.inner_process(discovery, lock, span).map_err(|error| {
-            AGGREGATE_ERRORS_COUNT.inc();
-            fail_span(error, span)
-        })
+        self.inner_process(ns, discovery, lock, span)
+            .map_err(|error| {
+                AGGREGATE_ERRORS_COUNT.inc();
+                fail_span(error, span)
+            })
     }
 }
 
@@ -56,6 +68,7 @@ impl Aggregator {
     /// Wrapped logic to handle error cases only once.
     pub fn inner_process(
         &self,
+        ns: Namespace,
         discovery: ClusterDiscovery,
         lock: NonBlockingLockWatcher,
         span: &mut Span,
@@ -64,12 +77,30 @@ impl Aggregator {
         debug!(self.logger, "Aggregating cluster"; "cluster_id" => &cluster_id);
 
         // (Re-)Aggregate cluster meta.
-        let mut meta = ClusterMetaAggregator::new(&discovery);
+        let mut meta = ClusterMetaAggregator::new(&discovery, &ns.health);
         meta.aggregate(self.store.clone(), span)?;
         let meta = meta.generate();
         if !lock.inspect() {
             return Err(ErrorKind::ClusterLockLost(cluster_id).into());
         }
+        let before = self
+            .store
+            .legacy()
+            .cluster_meta(cluster_id.clone(), span.context().clone())
+            .with_context(|_| ErrorKind::StoreRead("ClusterMeta"))?;
+        if health_changed(before.as_ref(), &meta) {
+            let event = Event::builder()
+                .cluster()
+                .health_changed(before, meta.clone());
+            let code = event.code();
+            let stream_key = event.stream_key();
+            let event = EmitMessage::with(stream_key, event)
+                .with_context(|_| ErrorKind::EventEmit(code))?
+                .trace(span.context().clone());
+            self.events
+                .emit(event)
+                .with_context(|_| ErrorKind::EventEmit(code))?;
+        }
         self.store
             .legacy()
             .persist_cluster_meta(meta, span.context().clone())
@@ -79,3 +110,67 @@ impl Aggregator {
         Ok(())
     }
 }
+
+/// Check if health-relevant attributes changed between two `ClusterMeta` records.
+///
+/// Metrics that change continuously (like replication lag) are only relevant
+/// when they cause the overall health status to change.
+fn health_changed(before: Option<&ClusterMeta>, after: &ClusterMeta) -> bool {
+    let before = match before {
+        None => return true,
+        Some(before) => before,
+    };
+    before.health != after.health
+        || before.agents_down != after.agents_down
+        || before.nodes_down != after.nodes_down
+        || before.shards_without_primary != after.shards_without_primary
+}
+
+#[cfg(test)]
+mod tests {
+    use replicante_models_core::cluster::ClusterHealth;
+    use replicante_models_core::cluster::ClusterMeta;
+
+    use super::health_changed;
+
+    fn meta() -> ClusterMeta {
+        let mut meta = ClusterMeta::new("test", "test");
+        meta.health = ClusterHealth::Healthy;
+        meta.nodes = 3;
+        meta
+    }
+
+    #[test]
+    fn first_aggregation() {
+        assert!(health_changed(None, &meta()));
+    }
+
+    #[test]
+    fn lag_only() {
+        let before = meta();
+        let mut after = meta();
+        after.max_lag_seconds = Some(4);
+        assert!(!health_changed(Some(&before), &after));
+    }
+
+    #[test]
+    fn nodes_down() {
+        let before = meta();
+        let mut after = meta();
+        after.nodes_down = 1;
+        assert!(health_changed(Some(&before), &after));
+    }
+
+    #[test]
+    fn shard_loses_primary() {
+        let before = meta();
+        let mut after = meta();
+        after.shards_without_primary = 1;
+        assert!(health_changed(Some(&before), &after));
+    }
+
+    #[test]
+    fn unchanged() {
+        assert!(!health_changed(Some(&meta()), &meta()));
+    }
+}
@@ -0,0 +1,345 @@
+use std::net::SocketAddr;
+use std::time::Duration;
+
+use failure::ResultExt;
+use slog::Logger;
+use trust_dns_resolver::config::NameServerConfigGroup;
+use trust_dns_resolver::config::ResolverConfig;
+use trust_dns_resolver::config::ResolverOpts;
+use trust_dns_resolver::system_conf::read_system_conf;
+use trust_dns_resolver::Resolver;
+
+use replicante_models_core::cluster::discovery::ClusterDiscovery;
+use replicante_models_core::cluster::discovery::DnsClusterRecord;
+use replicante_models_core::cluster::discovery::DnsDiscovery;
+use replicante_models_core::cluster::discovery::DnsRecordType;
+use replicante_util_failure::capture_fail;
+use replicante_util_failure::failure_info;
+
+use crate::metrics::DISCOVERY_ERRORS;
+use crate::metrics::DISCOVERY_SKIPPED;
+use crate::metrics::DISCOVERY_TOTAL;
+use crate::ErrorKind;
+use crate::Result;
+
+/// DNS discovery iterator.
+///
+/// Calls to `Iter::next` will perform a DNS lookup for the next configured record
+/// and return a cluster discovery record with the resolved agents.
+///
+/// ## Record types
+///
+///   * `SRV` (default): each target and port pair is an agent for the cluster.
+///   * `A`: each address is an agent for the cluster, listening on the configured `port`.
+///
+/// Agent addresses are returned as `{scheme}://{host}:{port}` and sorted
+/// so that the order in which DNS servers return records does not matter.
+///
+/// ## Failed lookups
+/// Records that fail to resolve are logged and skipped so that one broken record
+/// does not prevent the other clusters from being discovered.
+/// Errors setting up the resolver itself still fail the discovery.
+///
+/// ## Name servers
+/// By default the system resolver configuration is used.
+/// A list of `IP:PORT` name servers can be configured instead, which also allows
+/// the discovery to run against a local stub resolver.
+pub struct Iter {
+    config: Option<DnsDiscovery>,
+    failed_or_done: bool,
+    logger: Logger,
+    port: Option<u16>,
+    record_type: DnsRecordType,
+    records: Vec<DnsClusterRecord>,
+    resolver: Option<Resolver>,
+    scheme: String,
+}
+
+impl Iter {
+    pub fn new(config: DnsDiscovery, logger: Logger) -> Iter {
+        let port = config.port;
+        let record_type = config.record_type.clone();
+        let mut records = config.records.clone();
+        records.reverse();
+        let scheme = config.scheme.clone();
+        Iter {
+            config: Some(config),
+            failed_or_done: false,
+            logger,
+            port,
+            record_type,
+            records,
+            resolver: None,
+            scheme,
+        }
+    }
+
+    /// Ensure a DNS resolver is available for lookups, creating one if needed.
+    fn ensure_resolver(&mut self) -> Result<()> {
+        if self.resolver.is_none() {
+            let config = self
+                .config
+                .take()
+                .expect("DNS discovery configuration already consumed");
+            let resolver = Iter::init_resolver(config)?;
+            self.resolver = Some(resolver);
+        }
+        Ok(())
+    }
+
+    /// Initialise the DNS resolver to perform lookups with.
+    fn init_resolver(config: DnsDiscovery) -> Result<Resolver> {
+        if config.record_type == DnsRecordType::A && config.port.is_none() {
+            return Err(ErrorKind::DnsMissingPort.into());
+        }
+        let timeout = Duration::from_millis(config.timeout);
+        if config.nameservers.is_empty() {
+            let (resolver_config, mut opts) =
+                read_system_conf().with_context(|_| ErrorKind::DnsResolver)?;
+            opts.timeout = timeout;
+            let resolver =
+                Resolver::new(resolver_config, opts).with_context(|_| ErrorKind::DnsResolver)?;
+            return Ok(resolver);
+        }
+        let mut nameservers = NameServerConfigGroup::with_capacity(config.nameservers.len());
+        for nameserver in &config.nameservers {
+            let address: SocketAddr = nameserver
+                .parse()
+                .with_context(|_| ErrorKind::DnsNameServer(nameserver.clone()))?;
+            let group = NameServerConfigGroup::from_ips_clear(&[address.ip()], address.port());
+            nameservers.extend(group.iter().cloned());
+        }
+        let resolver_config = ResolverConfig::from_parts(None, Vec::new(), nameservers);
+        let opts = ResolverOpts {
+            timeout,
+            ..ResolverOpts::default()
+        };
+        let resolver =
+            Resolver::new(resolver_config, opts).with_context(|_| ErrorKind::DnsResolver)?;
+        Ok(resolver)
+    }
+
+    /// Look up the agents for a cluster and return its discovery record.
+    fn lookup(&self, record: DnsClusterRecord) -> Result<ClusterDiscovery> {
+        let resolver = self.resolver.as_ref().expect("resolver not initialised");
+        let mut nodes: Vec<String> = match self.record_type {
+            DnsRecordType::A => {
+                let port = self.port.expect("A record lookups require a port");
+                resolver
+                    .ipv4_lookup(record.name.as_str())
+                    .with_context(|_| ErrorKind::DnsLookup(record.name.clone()))?
+                    .iter()
+                    .map(|address| format!("{}://{}:{}", self.scheme, address, port))
+                    .collect()
+            }
+            DnsRecordType::Srv => resolver
+                .srv_lookup(record.name.as_str())
+                .with_context(|_| ErrorKind::DnsLookup(record.name.clone()))?
+                .iter()
+                .map(|srv| {
+                    let target = srv.target().to_utf8();
+                    let target = target.trim_end_matches('.');
+                    format!("{}://{}:{}", self.scheme, target, srv.port())
+                })
+                .collect(),
+        };
+        nodes.sort();
+        nodes.dedup();
+        let mut cluster = ClusterDiscovery::new(record.cluster_id, nodes);
+        cluster.display_name = record.display_name;
+        Ok(cluster)
+    }
+}
+
+impl Iterator for Iter {
+    type Item = Result<ClusterDiscovery>;
+    fn next(&mut self) -> Option<Self::Item> {
+        DISCOVERY_TOTAL.with_label_values(&["dns"]).inc();
+        if self.failed_or_done {
+            return None;
+        }
+        if self.records.is_empty() {
+            self.failed_or_done = true;
+            return None;
+        }
+        if let Err(error) = self.ensure_resolver() {
+            DISCOVERY_ERRORS.with_label_values(&["dns"]).inc();
+            self.failed_or_done = true;
+            return Some(Err(error));
+        }
+        while let Some(record) = self.records.pop() {
+            let cluster_id = record.cluster_id.clone();
+            let name = record.name.clone();
+            match self.lookup(record) {
+                Ok(cluster) => return Some(Ok(cluster)),
+                Err(error) => {
+                    DISCOVERY_ERRORS.with_label_values(&["dns"]).inc();
+                    DISCOVERY_SKIPPED.with_label_values(&["dns"]).inc();
+                    capture_fail!(
+                        &error,
+                        self.logger,
+                        "Skipping DNS cluster record that could not be resolved";
+                        "cluster_id" => cluster_id,
+                        "record" => name,
+                        failure_info(&error),
+                    );
+                }
+            }
+        }
+        self.failed_or_done = true;
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::Ipv4Addr;
+    use std::net::UdpSocket;
+    use std::str::FromStr;
+    use std::thread;
+    use std::time::Duration;
+
+    use slog::o;
+    use slog::Discard;
+    use slog::Logger;
+    use trust_dns_resolver::proto::op::Message;
+    use trust_dns_resolver::proto::op::MessageType;
+    use trust_dns_resolver::proto::rr::rdata::SRV;
+    use trust_dns_resolver::proto::rr::Name;
+    use trust_dns_resolver::proto::rr::RData;
+    use trust_dns_resolver::proto::rr::Record;
+    use trust_dns_resolver::proto::rr::RecordType;
+    use trust_dns_resolver::proto::serialize::binary::BinEncodable;
+
+    use replicante_models_core::cluster::discovery::ClusterDiscovery;
+    use replicante_models_core::cluster::discovery::DnsClusterRecord;
+    use replicante_models_core::cluster::discovery::DnsDiscovery;
+    use replicante_models_core::cluster::discovery::DnsRecordType;
+
+    use super::Iter;
+
+    /// Start a stub DNS server answering SRV and A queries for any name.
+    ///
+    /// Names starting with `missing` have no records so their lookups fail.
+    ///
+    /// The server stops once no query is received for a second.
+    fn stub_resolver() -> String {
+        let socket = UdpSocket::bind("127.0.0.1:0").unwrap();
+        socket
+            .set_read_timeout(Some(Duration::from_secs(1)))
+            .unwrap();
+        let address = socket.local_addr().unwrap().to_string();
+        thread::spawn(move || {
+            let mut buffer = [0u8; 512];
+            while let Ok((len, peer)) = socket.recv_from(&mut buffer) {
+                let request = Message::from_vec(&buffer[..len]).unwrap();
+                let mut response = Message::new();
+                response
+                    .set_id(request.id())
+                    .set_message_type(MessageType::Response)
+                    .set_op_code(request.op_code())
+                    .set_recursion_desired(request.recursion_desired())
+                    .set_recursion_available(true);
+                for query in request.queries() {
+                    response.add_query(query.clone());
+                    let name = query.name().clone();
+                    if name.to_string().starts_with("missing") {
+                        continue;
+                    }
+                    match query.query_type() {
+                        RecordType::A => {
+                            for ip in &[Ipv4Addr::new(10, 0, 0, 2), Ipv4Addr::new(10, 0, 0, 1)] {
+                                let record = Record::from_rdata(name.clone(), 60, RData::A(*ip));
+                                response.add_answer(record);
+                            }
+                        }
+                        RecordType::SRV => {
+                            for node in &["node2.mongo.local.", "node1.mongo.local."] {
+                                let target = Name::from_str(node).unwrap();
+                                let srv = SRV::new(0, 0, 37017, target);
+                                let record = Record::from_rdata(name.clone(), 60, RData::SRV(srv));
+                                response.add_answer(record);
+                            }
+                        }
+                        _ => (),
+                    }
+                }
+                let response = response.to_bytes().unwrap();
+                socket.send_to(&response, peer).unwrap();
+            }
+        });
+        address
+    }
+
+    fn config(nameserver: String, record_type: DnsRecordType) -> DnsDiscovery {
+        DnsDiscovery {
+            nameservers: vec![nameserver],
+            port: Some(37000),
+            record_type,
+            records: vec![record("mongo", "_agent._tcp.mongo.local")],
+            scheme: "http".into(),
+            timeout: 1_000,
+        }
+    }
+
+    fn logger() -> Logger {
+        Logger::root(Discard, o!())
+    }
+
+    fn record(cluster_id: &str, name: &str) -> DnsClusterRecord {
+        DnsClusterRecord {
+            cluster_id: cluster_id.into(),
+            display_name: None,
+            name: name.into(),
+        }
+    }
+
+    #[test]
+    fn lookup_a_records() {
+        let nameserver = stub_resolver();
+        let clusters: Vec<ClusterDiscovery> =
+            Iter::new(config(nameserver, DnsRecordType::A), logger())
+                .map(Result::unwrap)
+                .collect();
+        let expected = ClusterDiscovery::new(
+            "mongo",
+            vec![
+                "http://10.0.0.1:37000".into(),
+                "http://10.0.0.2:37000".into(),
+            ],
+        );
+        assert_eq!(clusters, vec![expected]);
+    }
+
+    #[test]
+    fn lookup_srv_records() {
+        let nameserver = stub_resolver();
+        let clusters: Vec<ClusterDiscovery> =
+            Iter::new(config(nameserver, DnsRecordType::Srv), logger())
+                .map(Result::unwrap)
+                .collect();
+        let expected = ClusterDiscovery::new(
+            "mongo",
+            vec![
+                "http://node1.mongo.local:37017".into(),
+                "http://node2.mongo.local:37017".into(),
+            ],
+        );
+        assert_eq!(clusters, vec![expected]);
+    }
+
+    #[test]
+    fn lookup_skips_failed_records() {
+        let nameserver = stub_resolver();
+        let mut config = config(nameserver, DnsRecordType::Srv);
+        config.records = vec![
+            record("kafka", "_agent._tcp.kafka.local"),
+            record("missing", "missing.local"),
+            record("mongo", "_agent._tcp.mongo.local"),
+        ];
+        let clusters: Vec<String> = Iter::new(config, logger())
+            .map(|cluster| cluster.unwrap().cluster_id)
+            .collect();
+        assert_eq!(clusters, vec!["kafka".to_string(), "mongo".to_string()]);
+    }
+}
@@ -0,0 +1,217 @@
+use std::fs::File;
+use std::path::Path;
+use std::path::PathBuf;
+
+use failure::ResultExt;
+
+use replicante_models_core::cluster::discovery::ClusterDiscovery;
+use replicante_models_core::cluster::discovery::FileDiscovery;
+
+use crate::metrics::DISCOVERY_ERRORS;
+use crate::metrics::DISCOVERY_TOTAL;
+use crate::ErrorKind;
+use crate::Result;
+
+/// Extensions of files loaded when the discovery path is a directory.
+const EXTENSIONS: [&str; 3] = ["json", "yaml", "yml"];
+
+/// Local files discovery iterator.
+///
+/// Calls to `Iter::next` will read cluster discovery records from local files.
+///
+/// Each file is expected to contain a list of cluster discovery records.
+/// Files are decoded as YAML which, being a superset of JSON, allows for both formats.
+///
+/// If the configured path is a directory, all files in it with a `.json`, `.yaml`
+/// or `.yml` extension are loaded in alphabetical order.
+/// Sub-directories are NOT traversed.
+///
+/// Files are read one at a time as records are requested.
+pub struct Iter {
+    buffer: Vec<ClusterDiscovery>,
+    failed_or_done: bool,
+    files: Option<Vec<PathBuf>>,
+    path: String,
+}
+
+impl Iter {
+    pub fn new(config: FileDiscovery) -> Iter {
+        Iter {
+            buffer: Vec::new(),
+            failed_or_done: false,
+            files: None,
+            path: config.path,
+        }
+    }
+
+    /// List the files to load discovery records from.
+    fn list_files(path: &str) -> Result<Vec<PathBuf>> {
+        let root = Path::new(path);
+        if !root.is_dir() {
+            return Ok(vec![root.to_path_buf()]);
+        }
+        let mut files = Vec::new();
+        let entries = root
+            .read_dir()
+            .with_context(|_| ErrorKind::FileList(path.to_string()))?;
+        for entry in entries {
+            let entry = entry.with_context(|_| ErrorKind::FileList(path.to_string()))?;
+            let path = entry.path();
+            let extension = path
+                .extension()
+                .and_then(|extension| extension.to_str())
+                .unwrap_or("");
+            if path.is_file() && EXTENSIONS.contains(&extension) {
+                files.push(path);
+            }
+        }
+        files.sort();
+        Ok(files)
+    }
+
+    /// Decode all discovery records from a file.
+    fn load_file(path: &Path) -> Result<Vec<ClusterDiscovery>> {
+        let name = path.display().to_string();
+        let file = File::open(path).with_context(|_| ErrorKind::FileRead(name.clone()))?;
+        let clusters =
+            serde_yaml::from_reader(file).with_context(|_| ErrorKind::FileDecode(name))?;
+        Ok(clusters)
+    }
+
+    /// Load records from the next file, listing files first if needed.
+    fn read_more(&mut self) -> Result<Option<Vec<ClusterDiscovery>>> {
+        if self.files.is_none() {
+            let mut files = Iter::list_files(&self.path)?;
+            files.reverse();
+            self.files = Some(files);
+        }
+        let next = self
+            .files
+            .as_mut()
+            .expect("files should have been listed")
+            .pop();
+        match next {
+            None => Ok(None),
+            Some(path) => Iter::load_file(&path).map(Some),
+        }
+    }
+}
+
+impl Iterator for Iter {
+    type Item = Result<ClusterDiscovery>;
+    fn next(&mut self) -> Option<Self::Item> {
+        DISCOVERY_TOTAL.with_label_values(&["file"]).inc();
+        loop {
+            // Return any buffered discoveries.
+            if let Some(cluster) = self.buffer.pop() {
+                return Some(Ok(cluster));
+            }
+
+            // Stop trying once we enter a failed state or there are no more files to read.
+            if self.failed_or_done {
+                return None;
+            }
+
+            // Read the next file, skipping over empty ones.
+            match self.read_more() {
+                Ok(None) => {
+                    self.failed_or_done = true;
+                    return None;
+                }
+                Ok(Some(clusters)) => {
+                    self.buffer = clusters;
+                    self.buffer.reverse();
+                }
+                Err(error) => {
+                    DISCOVERY_ERRORS.with_label_values(&["file"]).inc();
+                    self.failed_or_done = true;
+                    return Some(Err(error));
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use replicante_models_core::cluster::discovery::ClusterDiscovery;
+    use replicante_models_core::cluster::discovery::FileDiscovery;
+
+    use super::Iter;
+    use crate::ErrorKind;
+
+    fn iter(path: &std::path::Path) -> Iter {
+        let config = FileDiscovery {
+            path: path.to_string_lossy().into_owned(),
+        };
+        Iter::new(config)
+    }
+
+    #[test]
+    fn directory() {
+        let dir = tempfile::tempdir().unwrap();
+        let b = r#"[{"cluster_id": "b", "nodes": ["http://b1:37000"]}]"#;
+        fs::write(dir.path().join("b.json"), b).unwrap();
+        let a = "- cluster_id: a\n  nodes:\n    - http://a1:37000\n";
+        fs::write(dir.path().join("a.yaml"), a).unwrap();
+        fs::write(dir.path().join("ignored.txt"), "not a discovery file").unwrap();
+        fs::create_dir(dir.path().join("nested.yaml")).unwrap();
+        let clusters: Vec<ClusterDiscovery> = iter(dir.path()).map(Result::unwrap).collect();
+        assert_eq!(
+            clusters,
+            vec![
+                ClusterDiscovery::new("a", vec!["http://a1:37000".into()]),
+                ClusterDiscovery::new("b", vec!["http://b1:37000".into()]),
+            ],
+        );
+    }
+
+    #[test]
+    fn malformed_yaml() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("clusters.yaml");
+        fs::write(&path, "- cluster_id: [not, a, string\n").unwrap();
+        let mut iter = iter(&path);
+        match iter.next() {
+            Some(Err(error)) => match error.kind() {
+                ErrorKind::FileDecode(_) => (),
+                _ => panic!("unexpected error: {:?}", error),
+            },
+            Some(Ok(cluster)) => panic!("malformed file decoded as {:?}", cluster),
+            None => panic!("malformed file did not return an error"),
+        }
+        assert!(iter.next().is_none());
+    }
+
+    #[test]
+    fn single_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("clusters.yaml");
+        let content = concat!(
+            "- cluster_id: a\n",
+            "  nodes:\n",
+            "    - http://a1:37000\n",
+            "- cluster_id: b\n",
+            "  display_name: Cluster B\n",
+            "  nodes:\n",
+            "    - http://b1:37000\n",
+            "    - http://b2:37000\n",
+        );
+        fs::write(&path, content).unwrap();
+        let clusters: Vec<ClusterDiscovery> = iter(&path).map(Result::unwrap).collect();
+        let mut b = ClusterDiscovery::new(
+            "b",
+            vec!["http://b1:37000".into(), "http://b2:37000".into()],
+        );
+        b.display_name = Some("Cluster B".into());
+        assert_eq!(
+            clusters,
+            vec![
+                ClusterDiscovery::new("a", vec!["http://a1:37000".into()]),
+                b
+            ],
+        );
+    }
+}
@@ -2,21 +2,28 @@ use std::fs::File;
 use std::io::Read;
 use std::time::Duration;
 
+use failure::Fail;
 use failure::ResultExt;
 use reqwest::blocking::Client;
 use reqwest::header::HeaderMap;
 use reqwest::header::HeaderName;
 use reqwest::header::HeaderValue;
+use reqwest::header::RETRY_AFTER;
+use reqwest::StatusCode;
 use serde_derive::Deserialize;
 use serde_derive::Serialize;
 use serde_json::Map;
 use serde_json::Value;
 
 use replicante_models_core::cluster::discovery::ClusterDiscovery;
+use replicante_models_core::cluster::discovery::DiscoveryRetry;
 use replicante_models_core::cluster::discovery::HttpDiscovery;
 use replicante_models_core::cluster::discovery::HttpRequestMethod;
+use replicante_models_core::cluster::discovery::HttpRetry;
 
+use super::http_auth::RequestAuth;
 use crate::metrics::DISCOVERY_ERRORS;
+use crate::metrics::DISCOVERY_RETRIES;
 use crate::metrics::DISCOVERY_TOTAL;
 use crate::Error;
 use crate::ErrorKind;
@@ -45,12 +52,37 @@ struct DiscoveryResponse {
 /// discovery with support for region and other filters).
 /// Security can also be added with HTTPS certificates and API tokens support.
 ///
+/// ### Authentication
+/// Requests can be authenticated with HTTP basic auth, a bearer token, or a bearer token
+/// obtained from an OAuth2 server with the client credentials grant.
+/// Credentials are referenced by name (environment variable or file) and resolved
+/// when the client is initialised so secrets are never stored with the settings.
+/// OAuth2 tokens are cached in memory until shortly before they expire.
+///
 /// Pagination support is also available by attaching an optional "cursor" returned
 /// by the server to future requests until a null "cursor" is returned.
 /// What the cursor means is determied by the server itself and could be a page number
 /// or a more complex serialised state to allow for stateless servers.
 /// Pagination can also be disabled by returning a null cursor.
 ///
+/// Should the discovery fail part way through, the cursor of the first page that was
+/// not fully returned is available from `Iter::resume_cursor` so that a later
+/// discovery can resume from there instead of starting over.
+/// If the very first request of an iterator fails the resume cursor is the one the
+/// iterator was created with: a new discovery starts over but nothing was fetched yet.
+///
+/// ### Retries
+/// Requests that fail because of connection errors, `429 Too Many Requests`
+/// or `5xx` responses can be retried with an exponential backoff.
+/// When the server sends a `Retry-After` header (in seconds) that delay is used instead,
+/// capped to the maximum backoff.
+///
+/// The iterator does not wait for retries itself, to avoid blocking the calling thread.
+/// Instead it stops on the first failure and `Iter::retry` returns the state and delay
+/// to continue from, if the failure can be retried.
+/// The caller is responsible for creating a new iterator, with the returned attempt,
+/// once the delay has passed.
+///
 /// ### HTTP Request Method
 /// By default `POST` requests are issued to the server.
 /// The method can be changed to issue `GET` requests to the server.
@@ -81,6 +113,8 @@ struct DiscoveryResponse {
 ///   * `cursor`: an optional string used for pagination, the server MUST return `null` when
 ///               there are no more discovery records to fetch on the same "cursor".
 pub struct Iter {
+    attempt: u32,
+    auth: Option<RequestAuth>,
     body: Map<String, Value>,
     buffer: Vec<ClusterDiscovery>,
     client: Option<Client>,
@@ -88,33 +122,59 @@ pub struct Iter {
     cursor: Option<String>,
     failed_or_done: bool,
     method: HttpRequestMethod,
+    page_cursor: Option<String>,
+    retry: HttpRetry,
+    retry_delay: Option<Duration>,
     url: String,
 }
 
 impl Iter {
-    pub fn new(config: HttpDiscovery) -> Iter {
+    pub fn new(config: HttpDiscovery, cursor: Option<String>, attempt: u32) -> Iter {
         let body = config.body.clone().unwrap_or_default();
         let method = config.method.clone();
+        let retry = config.retry.clone();
         let url = config.url.clone();
         Iter {
+            attempt,
+            auth: None,
             body,
             buffer: Vec::new(),
             client: None,
             config: Some(config),
-            cursor: None,
+            cursor: cursor.clone(),
             failed_or_done: false,
             method,
+            page_cursor: cursor,
+            retry,
+            retry_delay: None,
             url,
         }
     }
 
+    /// Delay before the next attempt when the server does not indicate one.
+    fn backoff(&self, attempt: u32) -> Duration {
+        let factor = 2u64.saturating_pow(attempt);
+        let delay = self.retry.backoff.saturating_mul(factor);
+        Duration::from_millis(delay.min(self.retry.backoff_max))
+    }
+
+    /// Delay before the next attempt, preferring the one indicated by the server.
+    fn delay(&self, retry_after: Option<Duration>) -> Duration {
+        retry_after
+            .map(|delay| delay.min(Duration::from_millis(self.retry.backoff_max)))
+            .unwrap_or_else(|| self.backoff(self.attempt))
+    }
+
     /// Ensure an HTTP client is available for requests, creating one if needed.
     fn ensure_client(&mut self) -> Option<Result<()>> {
         if self.client.is_none() {
             let client = match self.config.take() {
                 None => return None,
                 Some(config) => match Iter::init_client(config) {
-                    Ok(client) => client,
+                    Ok((client, auth)) => {
+                        self.auth = auth;
+                        client
+                    }
                     Err(error) => {
                         DISCOVERY_ERRORS.with_label_values(&["http"]).inc();
                         self.failed_or_done = true;
@@ -127,8 +187,8 @@ impl Iter {
         Some(Ok(()))
     }
 
-    /// Initialise the HTTP client to make requests with.
-    fn init_client(config: HttpDiscovery) -> Result<Client> {
+    /// Initialise the HTTP client to make requests with and resolve authentication secrets.
+    fn init_client(config: HttpDiscovery) -> Result<(Client, Option<RequestAuth>)> {
         let mut headers = HeaderMap::with_capacity(config.headers.len());
         for (key, value) in config.headers {
             let key = HeaderName::from_bytes(key.as_bytes())
@@ -160,17 +220,42 @@ impl Iter {
             builder = builder.identity(id);
         }
         let client = builder.build().with_context(|_| ErrorKind::HttpClient)?;
-        Ok(client)
+        let auth = match config.auth {
+            None => None,
+            Some(auth) => Some(RequestAuth::resolve(auth, &client)?),
+        };
+        Ok((client, auth))
     }
 
     /// Request a new set of discoveries from the remote HTTP server.
+    ///
+    /// Failures are not retried here but, if they can be, a retry delay is recorded.
     fn request_more(&mut self) -> Option<Result<DiscoveryResponse>> {
-        let client = match self.ensure_client() {
+        match self.ensure_client() {
             None => return None,
             Some(Err(error)) => return Some(Err(error)),
-            Some(Ok(())) => self.client.as_ref().expect("client not initialised"),
+            Some(Ok(())) => (),
+        };
+        let failure = match self.request_once() {
+            Ok(response) => {
+                self.attempt = 0;
+                return Some(Ok(response));
+            }
+            Err(failure) => failure,
         };
-        let response = match &self.method {
+        DISCOVERY_ERRORS.with_label_values(&["http"]).inc();
+        self.failed_or_done = true;
+        if failure.retry && self.attempt < self.retry.attempts {
+            DISCOVERY_RETRIES.with_label_values(&["http"]).inc();
+            self.retry_delay = Some(self.delay(failure.retry_after));
+        }
+        Some(Err(failure.error))
+    }
+
+    /// Perform a single request to the remote HTTP server.
+    fn request_once(&self) -> std::result::Result<DiscoveryResponse, RequestFailure> {
+        let client = self.client.as_ref().expect("client not initialised");
+        let request = match &self.method {
             HttpRequestMethod::Get => client.get(&self.url),
             HttpRequestMethod::Post => {
                 let mut body = self.body.clone();
@@ -182,41 +267,69 @@ impl Iter {
                 client.post(&self.url).json(&body)
             }
         };
-        let response = response
-            .send()
-            .with_context(|_| ErrorKind::HttpRequest)
-            .map_err(Error::from);
-        let response = match response {
-            Ok(response) => response,
-            Err(error) => {
-                DISCOVERY_ERRORS.with_label_values(&["http"]).inc();
-                self.failed_or_done = true;
-                return Some(Err(error));
-            }
-        };
-        let response = match response.error_for_status() {
-            Ok(response) => response,
-            Err(error) => {
-                DISCOVERY_ERRORS.with_label_values(&["http"]).inc();
-                let error = Err(error)
-                    .with_context(|_| ErrorKind::HttpRequest)
-                    .map_err(Error::from);
-                self.failed_or_done = true;
-                return Some(error);
-            }
+        let request = match &self.auth {
+            None => request,
+            Some(auth) => auth.apply(request),
         };
-        let response: DiscoveryResponse = match response.json() {
-            Ok(response) => response,
-            Err(error) => {
-                DISCOVERY_ERRORS.with_label_values(&["http"]).inc();
-                let error = Err(error)
-                    .with_context(|_| ErrorKind::HttpRequest)
-                    .map_err(Error::from);
-                self.failed_or_done = true;
-                return Some(error);
-            }
-        };
-        Some(Ok(response))
+        let response = request
+            .send()
+            .map_err(|error| RequestFailure::new(error, true, None))?;
+        let status = response.status();
+        if status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error() {
+            let retry_after = response
+                .headers()
+                .get(RETRY_AFTER)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.trim().parse::<u64>().ok())
+                .map(Duration::from_secs);
+            let error = response
+                .error_for_status()
+                .expect_err("response status should be an error");
+            return Err(RequestFailure::new(error, true, retry_after));
+        }
+        let response = response
+            .error_for_status()
+            .map_err(|error| RequestFailure::new(error, false, None))?;
+        response
+            .json()
+            .map_err(|error| RequestFailure::new(error, false, None))
+    }
+
+    /// Cursor to resume discovery from, should the current run fail.
+    ///
+    /// This is the cursor used to request the page currently being returned so
+    /// resuming from it never skips records, although some may be returned again.
+    pub fn resume_cursor(&self) -> Option<String> {
+        self.page_cursor.clone()
+    }
+
+    /// State and delay to retry the discovery with, if the last request failed and can be retried.
+    pub fn retry(&self) -> Option<(DiscoveryRetry, Duration)> {
+        self.retry_delay.map(|delay| {
+            let retry = DiscoveryRetry {
+                attempt: self.attempt + 1,
+                cursor: self.page_cursor.clone(),
+            };
+            (retry, delay)
+        })
+    }
+}
+
+/// Details of a failed HTTP discovery request.
+struct RequestFailure {
+    error: Error,
+    retry: bool,
+    retry_after: Option<Duration>,
+}
+
+impl RequestFailure {
+    fn new(error: reqwest::Error, retry: bool, retry_after: Option<Duration>) -> RequestFailure {
+        let error = Error::from(error.context(ErrorKind::HttpRequest));
+        RequestFailure {
+            error,
+            retry,
+            retry_after,
+        }
     }
 }
 
@@ -235,11 +348,17 @@ impl Iterator for Iter {
         }
 
         // Request more discoveries to return.
+        let request_cursor = self.cursor.clone();
         let response = match self.request_more() {
             None => return None,
-            Some(Err(error)) => return Some(Err(error)),
+            Some(Err(error)) => {
+                // All records from previous pages have been returned so resume from this one.
+                self.page_cursor = request_cursor;
+                return Some(Err(error));
+            }
             Some(Ok(response)) => response,
         };
+        self.page_cursor = request_cursor;
         self.buffer = response.clusters;
         self.buffer.reverse();
 
@@ -253,3 +372,182 @@ impl Iterator for Iter {
         self.buffer.pop().map(Ok)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::io::BufRead;
+    use std::io::BufReader;
+    use std::io::Read;
+    use std::io::Write;
+    use std::net::TcpListener;
+    use std::thread;
+    use std::time::Duration;
+
+    use serde_json::json;
+
+    use replicante_models_core::cluster::discovery::ClusterDiscovery;
+    use replicante_models_core::cluster::discovery::DiscoveryRetry;
+    use replicante_models_core::cluster::discovery::HttpDiscovery;
+
+    use super::Iter;
+
+    /// Start a stub HTTP server returning the given responses in order, one per connection.
+    fn stub_server(responses: Vec<String>) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        thread::spawn(move || {
+            for response in responses {
+                let (stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream);
+                let mut length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    let line = line.trim().to_lowercase();
+                    if line.is_empty() {
+                        break;
+                    }
+                    if line.starts_with("content-length:") {
+                        length = line["content-length:".len()..].trim().parse().unwrap();
+                    }
+                }
+                let mut body = vec![0u8; length];
+                reader.read_exact(&mut body).unwrap();
+                let mut stream = reader.into_inner();
+                stream.write_all(response.as_bytes()).unwrap();
+            }
+        });
+        format!("http://{}/", address)
+    }
+
+    fn response(status: &str, headers: &[&str], body: &str) -> String {
+        let mut response = format!("HTTP/1.1 {}\r\nconnection: close\r\n", status);
+        for header in headers {
+            response.push_str(header);
+            response.push_str("\r\n");
+        }
+        response.push_str(&format!("content-length: {}\r\n\r\n{}", body.len(), body));
+        response
+    }
+
+    fn page(cluster: &str, cursor: Option<&str>) -> String {
+        let body = json!({
+            "clusters": [{"cluster_id": cluster, "nodes": []}],
+            "cursor": cursor,
+        });
+        response(
+            "200 OK",
+            &["content-type: application/json"],
+            &body.to_string(),
+        )
+    }
+
+    fn config(url: String) -> HttpDiscovery {
+        serde_json::from_value(json!({
+            "retry": {"attempts": 2, "backoff": 100, "backoff_max": 1000},
+            "url": url,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn backoff_doubles_up_to_max() {
+        let iter = Iter::new(config("http://localhost/".into()), None, 0);
+        assert_eq!(iter.backoff(0), Duration::from_millis(100));
+        assert_eq!(iter.backoff(1), Duration::from_millis(200));
+        assert_eq!(iter.backoff(3), Duration::from_millis(800));
+        assert_eq!(iter.backoff(4), Duration::from_millis(1000));
+        assert_eq!(iter.backoff(64), Duration::from_millis(1000));
+    }
+
+    #[test]
+    fn client_errors_are_not_retried() {
+        let url = stub_server(vec![response("400 Bad Request", &[], "")]);
+        let mut iter = Iter::new(config(url), None, 0);
+        assert!(iter.next().unwrap().is_err());
+        assert!(iter.next().is_none());
+        assert_eq!(iter.retry(), None);
+    }
+
+    #[test]
+    fn connection_errors_are_retried() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        drop(listener);
+        let mut iter = Iter::new(config(url), None, 0);
+        assert!(iter.next().unwrap().is_err());
+        let retry = DiscoveryRetry {
+            attempt: 1,
+            cursor: None,
+        };
+        assert_eq!(iter.retry(), Some((retry, Duration::from_millis(100))));
+    }
+
+    #[test]
+    fn retries_are_exhausted() {
+        let url = stub_server(vec![response("503 Service Unavailable", &[], "")]);
+        let mut iter = Iter::new(config(url), Some("2".into()), 2);
+        assert!(iter.next().unwrap().is_err());
+        assert_eq!(iter.retry(), None);
+        assert_eq!(iter.resume_cursor(), Some("2".into()));
+    }
+
+    #[test]
+    fn retry_after_header_is_capped() {
+        let url = stub_server(vec![response(
+            "429 Too Many Requests",
+            &["retry-after: 60"],
+            "",
+        )]);
+        let mut iter = Iter::new(config(url), None, 0);
+        assert!(iter.next().unwrap().is_err());
+        let (_, delay) = iter.retry().unwrap();
+        assert_eq!(delay, Duration::from_millis(1000));
+    }
+
+    #[test]
+    fn retry_after_header_is_used() {
+        let url = stub_server(vec![response(
+            "503 Service Unavailable",
+            &["retry-after: 0"],
+            "",
+        )]);
+        let mut iter = Iter::new(config(url), None, 1);
+        assert!(iter.next().unwrap().is_err());
+        let retry = DiscoveryRetry {
+            attempt: 2,
+            cursor: None,
+        };
+        assert_eq!(iter.retry(), Some((retry, Duration::from_secs(0))));
+    }
+
+    #[test]
+    fn resume_from_failed_page() {
+        let url = stub_server(vec![
+            page("cluster-1", Some("2")),
+            response("500 Internal Server Error", &[], ""),
+        ]);
+        let mut iter = Iter::new(config(url), None, 0);
+        let cluster = iter.next().unwrap().unwrap();
+        assert_eq!(cluster, ClusterDiscovery::new("cluster-1", vec![]));
+        assert_eq!(iter.resume_cursor(), None);
+        assert!(iter.next().unwrap().is_err());
+        assert!(iter.next().is_none());
+        assert_eq!(iter.resume_cursor(), Some("2".into()));
+        let retry = DiscoveryRetry {
+            attempt: 1,
+            cursor: Some("2".into()),
+        };
+        assert_eq!(iter.retry(), Some((retry, Duration::from_millis(100))));
+    }
+
+    #[test]
+    fn resumed_iterator_sends_cursor() {
+        let url = stub_server(vec![page("cluster-2", None)]);
+        let mut iter = Iter::new(config(url), Some("2".into()), 1);
+        let cluster = iter.next().unwrap().unwrap();
+        assert_eq!(cluster, ClusterDiscovery::new("cluster-2", vec![]));
+        assert!(iter.next().is_none());
+        assert_eq!(iter.retry(), None);
+    }
+}
@@ -0,0 +1,318 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::hash::Hasher;
+use std::sync::Mutex;
+use std::time::Duration;
+use std::time::Instant;
+
+use failure::ResultExt;
+use lazy_static::lazy_static;
+use reqwest::blocking::Client;
+use reqwest::blocking::RequestBuilder;
+use serde_derive::Deserialize;
+
+use replicante_models_core::cluster::discovery::HttpAuth;
+use replicante_models_core::cluster::discovery::HttpOAuth2ClientCredentials;
+use replicante_models_core::cluster::discovery::SecretRef;
+
+use crate::ErrorKind;
+use crate::Result;
+
+/// Expire cached OAuth2 tokens this long before the server says they expire.
+const TOKEN_EXPIRY_MARGIN: Duration = Duration::from_secs(30);
+
+lazy_static! {
+    /// Cache of OAuth2 access tokens by client credentials.
+    static ref TOKENS_CACHE: Mutex<TokensCache> = Mutex::new(TokensCache::default());
+}
+
+/// OAuth2 access token with its expiry time.
+struct CachedToken {
+    expires: Instant,
+    token: String,
+}
+
+/// Identify the client credentials an OAuth2 access token was granted for.
+///
+/// The secret is stored as a fingerprint so that rotated secrets request a new token
+/// without keeping the secret itself in the cache.
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+struct TokenKey {
+    client_id: String,
+    scopes: Vec<String>,
+    secret: u64,
+    token_url: String,
+}
+
+impl TokenKey {
+    fn new(config: &HttpOAuth2ClientCredentials, secret: &str) -> TokenKey {
+        let mut hasher = DefaultHasher::new();
+        secret.hash(&mut hasher);
+        TokenKey {
+            client_id: config.client_id.clone(),
+            scopes: config.scopes.clone(),
+            secret: hasher.finish(),
+            token_url: config.token_url.clone(),
+        }
+    }
+}
+
+/// OAuth2 access tokens that have not expired yet.
+///
+/// Expired tokens are evicted when looked up and whenever a new token is cached
+/// so tokens for rotated secrets or removed discoveries don't linger forever.
+#[derive(Default)]
+struct TokensCache {
+    tokens: HashMap<TokenKey, CachedToken>,
+}
+
+impl TokensCache {
+    /// Return the cached token for the key, evicting it if it expired by the given time.
+    fn get(&mut self, key: &TokenKey, now: Instant) -> Option<String> {
+        let expired = match self.tokens.get(key) {
+            None => return None,
+            Some(cached) => cached.expires <= now,
+        };
+        if expired {
+            self.tokens.remove(key);
+            return None;
+        }
+        self.tokens.get(key).map(|cached| cached.token.clone())
+    }
+
+    /// Cache a token unless it expires too soon to be worth it.
+    fn insert(&mut self, key: TokenKey, token: String, expires_in: Duration, now: Instant) {
+        self.tokens.retain(|_, cached| cached.expires > now);
+        if expires_in <= TOKEN_EXPIRY_MARGIN {
+            return;
+        }
+        let cached = CachedToken {
+            expires: now + expires_in - TOKEN_EXPIRY_MARGIN,
+            token,
+        };
+        self.tokens.insert(key, cached);
+    }
+}
+
+/// Response returned by OAuth2 token endpoints.
+#[derive(Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    #[serde(default)]
+    expires_in: Option<u64>,
+}
+
+/// Authentication with secrets resolved, ready to be attached to requests.
+pub enum RequestAuth {
+    Basic(String, String),
+    Bearer(String),
+}
+
+impl RequestAuth {
+    /// Resolve the secrets referenced by an `HttpAuth` configuration.
+    ///
+    /// OAuth2 tokens are requested with the given client and cached until they expire.
+    pub fn resolve(auth: HttpAuth, client: &Client) -> Result<RequestAuth> {
+        let auth = match auth {
+            HttpAuth::Basic { username, password } => {
+                RequestAuth::Basic(username, read_secret(&password)?)
+            }
+            HttpAuth::Bearer { token } => RequestAuth::Bearer(read_secret(&token)?),
+            HttpAuth::OAuth2(config) => RequestAuth::Bearer(oauth2_token(config, client)?),
+        };
+        Ok(auth)
+    }
+
+    /// Attach authentication details to a request.
+    pub fn apply(&self, request: RequestBuilder) -> RequestBuilder {
+        match self {
+            RequestAuth::Basic(username, password) => request.basic_auth(username, Some(password)),
+            RequestAuth::Bearer(token) => request.bearer_auth(token),
+        }
+    }
+}
+
+/// Fetch an OAuth2 access token with the client credentials grant, using the cache if possible.
+fn oauth2_token(config: HttpOAuth2ClientCredentials, client: &Client) -> Result<String> {
+    let secret = read_secret(&config.client_secret)?;
+    let key = TokenKey::new(&config, &secret);
+    let cached = TOKENS_CACHE
+        .lock()
+        .expect("OAuth2 tokens cache lock poisoned")
+        .get(&key, Instant::now());
+    if let Some(token) = cached {
+        return Ok(token);
+    }
+
+    let mut form = vec![
+        ("grant_type", "client_credentials".to_string()),
+        ("client_id", config.client_id.clone()),
+        ("client_secret", secret),
+    ];
+    if !config.scopes.is_empty() {
+        form.push(("scope", config.scopes.join(" ")));
+    }
+    let response: TokenResponse = client
+        .post(&config.token_url)
+        .form(&form)
+        .send()
+        .and_then(|response| response.error_for_status())
+        .and_then(|response| response.json())
+        .with_context(|_| ErrorKind::HttpOAuth2Token(config.token_url.clone()))?;
+
+    if let Some(expires_in) = response.expires_in {
+        TOKENS_CACHE
+            .lock()
+            .expect("OAuth2 tokens cache lock poisoned")
+            .insert(
+                key,
+                response.access_token.clone(),
+                Duration::from_secs(expires_in),
+                Instant::now(),
+            );
+    }
+    Ok(response.access_token)
+}
+
+/// Read the value of a referenced secret.
+fn read_secret(secret: &SecretRef) -> Result<String> {
+    let value = match secret {
+        SecretRef::Env(name) => {
+            std::env::var(name).with_context(|_| ErrorKind::SecretEnv(name.clone()))?
+        }
+        SecretRef::File(path) => {
+            std::fs::read_to_string(path).with_context(|_| ErrorKind::SecretFile(path.clone()))?
+        }
+    };
+    Ok(value.trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::time::Duration;
+    use std::time::Instant;
+
+    use replicante_models_core::cluster::discovery::HttpOAuth2ClientCredentials;
+    use replicante_models_core::cluster::discovery::SecretRef;
+
+    use super::read_secret;
+    use super::TokenKey;
+    use super::TokensCache;
+    use crate::ErrorKind;
+
+    fn credentials(scopes: Vec<String>) -> HttpOAuth2ClientCredentials {
+        HttpOAuth2ClientCredentials {
+            client_id: "replicore".into(),
+            client_secret: SecretRef::Env("UNUSED".into()),
+            scopes,
+            token_url: "https://auth.example.com/token".into(),
+        }
+    }
+
+    #[test]
+    fn cache_expires_tokens_early() {
+        let key = TokenKey::new(&credentials(vec![]), "secret");
+        let now = Instant::now();
+        let mut cache = TokensCache::default();
+        cache.insert(key.clone(), "token".into(), Duration::from_secs(60), now);
+        let before = now + Duration::from_secs(29);
+        assert_eq!(cache.get(&key, before), Some("token".to_string()));
+        let after = now + Duration::from_secs(31);
+        assert_eq!(cache.get(&key, after), None);
+    }
+
+    #[test]
+    fn cache_evicts_expired_tokens() {
+        let key = TokenKey::new(&credentials(vec![]), "secret");
+        let rotated = TokenKey::new(&credentials(vec![]), "rotated");
+        let now = Instant::now();
+        let mut cache = TokensCache::default();
+        cache.insert(key.clone(), "token".into(), Duration::from_secs(60), now);
+        cache.insert(
+            rotated.clone(),
+            "token".into(),
+            Duration::from_secs(60),
+            now,
+        );
+
+        // Expired tokens are dropped when looked up.
+        let later = now + Duration::from_secs(31);
+        assert_eq!(cache.get(&key, later), None);
+        assert!(!cache.tokens.contains_key(&key));
+
+        // Expired tokens for other keys are dropped when new tokens are cached.
+        let other = TokenKey::new(&credentials(vec!["read".into()]), "secret");
+        cache.insert(
+            other.clone(),
+            "token".into(),
+            Duration::from_secs(60),
+            later,
+        );
+        assert!(!cache.tokens.contains_key(&rotated));
+        assert!(cache.tokens.contains_key(&other));
+    }
+
+    #[test]
+    fn cache_skips_short_lived_tokens() {
+        let key = TokenKey::new(&credentials(vec![]), "secret");
+        let now = Instant::now();
+        let mut cache = TokensCache::default();
+        cache.insert(key.clone(), "token".into(), Duration::from_secs(30), now);
+        assert_eq!(cache.get(&key, now), None);
+    }
+
+    #[test]
+    fn cache_key_includes_scopes_and_secret() {
+        let key = TokenKey::new(&credentials(vec![]), "secret");
+        let now = Instant::now();
+        let mut cache = TokensCache::default();
+        cache.insert(key, "token".into(), Duration::from_secs(60), now);
+        let scoped = TokenKey::new(&credentials(vec!["read".into()]), "secret");
+        assert_eq!(cache.get(&scoped, now), None);
+        let rotated = TokenKey::new(&credentials(vec![]), "rotated");
+        assert_eq!(cache.get(&rotated, now), None);
+    }
+
+    #[test]
+    fn read_secret_from_env() {
+        std::env::set_var("REPLICORE_TEST_HTTP_AUTH_SECRET", " secret\n");
+        let secret = SecretRef::Env("REPLICORE_TEST_HTTP_AUTH_SECRET".into());
+        assert_eq!(read_secret(&secret).unwrap(), "secret");
+    }
+
+    #[test]
+    fn read_secret_from_env_missing() {
+        let secret = SecretRef::Env("REPLICORE_TEST_HTTP_AUTH_MISSING".into());
+        match read_secret(&secret) {
+            Err(error) => match error.kind() {
+                ErrorKind::SecretEnv(name) => assert_eq!(name, "REPLICORE_TEST_HTTP_AUTH_MISSING"),
+                _ => panic!("unexpected error: {:?}", error),
+            },
+            Ok(_) => panic!("missing secret was read"),
+        }
+    }
+
+    #[test]
+    fn read_secret_from_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("secret");
+        fs::write(&path, "secret\n").unwrap();
+        let secret = SecretRef::File(path.to_string_lossy().into_owned());
+        assert_eq!(read_secret(&secret).unwrap(), "secret");
+    }
+
+    #[test]
+    fn read_secret_from_file_missing() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("missing").to_string_lossy().into_owned();
+        match read_secret(&SecretRef::File(path.clone())) {
+            Err(error) => match error.kind() {
+                ErrorKind::SecretFile(missing) => assert_eq!(missing, &path),
+                _ => panic!("unexpected error: {:?}", error),
+            },
+            Ok(_) => panic!("missing secret was read"),
+        }
+    }
+}
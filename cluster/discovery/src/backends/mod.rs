@@ -1 +1,4 @@
+pub mod dns;
+pub mod file;
 pub mod http;
+mod http_auth;
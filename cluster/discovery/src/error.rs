@@ -49,6 +49,27 @@ impl fmt::Display for Error {
 /// Exhaustive list of possible errors emitted by this crate.
 #[derive(Debug, Fail)]
 pub enum ErrorKind {
+    #[fail(display = "DNS lookup for record {} failed", _0)]
+    DnsLookup(String),
+
+    #[fail(display = "DNS discovery of A records requires a port")]
+    DnsMissingPort,
+
+    #[fail(display = "invalid DNS name server address '{}'", _0)]
+    DnsNameServer(String),
+
+    #[fail(display = "unable to initialise DNS resolver")]
+    DnsResolver,
+
+    #[fail(display = "unable to decode discovery records from file {}", _0)]
+    FileDecode(String),
+
+    #[fail(display = "unable to list discovery files in {}", _0)]
+    FileList(String),
+
+    #[fail(display = "unable to read discovery file {}", _0)]
+    FileRead(String),
+
     #[fail(display = "unable to load PEM certificate for HTTP client")]
     HttpCertLoad,
 
@@ -61,18 +82,37 @@ pub enum ErrorKind {
     #[fail(display = "invalid HTTP header value '{}'", _0)]
     HttpHeaderValue(String),
 
+    #[fail(display = "unable to fetch OAuth2 token from {}", _0)]
+    HttpOAuth2Token(String),
+
     #[fail(display = "HTTP request failed")]
     HttpRequest,
+
+    #[fail(display = "unable to read secret from environment variable {}", _0)]
+    SecretEnv(String),
+
+    #[fail(display = "unable to read secret from file {}", _0)]
+    SecretFile(String),
 }
 
 impl ErrorKind {
     fn kind_name(&self) -> Option<&str> {
         let name = match self {
+            ErrorKind::DnsLookup(_) => "DnsLookup",
+            ErrorKind::DnsMissingPort => "DnsMissingPort",
+            ErrorKind::DnsNameServer(_) => "DnsNameServer",
+            ErrorKind::DnsResolver => "DnsResolver",
+            ErrorKind::FileDecode(_) => "FileDecode",
+            ErrorKind::FileList(_) => "FileList",
+            ErrorKind::FileRead(_) => "FileRead",
             ErrorKind::HttpCertLoad => "HttpCertLoad",
             ErrorKind::HttpClient => "HttpClient",
             ErrorKind::HttpHeaderName(_) => "HttpHeaderName",
             ErrorKind::HttpHeaderValue(_) => "HttpHeaderValue",
+            ErrorKind::HttpOAuth2Token(_) => "HttpOAuth2Token",
             ErrorKind::HttpRequest => "HttpRequest",
+            ErrorKind::SecretEnv(_) => "SecretEnv",
+            ErrorKind::SecretFile(_) => "SecretFile",
         };
         Some(name)
     }
@@ -1,5 +1,10 @@
+use std::time::Duration;
+
+use slog::Logger;
+
 use replicante_models_core::cluster::discovery::ClusterDiscovery;
 use replicante_models_core::cluster::discovery::DiscoveryBackend;
+use replicante_models_core::cluster::discovery::DiscoveryRetry;
 use replicante_models_core::cluster::discovery::DiscoverySettings;
 
 mod backends;
@@ -11,10 +16,14 @@ pub use self::error::ErrorKind;
 pub use self::error::Result;
 pub use self::metrics::register_metrics;
 
+use self::backends::dns::Iter as DnsIter;
+use self::backends::file::Iter as FileIter;
 use self::backends::http::Iter as HttpIter;
 
 /// Wrapper backend-specific iterators without exposing implementation details.
 enum InnerIter {
+    Dns(DnsIter),
+    File(FileIter),
     Http(HttpIter),
 
     #[cfg(any(test, feature = "with_test_support"))]
@@ -27,6 +36,26 @@ pub struct Iter {
 }
 
 impl Iter {
+    /// Cursor to resume discovery from should this iteration fail, if the backend supports it.
+    ///
+    /// Only paginated HTTP discovery returns a cursor, all other backends return `None`.
+    pub fn resume_cursor(&self) -> Option<String> {
+        match self.inner {
+            InnerIter::Http(ref iter) => iter.resume_cursor(),
+            _ => None,
+        }
+    }
+
+    /// State and delay to retry a failed discovery with, if the failure can be retried.
+    ///
+    /// Only HTTP discovery supports delayed retries, all other backends return `None`.
+    pub fn retry(&self) -> Option<(DiscoveryRetry, Duration)> {
+        match self.inner {
+            InnerIter::Http(ref iter) => iter.retry(),
+            _ => None,
+        }
+    }
+
     /// Mock cluster discovery by iterating over the given results.
     #[cfg(any(test, feature = "with_test_support"))]
     pub fn mock(iter: Vec<Result<ClusterDiscovery>>) -> Iter {
@@ -40,6 +69,8 @@ impl Iterator for Iter {
 
     fn next(&mut self) -> Option<Self::Item> {
         match self.inner {
+            InnerIter::Dns(ref mut iter) => iter.next(),
+            InnerIter::File(ref mut iter) => iter.next(),
             InnerIter::Http(ref mut iter) => iter.next(),
             #[cfg(any(test, feature = "with_test_support"))]
             InnerIter::Test(ref mut iter) => iter.next(),
@@ -48,9 +79,27 @@ impl Iterator for Iter {
 }
 
 /// Fetch cluster records from a discovery backend and iterate over them.
-pub fn discover(settings: DiscoverySettings) -> Iter {
+///
+/// The logger is used by backends to report records they skip.
+pub fn discover(settings: DiscoverySettings, logger: Logger) -> Iter {
+    discover_from(settings, None, 0, logger)
+}
+
+/// Fetch cluster records from a discovery backend, resuming from a cursor if possible.
+///
+/// The cursor should have been returned by `Iter::resume_cursor` or `Iter::retry`
+/// for the same settings, with `attempt` the number of failed attempts to fetch it.
+/// Backends that do not support resuming ignore the cursor and start from the beginning.
+pub fn discover_from(
+    settings: DiscoverySettings,
+    cursor: Option<String>,
+    attempt: u32,
+    logger: Logger,
+) -> Iter {
     let inner = match settings.backend {
-        DiscoveryBackend::Http(config) => InnerIter::Http(HttpIter::new(config)),
+        DiscoveryBackend::Dns(config) => InnerIter::Dns(DnsIter::new(config, logger)),
+        DiscoveryBackend::File(config) => InnerIter::File(FileIter::new(config)),
+        DiscoveryBackend::Http(config) => InnerIter::Http(HttpIter::new(config, cursor, attempt)),
     };
     Iter { inner }
 }
@@ -14,6 +14,22 @@ lazy_static! {
         &["backend"]
     )
     .expect("Failed to create DISCOVERY_ERRORS counter");
+    pub static ref DISCOVERY_RETRIES: CounterVec = CounterVec::new(
+        Opts::new(
+            "replicore_discovery_retries",
+            "Number of cluster discovery requests retried after a failure"
+        ),
+        &["backend"]
+    )
+    .expect("Failed to create DISCOVERY_RETRIES counter");
+    pub static ref DISCOVERY_SKIPPED: CounterVec = CounterVec::new(
+        Opts::new(
+            "replicore_discovery_skipped",
+            "Number of discovered cluster records skipped because they could not be fetched"
+        ),
+        &["backend"]
+    )
+    .expect("Failed to create DISCOVERY_SKIPPED counter");
     pub static ref DISCOVERY_TOTAL: CounterVec = CounterVec::new(
         Opts::new(
             "replicore_discovery_total",
@@ -33,6 +49,12 @@ pub fn register_metrics(logger: &Logger, registry: &Registry) {
     if let Err(error) = registry.register(Box::new(DISCOVERY_ERRORS.clone())) {
         debug!(logger, "Failed to register DISCOVERY_ERRORS"; "error" => ?error);
     }
+    if let Err(error) = registry.register(Box::new(DISCOVERY_RETRIES.clone())) {
+        debug!(logger, "Failed to register DISCOVERY_RETRIES"; "error" => ?error);
+    }
+    if let Err(error) = registry.register(Box::new(DISCOVERY_SKIPPED.clone())) {
+        debug!(logger, "Failed to register DISCOVERY_SKIPPED"; "error" => ?error);
+    }
     if let Err(error) = registry.register(Box::new(DISCOVERY_TOTAL.clone())) {
         debug!(logger, "Failed to register DISCOVERY_TOTAL"; "error" => ?error);
     }
@@ -1,10 +1,13 @@
 use std::collections::HashSet;
 
+use chrono::DateTime;
+use chrono::Duration;
 use chrono::Utc;
 use failure::Fail;
 use failure::ResultExt;
 use opentracingrust::Span;
 use slog::info;
+use slog::warn;
 use slog::Logger;
 use uuid::Uuid;
 
@@ -13,11 +16,13 @@ use replicante_models_agent::actions::api::ActionScheduleRequest;
 use replicante_models_core::actions::Action;
 use replicante_models_core::actions::ActionState;
 use replicante_models_core::events::Event;
+use replicante_models_core::scope::Namespace;
 use replicante_store_primary::store::actions::ActionSyncState;
 use replicante_store_primary::store::actions::MAX_ACTIONS_STATE_FOR_SYNC;
 use replicante_store_primary::store::Store as PrimaryStore;
 use replicante_stream_events::EmitMessage;
 use replicante_stream_events::Stream as EventsStream;
+use replicante_util_failure::failure_info;
 use replicante_util_failure::SerializableFail;
 
 use crate::metrics::FETCHER_ACTIONS_CHUNKS;
@@ -25,10 +30,40 @@ use crate::metrics::FETCHER_ACTIONS_SYNCED;
 use crate::metrics::FETCHER_ACTION_SCHEDULE_DUPLICATE;
 use crate::metrics::FETCHER_ACTION_SCHEDULE_ERROR;
 use crate::metrics::FETCHER_ACTION_SCHEDULE_TOTAL;
+use crate::metrics::FETCHER_ACTION_TIMEOUT;
+use crate::Error;
 use crate::ErrorKind;
 use crate::Result;
 
-const MAX_SCHEDULE_ATTEMPTS: i32 = 10;
+/// Return the deadline for an action to finish by, if the action has one.
+///
+/// Actions without an explicit deadline use the namespace default timeout, if set.
+fn action_deadline(ns: &Namespace, action: &Action) -> Option<DateTime<Utc>> {
+    action.deadline_ts.or_else(|| {
+        ns.actions
+            .timeout
+            .map(|timeout| action.created_ts + Duration::seconds(timeout))
+    })
+}
+
+/// Return the deadline of an unfinished action if it has passed.
+fn expired_deadline(ns: &Namespace, action: &Action) -> Option<DateTime<Utc>> {
+    if action.finished_ts.is_some() {
+        return None;
+    }
+    action_deadline(ns, action).filter(|deadline| *deadline <= Utc::now())
+}
+
+/// Record that an action did not finish before its deadline.
+///
+/// The action is NOT marked as finished: callers decide when the action is finished.
+fn fail_timed_out(action: &mut Action, deadline: DateTime<Utc>) {
+    let error: Error = ErrorKind::ActionTimeout(deadline).into();
+    let payload = SerializableFail::from(&error);
+    let payload = serde_json::to_value(payload).expect("errors must always serialise");
+    action.state_payload = Some(payload);
+    action.state = ActionState::Failed;
+}
 
 /// Actions fetch and sync processing.
 pub(crate) struct ActionsFetcher {
@@ -53,6 +88,7 @@ impl ActionsFetcher {
     /// Sync the actions for the given node.
     pub fn sync(
         &self,
+        ns: &Namespace,
         client: &dyn Client,
         cluster_id: &str,
         agent_id: &str,
@@ -63,11 +99,36 @@ impl ActionsFetcher {
         let sync_ids = self.check_ids_to_sync(cluster_id, agent_id, remote_ids, span)?;
         let sync_size = sync_ids.len();
         for action_info in sync_ids {
-            self.sync_action(client, cluster_id, agent_id, action_info, refresh_id, span)?;
+            self.sync_action(
+                ns,
+                client,
+                cluster_id,
+                agent_id,
+                action_info,
+                refresh_id,
+                span,
+            )?;
         }
         FETCHER_ACTIONS_SYNCED.observe(sync_size as f64);
         self.mark_lost_actions(cluster_id, agent_id, refresh_id, span)?;
-        self.schedule_pending(client, cluster_id, agent_id, span)
+        self.schedule_pending(ns, client, cluster_id, agent_id, span)
+    }
+
+    /// Request the agent to cancel a timed out action.
+    ///
+    /// Errors are logged and otherwise ignored: the request is repeated on the next sync
+    /// until the agent reports the action as finished.
+    fn cancel_on_agent(&self, client: &dyn Client, action: &Action, span: &mut Span) {
+        if let Err(error) = client.cancel_action(&action.action_id, Some(span.context().clone())) {
+            warn!(
+                self.logger,
+                "Unable to cancel timed out action on the agent";
+                "cluster_id" => &action.cluster_id,
+                "agent_id" => &action.node_id,
+                "action_id" => %action.action_id,
+                failure_info(&error),
+            );
+        }
     }
 
     /// Check the given remote IDs against the primary store and return a list of IDs to sync.
@@ -177,6 +238,7 @@ impl ActionsFetcher {
 
     pub fn schedule_pending(
         &self,
+        ns: &Namespace,
         client: &dyn Client,
         cluster_id: &str,
         agent_id: &str,
@@ -189,6 +251,10 @@ impl ActionsFetcher {
             .with_context(|_| ErrorKind::PrimaryStoreRead("pending actions"))?;
         for action in actions {
             let mut action = action.with_context(|_| ErrorKind::PrimaryStoreRead("action"))?;
+            if let Some(deadline) = expired_deadline(ns, &action) {
+                self.timeout_pending(action, deadline, span)?;
+                continue;
+            }
             let action_id = action.action_id.to_string();
             let request = ActionScheduleRequest {
                 action_id: Some(action.action_id),
@@ -223,8 +289,7 @@ impl ActionsFetcher {
                             serde_json::to_value(payload).expect("errors must always serialise");
                         action.schedule_attempt += 1;
                         action.state_payload = Some(payload);
-                        // TODO: make MAX_SCHEDULE_ATTEMPTS a namespace configuration once namesapces exist.
-                        if action.schedule_attempt > MAX_SCHEDULE_ATTEMPTS {
+                        if action.schedule_attempt > ns.actions.max_schedule_attempts {
                             action.finish(ActionState::Failed);
                         }
                         self.primary_store
@@ -251,9 +316,49 @@ impl ActionsFetcher {
         Ok(())
     }
 
+    /// Fail a PENDING_SCHEDULE action that was not scheduled before its deadline.
+    fn timeout_pending(
+        &self,
+        mut action: Action,
+        deadline: DateTime<Utc>,
+        span: &mut Span,
+    ) -> Result<()> {
+        info!(
+            self.logger,
+            "Failing action not scheduled before its deadline";
+            "cluster_id" => &action.cluster_id,
+            "agent_id" => &action.node_id,
+            "action_id" => %action.action_id,
+        );
+        fail_timed_out(&mut action, deadline);
+        action.finish(ActionState::Failed);
+        FETCHER_ACTION_TIMEOUT.inc();
+        let event = Event::builder().action().finished(action.clone());
+        let code = event.code();
+        let stream_key = event.stream_key();
+        let event = EmitMessage::with(stream_key, event)
+            .with_context(|_| ErrorKind::EventEmit(code))?
+            .trace(span.context().clone());
+        self.events
+            .emit(event)
+            .with_context(|_| ErrorKind::EventEmit(code))?;
+        self.primary_store
+            .persist()
+            .action(action, span.context().clone())
+            .with_context(|_| ErrorKind::PrimaryStoreWrite("action"))?;
+        Ok(())
+    }
+
     /// Sync a single action's details.
+    ///
+    /// Unfinished actions past their deadline are failed and the agent is asked to cancel them.
+    /// Timed out actions are not marked as finished until the agent reports them as finished:
+    /// the sync process stops at the first finished action so finishing them early would
+    /// stop the actions queued after them from being synced (and have them marked as lost).
+    #[allow(clippy::too_many_arguments)]
     fn sync_action(
         &self,
+        ns: &Namespace,
         client: &dyn Client,
         cluster_id: &str,
         node_id: &str,
@@ -268,13 +373,29 @@ impl ActionsFetcher {
             Err(ref error) if error.not_found() => return Ok(()),
             _ => info.with_context(|_| ErrorKind::AgentDown("action info", node_id.to_string())),
         }?;
-        let action = Action::new(
+        let mut action = Action::new(
             cluster_id.to_string(),
             node_id.to_string(),
             refresh_id,
             info.action,
         );
 
+        // Deadlines are a Core concept the agent knows nothing about.
+        if let ActionSyncState::Found(old) = &action_sync_state {
+            action.deadline_ts = old.deadline_ts;
+        }
+        if let Some(deadline) = expired_deadline(ns, &action) {
+            let timed_out = match &action_sync_state {
+                ActionSyncState::Found(old) => old.state == ActionState::Failed,
+                _ => false,
+            };
+            if !timed_out {
+                FETCHER_ACTION_TIMEOUT.inc();
+            }
+            self.cancel_on_agent(client, &action, span);
+            fail_timed_out(&mut action, deadline);
+        }
+
         // Emit action-related events.
         match action_sync_state {
             ActionSyncState::Found(old) => {
@@ -345,6 +466,7 @@ impl ActionsFetcher {
 mod tests {
     use std::collections::HashMap;
 
+    use chrono::Duration;
     use chrono::Utc;
     use opentracingrust::tracers::NoopTracer;
     use serde_json::json;
@@ -361,6 +483,10 @@ mod tests {
     use replicante_models_core::actions::Action as CoreAction;
     use replicante_models_core::actions::ActionRequester;
     use replicante_models_core::actions::ActionState as ActionStateCore;
+    use replicante_models_core::scope::Namespace;
+    use replicante_models_core::scope::NsActions;
+    use replicante_models_core::scope::NsHealth;
+    use replicante_models_core::scope::NsHttpsTransport;
     use replicante_store_primary::mock::Mock as PrimaryStoreMock;
     use replicante_store_primary::store::actions::ActionSyncState;
     use replicante_stream_events::Stream as EventsStream;
@@ -392,6 +518,15 @@ mod tests {
         }
     }
 
+    fn mock_namespace() -> Namespace {
+        Namespace {
+            ns_id: "test".into(),
+            actions: NsActions::default(),
+            health: NsHealth::default(),
+            https_transport: NsHttpsTransport::default(),
+        }
+    }
+
     fn mock_core_action(id: Uuid, finished: bool) -> CoreAction {
         let created_ts = Utc::now();
         let finished_ts = if finished { Some(Utc::now()) } else { None };
@@ -401,6 +536,7 @@ mod tests {
             args: json!({}),
             cluster_id: "cluster".into(),
             created_ts,
+            deadline_ts: None,
             finished_ts,
             headers: HashMap::new(),
             kind: "action".into(),
@@ -620,6 +756,7 @@ mod tests {
         let mut span = tracer.span("test");
         fetcher
             .sync_action(
+                &mock_namespace(),
                 &client,
                 "cluster",
                 "node",
@@ -663,6 +800,7 @@ mod tests {
         let mut span = tracer.span("test");
         fetcher
             .sync_action(
+                &mock_namespace(),
                 &client,
                 "cluster",
                 "node",
@@ -682,4 +820,162 @@ mod tests {
         };
         assert!(!found, "should not have action");
     }
+
+    #[test]
+    fn schedule_pending_timeout() {
+        let store = PrimaryStoreMock::default();
+        {
+            let mut store = store.state.lock().expect("MockStore state lock poisoned");
+            let mut action = mock_core_action(*UUID1, false);
+            action.deadline_ts = Some(Utc::now() - Duration::seconds(10));
+            action.state = ActionStateCore::PendingSchedule;
+            store.actions.insert(
+                (
+                    action.cluster_id.clone(),
+                    action.node_id.clone(),
+                    action.action_id,
+                ),
+                action,
+            );
+        }
+        let client = MockClient::new(
+            || panic!("unused in these tests"),
+            || panic!("unused in these tests"),
+            || panic!("unused in these tests"),
+        );
+        let stream = EventsStream::mock();
+        let fetcher =
+            ActionsFetcher::new(stream, store.clone().store(), Logger::root(Discard, o!()));
+        let (tracer, _) = NoopTracer::new();
+        let mut span = tracer.span("test");
+        fetcher
+            .schedule_pending(&mock_namespace(), &client, "cluster", "node", &mut span)
+            .expect("scheduling pending actions failed");
+
+        let action = {
+            let store = store.state.lock().expect("MockStore state lock poisoned");
+            store
+                .actions
+                .get(&("cluster".into(), "node".into(), *UUID1))
+                .expect("expected action not found")
+                .clone()
+        };
+        assert_eq!(action.state, ActionStateCore::Failed);
+        assert!(action.finished_ts.is_some());
+        assert!(action.state_payload.is_some());
+    }
+
+    #[test]
+    fn sync_action_namespace_timeout() {
+        let mut client = MockClient::new(
+            || panic!("unused in these tests"),
+            || panic!("unused in these tests"),
+            || panic!("unused in these tests"),
+        );
+        let mut action = mock_agent_action(*UUID1, false);
+        action.created_ts = Utc::now() - Duration::seconds(120);
+        action.state = ActionStateAgent::Running;
+        let info = ActionInfoResponse {
+            action: action.clone(),
+            history: Vec::new(),
+        };
+        client.actions.insert(*UUID1, info);
+
+        let store = PrimaryStoreMock::default();
+        let stream = EventsStream::mock();
+        let fetcher =
+            ActionsFetcher::new(stream, store.clone().store(), Logger::root(Discard, o!()));
+        let (tracer, _) = NoopTracer::new();
+        let refresh_id = 1234;
+        let action = CoreAction::new("cluster", "node", refresh_id, action);
+        let mut ns = mock_namespace();
+        ns.actions.timeout = Some(60);
+        let mut span = tracer.span("test");
+        fetcher
+            .sync_action(
+                &ns,
+                &client,
+                "cluster",
+                "node",
+                (*UUID1, ActionSyncState::Found(action)),
+                refresh_id,
+                &mut span,
+            )
+            .expect("action sync failed");
+
+        let action = {
+            let store = store.state.lock().expect("MockStore state lock poisoned");
+            store
+                .actions
+                .get(&("cluster".into(), "node".into(), *UUID1))
+                .expect("expected action not found")
+                .clone()
+        };
+        assert_eq!(action.state, ActionStateCore::Failed);
+        assert!(action.finished_ts.is_none());
+        assert!(action.state_payload.is_some());
+    }
+
+    #[test]
+    fn sync_continues_after_timed_out_action() {
+        let mut client = MockClient::new(
+            || panic!("unused in these tests"),
+            || panic!("unused in these tests"),
+            || panic!("unused in these tests"),
+        );
+        let mut action1 = mock_agent_action(*UUID1, false);
+        action1.created_ts = Utc::now() - Duration::seconds(120);
+        action1.state = ActionStateAgent::Running;
+        let mut action2 = mock_agent_action(*UUID2, false);
+        action2.state = ActionStateAgent::New;
+        client.actions_queue = vec![
+            ActionListItem {
+                id: *UUID1,
+                kind: "test".into(),
+                state: ActionStateAgent::Running,
+            },
+            ActionListItem {
+                id: *UUID2,
+                kind: "test".into(),
+                state: ActionStateAgent::New,
+            },
+        ];
+        for action in vec![action1, action2] {
+            let info = ActionInfoResponse {
+                action: action.clone(),
+                history: Vec::new(),
+            };
+            client.actions.insert(action.id, info);
+        }
+
+        let store = PrimaryStoreMock::default();
+        let stream = EventsStream::mock();
+        let fetcher =
+            ActionsFetcher::new(stream, store.clone().store(), Logger::root(Discard, o!()));
+        let (tracer, _) = NoopTracer::new();
+        let mut ns = mock_namespace();
+        ns.actions.timeout = Some(60);
+        let mut span = tracer.span("test");
+        for refresh_id in &[1, 2] {
+            fetcher
+                .sync(&ns, &client, "cluster", "node", *refresh_id, &mut span)
+                .expect("actions sync failed");
+        }
+
+        let store = store.state.lock().expect("MockStore state lock poisoned");
+        let action1 = store
+            .actions
+            .get(&("cluster".into(), "node".into(), *UUID1))
+            .expect("expected action not found");
+        let action2 = store
+            .actions
+            .get(&("cluster".into(), "node".into(), *UUID2))
+            .expect("expected action not found");
+        assert_eq!(action1.state, ActionStateCore::Failed);
+        assert!(action1.finished_ts.is_none());
+        assert_eq!(action1.refresh_id, 2);
+        assert_eq!(action2.state, ActionStateCore::New);
+        assert!(action2.finished_ts.is_none());
+        assert_eq!(action2.refresh_id, 2);
+    }
 }
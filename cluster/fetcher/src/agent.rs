@@ -25,17 +25,31 @@ impl AgentFetcher {
         AgentFetcher { events, store }
     }
 
-    pub(crate) fn process_agent(&self, agent: Agent, span: &mut Span) -> Result<()> {
-        let old = self
+    /// Fetch the current record for an agent, if any.
+    pub(crate) fn get(
+        &self,
+        cluster_id: String,
+        host: String,
+        span: &mut Span,
+    ) -> Result<Option<Agent>> {
+        let agent = self
             .store
-            .agent(agent.cluster_id.clone(), agent.host.clone())
-            .get(span.context().clone());
+            .agent(cluster_id, host)
+            .get(span.context().clone())
+            .with_context(|_| ErrorKind::PrimaryStoreRead("agent"))?;
+        Ok(agent)
+    }
+
+    /// Process an agent given its previously known record, as returned by `get`.
+    pub(crate) fn process_agent(
+        &self,
+        agent: Agent,
+        old: Option<Agent>,
+        span: &mut Span,
+    ) -> Result<()> {
         match old {
-            Err(error) => Err(error)
-                .with_context(|_| ErrorKind::PrimaryStoreRead("agent"))
-                .map_err(Error::from),
-            Ok(None) => self.process_agent_new(agent, span),
-            Ok(Some(old)) => self.process_agent_existing(agent, old, span),
+            None => self.process_agent_new(agent, span),
+            Some(old) => self.process_agent_existing(agent, old, span),
         }
     }
 
@@ -0,0 +1,121 @@
+use std::time::Duration;
+
+use chrono::DateTime;
+use chrono::Duration as ChronoDuration;
+use chrono::Utc;
+
+use replicante_models_core::agent::AgentCircuit;
+
+/// Largest exponent used to compute backoff delays, to avoid overflows.
+const MAX_BACKOFF_EXPONENT: u32 = 16;
+
+/// Circuit breaker settings for agents that consecutively fail to respond.
+///
+/// Once an agent fails `threshold` consecutive times it is no longer contacted
+/// until a backoff delay expires, at which point it is probed again.
+/// The delay starts at `backoff_initial` and doubles on each failed probe, up to `backoff_max`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CircuitBreaker {
+    pub backoff_initial: Duration,
+    pub backoff_max: Duration,
+    pub threshold: i32,
+}
+
+impl CircuitBreaker {
+    /// Compute the circuit state after a failed attempt to reach the agent.
+    pub(crate) fn failure(
+        &self,
+        circuit: Option<&AgentCircuit>,
+        now: DateTime<Utc>,
+    ) -> AgentCircuit {
+        let failures = circuit.map(|circuit| circuit.failures).unwrap_or(0) + 1;
+        let retry_at = if failures >= self.threshold {
+            let exponent = ((failures - self.threshold) as u32).min(MAX_BACKOFF_EXPONENT);
+            let backoff = self
+                .backoff_initial
+                .checked_mul(2u32.pow(exponent))
+                .unwrap_or(self.backoff_max)
+                .min(self.backoff_max);
+            let backoff = ChronoDuration::from_std(backoff)
+                .expect("circuit breaker backoff to be a valid duration");
+            Some(now + backoff)
+        } else {
+            None
+        };
+        AgentCircuit { failures, retry_at }
+    }
+}
+
+impl Default for CircuitBreaker {
+    fn default() -> CircuitBreaker {
+        CircuitBreaker {
+            backoff_initial: Duration::from_secs(30),
+            backoff_max: Duration::from_secs(900),
+            threshold: 3,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use chrono::Duration as ChronoDuration;
+    use chrono::Utc;
+
+    use replicante_models_core::agent::AgentCircuit;
+
+    use super::CircuitBreaker;
+
+    fn breaker() -> CircuitBreaker {
+        CircuitBreaker {
+            backoff_initial: Duration::from_secs(10),
+            backoff_max: Duration::from_secs(60),
+            threshold: 2,
+        }
+    }
+
+    #[test]
+    fn below_threshold() {
+        let now = Utc::now();
+        let circuit = breaker().failure(None, now);
+        assert_eq!(circuit.failures, 1);
+        assert_eq!(circuit.retry_at, None);
+    }
+
+    #[test]
+    fn opens_at_threshold() {
+        let now = Utc::now();
+        let previous = AgentCircuit {
+            failures: 1,
+            retry_at: None,
+        };
+        let circuit = breaker().failure(Some(&previous), now);
+        assert_eq!(circuit.failures, 2);
+        assert_eq!(circuit.retry_at, Some(now + ChronoDuration::seconds(10)));
+        assert!(circuit.is_open(now));
+    }
+
+    #[test]
+    fn backoff_doubles() {
+        let now = Utc::now();
+        let previous = AgentCircuit {
+            failures: 3,
+            retry_at: Some(now),
+        };
+        let circuit = breaker().failure(Some(&previous), now);
+        assert_eq!(circuit.failures, 4);
+        assert_eq!(circuit.retry_at, Some(now + ChronoDuration::seconds(40)));
+    }
+
+    #[test]
+    fn backoff_capped() {
+        let now = Utc::now();
+        let previous = AgentCircuit {
+            failures: 40,
+            retry_at: Some(now),
+        };
+        let circuit = breaker().failure(Some(&previous), now);
+        assert_eq!(circuit.retry_at, Some(now + ChronoDuration::seconds(60)));
+    }
+}
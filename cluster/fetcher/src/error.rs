@@ -1,5 +1,7 @@
 use std::fmt;
 
+use chrono::DateTime;
+use chrono::Utc;
 use failure::Backtrace;
 use failure::Context;
 use failure::Fail;
@@ -87,6 +89,9 @@ impl fmt::Display for Error {
 /// Exhaustive list of possible errors emitted by this crate.
 #[derive(Debug, Fail)]
 pub enum ErrorKind {
+    #[fail(display = "action did not finish before its deadline ({})", _0)]
+    ActionTimeout(DateTime<Utc>),
+
     #[fail(display = "error connecting to agent {}", _0)]
     AgentConnect(String),
 
@@ -127,6 +132,7 @@ pub enum ErrorKind {
 impl ErrorKind {
     fn kind_name(&self) -> Option<&str> {
         let name = match self {
+            ErrorKind::ActionTimeout(_) => "ActionTimeout",
             ErrorKind::AgentConnect(_) => "AgentConnect",
             ErrorKind::AgentDown(_, _) => "AgentDown",
             ErrorKind::DatastoreDown(_, _) => "DatastoreDown",
@@ -1,15 +1,21 @@
+use std::collections::HashSet;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
-use std::time::Duration;
+use std::sync::Mutex;
 
+use chrono::Utc;
 use failure::ResultExt;
 use opentracingrust::Log;
 use opentracingrust::Span;
+use opentracingrust::SpanContext;
+use opentracingrust::StartOptions;
 use opentracingrust::Tracer;
 use slog::debug;
 use slog::warn;
 use slog::Logger;
 
-use replicante_agent_client::HttpClient;
+use replicante_agent_client::ClientPool;
 use replicante_models_core::agent::Agent;
 use replicante_models_core::agent::AgentStatus;
 use replicante_models_core::cluster::discovery::ClusterDiscovery;
@@ -21,35 +27,48 @@ use replicante_util_failure::failure_info;
 
 mod actions;
 mod agent;
+mod circuit;
 mod error;
 mod metrics;
 mod node;
+mod removals;
 mod shard;
 
 use self::actions::ActionsFetcher;
 use self::agent::AgentFetcher;
+use self::metrics::FETCHER_AGENT_CIRCUIT;
 use self::metrics::FETCHER_DURATION;
 use self::metrics::FETCHER_ERRORS_COUNT;
 use self::node::NodeFetcher;
+use self::removals::RefreshOutcome;
+use self::removals::RemovalsProcessor;
 use self::shard::ShardFetcher;
 
+pub use self::circuit::CircuitBreaker;
 pub use self::error::Error;
 pub use self::error::ErrorKind;
 pub use self::error::Result;
 pub use self::metrics::register_metrics;
 
+/// Thread-safe check that all nodes in a cluster agree on the cluster identity.
 struct ClusterIdentityChecker {
-    display_name: Option<String>,
+    display_name: Mutex<Option<String>>,
     id: String,
 }
 
 impl ClusterIdentityChecker {
-    fn check_or_set_display_name(&mut self, display_name: &str, node_id: &str) -> Result<()> {
-        if self.display_name.is_none() {
-            self.display_name = Some(display_name.to_string());
-            return Ok(());
-        }
-        let current = self.display_name.as_ref().unwrap();
+    fn check_or_set_display_name(&self, display_name: &str, node_id: &str) -> Result<()> {
+        let mut current = self
+            .display_name
+            .lock()
+            .expect("ClusterIdentityChecker lock poisoned");
+        let current = match current.as_ref() {
+            None => {
+                *current = Some(display_name.to_string());
+                return Ok(());
+            }
+            Some(current) => current,
+        };
         if current == display_name {
             return Ok(());
         }
@@ -61,7 +80,7 @@ impl ClusterIdentityChecker {
         .into())
     }
 
-    fn check_id(&mut self, id: &str, node_id: &str) -> Result<()> {
+    fn check_id(&self, id: &str, node_id: &str) -> Result<()> {
         if self.id == id {
             return Ok(());
         }
@@ -72,7 +91,50 @@ impl ClusterIdentityChecker {
     }
 
     fn new(id: String, display_name: Option<String>) -> ClusterIdentityChecker {
-        ClusterIdentityChecker { display_name, id }
+        ClusterIdentityChecker {
+            display_name: Mutex::new(display_name),
+            id,
+        }
+    }
+}
+
+/// State shared by all workers fetching nodes for the same cluster.
+struct FetchState<'a> {
+    aborted: AtomicBool,
+    cluster_id: &'a str,
+    id_checker: ClusterIdentityChecker,
+    lock: &'a NonBlockingLockWatcher,
+    nodes: Mutex<Vec<String>>,
+    ns: &'a Namespace,
+    refresh_id: i64,
+    refreshed: Mutex<HashSet<String>>,
+}
+
+impl<'a> FetchState<'a> {
+    /// Stop all workers from processing more nodes.
+    fn abort(&self) {
+        self.aborted.store(true, Ordering::Relaxed);
+    }
+
+    /// Check if workers should stop processing nodes.
+    fn is_aborted(&self) -> bool {
+        self.aborted.load(Ordering::Relaxed)
+    }
+
+    /// Take the next node to process, if any is left.
+    fn next_node(&self) -> Option<String> {
+        self.nodes
+            .lock()
+            .expect("FetchState nodes lock poisoned")
+            .pop()
+    }
+
+    /// Record that an agent was fully refreshed.
+    fn refreshed(&self, agent_id: String) {
+        self.refreshed
+            .lock()
+            .expect("FetchState refreshed lock poisoned")
+            .insert(agent_id);
     }
 }
 
@@ -80,14 +142,20 @@ impl ClusterIdentityChecker {
 ///
 /// Fetches agent data to "refresh" the persisted view of cluster nodes.
 /// See bin/replicante/tasks/cluster_refresh/mod.rs for details on the sync process.
+///
+/// Nodes are processed concurrently by up to `parallelism` threads.
+/// Each node is processed by one thread only so records for a node are updated in order.
 pub struct Fetcher {
     actions: ActionsFetcher,
     agent: AgentFetcher,
+    circuit: CircuitBreaker,
+    clients: ClientPool,
     logger: Logger,
     node: NodeFetcher,
+    parallelism: usize,
+    removals: RemovalsProcessor,
     shard: ShardFetcher,
     primary_store: PrimaryStore,
-    timeout: Duration,
     tracer: Arc<Tracer>,
 }
 
@@ -96,21 +164,27 @@ impl Fetcher {
         logger: Logger,
         events: EventsStream,
         primary_store: PrimaryStore,
-        timeout: Duration,
+        clients: ClientPool,
+        circuit: CircuitBreaker,
+        parallelism: usize,
         tracer: Arc<Tracer>,
     ) -> Fetcher {
         let actions = ActionsFetcher::new(events.clone(), primary_store.clone(), logger.clone());
         let agent = AgentFetcher::new(events.clone(), primary_store.clone());
         let node = NodeFetcher::new(events.clone(), primary_store.clone());
+        let removals = RemovalsProcessor::new(events.clone(), primary_store.clone());
         let shard = ShardFetcher::new(events, primary_store.clone());
         Fetcher {
             actions,
             agent,
+            circuit,
+            clients,
             logger,
             node,
+            parallelism,
             primary_store,
+            removals,
             shard,
-            timeout,
             tracer,
         }
     }
@@ -125,9 +199,15 @@ impl Fetcher {
     ///
     /// Core errors are returned and interupt the fetching process early (if the primary store is
     /// failing to respond it is likely to fail again in a short time).
+    /// Nodes already being processed when a core error occurs are completed
+    /// but no new nodes are processed.
     ///
     /// Remote errors are logged and accounted for as part of the refresh process (a remote agent
     /// crashing should not prevent the full cluster from being checked).
+    ///
+    /// # Removed entities
+    /// Once all nodes are processed, agents, nodes and shards that are no longer part of
+    /// the cluster are removed from the primary store (see `RemovalsProcessor` for details).
     pub fn fetch(
         &self,
         ns: Namespace,
@@ -156,88 +236,194 @@ impl Fetcher {
     ) -> Result<()> {
         let cluster_id = cluster.cluster_id;
         debug!(self.logger, "Refreshing cluster state"; "cluster_id" => &cluster_id);
-        let mut id_checker = ClusterIdentityChecker::new(cluster_id.clone(), cluster.display_name);
         self.primary_store
             .cluster(ns.ns_id.clone(), cluster_id.clone())
             .mark_stale(span.context().clone())
             .with_context(|_| ErrorKind::PrimaryStoreWrite("cluster staleness"))?;
 
-        for agent_id in cluster.nodes {
+        // Nodes are popped from the back of the list so reverse it to process them in order.
+        let discovered = cluster.nodes;
+        let mut nodes = discovered.clone();
+        nodes.reverse();
+        let workers = self.parallelism.max(1).min(nodes.len());
+        span.tag("fetch.workers", workers as i64);
+        let state = FetchState {
+            aborted: AtomicBool::new(false),
+            cluster_id: &cluster_id,
+            id_checker: ClusterIdentityChecker::new(cluster_id.clone(), cluster.display_name),
+            lock: &lock,
+            nodes: Mutex::new(nodes),
+            ns: &ns,
+            refresh_id,
+            refreshed: Mutex::new(HashSet::new()),
+        };
+
+        // Process nodes in a pool of scoped threads and wait for all of them to finish.
+        let context = span.context().clone();
+        let results = crossbeam_utils::thread::scope(|scope| {
+            let handles: Vec<_> = (0..workers)
+                .map(|_| {
+                    let context = context.clone();
+                    let state = &state;
+                    scope.spawn(move |_| self.fetch_worker(state, context))
+                })
+                .collect();
+            handles
+                .into_iter()
+                .map(|handle| match handle.join() {
+                    Ok(result) => result,
+                    Err(panic) => std::panic::resume_unwind(panic),
+                })
+                .collect::<Vec<_>>()
+        })
+        .expect("all fetcher workers to be joined");
+
+        if !lock.inspect() {
+            span.log(Log::new().log("abbandoned", "lock lost"));
+            return results.into_iter().collect();
+        }
+        results.into_iter().collect::<Result<()>>()?;
+
+        // Clean up entities that are no longer part of the cluster.
+        let refreshed = state
+            .refreshed
+            .into_inner()
+            .expect("FetchState refreshed lock poisoned");
+        let outcome = RefreshOutcome {
+            discovered: &discovered,
+            refreshed: &refreshed,
+        };
+        self.removals.process(&cluster_id, outcome, span)
+    }
+
+    /// Process nodes from the shared state until there are none left or processing is aborted.
+    fn fetch_worker(&self, state: &FetchState, context: SpanContext) -> Result<()> {
+        while !state.is_aborted() {
             // Exit early if lock was lost.
-            if !lock.inspect() {
-                span.log(Log::new().log("abbandoned", "lock lost"));
+            if !state.lock.inspect() {
+                state.abort();
                 warn!(
                     self.logger,
                     "Cluster fetcher lock lost, skipping futher nodes";
-                    "cluster_id" => &cluster_id,
-                    "agent_id" => &agent_id
+                    "cluster_id" => state.cluster_id,
                 );
                 return Ok(());
             }
 
-            // Process the target node and inspect the result.
-            // If an error within Replicante Core is reported pass it back to the caller
-            // and abort the refresh operation, otherwise update the agent status.
-            let target = self.process_target(
-                &ns,
-                &cluster_id,
-                &agent_id,
-                refresh_id,
-                &mut id_checker,
-                span,
-            );
-            let agent_status = match target {
-                Err(error) => match error.agent_status() {
-                    None => return Err(error),
-                    Some(status) => {
-                        // Log error at debug level and send to sentry as debug.
-                        let mut event = sentry::integrations::failure::event_from_fail(&error);
-                        event.level = sentry::Level::Debug;
-                        sentry::capture_event(event);
-                        debug!(
-                            self.logger,
-                            "Cluster sync operation not successful";
-                            "cluster_id" => &cluster_id,
-                            "agent_id" => &agent_id,
-                            failure_info(&error),
-                        );
-                        status
-                    }
-                },
-                Ok(()) => AgentStatus::Up,
+            let agent_id = match state.next_node() {
+                None => return Ok(()),
+                Some(agent_id) => agent_id,
             };
-            self.agent.process_agent(
-                Agent::new(cluster_id.to_string(), agent_id.to_string(), agent_status),
-                span,
-            )?;
+            let options = StartOptions::default().child_of(context.clone());
+            let mut span = self
+                .tracer
+                .span_with_options("fetch.node", options)
+                .auto_finish();
+            span.tag("agent.id", agent_id.clone());
+            if let Err(error) = self.process_node(state, agent_id, &mut span) {
+                state.abort();
+                return Err(error);
+            }
         }
         Ok(())
     }
 
+    /// Process a node and update its agent status.
+    fn process_node(&self, state: &FetchState, agent_id: String, span: &mut Span) -> Result<()> {
+        // Skip agents with an open circuit: they stay down until they are probed again.
+        let old = self
+            .agent
+            .get(state.cluster_id.to_string(), agent_id.clone(), span)?;
+        let circuit = old.as_ref().and_then(|agent| agent.circuit.clone());
+        let now = Utc::now();
+        if circuit.as_ref().map(|c| c.is_open(now)).unwrap_or(false) {
+            FETCHER_AGENT_CIRCUIT.with_label_values(&["skipped"]).inc();
+            debug!(
+                self.logger,
+                "Skipping agent with open circuit";
+                "cluster_id" => state.cluster_id,
+                "agent_id" => &agent_id,
+            );
+            return Ok(());
+        }
+
+        // Process the target node and inspect the result.
+        // If an error within Replicante Core is reported pass it back to the caller
+        // and abort the refresh operation, otherwise update the agent status.
+        let target = self.process_target(
+            state.ns,
+            state.cluster_id,
+            &agent_id,
+            state.refresh_id,
+            &state.id_checker,
+            span,
+        );
+        let agent_status = match target {
+            Err(error) => match error.agent_status() {
+                None => return Err(error),
+                Some(status) => {
+                    // Log error at debug level and send to sentry as debug.
+                    let mut event = sentry::integrations::failure::event_from_fail(&error);
+                    event.level = sentry::Level::Debug;
+                    sentry::capture_event(event);
+                    debug!(
+                        self.logger,
+                        "Cluster sync operation not successful";
+                        "cluster_id" => state.cluster_id,
+                        "agent_id" => &agent_id,
+                        failure_info(&error),
+                    );
+                    status
+                }
+            },
+            Ok(()) => {
+                state.refreshed(agent_id.clone());
+                AgentStatus::Up
+            }
+        };
+
+        // Track consecutive failures to reach the agent and open the circuit as needed.
+        let circuit = match agent_status {
+            AgentStatus::AgentDown(_) => {
+                let next = self.circuit.failure(circuit.as_ref(), now);
+                if next.retry_at.is_some() {
+                    FETCHER_AGENT_CIRCUIT.with_label_values(&["opened"]).inc();
+                }
+                Some(next)
+            }
+            _ => {
+                if circuit.is_some() {
+                    FETCHER_AGENT_CIRCUIT.with_label_values(&["closed"]).inc();
+                }
+                None
+            }
+        };
+        let mut agent = Agent::new(state.cluster_id.to_string(), agent_id, agent_status);
+        agent.circuit = circuit;
+        self.agent.process_agent(agent, old, span)
+    }
+
     fn process_target(
         &self,
         ns: &Namespace,
         cluster: &str,
         node: &str,
         refresh_id: i64,
-        id_checker: &mut ClusterIdentityChecker,
+        id_checker: &ClusterIdentityChecker,
         span: &mut Span,
     ) -> Result<()> {
-        let client = HttpClient::new(
-            ns,
-            node.to_string(),
-            self.timeout,
-            self.logger.clone(),
-            Arc::clone(&self.tracer),
-        )
-        .with_context(|_| ErrorKind::AgentConnect(node.to_string()))?;
+        let client = self
+            .clients
+            .get(ns, node)
+            .with_context(|_| ErrorKind::AgentConnect(node.to_string()))?;
+        let client = client.as_ref();
 
         self.agent
-            .process_agent_info(&client, cluster.to_string(), node.to_string(), span)?;
-        self.node.process_node(&client, id_checker, span)?;
-        self.shard.process_shards(&client, cluster, node, span)?;
+            .process_agent_info(client, cluster.to_string(), node.to_string(), span)?;
+        self.node.process_node(client, id_checker, span)?;
+        self.shard.process_shards(client, cluster, node, span)?;
         self.actions
-            .sync(&client, cluster, node, refresh_id, span)?;
+            .sync(ns, client, cluster, node, refresh_id, span)?;
 
         Ok(())
     }
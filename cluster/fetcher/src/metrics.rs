@@ -1,12 +1,22 @@
 use lazy_static::lazy_static;
 use prometheus::Counter;
+use prometheus::CounterVec;
 use prometheus::Histogram;
 use prometheus::HistogramOpts;
+use prometheus::Opts;
 use prometheus::Registry;
 use slog::debug;
 use slog::Logger;
 
 lazy_static! {
+    pub static ref FETCHER_AGENT_CIRCUIT: CounterVec = CounterVec::new(
+        Opts::new(
+            "replicore_fetcher_agent_circuit",
+            "Number of agent circuit breaker events (opened, skipped, closed)",
+        ),
+        &["event"],
+    )
+    .expect("Failed to create FETCHER_AGENT_CIRCUIT counter");
     pub static ref FETCHER_ACTIONS_CHUNKS: Histogram = Histogram::with_opts(
         HistogramOpts::new(
             "replicore_fetcher_actions_chunks",
@@ -38,6 +48,11 @@ lazy_static! {
         "Total number of action scheduling attempts",
     )
     .expect("Failed to create FETCHER_ACTION_SCHEDULE_TOTAL counter");
+    pub static ref FETCHER_ACTION_TIMEOUT: Counter = Counter::new(
+        "replicore_fetcher_action_timeout",
+        "Number of actions failed because they did not finish before their deadline",
+    )
+    .expect("Failed to create FETCHER_ACTION_TIMEOUT counter");
     pub static ref FETCHER_DURATION: Histogram = Histogram::with_opts(
         HistogramOpts::new(
             "replicore_fetcher_duration",
@@ -51,12 +66,23 @@ lazy_static! {
         "replicore_fetcher_errors", "Number of fetchers errors"
     )
     .expect("Failed to create FETCHER_ERRORS_COUNT counter");
+    pub static ref FETCHER_REMOVED_COUNT: CounterVec = CounterVec::new(
+        Opts::new(
+            "replicore_fetcher_removed",
+            "Number of agents, nodes and shards removed from clusters",
+        ),
+        &["kind"],
+    )
+    .expect("Failed to create FETCHER_REMOVED_COUNT counter");
 }
 
 /// Attemps to register metrics with the Registry.
 ///
 /// Metrics that fail to register are logged and ignored.
 pub fn register_metrics(logger: &Logger, registry: &Registry) {
+    if let Err(error) = registry.register(Box::new(FETCHER_AGENT_CIRCUIT.clone())) {
+        debug!(logger, "Failed to register FETCHER_AGENT_CIRCUIT"; "error" => ?error);
+    }
     if let Err(error) = registry.register(Box::new(FETCHER_ACTIONS_CHUNKS.clone())) {
         debug!(logger, "Failed to register FETCHER_ACTIONS_CHUNKS"; "error" => ?error);
     }
@@ -72,10 +98,16 @@ pub fn register_metrics(logger: &Logger, registry: &Registry) {
     if let Err(error) = registry.register(Box::new(FETCHER_ACTION_SCHEDULE_TOTAL.clone())) {
         debug!(logger, "Failed to register FETCHER_ACTION_SCHEDULE_TOTAL"; "error" => ?error);
     }
+    if let Err(error) = registry.register(Box::new(FETCHER_ACTION_TIMEOUT.clone())) {
+        debug!(logger, "Failed to register FETCHER_ACTION_TIMEOUT"; "error" => ?error);
+    }
     if let Err(error) = registry.register(Box::new(FETCHER_DURATION.clone())) {
         debug!(logger, "Failed to register FETCHER_DURATION"; "error" => ?error);
     }
     if let Err(error) = registry.register(Box::new(FETCHER_ERRORS_COUNT.clone())) {
         debug!(logger, "Failed to register FETCHER_ERRORS_COUNT"; "error" => ?error);
     }
+    if let Err(error) = registry.register(Box::new(FETCHER_REMOVED_COUNT.clone())) {
+        debug!(logger, "Failed to register FETCHER_REMOVED_COUNT"; "error" => ?error);
+    }
 }
@@ -27,7 +27,7 @@ impl NodeFetcher {
     pub(crate) fn process_node(
         &self,
         client: &dyn Client,
-        id_checker: &mut ClusterIdentityChecker,
+        id_checker: &ClusterIdentityChecker,
         span: &mut Span,
     ) -> Result<()> {
         let info = client
@@ -0,0 +1,215 @@
+use std::collections::HashSet;
+
+use failure::ResultExt;
+use opentracingrust::Span;
+
+use replicante_models_core::events::Event;
+use replicante_store_primary::store::Store;
+use replicante_stream_events::EmitMessage;
+use replicante_stream_events::Stream as EventsStream;
+
+use crate::metrics::FETCHER_REMOVED_COUNT;
+use crate::ErrorKind;
+use crate::Result;
+
+/// Outcome of a cluster refresh used to detect entities that are no longer in the cluster.
+pub(crate) struct RefreshOutcome<'a> {
+    /// All agents discovered for the cluster.
+    pub discovered: &'a [String],
+
+    /// Agents that were fully refreshed.
+    pub refreshed: &'a HashSet<String>,
+}
+
+impl<'a> RefreshOutcome<'a> {
+    /// True if every discovered agent was fully refreshed.
+    fn complete(&self) -> bool {
+        self.discovered
+            .iter()
+            .all(|agent| self.refreshed.contains(agent))
+    }
+
+    /// True if the agent is no longer discovered.
+    fn is_removed(&self, agent: &str) -> bool {
+        !self.discovered.iter().any(|discovered| discovered == agent)
+    }
+}
+
+/// Subset of fetcher logic that deals with agents, nodes and shards removed from a cluster.
+///
+/// Entities are removed once a refresh completes without seeing them:
+///
+///   * Agents are removed once they are no longer discovered.
+///   * Shards are removed when they are still stale after their agent was refreshed
+///     or when their agent is no longer discovered.
+///   * Nodes are removed when they are still stale after ALL agents were refreshed.
+///     Nodes are identified by the datastore so there is no way to know which agent
+///     a stale node belongs to when some agents could not be refreshed.
+///
+/// A `*_REMOVED` event is emitted for every entity before its records are deleted.
+pub(crate) struct RemovalsProcessor {
+    events: EventsStream,
+    store: Store,
+}
+
+impl RemovalsProcessor {
+    pub(crate) fn new(events: EventsStream, store: Store) -> RemovalsProcessor {
+        RemovalsProcessor { events, store }
+    }
+
+    /// Emit removal events for and delete entities no longer in the cluster.
+    pub(crate) fn process(
+        &self,
+        cluster_id: &str,
+        outcome: RefreshOutcome,
+        span: &mut Span,
+    ) -> Result<()> {
+        self.process_shards(cluster_id, &outcome, span)?;
+        if outcome.complete() {
+            self.process_nodes(cluster_id, span)?;
+        }
+        self.process_agents(cluster_id, &outcome, span)
+    }
+}
+
+impl RemovalsProcessor {
+    fn emit(&self, event: Event, span: &mut Span) -> Result<()> {
+        let code = event.code();
+        let stream_key = event.stream_key();
+        let event = EmitMessage::with(stream_key, event)
+            .with_context(|_| ErrorKind::EventEmit(code))?
+            .trace(span.context().clone());
+        self.events
+            .emit(event)
+            .with_context(|_| ErrorKind::EventEmit(code))?;
+        Ok(())
+    }
+
+    fn process_agents(
+        &self,
+        cluster_id: &str,
+        outcome: &RefreshOutcome,
+        span: &mut Span,
+    ) -> Result<()> {
+        let agents = self
+            .store
+            .agents(cluster_id.to_string())
+            .iter(span.context().clone())
+            .with_context(|_| ErrorKind::PrimaryStoreRead("agents"))?;
+        let mut removed = Vec::new();
+        for agent in agents {
+            let agent = agent.with_context(|_| ErrorKind::PrimaryStoreRead("agent"))?;
+            if outcome.is_removed(&agent.host) {
+                removed.push(agent);
+            }
+        }
+        for agent in removed {
+            let host = agent.host.clone();
+            self.emit(Event::builder().agent().removed(agent), span)?;
+            self.store
+                .agent(cluster_id.to_string(), host)
+                .delete(span.context().clone())
+                .with_context(|_| ErrorKind::PrimaryStoreWrite("agent removal"))?;
+            FETCHER_REMOVED_COUNT.with_label_values(&["agent"]).inc();
+        }
+        Ok(())
+    }
+
+    fn process_nodes(&self, cluster_id: &str, span: &mut Span) -> Result<()> {
+        let nodes = self
+            .store
+            .nodes(cluster_id.to_string())
+            .iter_stale(span.context().clone())
+            .with_context(|_| ErrorKind::PrimaryStoreRead("stale nodes"))?;
+        let mut removed = Vec::new();
+        for node in nodes {
+            let node = node.with_context(|_| ErrorKind::PrimaryStoreRead("stale node"))?;
+            removed.push(node);
+        }
+        for node in removed {
+            let node_id = node.node_id.clone();
+            self.emit(Event::builder().node().removed(node), span)?;
+            self.store
+                .node(cluster_id.to_string(), node_id)
+                .delete(span.context().clone())
+                .with_context(|_| ErrorKind::PrimaryStoreWrite("node removal"))?;
+            FETCHER_REMOVED_COUNT.with_label_values(&["node"]).inc();
+        }
+        Ok(())
+    }
+
+    fn process_shards(
+        &self,
+        cluster_id: &str,
+        outcome: &RefreshOutcome,
+        span: &mut Span,
+    ) -> Result<()> {
+        let shards = self
+            .store
+            .shards(cluster_id.to_string())
+            .iter_stale(span.context().clone())
+            .with_context(|_| ErrorKind::PrimaryStoreRead("stale shards"))?;
+        let mut removed = Vec::new();
+        for shard in shards {
+            let shard = shard.with_context(|_| ErrorKind::PrimaryStoreRead("stale shard"))?;
+            if outcome.refreshed.contains(&shard.node_id) || outcome.is_removed(&shard.node_id) {
+                removed.push(shard);
+            }
+        }
+        for shard in removed {
+            let node_id = shard.node_id.clone();
+            let shard_id = shard.shard_id.clone();
+            self.emit(Event::builder().shard().allocation_removed(shard), span)?;
+            self.store
+                .shard(cluster_id.to_string(), node_id, shard_id)
+                .delete(span.context().clone())
+                .with_context(|_| ErrorKind::PrimaryStoreWrite("shard removal"))?;
+            FETCHER_REMOVED_COUNT.with_label_values(&["shard"]).inc();
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::RefreshOutcome;
+
+    #[test]
+    fn outcome_complete() {
+        let discovered = vec!["a".to_string(), "b".to_string()];
+        let mut refreshed = HashSet::new();
+        refreshed.insert("a".to_string());
+        refreshed.insert("b".to_string());
+        let outcome = RefreshOutcome {
+            discovered: &discovered,
+            refreshed: &refreshed,
+        };
+        assert!(outcome.complete());
+    }
+
+    #[test]
+    fn outcome_incomplete() {
+        let discovered = vec!["a".to_string(), "b".to_string()];
+        let mut refreshed = HashSet::new();
+        refreshed.insert("a".to_string());
+        let outcome = RefreshOutcome {
+            discovered: &discovered,
+            refreshed: &refreshed,
+        };
+        assert!(!outcome.complete());
+    }
+
+    #[test]
+    fn outcome_removed() {
+        let discovered = vec!["a".to_string()];
+        let refreshed = HashSet::new();
+        let outcome = RefreshOutcome {
+            discovered: &discovered,
+            refreshed: &refreshed,
+        };
+        assert!(!outcome.is_removed("a"));
+        assert!(outcome.is_removed("b"));
+    }
+}
@@ -61,10 +61,19 @@ impl ShardFetcher {
 
     fn process_shard_existing(&self, shard: Shard, old: Shard, span: &mut Span) -> Result<()> {
         // If anything other then offset or lag changed emit and event.
-        if self.shard_changed(&shard, &old) {
-            let event = Event::builder()
-                .shard()
-                .allocation_changed(old, shard.clone());
+        // Otherwise emit a lag sample if the offset or lag changed.
+        let event = if self.shard_changed(&shard, &old) {
+            Some(
+                Event::builder()
+                    .shard()
+                    .allocation_changed(old, shard.clone()),
+            )
+        } else if shard != old {
+            Some(Event::builder().shard().lag_sample(shard.clone()))
+        } else {
+            None
+        };
+        if let Some(event) = event {
             let code = event.code();
             let stream_key = event.stream_key();
             let event = EmitMessage::with(stream_key, event)
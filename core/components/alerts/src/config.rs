@@ -0,0 +1,36 @@
+use serde::Deserialize;
+use serde::Serialize;
+
+/// Alert rules evaluation options.
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
+pub struct Config {
+    /// Interval (in seconds) between checks for firing alerts that should be resolved.
+    ///
+    /// Alerts based on events counts resolve once enough time passed without new events.
+    /// Because no event marks this moment, firing alerts are checked periodically.
+    #[serde(default = "Config::default_resolve_interval")]
+    pub resolve_interval: u64,
+
+    /// Interval (in seconds) after which alert rules are reloaded from the primary store.
+    #[serde(default = "Config::default_rules_refresh")]
+    pub rules_refresh: u64,
+}
+
+impl Default for Config {
+    fn default() -> Config {
+        Config {
+            resolve_interval: Config::default_resolve_interval(),
+            rules_refresh: Config::default_rules_refresh(),
+        }
+    }
+}
+
+impl Config {
+    fn default_resolve_interval() -> u64 {
+        60
+    }
+
+    fn default_rules_refresh() -> u64 {
+        60
+    }
+}
@@ -0,0 +1,58 @@
+use failure::ResultExt;
+use opentracingrust::SpanContext;
+use slog::debug;
+use slog::Logger;
+
+use replicante_models_core::alerts::Alert;
+use replicante_models_core::alerts::AlertState;
+use replicante_models_core::events::Event;
+use replicante_store_primary::store::alert_rules::AlertRules;
+use replicante_stream_events::EmitMessage;
+use replicante_stream_events::Stream;
+
+use crate::ErrorKind;
+use crate::Result;
+
+/// Emit the event for the last state change of a persisted alert, if it is pending.
+///
+/// Once the event is emitted the alert is marked as no longer pending, unless it was
+/// changed in the meantime, in which case the next change will emit the event again.
+pub fn pending_event(
+    events: &Stream,
+    logger: &Logger,
+    rules: &AlertRules,
+    mut alert: Alert,
+    span: Option<SpanContext>,
+) -> Result<()> {
+    if !alert.event_pending {
+        return Ok(());
+    }
+    alert.event_pending = false;
+    let event = match alert.state {
+        AlertState::Firing => Event::builder().alert().firing(alert.clone()),
+        AlertState::Resolved => Event::builder().alert().resolved(alert.clone()),
+        AlertState::Inactive => return Ok(()),
+    };
+    debug!(
+        logger, "Alert changed state";
+        "cluster_id" => &alert.cluster_id,
+        "event" => event.code(),
+        "namespace" => &alert.namespace,
+        "rule" => &alert.rule,
+    );
+    let code = event.code();
+    let stream_key = event.stream_key();
+    let event = EmitMessage::with(stream_key, event)
+        .with_context(|_| ErrorKind::EventsStreamEmit(code))?
+        .trace(span.clone());
+    events
+        .emit(event)
+        .with_context(|_| ErrorKind::EventsStreamEmit(code))?;
+
+    // Clearing the pending flag does not change the alert so its timestamp is kept as is.
+    let updated_ts = alert.updated_ts;
+    rules
+        .update_unchanged(alert, updated_ts, span)
+        .with_context(|_| ErrorKind::StoreWrite("alert"))?;
+    Ok(())
+}
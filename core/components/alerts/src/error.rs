@@ -0,0 +1,90 @@
+use std::fmt;
+
+use failure::Backtrace;
+use failure::Context;
+use failure::Fail;
+
+/// Error information returned by this crate.
+#[derive(Debug)]
+pub struct Error(Context<ErrorKind>);
+
+impl Error {
+    pub fn kind(&self) -> &ErrorKind {
+        self.0.get_context()
+    }
+}
+
+impl Fail for Error {
+    fn backtrace(&self) -> Option<&Backtrace> {
+        self.0.backtrace()
+    }
+
+    fn cause(&self) -> Option<&dyn Fail> {
+        self.0.cause()
+    }
+
+    fn name(&self) -> Option<&str> {
+        self.kind().kind_name()
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl From<Context<ErrorKind>> for Error {
+    fn from(inner: Context<ErrorKind>) -> Error {
+        Error(inner)
+    }
+}
+
+impl From<ErrorKind> for Error {
+    fn from(kind: ErrorKind) -> Error {
+        Error(Context::new(kind))
+    }
+}
+
+/// Exhaustive list of possible errors emitted by this crate.
+#[derive(Debug, Fail)]
+pub enum ErrorKind {
+    #[fail(display = "message with ID '{}' has not event code", _0)]
+    EventHasNoCode(String),
+
+    #[fail(display = "could not acknowledge message with ID '{}'", _0)]
+    EventsStreamAck(String),
+
+    #[fail(display = "could not emit {} event to the events stream", _0)]
+    EventsStreamEmit(&'static str),
+
+    #[fail(display = "could not follow the events stream to evaluate alert rules")]
+    EventsStreamFollow,
+
+    #[fail(display = "failed to read {} from the primary store", _0)]
+    StoreRead(&'static str),
+
+    #[fail(display = "failed to persist {} to the primary store", _0)]
+    StoreWrite(&'static str),
+
+    #[fail(display = "failed to spawn {} thread", _0)]
+    ThreadSpawn(&'static str),
+}
+
+impl ErrorKind {
+    fn kind_name(&self) -> Option<&str> {
+        let name = match self {
+            ErrorKind::EventHasNoCode(_) => "EventHasNoCode",
+            ErrorKind::EventsStreamAck(_) => "EventsStreamAck",
+            ErrorKind::EventsStreamEmit(_) => "EventsStreamEmit",
+            ErrorKind::EventsStreamFollow => "EventsStreamFollow",
+            ErrorKind::StoreRead(_) => "StoreRead",
+            ErrorKind::StoreWrite(_) => "StoreWrite",
+            ErrorKind::ThreadSpawn(_) => "ThreadSpawn",
+        };
+        Some(name)
+    }
+}
+
+/// Short form alias for functions returning `Error`s.
+pub type Result<T> = ::std::result::Result<T, Error>;
@@ -0,0 +1,333 @@
+use chrono::DateTime;
+use chrono::Duration;
+use chrono::TimeZone;
+use chrono::Utc;
+
+use replicante_models_core::actions::ActionState;
+use replicante_models_core::alerts::Alert;
+use replicante_models_core::alerts::AlertCondition;
+use replicante_models_core::alerts::AlertRule;
+use replicante_models_core::alerts::AlertState;
+use replicante_models_core::events::action::ActionEvent;
+use replicante_models_core::events::Event;
+use replicante_models_core::events::Payload;
+
+/// Effect of an event on an alert.
+#[derive(Debug, Eq, PartialEq)]
+pub enum Transition {
+    /// The alert started firing.
+    Fired,
+
+    /// The alert was firing and is now resolved.
+    Resolved,
+
+    /// The alert state did not change and the record does not need to be persisted.
+    Unchanged,
+
+    /// The alert state did not change but the record was updated and needs to be persisted.
+    Updated,
+}
+
+/// Check if an event is relevant to a rule and return the cluster the event is about.
+///
+/// Alert events are never relevant to avoid alerts feeding back into rules evaluation.
+pub fn matches<'a>(rule: &AlertRule, event: &'a Event) -> Option<&'a str> {
+    if let Payload::Alert(_) = event.payload {
+        return None;
+    }
+    let cluster_id = event.cluster_id()?;
+    if !rule.matches_cluster(cluster_id) {
+        return None;
+    }
+    let relevant = match &rule.condition {
+        AlertCondition::ActionFailed { kind } => match &event.payload {
+            Payload::Action(ActionEvent::Finished(action)) => &action.kind == kind,
+            _ => false,
+        },
+        AlertCondition::EventCount { event: code, .. } => event.code() == code,
+    };
+    if relevant {
+        Some(cluster_id)
+    } else {
+        None
+    }
+}
+
+/// Update an alert with an event that `matches` its rule.
+///
+/// Events already applied to the alert leave it `Unchanged`.
+pub fn apply(rule: &AlertRule, mut alert: Alert, event: &Event) -> (Alert, Transition) {
+    let now = event.timestamp;
+    let transition = match &rule.condition {
+        AlertCondition::ActionFailed { .. } => {
+            let state = match &event.payload {
+                Payload::Action(ActionEvent::Finished(action)) => &action.state,
+                _ => return (alert, Transition::Unchanged),
+            };
+            let firing = alert.state == AlertState::Firing;
+            match state {
+                ActionState::Failed if !firing => {
+                    alert.fire(now);
+                    Transition::Fired
+                }
+                ActionState::Done if firing => {
+                    alert.resolve(now);
+                    Transition::Resolved
+                }
+                _ => Transition::Unchanged,
+            }
+        }
+        AlertCondition::EventCount {
+            threshold, window, ..
+        } => {
+            // Events are identified by their timestamp so retried messages are not counted twice.
+            // Stores may only keep milliseconds so timestamps are truncated to compare them.
+            let matched = Utc.timestamp_millis(now.timestamp_millis());
+            if alert.matches.contains(&matched) {
+                return (alert, Transition::Unchanged);
+            }
+            alert.matches.push(matched);
+            alert.matches.sort();
+            prune(&mut alert.matches, now, *threshold, *window);
+            let over = alert.matches.len() > *threshold as usize;
+            match (alert.state == AlertState::Firing, over) {
+                (false, true) => {
+                    alert.fire(now);
+                    Transition::Fired
+                }
+                (true, false) => {
+                    alert.resolve(now);
+                    Transition::Resolved
+                }
+                _ => Transition::Updated,
+            }
+        }
+    };
+    (alert, transition)
+}
+
+/// Resolve a firing alert if, at time `now`, its rule condition is no longer met.
+///
+/// Only rules resolving with the passing of time are checked, others resolve on events.
+pub fn expire(rule: &AlertRule, mut alert: Alert, now: DateTime<Utc>) -> Option<Alert> {
+    if alert.state != AlertState::Firing {
+        return None;
+    }
+    match &rule.condition {
+        AlertCondition::ActionFailed { .. } => None,
+        AlertCondition::EventCount {
+            threshold, window, ..
+        } => {
+            prune(&mut alert.matches, now, *threshold, *window);
+            if alert.matches.len() > *threshold as usize {
+                return None;
+            }
+            alert.resolve(now);
+            Some(alert)
+        }
+    }
+}
+
+/// Drop matches outside of the window and retain no more than needed to evaluate the rule.
+fn prune(matches: &mut Vec<DateTime<Utc>>, now: DateTime<Utc>, threshold: u32, window: i64) {
+    let cutoff = now - Duration::seconds(window);
+    matches.retain(|ts| *ts >= cutoff);
+    let keep = threshold as usize + 1;
+    if matches.len() > keep {
+        let drop = matches.len() - keep;
+        matches.drain(..drop);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use chrono::DateTime;
+    use chrono::Duration;
+    use chrono::TimeZone;
+    use chrono::Utc;
+    use uuid::Uuid;
+
+    use replicante_models_core::actions::Action;
+    use replicante_models_core::actions::ActionRequester;
+    use replicante_models_core::actions::ActionState;
+    use replicante_models_core::alerts::Alert;
+    use replicante_models_core::alerts::AlertCondition;
+    use replicante_models_core::alerts::AlertRule;
+    use replicante_models_core::alerts::AlertState;
+    use replicante_models_core::events::Event;
+
+    use super::apply;
+    use super::expire;
+    use super::matches;
+    use super::Transition;
+
+    fn action(kind: &str, state: ActionState) -> Event {
+        let action = Action {
+            action_id: Uuid::new_v4(),
+            args: serde_json::json!({}),
+            cluster_id: "cluster".into(),
+            created_ts: Utc::now(),
+            deadline_ts: None,
+            finished_ts: None,
+            headers: HashMap::new(),
+            kind: kind.into(),
+            node_id: "node".into(),
+            refresh_id: 0,
+            requester: ActionRequester::CoreApi,
+            schedule_attempt: 0,
+            scheduled_ts: None,
+            state,
+            state_payload: None,
+        };
+        Event::builder().action().finished(action)
+    }
+
+    fn at(ts: DateTime<Utc>, mut event: Event) -> Event {
+        event.timestamp = ts;
+        event
+    }
+
+    fn rule(condition: AlertCondition) -> AlertRule {
+        AlertRule {
+            cluster_id: None,
+            condition,
+            description: None,
+            name: "test".into(),
+            namespace: "default".into(),
+        }
+    }
+
+    fn start() -> DateTime<Utc> {
+        Utc.ymd(2020, 1, 1).and_hms(12, 0, 0)
+    }
+
+    fn event_count() -> AlertRule {
+        rule(AlertCondition::EventCount {
+            event: "ACTION_FINISHED".into(),
+            threshold: 2,
+            window: 60,
+        })
+    }
+
+    #[test]
+    fn action_failed_fires_and_resolves() {
+        let rule = rule(AlertCondition::ActionFailed {
+            kind: "test.action".into(),
+        });
+        let alert = Alert::new(&rule, "cluster".into(), start());
+        let (alert, transition) = apply(&rule, alert, &action("test.action", ActionState::Failed));
+        assert_eq!(transition, Transition::Fired);
+        assert_eq!(alert.state, AlertState::Firing);
+        let (alert, transition) = apply(&rule, alert, &action("test.action", ActionState::Failed));
+        assert_eq!(transition, Transition::Unchanged);
+        let (alert, transition) = apply(&rule, alert, &action("test.action", ActionState::Done));
+        assert_eq!(transition, Transition::Resolved);
+        assert_eq!(alert.state, AlertState::Resolved);
+    }
+
+    #[test]
+    fn event_count_fires_above_threshold() {
+        let rule = event_count();
+        let alert = Alert::new(&rule, "cluster".into(), start());
+        let event = action("test.action", ActionState::Done);
+        let (alert, transition) = apply(&rule, alert, &at(start(), event.clone()));
+        assert_eq!(transition, Transition::Updated);
+        let ts = start() + Duration::seconds(10);
+        let (alert, transition) = apply(&rule, alert, &at(ts, event.clone()));
+        assert_eq!(transition, Transition::Updated);
+        let ts = start() + Duration::seconds(20);
+        let (alert, transition) = apply(&rule, alert, &at(ts, event));
+        assert_eq!(transition, Transition::Fired);
+        assert_eq!(alert.matches.len(), 3);
+    }
+
+    #[test]
+    fn event_count_ignores_events_outside_window() {
+        let rule = event_count();
+        let alert = Alert::new(&rule, "cluster".into(), start());
+        let event = action("test.action", ActionState::Done);
+        let (alert, _) = apply(&rule, alert, &at(start(), event.clone()));
+        let ts = start() + Duration::seconds(50);
+        let (alert, _) = apply(&rule, alert, &at(ts, event.clone()));
+        let ts = start() + Duration::seconds(100);
+        let (alert, transition) = apply(&rule, alert, &at(ts, event));
+        assert_eq!(transition, Transition::Updated);
+        assert_eq!(alert.matches.len(), 2);
+    }
+
+    #[test]
+    fn event_count_ignores_repeated_events() {
+        let rule = event_count();
+        let alert = Alert::new(&rule, "cluster".into(), start());
+        let event = at(start(), action("test.action", ActionState::Done));
+        let (alert, transition) = apply(&rule, alert, &event);
+        assert_eq!(transition, Transition::Updated);
+        let (alert, transition) = apply(&rule, alert, &event);
+        assert_eq!(transition, Transition::Unchanged);
+        assert_eq!(alert.matches, vec![start()]);
+    }
+
+    #[test]
+    fn event_count_ignores_repeated_events_after_store_round_trip() {
+        let rule = event_count();
+        let alert = Alert::new(&rule, "cluster".into(), start());
+        let ts = start() + Duration::nanoseconds(1_234_567);
+        let event = at(ts, action("test.action", ActionState::Done));
+        let (alert, _) = apply(&rule, alert, &event);
+
+        // Encode matches as BSON dates, like the MongoDB primary store does.
+        let matches: Vec<bson::Bson> = alert
+            .matches
+            .iter()
+            .cloned()
+            .map(bson::Bson::from)
+            .collect();
+        let document = bson::doc! {"matches": matches};
+        let mut buffer = Vec::new();
+        document.to_writer(&mut buffer).unwrap();
+        let document = bson::Document::from_reader(&mut buffer.as_slice()).unwrap();
+        let mut stored = alert.clone();
+        stored.matches = document
+            .get_array("matches")
+            .unwrap()
+            .iter()
+            .map(|ts| *ts.as_datetime().unwrap())
+            .collect();
+
+        let (alert, transition) = apply(&rule, stored, &event);
+        assert_eq!(transition, Transition::Unchanged);
+        assert_eq!(alert.matches.len(), 1);
+    }
+
+    #[test]
+    fn expire_resolves_once_window_passes() {
+        let rule = event_count();
+        let mut alert = Alert::new(&rule, "cluster".into(), start());
+        alert.matches = vec![start(), start(), start()];
+        alert.fire(start());
+        let ts = start() + Duration::seconds(30);
+        assert_eq!(expire(&rule, alert.clone(), ts), None);
+        let ts = start() + Duration::seconds(90);
+        let resolved = expire(&rule, alert, ts).expect("alert to be resolved");
+        assert_eq!(resolved.state, AlertState::Resolved);
+        assert!(resolved.matches.is_empty());
+    }
+
+    #[test]
+    fn matches_filters_events() {
+        let failed = rule(AlertCondition::ActionFailed {
+            kind: "test.action".into(),
+        });
+        let event = action("test.action", ActionState::Failed);
+        assert_eq!(matches(&failed, &event), Some("cluster"));
+        let event = action("other.action", ActionState::Failed);
+        assert_eq!(matches(&failed, &event), None);
+
+        let mut counted = event_count();
+        assert_eq!(matches(&counted, &event), Some("cluster"));
+        counted.cluster_id = Some("other".into());
+        assert_eq!(matches(&counted, &event), None);
+    }
+}
@@ -0,0 +1,230 @@
+use std::sync::Arc;
+use std::time::Duration;
+use std::time::Instant;
+
+use chrono::Utc;
+use failure::ResultExt;
+use humthreads::ThreadScope;
+use opentracingrust::AutoFinishingSpan;
+use opentracingrust::SpanContext;
+use opentracingrust::Tracer;
+use slog::debug;
+use slog::Logger;
+
+use replicante_models_core::alerts::Alert;
+use replicante_models_core::alerts::AlertRule;
+use replicante_models_core::events::DeserializeResult;
+use replicante_models_core::events::Event;
+use replicante_store_primary::store::Store;
+use replicante_stream_events::Message;
+use replicante_stream_events::Stream;
+use replicante_util_failure::capture_fail;
+use replicante_util_failure::failure_info;
+use replicante_util_tracing::fail_span;
+
+use crate::emit;
+use crate::evaluate::apply;
+use crate::evaluate::matches;
+use crate::evaluate::Transition;
+use crate::metrics::ALERTS_EVALUATION_ERRORS;
+use crate::metrics::ALERTS_FIRED;
+use crate::metrics::ALERTS_RESOLVED;
+use crate::ErrorKind;
+use crate::Result;
+
+const FOLLOW_GROUP: &str = "events:alerts";
+
+/// Stream follower used to keep the code readable.
+pub struct Follower<'a> {
+    pub events: Stream,
+    pub logger: Logger,
+    pub rules: Vec<AlertRule>,
+    pub rules_loaded: Option<Instant>,
+    pub rules_refresh: Duration,
+    pub store: Store,
+    pub thread: &'a ThreadScope,
+    pub tracer: Arc<Tracer>,
+}
+
+impl<'a> Follower<'a> {
+    /// Follow the event stream and evaluate alert rules against each event.
+    pub fn evaluate_events(&mut self) -> Result<()> {
+        let events = self.events.clone();
+        let iter = events
+            .follow(FOLLOW_GROUP, self.thread)
+            .context(ErrorKind::EventsStreamFollow)?;
+        self.thread.activity("waiting for events");
+        for message in iter {
+            let message = message.context(ErrorKind::EventsStreamFollow)?;
+            let _activity = self
+                .thread
+                .scoped_activity(format!("processing message: {}", message.id()));
+            let span =
+                Stream::span_for_message(&message, &self.tracer, &self.logger, "events.alerts");
+            let event = Stream::deserialize_event(&message);
+            match event {
+                DeserializeResult::Ok(event) => self.process(event, message, span)?,
+                DeserializeResult::Err(error) => {
+                    let message_id = message.id().to_string();
+                    Stream::report_event_error(
+                        &self.logger,
+                        "events.alerts",
+                        error,
+                        None,
+                        &message_id,
+                        span,
+                    );
+                    return Err(ErrorKind::EventHasNoCode(message_id).into());
+                }
+                DeserializeResult::Unknown(code, error) => {
+                    let message_id = message.id().to_string();
+                    Stream::report_event_error(
+                        &self.logger,
+                        "events.alerts",
+                        error,
+                        code,
+                        &message_id,
+                        span,
+                    );
+                    // Presume an upgrade is ongoing and retry the message.
+                    // We don't expect to succeed here but instead preserve other functions on the
+                    // node until the presumed upgrade process replaces us.
+                    message.retry();
+                }
+            };
+        }
+        Ok(())
+    }
+
+    /// Evaluate an event against a single rule, persisting and emitting any alert changes.
+    ///
+    /// Existing alerts are persisted only if they were not updated since they were read,
+    /// otherwise the event is evaluated again against the latest record.
+    /// Events are emitted after the alert is persisted and flagged as pending until then,
+    /// so retried messages, which `apply` ignores, emit events that failed to emit before.
+    fn evaluate(&self, rule: &AlertRule, event: &Event, span: Option<SpanContext>) -> Result<()> {
+        let cluster_id = match matches(rule, event) {
+            None => return Ok(()),
+            Some(cluster_id) => cluster_id,
+        };
+        let rules = self.store.alert_rules(rule.namespace.clone());
+        loop {
+            let stored = rules
+                .alert(&rule.name, cluster_id, span.clone())
+                .with_context(|_| ErrorKind::StoreRead("alert"))?;
+            let updated_ts = stored.as_ref().map(|alert| alert.updated_ts);
+            let alert =
+                stored.unwrap_or_else(|| Alert::new(rule, cluster_id.to_string(), event.timestamp));
+            let (mut alert, transition) = apply(rule, alert, event);
+            if transition == Transition::Unchanged {
+                return emit::pending_event(&self.events, &self.logger, &rules, alert, span);
+            }
+            alert.updated_ts = Utc::now();
+            let persisted = match updated_ts {
+                None => {
+                    self.store
+                        .persist()
+                        .alert(alert.clone(), span.clone())
+                        .with_context(|_| ErrorKind::StoreWrite("alert"))?;
+                    true
+                }
+                Some(updated_ts) => rules
+                    .update_unchanged(alert.clone(), updated_ts, span.clone())
+                    .with_context(|_| ErrorKind::StoreWrite("alert"))?,
+            };
+            if !persisted {
+                debug!(
+                    self.logger, "Alert updated while evaluating event, evaluating again";
+                    "cluster_id" => cluster_id,
+                    "namespace" => &rule.namespace,
+                    "rule" => &rule.name,
+                );
+                continue;
+            }
+            match transition {
+                Transition::Fired => ALERTS_FIRED
+                    .with_label_values(&[&rule.namespace, &rule.name])
+                    .inc(),
+                Transition::Resolved => ALERTS_RESOLVED
+                    .with_label_values(&[&rule.namespace, &rule.name])
+                    .inc(),
+                Transition::Unchanged | Transition::Updated => (),
+            };
+            return emit::pending_event(&self.events, &self.logger, &rules, alert, span);
+        }
+    }
+
+    fn process(
+        &mut self,
+        event: Event,
+        message: Message,
+        mut span: Option<AutoFinishingSpan>,
+    ) -> Result<()> {
+        let message_id = message.id().to_string();
+        let context = span.as_ref().map(|span| span.context().clone());
+        if let Err(error) = self.refresh_rules(context.clone()) {
+            capture_fail!(
+                &error,
+                self.logger,
+                "Failed to refresh alert rules";
+                "message_id" => &message_id,
+                failure_info(&error),
+            );
+            ALERTS_EVALUATION_ERRORS.inc();
+            fail_span(error, span.as_deref_mut());
+            message.retry();
+            return Ok(());
+        }
+
+        // Evaluate all rules against the event.
+        // If the message is retried, rules evaluated before the failure ignore the event
+        // but still emit alert events that failed to emit.
+        for rule in &self.rules {
+            if let Err(error) = self.evaluate(rule, &event, context.clone()) {
+                capture_fail!(
+                    &error,
+                    self.logger,
+                    "Failed to evaluate alert rule";
+                    "message_id" => &message_id,
+                    "namespace" => &rule.namespace,
+                    "rule" => &rule.name,
+                    failure_info(&error),
+                );
+                ALERTS_EVALUATION_ERRORS.inc();
+                fail_span(error, span.as_deref_mut());
+                message.retry();
+                return Ok(());
+            }
+        }
+        message
+            .async_ack()
+            .map_err(|error| fail_span(error, span.as_deref_mut()))
+            .with_context(|_| ErrorKind::EventsStreamAck(message_id))?;
+        Ok(())
+    }
+
+    /// Reload alert rules from the primary store if the cached ones are too old.
+    fn refresh_rules(&mut self, span: Option<SpanContext>) -> Result<()> {
+        let fresh = self
+            .rules_loaded
+            .map(|loaded| loaded.elapsed() < self.rules_refresh)
+            .unwrap_or(false);
+        if fresh {
+            return Ok(());
+        }
+        let mut rules = Vec::new();
+        let cursor = self
+            .store
+            .global_search()
+            .alert_rules(span)
+            .with_context(|_| ErrorKind::StoreRead("alert rules"))?;
+        for rule in cursor {
+            let rule = rule.with_context(|_| ErrorKind::StoreRead("alert rules"))?;
+            rules.push(rule);
+        }
+        debug!(self.logger, "Alert rules reloaded"; "rules" => rules.len());
+        self.rules = rules;
+        self.rules_loaded = Some(Instant::now());
+        Ok(())
+    }
+}
@@ -0,0 +1,115 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use failure::ResultExt;
+use humthreads::Builder as ThreadBuilder;
+use opentracingrust::Tracer;
+use slog::debug;
+use slog::Logger;
+
+use replicante_store_primary::store::Store;
+use replicante_stream_events::Stream;
+use replicante_util_failure::capture_fail;
+use replicante_util_failure::failure_info;
+use replicante_util_upkeep::Upkeep;
+
+mod config;
+mod emit;
+mod error;
+mod evaluate;
+mod follower;
+mod metrics;
+mod resolver;
+
+pub use self::config::Config;
+pub use self::error::Error;
+pub use self::error::ErrorKind;
+pub use self::error::Result;
+pub use self::metrics::register_metrics;
+
+/// Evaluate `AlertRule`s against the events stream to fire and resolve alerts.
+pub struct Alerts {
+    config: Config,
+    events: Stream,
+    logger: Logger,
+    store: Store,
+    tracer: Arc<Tracer>,
+}
+
+impl Alerts {
+    pub fn new(
+        config: Config,
+        events: Stream,
+        logger: Logger,
+        store: Store,
+        tracer: Arc<Tracer>,
+    ) -> Alerts {
+        Alerts {
+            config,
+            events,
+            logger,
+            store,
+            tracer,
+        }
+    }
+
+    /// Start the component in background threads and return.
+    ///
+    /// One thread follows the events stream while the other resolves expired alerts.
+    pub fn run(&self, upkeep: &mut Upkeep) -> Result<()> {
+        let events = self.events.clone();
+        let logger = self.logger.clone();
+        let rules_refresh = Duration::from_secs(self.config.rules_refresh);
+        let store = self.store.clone();
+        let tracer = self.tracer.clone();
+        debug!(logger, "Starting alerts thread");
+        let thread = ThreadBuilder::new("r:c:alerts")
+            .full_name("replicore:component:alerts")
+            .spawn(move |scope| {
+                let thread = &scope;
+                let mut worker = self::follower::Follower {
+                    events,
+                    logger: logger.clone(),
+                    rules: Vec::new(),
+                    rules_loaded: None,
+                    rules_refresh,
+                    store,
+                    thread,
+                    tracer,
+                };
+                if let Err(error) = worker.evaluate_events() {
+                    capture_fail!(
+                        &error,
+                        logger,
+                        "Alert rules evaluation stopped";
+                        failure_info(&error),
+                    );
+                }
+            })
+            .with_context(|_| ErrorKind::ThreadSpawn("alerts"))?;
+        upkeep.register_thread(thread);
+
+        let events = self.events.clone();
+        let interval = Duration::from_secs(self.config.resolve_interval);
+        let logger = self.logger.clone();
+        let store = self.store.clone();
+        let tracer = self.tracer.clone();
+        debug!(logger, "Starting alerts resolver thread");
+        let thread = ThreadBuilder::new("r:c:alerts:resolver")
+            .full_name("replicore:component:alerts:resolver")
+            .spawn(move |scope| {
+                let resolver = self::resolver::Resolver {
+                    events,
+                    interval,
+                    logger,
+                    store,
+                    thread: &scope,
+                    tracer,
+                };
+                resolver.loop_forever();
+            })
+            .with_context(|_| ErrorKind::ThreadSpawn("alerts resolver"))?;
+        upkeep.register_thread(thread);
+        Ok(())
+    }
+}
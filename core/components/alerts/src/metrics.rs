@@ -0,0 +1,45 @@
+use prometheus::Counter;
+use prometheus::CounterVec;
+use prometheus::Opts;
+use prometheus::Registry;
+use slog::debug;
+use slog::Logger;
+
+lazy_static::lazy_static! {
+    pub static ref ALERTS_EVALUATION_ERRORS: Counter = Counter::with_opts(Opts::new(
+        "replicore_alerts_evaluation_errors",
+        "Number of errors while evaluating alert rules",
+    ))
+    .expect("Failed to create ALERTS_EVALUATION_ERRORS");
+    pub static ref ALERTS_FIRED: CounterVec = CounterVec::new(
+        Opts::new(
+            "replicore_alerts_fired",
+            "Number of times alerts started firing",
+        ),
+        &["namespace", "rule"]
+    )
+    .expect("Failed to create ALERTS_FIRED");
+    pub static ref ALERTS_RESOLVED: CounterVec = CounterVec::new(
+        Opts::new(
+            "replicore_alerts_resolved",
+            "Number of times firing alerts resolved",
+        ),
+        &["namespace", "rule"]
+    )
+    .expect("Failed to create ALERTS_RESOLVED");
+}
+
+/// Attemps to register metrics with the Registry.
+///
+/// Metrics that fail to register are logged and ignored.
+pub fn register_metrics(logger: &Logger, registry: &Registry) {
+    if let Err(error) = registry.register(Box::new(ALERTS_EVALUATION_ERRORS.clone())) {
+        debug!(logger, "Failed to register ALERTS_EVALUATION_ERRORS"; "error" => ?error);
+    }
+    if let Err(error) = registry.register(Box::new(ALERTS_FIRED.clone())) {
+        debug!(logger, "Failed to register ALERTS_FIRED"; "error" => ?error);
+    }
+    if let Err(error) = registry.register(Box::new(ALERTS_RESOLVED.clone())) {
+        debug!(logger, "Failed to register ALERTS_RESOLVED"; "error" => ?error);
+    }
+}
@@ -0,0 +1,121 @@
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+use std::time::Instant;
+
+use chrono::Utc;
+use failure::ResultExt;
+use humthreads::ThreadScope;
+use opentracingrust::SpanContext;
+use opentracingrust::Tracer;
+use slog::Logger;
+
+use replicante_models_core::alerts::Alert;
+use replicante_store_primary::store::Store;
+use replicante_stream_events::Stream;
+use replicante_util_failure::capture_fail;
+use replicante_util_failure::failure_info;
+use replicante_util_tracing::fail_span;
+
+use crate::emit;
+use crate::evaluate::expire;
+use crate::metrics::ALERTS_EVALUATION_ERRORS;
+use crate::metrics::ALERTS_RESOLVED;
+use crate::ErrorKind;
+use crate::Result;
+
+/// Periodically resolve firing alerts whose condition is no longer met.
+pub struct Resolver<'a> {
+    pub events: Stream,
+    pub interval: Duration,
+    pub logger: Logger,
+    pub store: Store,
+    pub thread: &'a ThreadScope,
+    pub tracer: Arc<Tracer>,
+}
+
+impl<'a> Resolver<'a> {
+    /// Check firing alerts every interval until the thread is asked to shut down.
+    pub fn loop_forever(&self) {
+        while !self.thread.should_shutdown() {
+            if let Err(error) = self.run() {
+                capture_fail!(
+                    &error,
+                    self.logger,
+                    "Failed to resolve firing alerts";
+                    failure_info(&error),
+                );
+                ALERTS_EVALUATION_ERRORS.inc();
+            }
+            self.thread.activity("waiting for next resolve check");
+            let start = Instant::now();
+            while start.elapsed() < self.interval {
+                if self.thread.should_shutdown() {
+                    return;
+                }
+                thread::sleep(Duration::from_millis(100));
+            }
+        }
+    }
+
+    /// Resolve an alert if its rule condition is no longer met or the rule no longer exists.
+    ///
+    /// Alerts are resolved only if they were not updated since they were read.
+    /// This means resolved events are emitted after the alert is persisted, otherwise
+    /// an event would be emitted for alerts that were changed by the events follower.
+    ///
+    /// Alerts with an event that previously failed to emit have the event emitted instead.
+    fn resolve(&self, mut alert: Alert, span: SpanContext) -> Result<()> {
+        let rules = self.store.alert_rules(alert.namespace.clone());
+        if alert.event_pending {
+            return emit::pending_event(&self.events, &self.logger, &rules, alert, Some(span));
+        }
+        let rule = rules
+            .get(&alert.rule, span.clone())
+            .with_context(|_| ErrorKind::StoreRead("alert rule"))?;
+        let updated_ts = alert.updated_ts;
+        let now = Utc::now();
+        let resolved = match rule {
+            // Alerts of deleted rules would otherwise fire forever.
+            None => {
+                alert.resolve(now);
+                alert
+            }
+            Some(rule) => match expire(&rule, alert, now) {
+                None => return Ok(()),
+                Some(resolved) => resolved,
+            },
+        };
+        let changed = rules
+            .update_unchanged(resolved.clone(), updated_ts, span.clone())
+            .with_context(|_| ErrorKind::StoreWrite("alert"))?;
+        if !changed {
+            return Ok(());
+        }
+        ALERTS_RESOLVED
+            .with_label_values(&[&resolved.namespace, &resolved.rule])
+            .inc();
+        emit::pending_event(&self.events, &self.logger, &rules, resolved, Some(span))
+    }
+
+    /// Search for active alerts, resolve the ones that expired and emit pending events.
+    fn run(&self) -> Result<()> {
+        let _activity = self.thread.scoped_activity("resolving expired alerts");
+        let mut span = self.tracer.span("component.alerts.resolve").auto_finish();
+        let span_context = span.context().clone();
+        let alerts = self
+            .store
+            .global_search()
+            .active_alerts(span_context.clone())
+            .with_context(|_| ErrorKind::StoreRead("active alerts"))
+            .map_err(|error| fail_span(error, &mut *span))?;
+        for alert in alerts {
+            let alert = alert
+                .with_context(|_| ErrorKind::StoreRead("active alerts"))
+                .map_err(|error| fail_span(error, &mut *span))?;
+            self.resolve(alert, span_context.clone())
+                .map_err(|error| fail_span(error, &mut *span))?;
+        }
+        Ok(())
+    }
+}
@@ -6,7 +6,7 @@ use opentracingrust::Tracer;
 use slog::debug;
 use slog::Logger;
 
-use replicante_models_core::cluster::discovery::DiscoverySettings;
+use replicante_models_core::cluster::discovery::DiscoveryRun;
 use replicante_service_tasks::TaskRequest;
 use replicante_store_primary::store::Store;
 use replicante_util_failure::capture_fail;
@@ -70,10 +70,11 @@ impl DiscoveryLogic {
     /// Process an individual DiscoverySettings record and schedule a discovery task for it.
     fn schedule_discovery(
         &self,
-        discovery: replicante_store_primary::Result<DiscoverySettings>,
+        run: replicante_store_primary::Result<DiscoveryRun>,
         span_context: SpanContext,
     ) -> Result<()> {
-        let discovery = discovery.context(ErrorKind::DiscoveriesPartialSearch)?;
+        let run = run.context(ErrorKind::DiscoveriesPartialSearch)?;
+        let discovery = run.settings;
         debug!(
             self.logger,
             "Scheduling pending discovery";
@@ -81,8 +82,11 @@ impl DiscoveryLogic {
             "name" => &discovery.name,
         );
 
-        // Enqueue clusters discovery task.
-        let payload = DiscoverClustersPayload::new(discovery.clone());
+        // Enqueue clusters discovery task, continuing a failed run if a retry is pending.
+        let payload = match run.retry {
+            None => DiscoverClustersPayload::new(discovery.clone()),
+            Some(retry) => DiscoverClustersPayload::retry(discovery.clone(), retry),
+        };
         let mut task = TaskRequest::new(ReplicanteQueues::DiscoverClusters);
         if let Err(error) = task.trace(&span_context, &self.tracer) {
             let error = failure::SyncFailure::new(error);
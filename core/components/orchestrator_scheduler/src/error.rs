@@ -49,13 +49,25 @@ impl From<ErrorKind> for Error {
 /// Exhaustive list of possible errors emitted by this crate.
 #[derive(Debug, Fail)]
 pub enum ErrorKind {
-    #[fail(display = "failed to spawn descovery thread")]
+    #[fail(display = "unable to fetch discovery record for cluster {}", _0)]
+    ClusterDiscoveryFetch(String),
+
+    #[fail(display = "unable to search for clusters to orchestrate")]
+    ClustersSearch,
+
+    #[fail(display = "unable to iterate over clusters to orchestrate search")]
+    ClustersPartialSearch,
+
+    #[fail(display = "failed to spawn orchestrator thread")]
     ThreadSpawn,
 }
 
 impl ErrorKind {
     fn kind_name(&self) -> Option<&str> {
         let name = match self {
+            ErrorKind::ClusterDiscoveryFetch(_) => "ClusterDiscoveryFetch",
+            ErrorKind::ClustersSearch => "ClustersSearch",
+            ErrorKind::ClustersPartialSearch => "ClustersPartialSearch",
             ErrorKind::ThreadSpawn => "ThreadSpawn",
         };
         Some(name)
@@ -1,42 +1,41 @@
-//use std::sync::Arc;
+use std::sync::Arc;
 
-//use failure::ResultExt;
-//use opentracingrust::SpanContext;
-//use opentracingrust::Tracer;
-//use slog::debug;
-//use slog::Logger;
+use failure::ResultExt;
+use opentracingrust::SpanContext;
+use opentracingrust::Tracer;
+use slog::debug;
+use slog::Logger;
 
-//use replicante_models_core::cluster::discovery::DiscoverySettings;
-//use replicante_service_tasks::TaskRequest;
-//use replicante_store_primary::store::Store;
-//use replicante_util_failure::capture_fail;
-//use replicante_util_failure::failure_info;
-//use replicante_util_tracing::fail_span;
+use replicante_models_core::cluster::ClusterSettings;
+use replicante_service_tasks::TaskRequest;
+use replicante_store_primary::store::Store;
+use replicante_util_failure::capture_fail;
+use replicante_util_failure::failure_info;
+use replicante_util_tracing::fail_span;
 
-//use replicore_models_tasks::payload::DiscoverClustersPayload;
-//use replicore_models_tasks::ReplicanteQueues;
-//use replicore_models_tasks::Tasks;
+use replicore_models_tasks::payload::ClusterRefreshPayload;
+use replicore_models_tasks::ReplicanteQueues;
+use replicore_models_tasks::Tasks;
 
-//use crate::metrics::DISCOVERY_SCHEDULE_COUNT;
-//use crate::ErrorKind;
+use crate::metrics::ORCHESTRATOR_SCHEDULE_COUNT;
+use crate::ErrorKind;
 use crate::Result;
 
-/// Handle fetching and scheduling cluster discovery tasks.
+/// Handle fetching and scheduling cluster orchestration tasks.
 pub struct Logic {
-    //logger: Logger,
-//    store: Store,
-//    tasks: Tasks,
-//    tracer: Arc<Tracer>,
+    logger: Logger,
+    store: Store,
+    tasks: Tasks,
+    tracer: Arc<Tracer>,
 }
 
 impl Logic {
-    //pub fn new(logger: Logger, store: Store, tasks: Tasks, tracer: Arc<Tracer>) -> Logic {
-    pub fn new() -> Logic {
+    pub fn new(logger: Logger, store: Store, tasks: Tasks, tracer: Arc<Tracer>) -> Logic {
         Logic {
-            //logger,
-            //store,
-            //tasks,
-            //tracer,
+            logger,
+            store,
+            tasks,
+            tracer,
         }
     }
 
@@ -45,26 +44,90 @@ impl Logic {
     /// Update the next_orchestrate attribute when the orchestration is scheduled.
     /// This prevents scheduling the same orchestration repetitively in many situations:
     ///  * Slow or busy workers may fail to keep up (adding more work won't help).
-    ///  * Incorrect configuration (short discovery loop intervals).
+    ///  * Incorrect configuration (short orchestration loop intervals).
     ///  * One of many many possible bugs ...
     pub fn run(&self) -> Result<()> {
-        //let mut span = self
-        //    .tracer
-        //    .span("component.discover_clusters")
-        //    .auto_finish();
-        //let span_context = span.context().clone();
-        //let discoveries = self
-        //    .store
-        //    .global_search()
-        //    .discoveries_to_run(span_context.clone())
-        //    .context(ErrorKind::DiscoveriesSearch)
-        //    .map_err(|error| fail_span(error, &mut *span))?;
+        let mut span = self
+            .tracer
+            .span("component.orchestrate_clusters")
+            .auto_finish();
+        let span_context = span.context().clone();
+        let clusters = self
+            .store
+            .global_search()
+            .clusters_to_orchestrate(span_context.clone())
+            .context(ErrorKind::ClustersSearch)
+            .map_err(|error| fail_span(error, &mut *span))?;
 
-        //for discovery in discoveries {
-        //    self.schedule_discovery(discovery, span_context.clone())
-        //        .map_err(|error| fail_span(error, &mut *span))?;
-        //    DISCOVERY_SCHEDULE_COUNT.inc();
-        //}
+        for cluster in clusters {
+            self.schedule_orchestrate(cluster, span_context.clone())
+                .map_err(|error| fail_span(error, &mut *span))?;
+            ORCHESTRATOR_SCHEDULE_COUNT.inc();
+        }
+        Ok(())
+    }
+
+    /// Process an individual ClusterSettings record and schedule a cluster refresh task for it.
+    fn schedule_orchestrate(
+        &self,
+        cluster: replicante_store_primary::Result<ClusterSettings>,
+        span_context: SpanContext,
+    ) -> Result<()> {
+        let cluster = cluster.context(ErrorKind::ClustersPartialSearch)?;
+        debug!(
+            self.logger,
+            "Scheduling pending cluster orchestration";
+            "namespace" => &cluster.namespace,
+            "cluster_id" => &cluster.cluster_id,
+        );
+
+        // The refresh task needs the latest discovery record for the cluster.
+        let discovery = self
+            .store
+            .cluster(cluster.namespace.clone(), cluster.cluster_id.clone())
+            .discovery(span_context.clone())
+            .with_context(|_| ErrorKind::ClusterDiscoveryFetch(cluster.cluster_id.clone()))?;
+        match discovery {
+            None => debug!(
+                self.logger,
+                "Skipping orchestration of cluster without a discovery record";
+                "namespace" => &cluster.namespace,
+                "cluster_id" => &cluster.cluster_id,
+            ),
+            Some(discovery) => {
+                // Enqueue cluster refresh task.
+                let payload = ClusterRefreshPayload::new(discovery, false)
+                    .with_namespace(cluster.namespace.clone());
+                let mut task = TaskRequest::new(ReplicanteQueues::ClusterRefresh);
+                if let Err(error) = task.trace(&span_context, &self.tracer) {
+                    let error = failure::SyncFailure::new(error);
+                    capture_fail!(
+                        &error,
+                        self.logger,
+                        "Unable to inject trace context in task request";
+                        "namespace" => &cluster.namespace,
+                        "cluster_id" => &cluster.cluster_id,
+                        failure_info(&error),
+                    );
+                }
+                if let Err(error) = self.tasks.request(task, payload) {
+                    capture_fail!(
+                        &error,
+                        self.logger,
+                        "Failed to request cluster orchestration";
+                        "namespace" => &cluster.namespace,
+                        "cluster_id" => &cluster.cluster_id,
+                        failure_info(&error),
+                    );
+                };
+            }
+        }
+
+        // Update next_orchestrate attribute so we don't spam ourselves with tasks.
+        self.store
+            .persist()
+            .next_cluster_orchestrate(cluster, span_context)
+            .context(ErrorKind::ClustersPartialSearch)?;
         Ok(())
     }
 }
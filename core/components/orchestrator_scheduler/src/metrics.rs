@@ -0,0 +1,51 @@
+use prometheus::Counter;
+use prometheus::Histogram;
+use prometheus::HistogramOpts;
+use prometheus::Opts;
+use prometheus::Registry;
+use slog::debug;
+use slog::Logger;
+
+lazy_static::lazy_static! {
+    pub static ref ORCHESTRATOR_DURATION: Histogram = Histogram::with_opts(
+        HistogramOpts::new(
+            "replicore_orchestrator_duration",
+            "Duration (in seconds) of pending cluster orchestrations search and schedule cycles",
+        )
+        .buckets(vec![0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 20.0, 40.0])
+    )
+    .expect("Failed to create ORCHESTRATOR_DURATION");
+    pub static ref ORCHESTRATOR_LOOP_COUNT: Counter = Counter::with_opts(Opts::new(
+        "replicore_orchestrator_loops",
+        "Number of pending cluster orchestrations search and schedule cycles",
+    ))
+    .expect("Failed to create ORCHESTRATOR_LOOP_COUNT");
+    pub static ref ORCHESTRATOR_LOOP_ERRORS: Counter = Counter::with_opts(Opts::new(
+        "replicore_orchestrator_loop_errors",
+        "Number of errors during pending cluster orchestrations search and schedule cycles",
+    ))
+    .expect("Failed to create ORCHESTRATOR_LOOP_ERRORS");
+    pub static ref ORCHESTRATOR_SCHEDULE_COUNT: Counter = Counter::with_opts(Opts::new(
+        "replicore_orchestrator_scheduled",
+        "Number of pending cluster orchestrations scheduled to run",
+    ))
+    .expect("Failed to create ORCHESTRATOR_SCHEDULE_COUNT");
+}
+
+/// Attemps to register metrics with the Registry.
+///
+/// Metrics that fail to register are logged and ignored.
+pub fn register_metrics(logger: &Logger, registry: &Registry) {
+    if let Err(error) = registry.register(Box::new(ORCHESTRATOR_DURATION.clone())) {
+        debug!(logger, "Failed to register ORCHESTRATOR_DURATION"; "error" => ?error);
+    }
+    if let Err(error) = registry.register(Box::new(ORCHESTRATOR_LOOP_COUNT.clone())) {
+        debug!(logger, "Failed to register ORCHESTRATOR_LOOP_COUNT"; "error" => ?error);
+    }
+    if let Err(error) = registry.register(Box::new(ORCHESTRATOR_LOOP_ERRORS.clone())) {
+        debug!(logger, "Failed to register ORCHESTRATOR_LOOP_ERRORS"; "error" => ?error);
+    }
+    if let Err(error) = registry.register(Box::new(ORCHESTRATOR_SCHEDULE_COUNT.clone())) {
+        debug!(logger, "Failed to register ORCHESTRATOR_SCHEDULE_COUNT"; "error" => ?error);
+    }
+}
@@ -20,6 +20,9 @@ use crate::Result;
 /// Extract and persist action information.
 pub fn process(follower: &Follower, event: &ActionEvent, span: Option<&mut Span>) -> Result<()> {
     match event {
+        ActionEvent::Cancelled(action) => process_cancelled(follower, &action, span),
+        // The action record and history are updated when the agent reports the cancellation.
+        ActionEvent::CancelRequested(_) => Ok(()),
         ActionEvent::Changed(info) => persist_action(follower, &info.current, span),
         ActionEvent::Finished(action) => persist_action(follower, &action, span),
         ActionEvent::History(info) => process_history(follower, &info, span),
@@ -41,6 +44,32 @@ fn persist_action(follower: &Follower, action: &Action, span: Option<&mut Span>)
     Ok(())
 }
 
+/// Persist a cancelled action and record the transition in its history.
+fn process_cancelled(
+    follower: &Follower,
+    action: &Action,
+    mut span: Option<&mut Span>,
+) -> Result<()> {
+    persist_action(follower, action, span.as_deref_mut())?;
+    let span_context = span.map(|span| span.context().clone());
+    let history = ActionHistory {
+        cluster_id: action.cluster_id.clone(),
+        node_id: action.node_id.clone(),
+        action_id: action.action_id,
+        finished_ts: action.finished_ts,
+        origin: ActionHistoryOrigin::Core,
+        timestamp: action.finished_ts.unwrap_or_else(Utc::now),
+        state: ActionState::Cancelled,
+        state_payload: None,
+    };
+    follower
+        .store
+        .persist()
+        .action_history(vec![history], span_context)
+        .with_context(|_| ErrorKind::StoreWrite("action history transitions"))?;
+    Ok(())
+}
+
 /// Process an action history to synchronize the core and agent records.
 fn process_history(
     follower: &Follower,
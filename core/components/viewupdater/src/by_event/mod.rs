@@ -1,9 +1,11 @@
 use opentracingrust::Span;
 
+use replicante_models_core::events::shard::ShardEvent;
 use replicante_models_core::events::Event;
 use replicante_models_core::events::Payload;
 
 mod action;
+mod shard;
 
 use crate::follower::Follower;
 use crate::Result;
@@ -12,6 +14,18 @@ use crate::Result;
 pub fn process(follower: &Follower, event: &Event, span: Option<&mut Span>) -> Result<()> {
     match &event.payload {
         Payload::Action(event) => action::process(follower, event, span),
+        Payload::Shard(shard) => shard::process(follower, shard, event.timestamp, span),
         _ => Ok(()),
     }
 }
+
+/// Check if the event should be persisted to the events index.
+///
+/// Shard lag samples are stored in their own collection by `process` and are
+/// emitted frequently so they are not also indexed as events.
+pub fn indexed(event: &Event) -> bool {
+    match &event.payload {
+        Payload::Shard(ShardEvent::LagSample(_)) => false,
+        _ => true,
+    }
+}
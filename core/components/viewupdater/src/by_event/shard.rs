@@ -0,0 +1,45 @@
+use chrono::DateTime;
+use chrono::Utc;
+use failure::ResultExt;
+use opentracingrust::Span;
+
+use replicante_models_core::agent::Shard;
+use replicante_models_core::agent::ShardLagSample;
+use replicante_models_core::events::shard::ShardEvent;
+
+use crate::follower::Follower;
+use crate::ErrorKind;
+use crate::Result;
+
+/// Extract and persist shard commit offset and lag samples.
+pub fn process(
+    follower: &Follower,
+    event: &ShardEvent,
+    timestamp: DateTime<Utc>,
+    span: Option<&mut Span>,
+) -> Result<()> {
+    match event {
+        ShardEvent::AllocationChanged(change) => {
+            persist_sample(follower, &change.after, timestamp, span)
+        }
+        ShardEvent::AllocationNew(shard) => persist_sample(follower, shard, timestamp, span),
+        ShardEvent::AllocationRemoved(_) => Ok(()),
+        ShardEvent::LagSample(shard) => persist_sample(follower, shard, timestamp, span),
+    }
+}
+
+/// Helper function to persist a shard lag sample to the view store.
+fn persist_sample(
+    follower: &Follower,
+    shard: &Shard,
+    timestamp: DateTime<Utc>,
+    span: Option<&mut Span>,
+) -> Result<()> {
+    let sample = ShardLagSample::new(shard, timestamp);
+    follower
+        .store
+        .persist()
+        .shard_lag_sample(sample, span.as_ref().map(|span| span.context().clone()))
+        .with_context(|_| ErrorKind::StoreWrite("shard lag sample"))?;
+    Ok(())
+}
@@ -0,0 +1,149 @@
+use std::collections::HashSet;
+
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::ErrorKind;
+use crate::Result;
+
+/// Webhooks forwarding options.
+#[derive(Clone, Default, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
+pub struct Config {
+    /// HTTP endpoints to POST matching events to.
+    #[serde(default)]
+    pub endpoints: Vec<EndpointConfig>,
+
+    /// Delivery retry options shared by all endpoints.
+    #[serde(default)]
+    pub retry: RetryConfig,
+}
+
+impl Config {
+    /// Check the configuration for errors that serde can't detect.
+    ///
+    /// Endpoint names identify the group following the events stream for each endpoint
+    /// so they must be unique.
+    pub fn validate(&self) -> Result<()> {
+        let mut names = HashSet::new();
+        for endpoint in &self.endpoints {
+            if !names.insert(endpoint.name.as_str()) {
+                return Err(ErrorKind::EndpointDuplicate(endpoint.name.clone()).into());
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Configuration of an HTTP endpoint to forward events to.
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
+pub struct EndpointConfig {
+    /// Only forward events about these clusters (forward events for all clusters if empty).
+    ///
+    /// Events that are not about a cluster are not forwarded when this filter is set.
+    #[serde(default)]
+    pub clusters: Vec<String>,
+
+    /// Only forward events with these codes (forward all events if empty).
+    ///
+    /// Codes can be glob patterns (`*` matches any sequence, `?` any character).
+    #[serde(default)]
+    pub events: Vec<String>,
+
+    /// Unique name of the endpoint, used in logs, metrics and dead-letter records.
+    pub name: String,
+
+    /// Shared secret used to sign request bodies with HMAC-SHA256.
+    ///
+    /// When set, the signature is sent in the `X-Replicante-Signature` header
+    /// as `sha256=<hex encoded digest>`.
+    #[serde(default)]
+    pub secret: Option<String>,
+
+    /// Timeout (in seconds) of each delivery attempt.
+    #[serde(default = "EndpointConfig::default_timeout")]
+    pub timeout: u64,
+
+    /// URL to POST matching events to.
+    pub url: String,
+}
+
+impl EndpointConfig {
+    fn default_timeout() -> u64 {
+        10
+    }
+}
+
+/// Delivery retry options.
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
+pub struct RetryConfig {
+    /// Maximum number of delivery attempts before an event is dead-lettered.
+    #[serde(default = "RetryConfig::default_attempts")]
+    pub attempts: u32,
+
+    /// Delay (in milliseconds) before the first retry, doubled after each failed attempt.
+    #[serde(default = "RetryConfig::default_initial_delay")]
+    pub initial_delay: u64,
+
+    /// Maximum delay (in milliseconds) between attempts.
+    #[serde(default = "RetryConfig::default_max_delay")]
+    pub max_delay: u64,
+}
+
+impl Default for RetryConfig {
+    fn default() -> RetryConfig {
+        RetryConfig {
+            attempts: RetryConfig::default_attempts(),
+            initial_delay: RetryConfig::default_initial_delay(),
+            max_delay: RetryConfig::default_max_delay(),
+        }
+    }
+}
+
+impl RetryConfig {
+    fn default_attempts() -> u32 {
+        5
+    }
+
+    fn default_initial_delay() -> u64 {
+        500
+    }
+
+    fn default_max_delay() -> u64 {
+        30000
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Config;
+    use super::EndpointConfig;
+
+    fn endpoint(name: &str) -> EndpointConfig {
+        EndpointConfig {
+            clusters: Vec::new(),
+            events: Vec::new(),
+            name: name.into(),
+            secret: None,
+            timeout: 10,
+            url: "http://localhost/".into(),
+        }
+    }
+
+    #[test]
+    fn duplicate_endpoint_names_are_rejected() {
+        let config = Config {
+            endpoints: vec![endpoint("a"), endpoint("b"), endpoint("a")],
+            retry: Default::default(),
+        };
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn unique_endpoint_names_are_valid() {
+        let config = Config {
+            endpoints: vec![endpoint("a"), endpoint("b")],
+            retry: Default::default(),
+        };
+        assert!(config.validate().is_ok());
+    }
+}
@@ -0,0 +1,186 @@
+use std::time::Duration;
+
+use failure::ResultExt;
+use hmac::Hmac;
+use hmac::Mac;
+use regex::Regex;
+use reqwest::blocking::Client;
+use reqwest::header::CONTENT_TYPE;
+use sha2::Sha256;
+
+use replicante_models_core::events::Event;
+use replicante_models_core::events::EventCodeMatch;
+
+use crate::config::EndpointConfig;
+use crate::config::RetryConfig;
+use crate::metrics::WEBHOOKS_DELIVERY_DURATION;
+use crate::ErrorKind;
+use crate::Result;
+
+/// HTTP header carrying the HMAC-SHA256 signature of the request body.
+pub const SIGNATURE_HEADER: &str = "X-Replicante-Signature";
+
+/// HTTP endpoint events are delivered to.
+pub struct Endpoint {
+    client: Client,
+    pub config: EndpointConfig,
+    events: Vec<Regex>,
+}
+
+impl Endpoint {
+    pub fn new(config: EndpointConfig) -> Result<Endpoint> {
+        let client = Client::builder()
+            .timeout(Duration::from_secs(config.timeout))
+            .build()
+            .with_context(|_| ErrorKind::EndpointClient(config.name.clone()))?;
+        let mut events = Vec::new();
+        for code in &config.events {
+            let pattern = EventCodeMatch::Glob(code.clone())
+                .regex()
+                .expect("event code globs have a regex");
+            let regex = Regex::new(&pattern).with_context(|_| {
+                ErrorKind::EndpointEventPattern(config.name.clone(), code.clone())
+            })?;
+            events.push(regex);
+        }
+        Ok(Endpoint {
+            client,
+            config,
+            events,
+        })
+    }
+
+    /// Check if the event should be forwarded to this endpoint.
+    pub fn matches(&self, event: &Event) -> bool {
+        let code = event.code();
+        if !self.events.is_empty() && !self.events.iter().any(|regex| regex.is_match(code)) {
+            return false;
+        }
+        if self.config.clusters.is_empty() {
+            return true;
+        }
+        match event.cluster_id() {
+            None => false,
+            Some(cluster_id) => self.config.clusters.iter().any(|id| id == cluster_id),
+        }
+    }
+
+    /// Make a single attempt at POSTing an encoded event to the endpoint.
+    ///
+    /// Responses with a non-2xx status code are considered failed attempts.
+    pub fn send(&self, body: &[u8]) -> Result<()> {
+        let name = &self.config.name;
+        let mut request = self
+            .client
+            .post(&self.config.url)
+            .header(CONTENT_TYPE, "application/json")
+            .body(body.to_vec());
+        if let Some(secret) = &self.config.secret {
+            request = request.header(SIGNATURE_HEADER, sign(secret, body));
+        }
+        let _timer = WEBHOOKS_DELIVERY_DURATION
+            .with_label_values(&[name])
+            .start_timer();
+        let response = request
+            .send()
+            .with_context(|_| ErrorKind::EndpointRequest(name.clone()))?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(ErrorKind::EndpointStatus(name.clone(), status.as_u16()).into());
+        }
+        Ok(())
+    }
+}
+
+/// Delay to wait after the given failed attempt (starting from 1) before trying again.
+pub fn backoff(retry: &RetryConfig, attempt: u32) -> Duration {
+    let factor = 2u64.saturating_pow(attempt.saturating_sub(1));
+    let delay = retry
+        .initial_delay
+        .saturating_mul(factor)
+        .min(retry.max_delay);
+    Duration::from_millis(delay)
+}
+
+/// Sign a request body with HMAC-SHA256 and return the value of the signature header.
+pub fn sign(secret: &str, body: &[u8]) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_varkey(secret.as_bytes()).expect("HMAC to accept keys of any size");
+    mac.input(body);
+    format!("sha256={}", hex::encode(mac.result().code()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use replicante_models_core::cluster::discovery::ClusterDiscovery;
+    use replicante_models_core::events::Event;
+
+    use super::backoff;
+    use super::sign;
+    use super::Endpoint;
+    use crate::config::EndpointConfig;
+    use crate::config::RetryConfig;
+
+    fn endpoint(events: Vec<&str>) -> Endpoint {
+        let config = EndpointConfig {
+            clusters: Vec::new(),
+            events: events.into_iter().map(String::from).collect(),
+            name: "test".into(),
+            secret: None,
+            timeout: 10,
+            url: "http://localhost/".into(),
+        };
+        Endpoint::new(config).unwrap()
+    }
+
+    fn event() -> Event {
+        let discovery = ClusterDiscovery::new("test", vec![]);
+        Event::builder().cluster().new_cluster(discovery)
+    }
+
+    #[test]
+    fn backoff_doubles_until_max() {
+        let retry = RetryConfig {
+            attempts: 10,
+            initial_delay: 500,
+            max_delay: 3000,
+        };
+        assert_eq!(backoff(&retry, 1), Duration::from_millis(500));
+        assert_eq!(backoff(&retry, 2), Duration::from_millis(1000));
+        assert_eq!(backoff(&retry, 3), Duration::from_millis(2000));
+        assert_eq!(backoff(&retry, 4), Duration::from_millis(3000));
+        assert_eq!(backoff(&retry, 80), Duration::from_millis(3000));
+    }
+
+    #[test]
+    fn matches_all_events_without_filters() {
+        assert!(endpoint(vec![]).matches(&event()));
+    }
+
+    #[test]
+    fn matches_event_codes() {
+        assert!(endpoint(vec!["CLUSTER_NEW"]).matches(&event()));
+        assert!(!endpoint(vec!["CLUSTER_NE"]).matches(&event()));
+        assert!(!endpoint(vec!["CLUSTER_NEWS"]).matches(&event()));
+    }
+
+    #[test]
+    fn matches_event_code_globs() {
+        assert!(endpoint(vec!["CLUSTER_*"]).matches(&event()));
+        assert!(endpoint(vec!["CLUSTER_NE?"]).matches(&event()));
+        assert!(endpoint(vec!["ACTION_*", "CLUSTER_NEW"]).matches(&event()));
+        assert!(!endpoint(vec!["ACTION_*"]).matches(&event()));
+        assert!(!endpoint(vec!["CLUSTER_?"]).matches(&event()));
+    }
+
+    #[test]
+    fn sign_body() {
+        let signature = sign("key", b"The quick brown fox jumps over the lazy dog");
+        assert_eq!(
+            signature,
+            "sha256=f7bc83f430538424b13298e6aa6fb143ef4d59a14946175997479dbc2d1a3cd8",
+        );
+    }
+}
@@ -0,0 +1,109 @@
+use std::fmt;
+
+use failure::Backtrace;
+use failure::Context;
+use failure::Fail;
+
+/// Error information returned by this crate.
+#[derive(Debug)]
+pub struct Error(Context<ErrorKind>);
+
+impl Error {
+    pub fn kind(&self) -> &ErrorKind {
+        self.0.get_context()
+    }
+}
+
+impl Fail for Error {
+    fn backtrace(&self) -> Option<&Backtrace> {
+        self.0.backtrace()
+    }
+
+    fn cause(&self) -> Option<&dyn Fail> {
+        self.0.cause()
+    }
+
+    fn name(&self) -> Option<&str> {
+        self.kind().kind_name()
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl From<Context<ErrorKind>> for Error {
+    fn from(inner: Context<ErrorKind>) -> Error {
+        Error(inner)
+    }
+}
+
+impl From<ErrorKind> for Error {
+    fn from(kind: ErrorKind) -> Error {
+        Error(Context::new(kind))
+    }
+}
+
+/// Exhaustive list of possible errors emitted by this crate.
+#[derive(Debug, Fail)]
+pub enum ErrorKind {
+    #[fail(display = "could not build HTTP client for webhook endpoint '{}'", _0)]
+    EndpointClient(String),
+
+    #[fail(display = "webhook endpoint name '{}' is used more than once", _0)]
+    EndpointDuplicate(String),
+
+    #[fail(
+        display = "invalid event code pattern '{}' for webhook endpoint '{}'",
+        _1, _0
+    )]
+    EndpointEventPattern(String, String),
+
+    #[fail(display = "could not send event to webhook endpoint '{}'", _0)]
+    EndpointRequest(String),
+
+    #[fail(display = "webhook endpoint '{}' responded with status {}", _0, _1)]
+    EndpointStatus(String, u16),
+
+    #[fail(display = "could not encode event for message with ID '{}'", _0)]
+    EventEncode(String),
+
+    #[fail(display = "message with ID '{}' has not event code", _0)]
+    EventHasNoCode(String),
+
+    #[fail(display = "could not acknowledge message with ID '{}'", _0)]
+    EventsStreamAck(String),
+
+    #[fail(display = "could not follow the events stream to forward events to webhooks")]
+    EventsStreamFollow,
+
+    #[fail(display = "failed to persist {} to the primary store", _0)]
+    StoreWrite(&'static str),
+
+    #[fail(display = "failed to spawn webhooks thread")]
+    ThreadSpawn,
+}
+
+impl ErrorKind {
+    fn kind_name(&self) -> Option<&str> {
+        let name = match self {
+            ErrorKind::EndpointClient(_) => "EndpointClient",
+            ErrorKind::EndpointDuplicate(_) => "EndpointDuplicate",
+            ErrorKind::EndpointEventPattern(_, _) => "EndpointEventPattern",
+            ErrorKind::EndpointRequest(_) => "EndpointRequest",
+            ErrorKind::EndpointStatus(_, _) => "EndpointStatus",
+            ErrorKind::EventEncode(_) => "EventEncode",
+            ErrorKind::EventHasNoCode(_) => "EventHasNoCode",
+            ErrorKind::EventsStreamAck(_) => "EventsStreamAck",
+            ErrorKind::EventsStreamFollow => "EventsStreamFollow",
+            ErrorKind::StoreWrite(_) => "StoreWrite",
+            ErrorKind::ThreadSpawn => "ThreadSpawn",
+        };
+        Some(name)
+    }
+}
+
+/// Short form alias for functions returning `Error`s.
+pub type Result<T> = ::std::result::Result<T, Error>;
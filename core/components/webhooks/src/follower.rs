@@ -0,0 +1,245 @@
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+use std::time::Instant;
+
+use chrono::Utc;
+use failure::ResultExt;
+use humthreads::ThreadScope;
+use opentracingrust::AutoFinishingSpan;
+use opentracingrust::Span;
+use opentracingrust::Tracer;
+use slog::warn;
+use slog::Logger;
+
+use replicante_models_core::events::DeserializeResult;
+use replicante_models_core::events::Event;
+use replicante_models_core::webhooks::WebhookDeadLetter;
+use replicante_store_primary::store::Store;
+use replicante_stream_events::Message;
+use replicante_stream_events::Stream;
+use replicante_util_failure::capture_fail;
+use replicante_util_failure::failure_info;
+use replicante_util_failure::format_fail;
+use replicante_util_tracing::fail_span;
+
+use crate::config::RetryConfig;
+use crate::delivery::backoff;
+use crate::delivery::Endpoint;
+use crate::metrics::WEBHOOKS_DEAD_LETTERS;
+use crate::metrics::WEBHOOKS_DELIVERED;
+use crate::metrics::WEBHOOKS_DELIVERY_ERRORS;
+use crate::Error;
+use crate::ErrorKind;
+use crate::Result;
+
+const FOLLOW_GROUP: &str = "events:webhooks";
+
+/// Stream follower delivering events to a single endpoint.
+///
+/// Each endpoint follows the stream in its own group so a failing endpoint
+/// only delays delivery of its own events.
+pub struct Follower<'a> {
+    pub endpoint: Endpoint,
+    pub events: Stream,
+    pub logger: Logger,
+    pub retry: RetryConfig,
+    pub store: Store,
+    pub thread: &'a ThreadScope,
+    pub tracer: Arc<Tracer>,
+}
+
+impl<'a> Follower<'a> {
+    /// Follow the event stream and forward matching events to the endpoint.
+    pub fn forward_events(&self) -> Result<()> {
+        let group = format!("{}:{}", FOLLOW_GROUP, self.endpoint.config.name);
+        let iter = self
+            .events
+            .follow(group, self.thread)
+            .context(ErrorKind::EventsStreamFollow)?;
+        self.thread.activity("waiting for events");
+        for message in iter {
+            let message = message.context(ErrorKind::EventsStreamFollow)?;
+            let _activity = self
+                .thread
+                .scoped_activity(format!("processing message: {}", message.id()));
+            let span =
+                Stream::span_for_message(&message, &self.tracer, &self.logger, "events.webhooks");
+            let event = Stream::deserialize_event(&message);
+            match event {
+                DeserializeResult::Ok(event) => self.process(event, message, span)?,
+                DeserializeResult::Err(error) => {
+                    let message_id = message.id().to_string();
+                    Stream::report_event_error(
+                        &self.logger,
+                        "events.webhooks",
+                        error,
+                        None,
+                        &message_id,
+                        span,
+                    );
+                    return Err(ErrorKind::EventHasNoCode(message_id).into());
+                }
+                DeserializeResult::Unknown(code, error) => {
+                    let message_id = message.id().to_string();
+                    Stream::report_event_error(
+                        &self.logger,
+                        "events.webhooks",
+                        error,
+                        code,
+                        &message_id,
+                        span,
+                    );
+                    // Presume an upgrade is ongoing and retry the message.
+                    // We don't expect to succeed here but instead preserve other functions on the
+                    // node until the presumed upgrade process replaces us.
+                    message.retry();
+                }
+            };
+        }
+        Ok(())
+    }
+
+    /// Deliver an event to the endpoint, retrying with backoff and dead-lettering on failure.
+    ///
+    /// Returns `false` if delivery was interrupted by a shutdown request.
+    fn deliver(&self, event: &Event, body: &[u8], span: Option<&mut Span>) -> Result<bool> {
+        let endpoint = &self.endpoint;
+        let name = endpoint.config.name.as_str();
+        let mut attempt = 0;
+        loop {
+            attempt += 1;
+            let error = match endpoint.send(body) {
+                Ok(()) => {
+                    WEBHOOKS_DELIVERED.with_label_values(&[name]).inc();
+                    return Ok(true);
+                }
+                Err(error) => error,
+            };
+            WEBHOOKS_DELIVERY_ERRORS.with_label_values(&[name]).inc();
+            if attempt >= self.retry.attempts {
+                self.dead_letter(event, body, attempt, error, span)?;
+                return Ok(true);
+            }
+            warn!(
+                self.logger,
+                "Failed to deliver event to webhook endpoint, will retry";
+                "attempt" => attempt,
+                "endpoint" => name,
+                "event.code" => event.code(),
+                failure_info(&error),
+            );
+
+            let delay = backoff(&self.retry, attempt);
+            let _activity = self.thread.scoped_activity(format!(
+                "backing off delivery to {} for {} milliseconds",
+                name,
+                delay.as_millis(),
+            ));
+            let start = Instant::now();
+            while start.elapsed() < delay {
+                if self.thread.should_shutdown() {
+                    return Ok(false);
+                }
+                thread::sleep(Duration::from_millis(100));
+            }
+        }
+    }
+
+    /// Record an event that could not be delivered to the endpoint.
+    fn dead_letter(
+        &self,
+        event: &Event,
+        body: &[u8],
+        attempts: u32,
+        error: Error,
+        span: Option<&mut Span>,
+    ) -> Result<()> {
+        let endpoint = &self.endpoint;
+        let name = endpoint.config.name.as_str();
+        capture_fail!(
+            &error,
+            self.logger,
+            "Failed to deliver event to webhook endpoint, recording dead letter";
+            "attempts" => attempts,
+            "endpoint" => name,
+            "event.code" => event.code(),
+            failure_info(&error),
+        );
+        WEBHOOKS_DEAD_LETTERS.with_label_values(&[name]).inc();
+        let letter = WebhookDeadLetter {
+            attempts,
+            cluster_id: event.cluster_id().map(String::from),
+            endpoint: name.to_string(),
+            error: format_fail(&error),
+            event_code: event.code().to_string(),
+            failed_ts: Utc::now(),
+            payload: String::from_utf8_lossy(body).into_owned(),
+            url: endpoint.config.url.clone(),
+        };
+        self.store
+            .persist()
+            .webhook_dead_letter(letter, span.map(|span| span.context().clone()))
+            .with_context(|_| ErrorKind::StoreWrite("webhook dead letter"))?;
+        Ok(())
+    }
+
+    fn process(
+        &self,
+        event: Event,
+        message: Message,
+        mut span: Option<AutoFinishingSpan>,
+    ) -> Result<()> {
+        let message_id = message.id().to_string();
+        let name = &self.endpoint.config.name;
+        if self.endpoint.matches(&event) {
+            let body = serde_json::to_vec(&event)
+                .with_context(|_| ErrorKind::EventEncode(message_id.clone()))
+                .map_err(Error::from);
+            let body = match body {
+                Ok(body) => body,
+                Err(error) => {
+                    capture_fail!(
+                        &error,
+                        self.logger,
+                        "Failed to encode event for webhook endpoint";
+                        "endpoint" => name,
+                        "message_id" => &message_id,
+                        failure_info(&error),
+                    );
+                    fail_span(error, span.as_deref_mut());
+                    message.retry();
+                    return Ok(());
+                }
+            };
+
+            // Delivery is at least once: if the message is retried the endpoint
+            // may receive the event again.
+            match self.deliver(&event, &body, span.as_deref_mut()) {
+                Ok(true) => (),
+                Ok(false) => {
+                    message.retry();
+                    return Ok(());
+                }
+                Err(error) => {
+                    capture_fail!(
+                        &error,
+                        self.logger,
+                        "Failed to forward event to webhook endpoint";
+                        "endpoint" => name,
+                        "message_id" => &message_id,
+                        failure_info(&error),
+                    );
+                    fail_span(error, span.as_deref_mut());
+                    message.retry();
+                    return Ok(());
+                }
+            }
+        }
+        message
+            .async_ack()
+            .map_err(|error| fail_span(error, span.as_deref_mut()))
+            .with_context(|_| ErrorKind::EventsStreamAck(message_id))?;
+        Ok(())
+    }
+}
@@ -0,0 +1,116 @@
+use std::sync::Arc;
+
+use failure::ResultExt;
+use humthreads::Builder as ThreadBuilder;
+use opentracingrust::Tracer;
+use slog::debug;
+use slog::warn;
+use slog::Logger;
+
+use replicante_store_primary::store::Store;
+use replicante_stream_events::Stream;
+use replicante_util_failure::capture_fail;
+use replicante_util_failure::failure_info;
+use replicante_util_upkeep::Upkeep;
+
+mod config;
+mod delivery;
+mod error;
+mod follower;
+mod metrics;
+
+pub use self::config::Config;
+pub use self::config::EndpointConfig;
+pub use self::config::RetryConfig;
+pub use self::delivery::SIGNATURE_HEADER;
+pub use self::error::Error;
+pub use self::error::ErrorKind;
+pub use self::error::Result;
+pub use self::metrics::register_metrics;
+
+/// Forward events from the events stream to HTTP webhook endpoints.
+pub struct Webhooks {
+    config: Config,
+    events: Stream,
+    logger: Logger,
+    store: Store,
+    tracer: Arc<Tracer>,
+}
+
+impl Webhooks {
+    pub fn new(
+        config: Config,
+        events: Stream,
+        logger: Logger,
+        store: Store,
+        tracer: Arc<Tracer>,
+    ) -> Webhooks {
+        Webhooks {
+            config,
+            events,
+            logger,
+            store,
+            tracer,
+        }
+    }
+
+    /// Start a background thread for each endpoint and return.
+    pub fn run(&self, upkeep: &mut Upkeep) -> Result<()> {
+        if self.config.endpoints.is_empty() {
+            warn!(
+                self.logger,
+                "Webhooks component enabled without endpoints, events will not be forwarded"
+            );
+            return Ok(());
+        }
+        self.config.validate()?;
+        let endpoints = self
+            .config
+            .endpoints
+            .iter()
+            .cloned()
+            .map(self::delivery::Endpoint::new)
+            .collect::<Result<Vec<_>>>()?;
+        for endpoint in endpoints {
+            self.spawn(endpoint, upkeep)?;
+        }
+        Ok(())
+    }
+
+    /// Start a background thread delivering events to a single endpoint.
+    fn spawn(&self, endpoint: self::delivery::Endpoint, upkeep: &mut Upkeep) -> Result<()> {
+        let events = self.events.clone();
+        let logger = self.logger.clone();
+        let name = endpoint.config.name.clone();
+        let retry = self.config.retry.clone();
+        let store = self.store.clone();
+        let tracer = self.tracer.clone();
+        debug!(logger, "Starting webhooks thread"; "endpoint" => &name);
+        let thread = ThreadBuilder::new(format!("r:c:webhooks:{}", name))
+            .full_name(format!("replicore:component:webhooks:{}", name))
+            .spawn(move |scope| {
+                let thread = &scope;
+                let worker = self::follower::Follower {
+                    endpoint,
+                    events,
+                    logger: logger.clone(),
+                    retry,
+                    store,
+                    thread,
+                    tracer,
+                };
+                if let Err(error) = worker.forward_events() {
+                    capture_fail!(
+                        &error,
+                        logger,
+                        "Webhooks forwarding stopped";
+                        "endpoint" => &name,
+                        failure_info(&error),
+                    );
+                }
+            })
+            .with_context(|_| ErrorKind::ThreadSpawn)?;
+        upkeep.register_thread(thread);
+        Ok(())
+    }
+}
@@ -0,0 +1,60 @@
+use prometheus::CounterVec;
+use prometheus::HistogramOpts;
+use prometheus::HistogramVec;
+use prometheus::Opts;
+use prometheus::Registry;
+use slog::debug;
+use slog::Logger;
+
+lazy_static::lazy_static! {
+    pub static ref WEBHOOKS_DEAD_LETTERS: CounterVec = CounterVec::new(
+        Opts::new(
+            "replicore_webhooks_dead_letters",
+            "Number of events that could not be delivered after all attempts",
+        ),
+        &["endpoint"]
+    )
+    .expect("Failed to create WEBHOOKS_DEAD_LETTERS");
+    pub static ref WEBHOOKS_DELIVERED: CounterVec = CounterVec::new(
+        Opts::new(
+            "replicore_webhooks_delivered",
+            "Number of events successfully delivered to webhook endpoints",
+        ),
+        &["endpoint"]
+    )
+    .expect("Failed to create WEBHOOKS_DELIVERED");
+    pub static ref WEBHOOKS_DELIVERY_DURATION: HistogramVec = HistogramVec::new(
+        HistogramOpts::new(
+            "replicore_webhooks_delivery_duration",
+            "Duration (in seconds) of event delivery attempts to webhook endpoints",
+        ),
+        &["endpoint"]
+    )
+    .expect("Failed to create WEBHOOKS_DELIVERY_DURATION");
+    pub static ref WEBHOOKS_DELIVERY_ERRORS: CounterVec = CounterVec::new(
+        Opts::new(
+            "replicore_webhooks_delivery_errors",
+            "Number of failed event delivery attempts to webhook endpoints",
+        ),
+        &["endpoint"]
+    )
+    .expect("Failed to create WEBHOOKS_DELIVERY_ERRORS");
+}
+
+/// Attemps to register metrics with the Registry.
+///
+/// Metrics that fail to register are logged and ignored.
+pub fn register_metrics(logger: &Logger, registry: &Registry) {
+    if let Err(error) = registry.register(Box::new(WEBHOOKS_DEAD_LETTERS.clone())) {
+        debug!(logger, "Failed to register WEBHOOKS_DEAD_LETTERS"; "error" => ?error);
+    }
+    if let Err(error) = registry.register(Box::new(WEBHOOKS_DELIVERED.clone())) {
+        debug!(logger, "Failed to register WEBHOOKS_DELIVERED"; "error" => ?error);
+    }
+    if let Err(error) = registry.register(Box::new(WEBHOOKS_DELIVERY_DURATION.clone())) {
+        debug!(logger, "Failed to register WEBHOOKS_DELIVERY_DURATION"; "error" => ?error);
+    }
+    if let Err(error) = registry.register(Box::new(WEBHOOKS_DELIVERY_ERRORS.clone())) {
+        debug!(logger, "Failed to register WEBHOOKS_DELIVERY_ERRORS"; "error" => ?error);
+    }
+}
@@ -73,11 +73,23 @@ pub enum ErrorKind {
     )]
     FetchSettings(String, String),
 
+    #[fail(
+        display = "unable to fetch clusters missing from discovery {}.{}",
+        _0, _1
+    )]
+    FetchMissing(String, String),
+
+    #[fail(display = "unable to mark clusters seen by discovery {}.{}", _0, _1)]
+    MarkSeen(String, String),
+
     #[fail(display = "unable to persist {}.{} discovery record", _0, _1)]
     PersistRecord(String, String),
 
     #[fail(display = "unable to persist {}.{} cluster settings", _0, _1)]
     PersistSettings(String, String),
+
+    #[fail(display = "unable to retire {}.{} discovery record", _0, _1)]
+    RetireRecord(String, String),
 }
 
 impl ErrorKind {
@@ -97,6 +109,14 @@ impl ErrorKind {
         ErrorKind::FetchSettings(namespace.to_string(), cluster_id.to_string())
     }
 
+    pub fn fetch_missing(namespace: &str, name: &str) -> ErrorKind {
+        ErrorKind::FetchMissing(namespace.to_string(), name.to_string())
+    }
+
+    pub fn mark_seen(namespace: &str, name: &str) -> ErrorKind {
+        ErrorKind::MarkSeen(namespace.to_string(), name.to_string())
+    }
+
     pub fn persist_record(namespace: &str, cluster_id: &str) -> ErrorKind {
         ErrorKind::PersistRecord(namespace.to_string(), cluster_id.to_string())
     }
@@ -105,15 +125,22 @@ impl ErrorKind {
         ErrorKind::PersistSettings(namespace.to_string(), cluster_id.to_string())
     }
 
+    pub fn retire_record(namespace: &str, cluster_id: &str) -> ErrorKind {
+        ErrorKind::RetireRecord(namespace.to_string(), cluster_id.to_string())
+    }
+
     fn kind_name(&self) -> Option<&str> {
         let name = match self {
             ErrorKind::EmitEvent(_) => "EmitEvent",
             ErrorKind::DeserializePayload => "DeserializePayload",
             ErrorKind::FetchCluster(_, _) => "FetchCluster",
             ErrorKind::FetchDiscovery(_, _) => "FetchDiscovery",
+            ErrorKind::FetchMissing(_, _) => "FetchMissing",
             ErrorKind::FetchSettings(_, _) => "FetchSettings",
+            ErrorKind::MarkSeen(_, _) => "MarkSeen",
             ErrorKind::PersistRecord(_, _) => "PersistRecord",
             ErrorKind::PersistSettings(_, _) => "PersistSettings",
+            ErrorKind::RetireRecord(_, _) => "RetireRecord",
         };
         Some(name)
     }
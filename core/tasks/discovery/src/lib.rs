@@ -1,17 +1,25 @@
 //! Implmentation of the cluster records discovery process.
+use std::collections::HashSet;
 use std::sync::Arc;
 
+use chrono::Duration;
+use chrono::Utc;
 use failure::ResultExt;
 use opentracingrust::Span;
 use opentracingrust::Tracer;
 use slog::debug;
+use slog::info;
 use slog::warn;
 use slog::Logger;
 
+use replicante_cluster_discovery::Iter as DiscoveryIter;
 use replicante_models_core::cluster::discovery::ClusterDiscovery;
+use replicante_models_core::cluster::discovery::DiscoveryRetry;
+use replicante_models_core::cluster::discovery::DiscoverySettings;
 use replicante_models_core::cluster::ClusterSettings;
 use replicante_models_core::events::Event;
 use replicante_service_tasks::TaskHandler;
+use replicante_service_tasks::TaskRequest;
 use replicante_store_primary::store::Store;
 use replicante_stream_events::EmitMessage;
 use replicante_stream_events::Stream;
@@ -22,6 +30,7 @@ use replicante_util_tracing::fail_span;
 use replicore_models_tasks::payload::DiscoverClustersPayload;
 use replicore_models_tasks::ReplicanteQueues;
 use replicore_models_tasks::Task;
+use replicore_models_tasks::Tasks;
 
 mod error;
 mod metrics;
@@ -31,14 +40,18 @@ pub use self::error::ErrorKind;
 pub use self::error::Result;
 pub use self::metrics::register_metrics;
 
+use self::metrics::DISCOVER_CLUSTER_DISAPPEARED_COUNT;
 use self::metrics::DISCOVER_CLUSTER_SETTINGS_COUNT;
 use self::metrics::DISCOVER_DISABLED_COUNT;
+use self::metrics::DISCOVER_RESUMED_COUNT;
+use self::metrics::DISCOVER_RETRY_COUNT;
 
 /// Task handler for `ReplicanteQueues::DiscoverClusters` tasks.
 pub struct DiscoverClusters {
     events: Stream,
     logger: Logger,
     store: Store,
+    tasks: Tasks,
     tracer: Arc<Tracer>,
 }
 
@@ -47,12 +60,14 @@ impl DiscoverClusters {
         events: Stream,
         logger: Logger,
         store: Store,
+        tasks: Tasks,
         tracer: Arc<Tracer>,
     ) -> DiscoverClusters {
         DiscoverClusters {
             events,
             logger,
             store,
+            tasks,
             tracer,
         }
     }
@@ -78,16 +93,61 @@ impl DiscoverClusters {
 
         let name = payload.settings.name.clone();
         let namespace = payload.settings.namespace.clone();
-        let discoveries = replicante_cluster_discovery::discover(payload.settings);
+        let grace_period = payload.settings.removal_grace_period();
+        let settings = payload.settings.clone();
+        let mut seen = HashSet::new();
+        let mut discoveries = replicante_cluster_discovery::discover_from(
+            payload.settings,
+            payload.cursor.clone(),
+            payload.attempt,
+            self.logger.clone(),
+        );
+        let result = self.handle_records(&namespace, &name, &mut discoveries, &mut seen, span);
+
+        // Track clusters that were successfully processed, even if the discovery failed.
+        let cluster_ids: Vec<String> = seen.iter().cloned().collect();
+        self.store
+            .discovery_settings(namespace.clone())
+            .mark_clusters_seen(&name, &cluster_ids, span.context().clone())
+            .with_context(|_| ErrorKind::mark_seen(&namespace, &name))?;
+
+        // Retry failed discoveries later if possible, otherwise resume them from
+        // the last good page if progress was made.
+        if let Err(error) = result {
+            if let Some((retry, delay)) = discoveries.retry() {
+                return self.retry_later(settings, retry, delay, error, span);
+            }
+            return match discoveries.resume_cursor() {
+                Some(cursor) if Some(&cursor) != payload.cursor.as_ref() => {
+                    self.resume(settings, cursor, error, span)
+                }
+                _ => Err(error),
+            };
+        }
+
+        // Only retire clusters once the discovery has fully completed.
+        self.retire_missing(&namespace, &name, grace_period, &seen, span)
+    }
+
+    fn handle_records(
+        &self,
+        namespace: &str,
+        name: &str,
+        discoveries: &mut DiscoveryIter,
+        seen: &mut HashSet<String>,
+        span: &mut Span,
+    ) -> Result<()> {
         for record in discoveries {
-            let record = record.with_context(|_| ErrorKind::fetch_cluster(&namespace, &name))?;
+            let record = record.with_context(|_| ErrorKind::fetch_cluster(namespace, name))?;
             debug!(
                 self.logger,
                 "Processing discovery record";
-                "namespace" => &namespace,
+                "namespace" => namespace,
                 "name" => &record.cluster_id,
             );
-            self.handle_record(&namespace, record, span)?;
+            let cluster_id = record.cluster_id.clone();
+            self.handle_record(namespace, record, span)?;
+            seen.insert(cluster_id);
         }
         Ok(())
     }
@@ -159,6 +219,161 @@ impl DiscoverClusters {
         }
         Ok(())
     }
+
+    /// Request a new discovery task to continue a failed discovery from the given cursor.
+    ///
+    /// The current task is then considered complete, as the new task takes over.
+    /// If the new task can't be requested the original error is returned to retry this task.
+    fn resume(
+        &self,
+        settings: DiscoverySettings,
+        cursor: String,
+        error: Error,
+        span: &Span,
+    ) -> Result<()> {
+        let namespace = settings.namespace.clone();
+        let name = settings.name.clone();
+        capture_fail!(
+            &error,
+            self.logger,
+            "Clusters discovery failed, resuming from the last good page";
+            "namespace" => &namespace,
+            "name" => &name,
+            failure_info(&error),
+        );
+        let payload = DiscoverClustersPayload::resume(settings, cursor);
+        let mut task = TaskRequest::new(ReplicanteQueues::DiscoverClusters);
+        if let Err(trace_error) = task.trace(span.context(), &self.tracer) {
+            let trace_error = failure::SyncFailure::new(trace_error);
+            capture_fail!(
+                &trace_error,
+                self.logger,
+                "Unable to inject trace context in task request";
+                "namespace" => &namespace,
+                "name" => &name,
+                failure_info(&trace_error),
+            );
+        }
+        if let Err(request_error) = self.tasks.request(task, payload) {
+            capture_fail!(
+                &request_error,
+                self.logger,
+                "Failed to request resumed clusters discovery";
+                "namespace" => &namespace,
+                "name" => &name,
+                failure_info(&request_error),
+            );
+            return Err(error);
+        }
+        DISCOVER_RESUMED_COUNT.inc();
+        Ok(())
+    }
+
+    /// Schedule a failed discovery to be retried once the given delay has passed.
+    ///
+    /// The retry is picked up by the discovery scheduler so no worker waits for it.
+    /// If the retry can't be scheduled the original error is returned to retry this task.
+    fn retry_later(
+        &self,
+        settings: DiscoverySettings,
+        retry: DiscoveryRetry,
+        delay: std::time::Duration,
+        error: Error,
+        span: &Span,
+    ) -> Result<()> {
+        let namespace = settings.namespace.clone();
+        let name = settings.name.clone();
+        capture_fail!(
+            &error,
+            self.logger,
+            "Clusters discovery failed, scheduling a retry";
+            "namespace" => &namespace,
+            "name" => &name,
+            "attempt" => retry.attempt,
+            "delay_ms" => delay.as_millis() as u64,
+            failure_info(&error),
+        );
+        let delay = Duration::from_std(delay).unwrap_or_else(|_| Duration::max_value());
+        let retry_at = Utc::now() + delay;
+        let span_context = span.context().clone();
+        if let Err(persist_error) =
+            self.store
+                .persist()
+                .discovery_retry(settings, retry, retry_at, span_context)
+        {
+            capture_fail!(
+                &persist_error,
+                self.logger,
+                "Failed to schedule clusters discovery retry";
+                "namespace" => &namespace,
+                "name" => &name,
+                failure_info(&persist_error),
+            );
+            return Err(error);
+        }
+        DISCOVER_RETRY_COUNT.inc();
+        Ok(())
+    }
+
+    /// Retire clusters that have not been returned by the discovery for the grace period.
+    ///
+    /// Retired clusters have their discovery and settings records deleted so they are no
+    /// longer orchestrated, along with the agents, nodes, shards and metadata last
+    /// observed for them. If a retired cluster is discovered again it will be treated
+    /// as a new cluster.
+    fn retire_missing(
+        &self,
+        namespace: &str,
+        name: &str,
+        grace_period: i64,
+        seen: &HashSet<String>,
+        span: &mut Span,
+    ) -> Result<()> {
+        let span_context = span.context().clone();
+        let seen_before = Utc::now() - Duration::seconds(grace_period);
+        let missing = self
+            .store
+            .discovery_settings(namespace.to_string())
+            .iter_missing_clusters(name, seen_before, span_context.clone())
+            .with_context(|_| ErrorKind::fetch_missing(namespace, name))?;
+        for record in missing {
+            let record = record.with_context(|_| ErrorKind::fetch_missing(namespace, name))?;
+            let cluster_id = record.cluster_id.clone();
+            if seen.contains(&cluster_id) {
+                continue;
+            }
+            DISCOVER_CLUSTER_DISAPPEARED_COUNT.inc();
+            info!(
+                self.logger,
+                "Retiring cluster no longer returned by discovery";
+                "namespace" => namespace,
+                "name" => name,
+                "cluster_id" => &cluster_id,
+            );
+            let event = Event::builder().cluster().disappeared(record);
+            let code = event.code();
+            let stream_key = event.stream_key();
+            let event = EmitMessage::with(stream_key, event)
+                .with_context(|_| ErrorKind::emit_event(code))?
+                .trace(span_context.clone());
+            self.events
+                .emit(event)
+                .with_context(|_| ErrorKind::emit_event(code))?;
+            let cluster = self
+                .store
+                .cluster(namespace.to_string(), cluster_id.clone());
+            cluster
+                .delete_discovery(span_context.clone())
+                .with_context(|_| ErrorKind::retire_record(namespace, &cluster_id))?;
+            cluster
+                .delete_settings(span_context.clone())
+                .with_context(|_| ErrorKind::retire_record(namespace, &cluster_id))?;
+            cluster
+                .delete_state(span_context.clone())
+                .with_context(|_| ErrorKind::retire_record(namespace, &cluster_id))?;
+        }
+        Ok(())
+    }
 }
 
 impl TaskHandler<ReplicanteQueues> for DiscoverClusters {
@@ -211,3 +426,137 @@ impl TaskHandler<ReplicanteQueues> for DiscoverClusters {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use std::sync::Arc;
+
+    use chrono::Duration;
+    use chrono::Utc;
+    use opentracingrust::tracers::NoopTracer;
+    use slog::o;
+    use slog::Discard;
+    use slog::Logger;
+
+    use replicante_models_core::agent::Agent;
+    use replicante_models_core::agent::AgentStatus;
+    use replicante_models_core::agent::Node;
+    use replicante_models_core::cluster::discovery::ClusterDiscovery;
+    use replicante_models_core::cluster::ClusterMeta;
+    use replicante_models_core::cluster::ClusterSettings;
+    use replicante_store_primary::mock::DiscoverySeen;
+    use replicante_store_primary::mock::Mock as MockStore;
+    use replicante_stream_events::Stream;
+
+    use replicore_models_tasks::MockTasks;
+
+    use super::DiscoverClusters;
+
+    fn mock_cluster(store: &MockStore, cluster_id: &str, last_seen_secs: i64) {
+        let mut state = store.state.lock().unwrap();
+        let discovery = ClusterDiscovery::new(cluster_id, vec![]);
+        state.discoveries.insert(cluster_id.into(), discovery);
+        let seen = DiscoverySeen {
+            discovery_namespace: "default".into(),
+            discovery_name: "test".into(),
+            last_seen: Utc::now() - Duration::seconds(last_seen_secs),
+        };
+        state.discoveries_seen.insert(cluster_id.into(), seen);
+        let settings = ClusterSettings::new("default", cluster_id, true);
+        state
+            .cluster_settings
+            .insert(("default".into(), cluster_id.into()), settings);
+    }
+
+    fn retire_missing(store: &MockStore, grace_period: i64, seen: &[&str]) {
+        let (tracer, _) = NoopTracer::new();
+        let tracer = Arc::new(tracer);
+        let tasks = MockTasks::new();
+        let discovery = DiscoverClusters::new(
+            Stream::mock(),
+            Logger::root(Discard, o!()),
+            store.store(),
+            tasks.mock(),
+            Arc::clone(&tracer),
+        );
+        let seen: HashSet<String> = seen.iter().map(|id| id.to_string()).collect();
+        let mut span = tracer.span("test");
+        discovery
+            .retire_missing("default", "test", grace_period, &seen, &mut span)
+            .expect("retire missing clusters failed");
+    }
+
+    #[test]
+    fn retire_after_grace_period() {
+        let store = MockStore::default();
+        mock_cluster(&store, "old", 120);
+        mock_cluster(&store, "recent", 30);
+        retire_missing(&store, 60, &[]);
+        let state = store.state.lock().unwrap();
+        assert!(!state.discoveries.contains_key("old"));
+        assert!(state.discoveries.contains_key("recent"));
+    }
+
+    #[test]
+    fn retire_skips_clusters_seen_by_current_run() {
+        let store = MockStore::default();
+        mock_cluster(&store, "old", 120);
+        retire_missing(&store, 60, &["old"]);
+        let state = store.state.lock().unwrap();
+        assert!(state.discoveries.contains_key("old"));
+    }
+
+    #[test]
+    fn retired_cluster_settings_are_removed() {
+        let store = MockStore::default();
+        mock_cluster(&store, "old", 120);
+        mock_cluster(&store, "recent", 30);
+        retire_missing(&store, 60, &[]);
+        let state = store.state.lock().unwrap();
+        let old = ("default".to_string(), "old".to_string());
+        let recent = ("default".to_string(), "recent".to_string());
+        assert!(!state.cluster_settings.contains_key(&old));
+        assert!(state.cluster_settings.contains_key(&recent));
+    }
+
+    #[test]
+    fn retired_cluster_state_is_removed() {
+        let store = MockStore::default();
+        mock_cluster(&store, "old", 120);
+        mock_cluster(&store, "recent", 30);
+        {
+            let mut state = store.state.lock().unwrap();
+            for cluster_id in &["old", "recent"] {
+                let agent = Agent::new(*cluster_id, "host", AgentStatus::Up);
+                state
+                    .agents
+                    .insert((cluster_id.to_string(), "host".into()), agent);
+                let meta = ClusterMeta::new(*cluster_id, *cluster_id);
+                state.clusters_meta.insert(cluster_id.to_string(), meta);
+                let node = Node {
+                    cluster_display_name: None,
+                    cluster_id: cluster_id.to_string(),
+                    kind: "test".into(),
+                    node_id: "node".into(),
+                    version: "1.0.0".into(),
+                };
+                state
+                    .nodes
+                    .insert((cluster_id.to_string(), "node".into()), node);
+            }
+        }
+        retire_missing(&store, 60, &[]);
+        let state = store.state.lock().unwrap();
+        let old = ("old".to_string(), "host".to_string());
+        let recent = ("recent".to_string(), "host".to_string());
+        assert!(!state.agents.contains_key(&old));
+        assert!(state.agents.contains_key(&recent));
+        assert!(!state.clusters_meta.contains_key("old"));
+        assert!(state.clusters_meta.contains_key("recent"));
+        let old = ("old".to_string(), "node".to_string());
+        let recent = ("recent".to_string(), "node".to_string());
+        assert!(!state.nodes.contains_key(&old));
+        assert!(state.nodes.contains_key(&recent));
+    }
+}
@@ -5,6 +5,11 @@ use slog::debug;
 use slog::Logger;
 
 lazy_static::lazy_static! {
+    pub static ref DISCOVER_CLUSTER_DISAPPEARED_COUNT: Counter = Counter::with_opts(Opts::new(
+        "replicore_discover_cluster_disappeared",
+        "Number of clusters retired because they were no longer returned by discovery",
+    ))
+    .expect("Failed to create DISCOVER_CLUSTER_DISAPPEARED_COUNT");
     pub static ref DISCOVER_CLUSTER_SETTINGS_COUNT: Counter = Counter::with_opts(Opts::new(
         "replicore_discover_cluster_settings",
         "Number of ClusterSettings record created while discovering clusters",
@@ -15,16 +20,35 @@ lazy_static::lazy_static! {
         "Number of discovery tasks skipped because the discovery was disabled",
     ))
     .expect("Failed to create DISCOVER_DISABLED_COUNT");
+    pub static ref DISCOVER_RESUMED_COUNT: Counter = Counter::with_opts(Opts::new(
+        "replicore_discover_resumed",
+        "Number of failed discovery tasks resumed from the last good page",
+    ))
+    .expect("Failed to create DISCOVER_RESUMED_COUNT");
+    pub static ref DISCOVER_RETRY_COUNT: Counter = Counter::with_opts(Opts::new(
+        "replicore_discover_retry",
+        "Number of failed discovery tasks scheduled to be retried later",
+    ))
+    .expect("Failed to create DISCOVER_RETRY_COUNT");
 }
 
 /// Attemps to register metrics with the Registry.
 ///
 /// Metrics that fail to register are logged and ignored.
 pub fn register_metrics(logger: &Logger, registry: &Registry) {
+    if let Err(error) = registry.register(Box::new(DISCOVER_CLUSTER_DISAPPEARED_COUNT.clone())) {
+        debug!(logger, "Failed to register DISCOVER_CLUSTER_DISAPPEARED_COUNT"; "error" => ?error);
+    }
     if let Err(error) = registry.register(Box::new(DISCOVER_CLUSTER_SETTINGS_COUNT.clone())) {
         debug!(logger, "Failed to register DISCOVER_CLUSTER_SETTINGS_COUNT"; "error" => ?error);
     }
     if let Err(error) = registry.register(Box::new(DISCOVER_DISABLED_COUNT.clone())) {
         debug!(logger, "Failed to register DISCOVER_DISABLED_COUNT"; "error" => ?error);
     }
+    if let Err(error) = registry.register(Box::new(DISCOVER_RESUMED_COUNT.clone())) {
+        debug!(logger, "Failed to register DISCOVER_RESUMED_COUNT"; "error" => ?error);
+    }
+    if let Err(error) = registry.register(Box::new(DISCOVER_RETRY_COUNT.clone())) {
+        debug!(logger, "Failed to register DISCOVER_RETRY_COUNT"; "error" => ?error);
+    }
 }
@@ -55,6 +55,9 @@ pub enum ErrorKind {
     #[fail(display = "MongoDB aggregate failed")]
     AggregateOp,
 
+    #[fail(display = "MongoDB deleteMany failed")]
+    DeleteMany,
+
     #[fail(display = "MongoDB deleteOne failed")]
     DeleteOne,
 
@@ -106,6 +109,7 @@ impl ErrorKind {
         let name = match self {
             ErrorKind::AggregateCursor => "AggregateCursor",
             ErrorKind::AggregateOp => "AggregateOp",
+            ErrorKind::DeleteMany => "DeleteMany",
             ErrorKind::DeleteOne => "DeleteOne",
             ErrorKind::FindCursor => "FindCursor",
             ErrorKind::FindOne => "FindOne",
@@ -26,6 +26,11 @@ pub struct Action {
     pub args: Json,
     /// Timestamp of action creation.
     pub created_ts: DateTime<Utc>,
+    /// Timestamp after which an unfinished action is failed, if the action has a deadline.
+    ///
+    /// Actions without an explicit deadline use the namespace's default timeout, if any.
+    #[serde(default)]
+    pub deadline_ts: Option<DateTime<Utc>>,
     /// Timestamp action entered a final state (success or failure).
     pub finished_ts: Option<DateTime<Utc>>,
     /// Headers attached to the action.
@@ -59,6 +64,7 @@ impl Action {
             args: action.args,
             cluster_id: cluster_id.into(),
             created_ts: action.created_ts,
+            deadline_ts: None,
             finished_ts: action.finished_ts,
             headers: action.headers,
             kind: action.kind,
@@ -1,3 +1,5 @@
+use chrono::DateTime;
+use chrono::Utc;
 use serde_derive::Deserialize;
 use serde_derive::Serialize;
 
@@ -60,6 +62,30 @@ impl Shard {
     }
 }
 
+/// Commit offset and replication lag of a shard on a node at a point in time.
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
+pub struct ShardLagSample {
+    pub cluster_id: String,
+    pub commit_offset: Option<CommitOffset>,
+    pub lag: Option<CommitOffset>,
+    pub node_id: String,
+    pub shard_id: String,
+    pub timestamp: DateTime<Utc>,
+}
+
+impl ShardLagSample {
+    pub fn new(shard: &Shard, timestamp: DateTime<Utc>) -> ShardLagSample {
+        ShardLagSample {
+            cluster_id: shard.cluster_id.clone(),
+            commit_offset: shard.commit_offset.clone(),
+            lag: shard.lag.clone(),
+            node_id: shard.node_id.clone(),
+            shard_id: shard.shard_id.clone(),
+            timestamp,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     mod node {
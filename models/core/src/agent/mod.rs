@@ -1,3 +1,5 @@
+use chrono::DateTime;
+use chrono::Utc;
 use serde_derive::Deserialize;
 use serde_derive::Serialize;
 
@@ -9,11 +11,15 @@ pub use self::datastore::CommitOffset;
 pub use self::datastore::CommitUnit;
 pub use self::datastore::Node;
 pub use self::datastore::Shard;
+pub use self::datastore::ShardLagSample;
 pub use self::datastore::ShardRole;
 
 /// Status of an agent.
 #[derive(Clone, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
 pub struct Agent {
+    /// Circuit breaker state, set while the agent is failing to respond.
+    #[serde(default)]
+    pub circuit: Option<AgentCircuit>,
     pub cluster_id: String,
     pub host: String,
     pub status: AgentStatus,
@@ -26,6 +32,7 @@ impl Agent {
         S2: Into<String>,
     {
         Agent {
+            circuit: None,
             cluster_id: cluster_id.into(),
             host: host.into(),
             status,
@@ -33,6 +40,25 @@ impl Agent {
     }
 }
 
+/// Circuit breaker state for an agent that consecutively failed to respond.
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
+pub struct AgentCircuit {
+    /// Number of consecutive attempts to reach the agent that failed.
+    pub failures: i32,
+
+    /// While set, the agent is not contacted until this time (the circuit is open).
+    pub retry_at: Option<DateTime<Utc>>,
+}
+
+impl AgentCircuit {
+    /// Check if the circuit is open at the given time.
+    pub fn is_open(&self, now: DateTime<Utc>) -> bool {
+        self.retry_at
+            .map(|retry_at| now < retry_at)
+            .unwrap_or(false)
+    }
+}
+
 /// Information about an Agent
 #[derive(Clone, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
 pub struct AgentInfo {
@@ -104,13 +130,49 @@ mod tests {
             let agent = Agent::new("cluster", "http://node/", status);
             let payload = serde_json::to_string(&agent).unwrap();
             let expected = concat!(
-                r#"{"cluster_id":"cluster","host":"http://node/","#,
+                r#"{"circuit":null,"cluster_id":"cluster","host":"http://node/","#,
                 r#""status":{"code":"AGENT_DOWN","data":"TEST"}}"#
             );
             assert_eq!(payload, expected);
         }
     }
 
+    mod agent_circuit {
+        use chrono::Duration;
+        use chrono::Utc;
+
+        use super::super::AgentCircuit;
+
+        #[test]
+        fn closed() {
+            let circuit = AgentCircuit {
+                failures: 1,
+                retry_at: None,
+            };
+            assert!(!circuit.is_open(Utc::now()));
+        }
+
+        #[test]
+        fn open() {
+            let now = Utc::now();
+            let circuit = AgentCircuit {
+                failures: 3,
+                retry_at: Some(now + Duration::seconds(30)),
+            };
+            assert!(circuit.is_open(now));
+        }
+
+        #[test]
+        fn open_expired() {
+            let now = Utc::now();
+            let circuit = AgentCircuit {
+                failures: 3,
+                retry_at: Some(now - Duration::seconds(30)),
+            };
+            assert!(!circuit.is_open(now));
+        }
+    }
+
     mod agent_info {
         use serde_json;
 
@@ -0,0 +1,215 @@
+use chrono::DateTime;
+use chrono::Utc;
+use serde_derive::Deserialize;
+use serde_derive::Serialize;
+
+/// Conditions that cause an `AlertRule` to fire.
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum AlertCondition {
+    /// Fire when an action of the given kind fails.
+    ///
+    /// The alert resolves once an action of the same kind completes successfully.
+    #[serde(rename = "action_failed")]
+    ActionFailed { kind: String },
+
+    /// Fire when more then `threshold` events with the given code are seen within `window`.
+    ///
+    /// The alert resolves once no more then `threshold` events are in the window.
+    #[serde(rename = "event_count")]
+    EventCount {
+        /// Code of the events to count.
+        event: String,
+
+        /// Number of events in the window above which the alert fires.
+        threshold: u32,
+
+        /// Size of the window, in seconds, to count events in.
+        window: i64,
+    },
+}
+
+/// Declarative rule that raises alerts based on events emitted by the system.
+///
+/// Alerts are tracked independently for each cluster the rule matches events for.
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
+pub struct AlertRule {
+    /// Only evaluate the rule for events about this cluster, if set.
+    #[serde(default)]
+    pub cluster_id: Option<String>,
+
+    /// Condition that causes the rule to fire.
+    pub condition: AlertCondition,
+
+    /// Optional human readable description of the alert.
+    #[serde(default)]
+    pub description: Option<String>,
+
+    /// Namespace unique name for this rule.
+    pub name: String,
+
+    /// Namespace the rule belongs to.
+    pub namespace: String,
+}
+
+impl AlertRule {
+    /// Create an `AlertRule` from an apply API object.
+    pub fn from_object(
+        namespace: String,
+        name: String,
+        rule: crate::api::objects::AlertRule,
+    ) -> AlertRule {
+        AlertRule {
+            cluster_id: rule.cluster_id,
+            condition: rule.condition,
+            description: rule.description,
+            name,
+            namespace,
+        }
+    }
+
+    /// Check if the rule applies to events about the given cluster.
+    pub fn matches_cluster(&self, cluster_id: &str) -> bool {
+        match &self.cluster_id {
+            None => true,
+            Some(rule_cluster) => rule_cluster == cluster_id,
+        }
+    }
+}
+
+/// State of an `AlertRule` for a specific cluster.
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
+pub struct Alert {
+    /// ID of the cluster the alert is about.
+    pub cluster_id: String,
+
+    /// The event for the last state change was not emitted yet.
+    ///
+    /// Alerts are persisted before their events are emitted so that
+    /// events that fail to emit can be emitted again later.
+    #[serde(default)]
+    pub event_pending: bool,
+
+    /// Time the alert last started firing, if it ever fired.
+    pub fired_ts: Option<DateTime<Utc>>,
+
+    /// Timestamps of recent events matching the rule (only for `event_count` rules).
+    ///
+    /// Only timestamps needed to evaluate the rule are retained.
+    #[serde(default)]
+    pub matches: Vec<DateTime<Utc>>,
+
+    /// Namespace the alert rule belongs to.
+    pub namespace: String,
+
+    /// Time the alert last resolved, if it resolved since it last fired.
+    pub resolved_ts: Option<DateTime<Utc>>,
+
+    /// Name of the alert rule.
+    pub rule: String,
+
+    /// Current state of the alert.
+    pub state: AlertState,
+
+    /// Time the alert record was last updated.
+    pub updated_ts: DateTime<Utc>,
+}
+
+impl Alert {
+    /// Create a new, inactive, alert record for the rule and cluster.
+    pub fn new(rule: &AlertRule, cluster_id: String, now: DateTime<Utc>) -> Alert {
+        Alert {
+            cluster_id,
+            event_pending: false,
+            fired_ts: None,
+            matches: Vec::new(),
+            namespace: rule.namespace.clone(),
+            resolved_ts: None,
+            rule: rule.name.clone(),
+            state: AlertState::Inactive,
+            updated_ts: now,
+        }
+    }
+
+    /// Transition the alert to the firing state.
+    pub fn fire(&mut self, now: DateTime<Utc>) {
+        self.event_pending = true;
+        self.fired_ts = Some(now);
+        self.resolved_ts = None;
+        self.state = AlertState::Firing;
+        self.updated_ts = now;
+    }
+
+    /// Transition the alert to the resolved state.
+    pub fn resolve(&mut self, now: DateTime<Utc>) {
+        self.event_pending = true;
+        self.resolved_ts = Some(now);
+        self.state = AlertState::Resolved;
+        self.updated_ts = now;
+    }
+}
+
+/// Possible states of an alert.
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
+pub enum AlertState {
+    /// The rule condition is met.
+    #[serde(rename = "FIRING")]
+    Firing,
+
+    /// Events matching the rule were observed but the alert never fired.
+    #[serde(rename = "INACTIVE")]
+    Inactive,
+
+    /// The rule condition was met but no longer is.
+    #[serde(rename = "RESOLVED")]
+    Resolved,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AlertCondition;
+    use super::AlertRule;
+
+    #[test]
+    fn decode_event_count_rule() {
+        let rule: AlertRule = serde_json::from_str(
+            r#"{
+                "cluster_id": "cluster-x",
+                "condition": {
+                    "type": "event_count",
+                    "event": "NODE_DOWN",
+                    "threshold": 2,
+                    "window": 600
+                },
+                "name": "nodes-down",
+                "namespace": "default"
+            }"#,
+        )
+        .unwrap();
+        let expected = AlertCondition::EventCount {
+            event: "NODE_DOWN".into(),
+            threshold: 2,
+            window: 600,
+        };
+        assert_eq!(rule.condition, expected);
+        assert!(rule.matches_cluster("cluster-x"));
+        assert!(!rule.matches_cluster("cluster-y"));
+    }
+
+    #[test]
+    fn decode_action_failed_rule() {
+        let rule: AlertRule = serde_json::from_str(
+            r#"{
+                "condition": {"type": "action_failed", "kind": "replicante.io/test.fail"},
+                "name": "test-fails",
+                "namespace": "default"
+            }"#,
+        )
+        .unwrap();
+        let expected = AlertCondition::ActionFailed {
+            kind: "replicante.io/test.fail".into(),
+        };
+        assert_eq!(rule.condition, expected);
+        assert!(rule.matches_cluster("cluster-x"));
+    }
+}
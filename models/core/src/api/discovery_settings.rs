@@ -1,6 +1,14 @@
 use serde::Deserialize;
 use serde::Serialize;
 
+use crate::cluster::discovery::DiscoverySettings;
+
+/// Revisions of a DiscoverySettings object, newest first.
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
+pub struct DiscoverySettingsHistoryResponse {
+    pub revisions: Vec<DiscoverySettings>,
+}
+
 /// Description of a validation error.
 #[derive(Clone, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
 pub struct DiscoverySettingsListResponse {
@@ -3,6 +3,7 @@ use serde_derive::Serialize;
 
 pub mod apply;
 pub mod discovery_settings;
+pub mod namespace;
 pub mod objects;
 pub mod validate;
 
@@ -0,0 +1,8 @@
+use serde::Deserialize;
+use serde::Serialize;
+
+/// IDs of all the namespaces, sorted.
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
+pub struct NamespaceListResponse {
+    pub names: Vec<String>,
+}
@@ -1,7 +1,44 @@
 use serde_derive::Deserialize;
 use serde_derive::Serialize;
 
+use crate::alerts::AlertCondition;
 use crate::cluster::discovery::DiscoveryBackend;
+use crate::scope::NsActions;
+use crate::scope::NsHealth;
+use crate::scope::NsHttpsTransport;
+
+/// Alert rule raising alerts based on events emitted by the system.
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
+pub struct AlertRule {
+    /// Only evaluate the rule for events about this cluster, if set.
+    #[serde(default)]
+    pub cluster_id: Option<String>,
+
+    /// Condition that causes the rule to fire.
+    pub condition: AlertCondition,
+
+    /// Optional human readable description of the alert.
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+/// Cluster orchestration settings.
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
+pub struct ClusterSettings {
+    /// Enable or disable orchestration of the cluster.
+    #[serde(default = "ClusterSettings::default_enabled")]
+    pub enabled: bool,
+
+    /// Interval, in seconds, between orchestration runs.
+    #[serde(default)]
+    pub interval: Option<i64>,
+}
+
+impl ClusterSettings {
+    fn default_enabled() -> bool {
+        true
+    }
+}
 
 /// Cluster discovery settings for a single discovery backend.
 #[derive(Clone, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
@@ -14,6 +51,12 @@ pub struct DiscoverySettings {
     #[serde(default = "DiscoverySettings::default_enabled")]
     pub enabled: bool,
 
+    /// Seconds a cluster can be missing from discovery results before it is retired.
+    ///
+    /// Defaults to three discovery intervals.
+    #[serde(default)]
+    pub grace_period: Option<i64>,
+
     /// Interval, in seconds, between discovery runs.
     pub interval: i64,
 }
@@ -23,3 +66,19 @@ impl DiscoverySettings {
         true
     }
 }
+
+/// Namespace settings.
+#[derive(Clone, Default, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
+pub struct Namespace {
+    /// Actions settings for clusters in the namespace.
+    #[serde(default)]
+    pub actions: NsActions,
+
+    /// Cluster health classification settings.
+    #[serde(default)]
+    pub health: NsHealth,
+
+    /// HTTPS Agent Transport settings.
+    #[serde(default)]
+    pub https_transport: NsHttpsTransport,
+}
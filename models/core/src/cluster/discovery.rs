@@ -44,6 +44,14 @@ impl ClusterDiscovery {
 #[derive(Clone, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
 #[serde(tag = "backend")]
 pub enum DiscoveryBackend {
+    /// DNS records discovery.
+    #[serde(rename = "dns")]
+    Dns(DnsDiscovery),
+
+    /// Local files discovery.
+    #[serde(rename = "file")]
+    File(FileDiscovery),
+
     /// HTTP Endpoint discovery.
     #[serde(rename = "http")]
     Http(HttpDiscovery),
@@ -60,6 +68,12 @@ pub struct DiscoverySettings {
     #[serde(default = "DiscoverySettings::default_enabled")]
     pub enabled: bool,
 
+    /// Seconds a cluster can be missing from discovery results before it is retired.
+    ///
+    /// Defaults to three discovery intervals.
+    #[serde(default)]
+    pub grace_period: Option<i64>,
+
     /// Interval, in seconds, between discovery runs.
     pub interval: i64,
 
@@ -68,6 +82,10 @@ pub struct DiscoverySettings {
 
     /// Namespace the discovery settings belongs to.
     pub namespace: String,
+
+    /// Revision of the settings, incremented every time their content changes.
+    #[serde(default)]
+    pub revision: u64,
 }
 
 impl DiscoverySettings {
@@ -75,6 +93,11 @@ impl DiscoverySettings {
         true
     }
 
+    /// Seconds a cluster can be missing from discovery results before it is retired.
+    pub fn removal_grace_period(&self) -> i64 {
+        self.grace_period.unwrap_or(self.interval * 3)
+    }
+
     /// Create a `DiscoverySettings` from an apply API object.
     pub fn from_object(
         namespace: String,
@@ -84,16 +107,136 @@ impl DiscoverySettings {
         DiscoverySettings {
             backend: settings.backend,
             enabled: settings.enabled,
+            grace_period: settings.grace_period,
             interval: settings.interval,
             name,
             namespace,
+            revision: 0,
         }
     }
+
+    /// Set the revision of these settings based on the currently stored ones, if any.
+    ///
+    /// The revision is incremented only if the content of the settings changed.
+    pub fn revise(mut self, current: Option<&DiscoverySettings>) -> DiscoverySettings {
+        self.revision = match current {
+            None => 1,
+            Some(current) => {
+                self.revision = current.revision;
+                if &self == current {
+                    current.revision
+                } else {
+                    current.revision + 1
+                }
+            }
+        };
+        self
+    }
+}
+
+/// Retry state of a failed discovery run, scheduled for later.
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
+pub struct DiscoveryRetry {
+    /// Number of consecutive failed attempts to fetch the same page.
+    pub attempt: u32,
+
+    /// Backend cursor to resume the discovery from, if supported by the backend.
+    #[serde(default)]
+    pub cursor: Option<String>,
+}
+
+/// Discovery that is due to run, with the retry state of a previously failed run, if any.
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
+pub struct DiscoveryRun {
+    pub retry: Option<DiscoveryRetry>,
+    pub settings: DiscoverySettings,
+}
+
+/// DNS cluster discovery configurations.
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
+pub struct DnsDiscovery {
+    /// Optional list of `IP:PORT` name servers to query instead of the system configuration.
+    #[serde(default)]
+    pub nameservers: Vec<String>,
+
+    /// Port agents listen on, required when looking up `A` records.
+    #[serde(default)]
+    pub port: Option<u16>,
+
+    /// Type of DNS records to look up.
+    #[serde(default)]
+    pub record_type: DnsRecordType,
+
+    /// Clusters to discover, one DNS record name for each.
+    pub records: Vec<DnsClusterRecord>,
+
+    /// URL scheme used to build agent addresses.
+    #[serde(default = "DnsDiscovery::default_scheme")]
+    pub scheme: String,
+
+    /// DNS queries timeout (in milliseconds).
+    #[serde(default = "DnsDiscovery::default_timeout")]
+    pub timeout: u64,
+}
+
+impl DnsDiscovery {
+    fn default_scheme() -> String {
+        "http".into()
+    }
+
+    fn default_timeout() -> u64 {
+        3_000
+    }
+}
+
+/// DNS record to look up to discover the nodes of a cluster.
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
+pub struct DnsClusterRecord {
+    /// ID of the cluster discovered by the record.
+    pub cluster_id: String,
+
+    /// Optional display name for the cluster.
+    #[serde(default)]
+    pub display_name: Option<String>,
+
+    /// DNS name to look up.
+    pub name: String,
+}
+
+/// Type of DNS records to look up.
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
+pub enum DnsRecordType {
+    /// IPv4 address records, the agents port is taken from the discovery configuration.
+    #[serde(rename = "A")]
+    A,
+
+    /// Service records, providing both target host and port for agents.
+    #[serde(rename = "SRV")]
+    Srv,
+}
+
+impl Default for DnsRecordType {
+    fn default() -> DnsRecordType {
+        DnsRecordType::Srv
+    }
+}
+
+/// Local files cluster discovery configurations.
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
+pub struct FileDiscovery {
+    /// Path to a YAML/JSON file or to a directory of such files.
+    ///
+    /// When a directory is given, all `.json`, `.yaml` and `.yml` files in it are loaded.
+    pub path: String,
 }
 
 /// HTTP cluster discovery configurations
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Eq, PartialEq, Debug, Serialize, Deserialize)]
 pub struct HttpDiscovery {
+    /// Optional authentication to attach to HTTP requests.
+    #[serde(default)]
+    pub auth: Option<HttpAuth>,
+
     /// Optional JSON object to used as the body in HTTP requests.
     #[serde(default)]
     pub body: Option<Map<String, Value>>,
@@ -106,6 +249,10 @@ pub struct HttpDiscovery {
     #[serde(default)]
     pub method: HttpRequestMethod,
 
+    /// Retry policy for failed HTTP requests.
+    #[serde(default)]
+    pub retry: HttpRetry,
+
     /// HTTP Requests timeout (in milliseconds).
     #[serde(default = "HttpDiscovery::default_timeout")]
     pub timeout: u64,
@@ -118,17 +265,19 @@ pub struct HttpDiscovery {
     pub url: String,
 }
 
-impl PartialEq for HttpDiscovery {
-    fn eq(&self, other: &HttpDiscovery) -> bool {
-        self.headers == other.headers && self.tls == other.tls && self.url == other.url
-    }
-}
-
-impl Eq for HttpDiscovery {}
-
+// JSON values do not implement `Hash` so the body is hashed in its JSON encoded form.
+// Objects are backed by sorted maps, which makes the encoding stable.
 impl Hash for HttpDiscovery {
     fn hash<H: Hasher>(&self, state: &mut H) {
+        self.auth.hash(state);
+        self.body
+            .as_ref()
+            .map(|body| serde_json::to_string(body).expect("JSON body should always encode"))
+            .hash(state);
         self.headers.hash(state);
+        self.method.hash(state);
+        self.retry.hash(state);
+        self.timeout.hash(state);
         self.tls.hash(state);
         self.url.hash(state);
     }
@@ -140,6 +289,45 @@ impl HttpDiscovery {
     }
 }
 
+/// Authentication methods supported by the HTTP discovery.
+///
+/// Credentials are never stored inline: they reference secrets resolved when discovery runs.
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum HttpAuth {
+    /// HTTP basic authentication.
+    #[serde(rename = "basic")]
+    Basic {
+        username: String,
+        password: SecretRef,
+    },
+
+    /// Bearer token authentication.
+    #[serde(rename = "bearer")]
+    Bearer { token: SecretRef },
+
+    /// Bearer token fetched with an OAuth2 client credentials grant.
+    #[serde(rename = "oauth2")]
+    OAuth2(HttpOAuth2ClientCredentials),
+}
+
+/// OAuth2 client credentials grant configuration.
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
+pub struct HttpOAuth2ClientCredentials {
+    /// OAuth2 client ID.
+    pub client_id: String,
+
+    /// Reference to the OAuth2 client secret.
+    pub client_secret: SecretRef,
+
+    /// Optional list of scopes to request the token for.
+    #[serde(default)]
+    pub scopes: Vec<String>,
+
+    /// URL of the token endpoint to request access tokens from.
+    pub token_url: String,
+}
+
 /// HTTP Method to use when sending requests.
 ///
 /// This impacts the use of pagination and body, which are only possible with POST requests.
@@ -158,6 +346,63 @@ impl Default for HttpRequestMethod {
     }
 }
 
+/// Reference to a secret value resolved when it is needed.
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
+pub enum SecretRef {
+    /// Read the secret from the named environment variable.
+    #[serde(rename = "env")]
+    Env(String),
+
+    /// Read the secret from a file, ignoring leading and trailing whitespace.
+    #[serde(rename = "file")]
+    File(String),
+}
+
+/// Retry policy for failed HTTP discovery requests.
+///
+/// Requests are retried on connection errors, `429 Too Many Requests` and `5xx` responses.
+/// The delay between attempts doubles every retry, starting at `backoff` and
+/// capped at `backoff_max`, unless the server sends a `Retry-After` header
+/// in which case that delay is used (also capped at `backoff_max`).
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
+pub struct HttpRetry {
+    /// Number of times a failed request is retried before giving up.
+    #[serde(default = "HttpRetry::default_attempts")]
+    pub attempts: u32,
+
+    /// Initial delay between attempts (in milliseconds).
+    #[serde(default = "HttpRetry::default_backoff")]
+    pub backoff: u64,
+
+    /// Maximum delay between attempts (in milliseconds).
+    #[serde(default = "HttpRetry::default_backoff_max")]
+    pub backoff_max: u64,
+}
+
+impl HttpRetry {
+    fn default_attempts() -> u32 {
+        3
+    }
+
+    fn default_backoff() -> u64 {
+        500
+    }
+
+    fn default_backoff_max() -> u64 {
+        30_000
+    }
+}
+
+impl Default for HttpRetry {
+    fn default() -> Self {
+        HttpRetry {
+            attempts: HttpRetry::default_attempts(),
+            backoff: HttpRetry::default_backoff(),
+            backoff_max: HttpRetry::default_backoff_max(),
+        }
+    }
+}
+
 /// TLS configuration used to connect to the remote server.
 #[derive(Clone, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
 pub struct HttpTlsConfig {
@@ -184,6 +429,12 @@ mod tests {
     use serde_json;
 
     use super::ClusterDiscovery;
+    use super::DiscoveryBackend;
+    use super::DiscoverySettings;
+    use super::DnsRecordType;
+    use super::HttpAuth;
+    use super::HttpRequestMethod;
+    use super::SecretRef;
 
     #[test]
     fn from_json() {
@@ -200,4 +451,121 @@ mod tests {
         let expected = r#"{"cluster_id":"test","display_name":null,"nodes":["a","b"]}"#;
         assert_eq!(payload, expected);
     }
+
+    #[test]
+    fn grace_period_default() {
+        let payload = r#"{
+            "backend": "http",
+            "url": "http://localhost:8000/",
+            "interval": 60,
+            "name": "test",
+            "namespace": "default"
+        }"#;
+        let settings: DiscoverySettings = serde_json::from_str(&payload).unwrap();
+        assert_eq!(settings.grace_period, None);
+        assert_eq!(settings.removal_grace_period(), 180);
+    }
+
+    #[test]
+    fn grace_period_set() {
+        let payload = r#"{
+            "backend": "http",
+            "url": "http://localhost:8000/",
+            "grace_period": 30,
+            "interval": 60,
+            "name": "test",
+            "namespace": "default"
+        }"#;
+        let settings: DiscoverySettings = serde_json::from_str(&payload).unwrap();
+        assert_eq!(settings.removal_grace_period(), 30);
+    }
+
+    #[test]
+    fn dns_backend_defaults() {
+        let payload = r#"{
+            "backend": "dns",
+            "records": [{"cluster_id": "mongo", "name": "_agent._tcp.mongo.local"}],
+            "interval": 60,
+            "name": "test",
+            "namespace": "default"
+        }"#;
+        let settings: DiscoverySettings = serde_json::from_str(&payload).unwrap();
+        let dns = match settings.backend {
+            DiscoveryBackend::Dns(dns) => dns,
+            backend => panic!("unexpected backend {:?}", backend),
+        };
+        assert_eq!(dns.record_type, DnsRecordType::Srv);
+        assert_eq!(dns.scheme, "http");
+        assert_eq!(dns.records[0].cluster_id, "mongo");
+        assert!(dns.nameservers.is_empty());
+    }
+
+    #[test]
+    fn http_auth_secret_ref() {
+        let payload = r#"{
+            "backend": "http",
+            "auth": {"type": "bearer", "token": {"env": "DISCOVERY_TOKEN"}},
+            "url": "http://localhost:8000/",
+            "interval": 60,
+            "name": "test",
+            "namespace": "default"
+        }"#;
+        let settings: DiscoverySettings = serde_json::from_str(&payload).unwrap();
+        let http = match settings.backend {
+            DiscoveryBackend::Http(http) => http,
+            backend => panic!("unexpected backend {:?}", backend),
+        };
+        let expected = HttpAuth::Bearer {
+            token: SecretRef::Env("DISCOVERY_TOKEN".into()),
+        };
+        assert_eq!(http.auth, Some(expected));
+    }
+
+    fn http_settings() -> DiscoverySettings {
+        let payload = r#"{
+            "backend": "http",
+            "url": "http://localhost:8000/",
+            "interval": 60,
+            "name": "test",
+            "namespace": "default"
+        }"#;
+        serde_json::from_str(&payload).unwrap()
+    }
+
+    #[test]
+    fn http_equality_includes_method() {
+        let before = http_settings();
+        let mut after = http_settings();
+        if let DiscoveryBackend::Http(ref mut http) = after.backend {
+            http.method = HttpRequestMethod::Get;
+        }
+        assert_ne!(before, after);
+    }
+
+    #[test]
+    fn http_equality_includes_timeout() {
+        let before = http_settings();
+        let mut after = http_settings();
+        if let DiscoveryBackend::Http(ref mut http) = after.backend {
+            http.timeout = 42;
+        }
+        assert_ne!(before, after);
+    }
+
+    #[test]
+    fn revise_changed() {
+        let current = http_settings().revise(None);
+        let mut settings = http_settings();
+        settings.interval = 30;
+        let settings = settings.revise(Some(&current));
+        assert_eq!(current.revision, 1);
+        assert_eq!(settings.revision, 2);
+    }
+
+    #[test]
+    fn revise_unchanged() {
+        let current = http_settings().revise(None);
+        let settings = http_settings().revise(Some(&current));
+        assert_eq!(settings.revision, 1);
+    }
 }
@@ -1,6 +1,29 @@
 use serde_derive::Deserialize;
 use serde_derive::Serialize;
 
+/// Overall health status of a cluster, derived from its aggregated state.
+#[derive(Clone, Copy, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ClusterHealth {
+    /// The cluster is fully operational.
+    Healthy,
+
+    /// The cluster is operational but some of its parts are not.
+    Degraded,
+
+    /// The cluster is unable to serve requests.
+    Unavailable,
+
+    /// The cluster health was never evaluated.
+    Unknown,
+}
+
+impl Default for ClusterHealth {
+    fn default() -> ClusterHealth {
+        ClusterHealth::Unknown
+    }
+}
+
 /// Cluster metadata generated while fetching cluster state.
 #[derive(Clone, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
 pub struct ClusterMeta {
@@ -14,6 +37,16 @@ pub struct ClusterMeta {
     pub nodes_down: i32,
     pub shards_count: i32,
     pub shards_primaries: i32,
+
+    // Cluster health and the details it is evaluated from.
+    #[serde(default)]
+    pub health: ClusterHealth,
+    #[serde(default)]
+    pub max_lag_seconds: Option<i64>,
+    #[serde(default)]
+    pub shards_without_primary: i32,
+    #[serde(default)]
+    pub versions: Vec<String>,
 }
 
 impl ClusterMeta {
@@ -32,6 +65,10 @@ impl ClusterMeta {
             nodes_down: 0,
             shards_count: 0,
             shards_primaries: 0,
+            health: ClusterHealth::Unknown,
+            max_lag_seconds: None,
+            shards_without_primary: 0,
+            versions: Vec::new(),
         }
     }
 }
@@ -41,6 +78,7 @@ mod tests {
     mod cluster_meta {
         use serde_json;
 
+        use super::super::ClusterHealth;
         use super::super::ClusterMeta;
 
         #[test]
@@ -64,6 +102,20 @@ mod tests {
             assert_eq!(clusters, expected);
         }
 
+        #[test]
+        fn from_json_with_health() {
+            let payload = concat!(
+                r#"{"cluster_display_name":"mongo","cluster_id":"c1","kinds":["mongo"],"#,
+                r#""agents_down":1,"nodes":4,"nodes_down":0,"shards_count":5,"shards_primaries":1,"#,
+                r#""health":"DEGRADED","max_lag_seconds":12,"shards_without_primary":0,"#,
+                r#""versions":["4.0.1","4.2.0"]}"#
+            );
+            let cluster: ClusterMeta = serde_json::from_str(payload).unwrap();
+            assert_eq!(cluster.health, ClusterHealth::Degraded);
+            assert_eq!(cluster.max_lag_seconds, Some(12));
+            assert_eq!(cluster.versions, vec!["4.0.1", "4.2.0"]);
+        }
+
         #[test]
         fn to_json() {
             let mut c1 = ClusterMeta::new("c1", "mongo");
@@ -76,9 +128,13 @@ mod tests {
             let payload = serde_json::to_string(&clusters).unwrap();
             let expected = concat!(
                 r#"[{"cluster_display_name":"mongo","cluster_id":"c1","kinds":["mongo"],"#,
-                r#""agents_down":0,"nodes":4,"nodes_down":0,"shards_count":0,"shards_primaries":0},"#,
+                r#""agents_down":0,"nodes":4,"nodes_down":0,"shards_count":0,"shards_primaries":0,"#,
+                r#""health":"UNKNOWN","max_lag_seconds":null,"shards_without_primary":0,"#,
+                r#""versions":[]},"#,
                 r#"{"cluster_display_name":"redis","cluster_id":"c2","kinds":["redis"],"#,
-                r#""agents_down":0,"nodes":2,"nodes_down":0,"shards_count":0,"shards_primaries":0}]"#
+                r#""agents_down":0,"nodes":2,"nodes_down":0,"shards_count":0,"shards_primaries":0,"#,
+                r#""health":"UNKNOWN","max_lag_seconds":null,"shards_without_primary":0,"#,
+                r#""versions":[]}]"#
             );
             assert_eq!(payload, expected);
         }
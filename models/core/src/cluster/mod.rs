@@ -1,6 +1,10 @@
 pub mod discovery;
 mod meta;
+mod orchestrate_report;
 mod settings;
 
+pub use self::meta::ClusterHealth;
 pub use self::meta::ClusterMeta;
+pub use self::orchestrate_report::OrchestrateOutcome;
+pub use self::orchestrate_report::OrchestrateReport;
 pub use self::settings::ClusterSettings;
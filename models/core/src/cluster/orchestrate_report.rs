@@ -0,0 +1,111 @@
+use chrono::DateTime;
+use chrono::Utc;
+use serde_derive::Deserialize;
+use serde_derive::Serialize;
+
+/// Summary of the last orchestration run for a cluster.
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
+pub struct OrchestrateReport {
+    pub cluster_id: String,
+    pub namespace: String,
+
+    /// Duration, in milliseconds, of the orchestration run.
+    pub duration: i64,
+
+    /// Error message, if the orchestration run failed.
+    #[serde(default)]
+    pub error: Option<String>,
+
+    /// Result of the orchestration run.
+    pub outcome: OrchestrateOutcome,
+
+    /// Time the orchestration run started at.
+    pub start_time: DateTime<Utc>,
+}
+
+impl OrchestrateReport {
+    /// Report a successful orchestration run.
+    pub fn success<S1, S2>(
+        namespace: S1,
+        cluster_id: S2,
+        start_time: DateTime<Utc>,
+    ) -> OrchestrateReport
+    where
+        S1: Into<String>,
+        S2: Into<String>,
+    {
+        let duration = (Utc::now() - start_time).num_milliseconds();
+        OrchestrateReport {
+            cluster_id: cluster_id.into(),
+            namespace: namespace.into(),
+            duration,
+            error: None,
+            outcome: OrchestrateOutcome::Success,
+            start_time,
+        }
+    }
+
+    /// Report a failed orchestration run.
+    pub fn failed<S1, S2, S3>(
+        namespace: S1,
+        cluster_id: S2,
+        start_time: DateTime<Utc>,
+        error: S3,
+    ) -> OrchestrateReport
+    where
+        S1: Into<String>,
+        S2: Into<String>,
+        S3: Into<String>,
+    {
+        let duration = (Utc::now() - start_time).num_milliseconds();
+        OrchestrateReport {
+            cluster_id: cluster_id.into(),
+            namespace: namespace.into(),
+            duration,
+            error: Some(error.into()),
+            outcome: OrchestrateOutcome::Failed,
+            start_time,
+        }
+    }
+}
+
+/// Possible results of a cluster orchestration run.
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
+pub enum OrchestrateOutcome {
+    /// The orchestration run failed, see the report error for details.
+    #[serde(rename = "FAILED")]
+    Failed,
+
+    /// The orchestration run completed successfully.
+    #[serde(rename = "SUCCESS")]
+    Success,
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use chrono::Utc;
+    use serde_json;
+
+    use super::OrchestrateOutcome;
+    use super::OrchestrateReport;
+
+    #[test]
+    fn from_json() {
+        let payload = concat!(
+            r#"{"cluster_id":"cluster1","namespace":"default_ns","duration":42,"#,
+            r#""error":"agents unreachable","outcome":"FAILED","#,
+            r#""start_time":"2020-06-01T12:00:00Z"}"#,
+        );
+        let actual: OrchestrateReport = serde_json::from_str(payload).unwrap();
+        let expected = OrchestrateReport {
+            cluster_id: "cluster1".into(),
+            namespace: "default_ns".into(),
+            duration: 42,
+            error: Some("agents unreachable".into()),
+            outcome: OrchestrateOutcome::Failed,
+            start_time: Utc.ymd(2020, 6, 1).and_hms(12, 0, 0),
+        };
+        assert_eq!(actual, expected);
+    }
+}
@@ -6,6 +6,11 @@ use serde_derive::Serialize;
 pub struct ClusterSettings {
     pub cluster_id: String,
     pub enabled: bool,
+
+    /// Interval, in seconds, between orchestration runs.
+    #[serde(default = "ClusterSettings::default_interval")]
+    pub interval: i64,
+
     pub namespace: String,
 }
 
@@ -20,9 +25,30 @@ impl ClusterSettings {
         ClusterSettings {
             cluster_id,
             enabled,
+            interval: ClusterSettings::default_interval(),
+            namespace,
+        }
+    }
+
+    /// Create a `ClusterSettings` from an apply API object.
+    pub fn from_object(
+        namespace: String,
+        cluster_id: String,
+        settings: crate::api::objects::ClusterSettings,
+    ) -> ClusterSettings {
+        ClusterSettings {
+            cluster_id,
+            enabled: settings.enabled,
+            interval: settings
+                .interval
+                .unwrap_or_else(ClusterSettings::default_interval),
             namespace,
         }
     }
+
+    fn default_interval() -> i64 {
+        60
+    }
 }
 
 #[cfg(test)]
@@ -40,10 +66,35 @@ mod tests {
         assert_eq!(actual, expected);
     }
 
+    #[test]
+    fn from_json_with_interval() {
+        let payload = concat!(
+            r#"{"cluster_id": "cluster1", "enabled": true, "interval": 15, "#,
+            r#""namespace": "default_ns"}"#,
+        );
+        let actual: ClusterSettings = serde_json::from_str(payload).unwrap();
+        let mut expected = ClusterSettings::new("default_ns", "cluster1", true);
+        expected.interval = 15;
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn from_object() {
+        let object = crate::api::objects::ClusterSettings {
+            enabled: false,
+            interval: None,
+        };
+        let actual = ClusterSettings::from_object("default_ns".into(), "cluster1".into(), object);
+        let expected = ClusterSettings::new("default_ns", "cluster1", false);
+        assert_eq!(actual, expected);
+    }
+
     #[test]
     fn to_json() {
-        let expected =
-            concat!(r#"{"cluster_id":"cluster2","enabled":true,"namespace":"default_ns"}"#);
+        let expected = concat!(
+            r#"{"cluster_id":"cluster2","enabled":true,"interval":60,"#,
+            r#""namespace":"default_ns"}"#,
+        );
         let cluster = ClusterSettings::new("default_ns", "cluster2", true);
         let actual = serde_json::to_string(&cluster).unwrap();
         assert_eq!(actual, expected);
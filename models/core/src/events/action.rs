@@ -35,6 +35,16 @@ pub struct ActionHistory {
 #[allow(clippy::large_enum_variant)]
 // TODO: use when possible #[non_exhaustive]
 pub enum ActionEvent {
+    /// An action was cancelled by a user, the latest action state is attached.
+    #[serde(rename = "ACTION_CANCELLED")]
+    Cancelled(Action),
+
+    /// A user requested the agent running an action to cancel it.
+    ///
+    /// The action state is updated once the agent reports the action as cancelled.
+    #[serde(rename = "ACTION_CANCEL_REQUESTED")]
+    CancelRequested(Action),
+
     /// An action change was observed.
     #[serde(rename = "ACTION_CHANGED")]
     Changed(Box<ActionChanged>),
@@ -60,6 +70,8 @@ impl ActionEvent {
     /// Look up the cluster ID for the event, if they have one.
     pub fn cluster_id(&self) -> Option<&str> {
         let cluster_id = match self {
+            ActionEvent::Cancelled(action) => &action.cluster_id,
+            ActionEvent::CancelRequested(action) => &action.cluster_id,
             ActionEvent::Changed(change) => &change.cluster_id,
             ActionEvent::Finished(action) => &action.cluster_id,
             ActionEvent::History(info) => &info.cluster_id,
@@ -72,6 +84,8 @@ impl ActionEvent {
     /// Returns the event "code", the string that represents the event type.
     pub fn code(&self) -> &'static str {
         match self {
+            ActionEvent::Cancelled(_) => "ACTION_CANCELLED",
+            ActionEvent::CancelRequested(_) => "ACTION_CANCEL_REQUESTED",
             ActionEvent::Changed(_) => "ACTION_CHANGED",
             ActionEvent::Finished(_) => "ACTION_FINISHED",
             ActionEvent::History(_) => "ACTION_HISTORY",
@@ -92,6 +106,20 @@ pub struct ActionEventBuilder {
 }
 
 impl ActionEventBuilder {
+    /// Build an `ActionEvent::Cancelled` event.
+    pub fn cancelled(self, action: Action) -> Event {
+        let event = ActionEvent::Cancelled(action);
+        let payload = Payload::Action(event);
+        self.builder.finish(payload)
+    }
+
+    /// Build an `ActionEvent::CancelRequested` event.
+    pub fn cancel_requested(self, action: Action) -> Event {
+        let event = ActionEvent::CancelRequested(action);
+        let payload = Payload::Action(event);
+        self.builder.finish(payload)
+    }
+
     /// Build an `ActionEvent::Changed` event.
     pub fn changed(self, previous: Action, current: Action) -> Event {
         let event = ActionEvent::Changed(Box::new(ActionChanged {
@@ -30,6 +30,10 @@ pub enum AgentEvent {
     #[serde(rename = "AGENT_NEW")]
     New(Agent),
 
+    /// An agent is no longer part of the cluster.
+    #[serde(rename = "AGENT_REMOVED")]
+    Removed(Agent),
+
     /// An agent was found to be up.
     #[serde(rename = "AGENT_UP")]
     Up(StatusChange),
@@ -43,6 +47,7 @@ impl AgentEvent {
             AgentEvent::InfoChanged(change) => &change.cluster_id,
             AgentEvent::InfoNew(info) => &info.cluster_id,
             AgentEvent::New(agent) => &agent.cluster_id,
+            AgentEvent::Removed(agent) => &agent.cluster_id,
             AgentEvent::Up(change) => &change.cluster_id,
         };
         Some(cluster_id)
@@ -55,6 +60,7 @@ impl AgentEvent {
             AgentEvent::InfoChanged(_) => "AGENT_INFO_CHANGED",
             AgentEvent::InfoNew(_) => "AGENT_INFO_NEW",
             AgentEvent::New(_) => "AGENT_NEW",
+            AgentEvent::Removed(_) => "AGENT_REMOVED",
             AgentEvent::Up(_) => "AGENT_UP",
         }
     }
@@ -96,6 +102,13 @@ impl AgentEventBuilder {
         self.builder.finish(payload)
     }
 
+    /// Build an `AgentEvent::Removed` event.
+    pub fn removed(self, agent: Agent) -> Event {
+        let event = AgentEvent::Removed(agent);
+        let payload = Payload::Agent(event);
+        self.builder.finish(payload)
+    }
+
     /// Build an agent status transition event.
     ///
     /// This method may return non-`AGENT_*` events when datastores change state.
@@ -266,6 +279,14 @@ mod tests {
         assert_eq!(event.payload, expected);
     }
 
+    #[test]
+    fn removed() {
+        let agent = Agent::new("cluster", "host", AgentStatus::Up);
+        let event = Event::builder().agent().removed(agent.clone());
+        let expected = Payload::Agent(AgentEvent::Removed(agent));
+        assert_eq!(event.payload, expected);
+    }
+
     #[test]
     fn transition_agent_down_to_agent_down() {
         let after = Agent::new("cluster", "host", AgentStatus::AgentDown("after".into()));
@@ -0,0 +1,131 @@
+use serde_derive::Deserialize;
+use serde_derive::Serialize;
+
+use super::Event;
+use super::EventBuilder;
+use super::Payload;
+use crate::alerts::Alert;
+use crate::alerts::AlertRule;
+
+/// Enumerates all possible alert events emitted by the system.
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
+#[serde(tag = "event", content = "payload")]
+// TODO: use when possible #[non_exhaustive]
+pub enum AlertEvent {
+    /// The condition of an alert rule is met for a cluster.
+    #[serde(rename = "ALERT_FIRING")]
+    Firing(Alert),
+
+    /// A firing alert is no longer met for a cluster.
+    #[serde(rename = "ALERT_RESOLVED")]
+    Resolved(Alert),
+
+    /// An AlertRule object was applied.
+    ///
+    /// This event is emitted even if the object already exists and was not changed.
+    #[serde(rename = "ALERT_RULE_APPLY")]
+    RuleApply(AlertRule),
+}
+
+impl AlertEvent {
+    /// Look up the cluster ID for the event, if they have one.
+    pub fn cluster_id(&self) -> Option<&str> {
+        match self {
+            AlertEvent::Firing(alert) => Some(&alert.cluster_id),
+            AlertEvent::Resolved(alert) => Some(&alert.cluster_id),
+            AlertEvent::RuleApply(rule) => rule.cluster_id.as_deref(),
+        }
+    }
+
+    /// Returns the event "code", the string that represents the event type.
+    pub fn code(&self) -> &'static str {
+        match self {
+            AlertEvent::Firing(_) => "ALERT_FIRING",
+            AlertEvent::Resolved(_) => "ALERT_RESOLVED",
+            AlertEvent::RuleApply(_) => "ALERT_RULE_APPLY",
+        }
+    }
+
+    /// Returns the "ordering ID" for correctly streaming the event.
+    pub fn stream_key(&self) -> &str {
+        match self {
+            AlertEvent::Firing(alert) => &alert.cluster_id,
+            AlertEvent::Resolved(alert) => &alert.cluster_id,
+            AlertEvent::RuleApply(rule) => &rule.namespace,
+        }
+    }
+}
+
+/// Build `AlertEvent`s, validating inputs.
+pub struct AlertEventBuilder {
+    pub(super) builder: EventBuilder,
+}
+
+impl AlertEventBuilder {
+    /// Build an `AlertEvent::Firing` event.
+    pub fn firing(self, alert: Alert) -> Event {
+        let event = AlertEvent::Firing(alert);
+        let payload = Payload::Alert(event);
+        self.builder.finish(payload)
+    }
+
+    /// Build an `AlertEvent::Resolved` event.
+    pub fn resolved(self, alert: Alert) -> Event {
+        let event = AlertEvent::Resolved(alert);
+        let payload = Payload::Alert(event);
+        self.builder.finish(payload)
+    }
+
+    /// Build an `AlertEvent::RuleApply` event.
+    pub fn rule_apply(self, rule: AlertRule) -> Event {
+        let event = AlertEvent::RuleApply(rule);
+        let payload = Payload::Alert(event);
+        self.builder.finish(payload)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Utc;
+
+    use super::AlertEvent;
+    use super::Event;
+    use super::Payload;
+    use crate::alerts::Alert;
+    use crate::alerts::AlertCondition;
+    use crate::alerts::AlertRule;
+
+    fn rule() -> AlertRule {
+        AlertRule {
+            cluster_id: None,
+            condition: AlertCondition::ActionFailed {
+                kind: "replicante.io/test.fail".into(),
+            },
+            description: None,
+            name: "test-fails".into(),
+            namespace: "default".into(),
+        }
+    }
+
+    #[test]
+    fn firing() {
+        let mut alert = Alert::new(&rule(), "cluster".into(), Utc::now());
+        alert.fire(Utc::now());
+        let event = Event::builder().alert().firing(alert.clone());
+        let expected = Payload::Alert(AlertEvent::Firing(alert));
+        assert_eq!(event.payload, expected);
+        assert_eq!(event.code(), "ALERT_FIRING");
+        assert_eq!(event.cluster_id(), Some("cluster"));
+        assert_eq!(event.stream_key(), "cluster");
+    }
+
+    #[test]
+    fn rule_apply() {
+        let event = Event::builder().alert().rule_apply(rule());
+        let expected = Payload::Alert(AlertEvent::RuleApply(rule()));
+        assert_eq!(event.payload, expected);
+        assert_eq!(event.code(), "ALERT_RULE_APPLY");
+        assert_eq!(event.cluster_id(), None);
+        assert_eq!(event.stream_key(), "default");
+    }
+}
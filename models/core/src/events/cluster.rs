@@ -5,6 +5,7 @@ use super::Event;
 use super::EventBuilder;
 use super::Payload;
 use crate::cluster::discovery::ClusterDiscovery;
+use crate::cluster::ClusterMeta;
 use crate::cluster::ClusterSettings;
 
 /// Metadata attached to cluster status change events.
@@ -15,6 +16,14 @@ pub struct ClusterChanged {
     pub cluster_id: String,
 }
 
+/// Metadata attached to cluster health change events.
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
+pub struct ClusterHealthChanged {
+    pub after: ClusterMeta,
+    pub before: Option<ClusterMeta>,
+    pub cluster_id: String,
+}
+
 /// Enumerates all possible cluster events emitted by the system.
 #[derive(Clone, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
 #[serde(tag = "event", content = "payload")]
@@ -24,10 +33,28 @@ pub enum ClusterEvent {
     #[serde(rename = "CLUSTER_CHANGED")]
     Changed(ClusterChanged),
 
+    /// A cluster was no longer reported by service discovery for longer than the grace period.
+    ///
+    /// The payload is the last known discovery record for the cluster.
+    #[serde(rename = "CLUSTER_DISAPPEARED")]
+    Disappeared(ClusterDiscovery),
+
+    /// The aggregated health of a cluster changed.
+    ///
+    /// The `before` state is `None` for the first aggregation of a cluster.
+    #[serde(rename = "CLUSTER_HEALTH_CHANGED")]
+    HealthChanged(ClusterHealthChanged),
+
     /// Service discovery found a new cluster.
     #[serde(rename = "CLUSTER_NEW")]
     New(ClusterDiscovery),
 
+    /// A ClusterSettings object was applied.
+    ///
+    /// This event is emitted even if the object already exists and was not changed.
+    #[serde(rename = "CLUSTER_SETTINGS_APPLY")]
+    SettingsApply(ClusterSettings),
+
     /// A synthetic ClusterSettings record was created for a discovered cluster without it.
     #[serde(rename = "CLUSTER_SETTINGS_SYNTHETIC")]
     SettingsSynthetic(ClusterSettings),
@@ -38,7 +65,10 @@ impl ClusterEvent {
     pub fn cluster_id(&self) -> Option<&str> {
         let cluster_id = match self {
             ClusterEvent::Changed(change) => &change.cluster_id,
+            ClusterEvent::Disappeared(discovery) => &discovery.cluster_id,
+            ClusterEvent::HealthChanged(change) => &change.cluster_id,
             ClusterEvent::New(discovery) => &discovery.cluster_id,
+            ClusterEvent::SettingsApply(settings) => &settings.cluster_id,
             ClusterEvent::SettingsSynthetic(settings) => &settings.cluster_id,
         };
         Some(cluster_id)
@@ -48,7 +78,10 @@ impl ClusterEvent {
     pub fn code(&self) -> &'static str {
         match self {
             ClusterEvent::Changed(_) => "CLUSTER_CHANGED",
+            ClusterEvent::Disappeared(_) => "CLUSTER_DISAPPEARED",
+            ClusterEvent::HealthChanged(_) => "CLUSTER_HEALTH_CHANGED",
             ClusterEvent::New(_) => "CLUSTER_NEW",
+            ClusterEvent::SettingsApply(_) => "CLUSTER_SETTINGS_APPLY",
             ClusterEvent::SettingsSynthetic(_) => "CLUSTER_SETTINGS_SYNTHETIC",
         }
     }
@@ -76,6 +109,24 @@ impl ClusterEventBuilder {
         self.builder.finish(payload)
     }
 
+    /// Build a `ClusterEvent::Disappeared` event.
+    pub fn disappeared(self, discovery: ClusterDiscovery) -> Event {
+        let event = ClusterEvent::Disappeared(discovery);
+        let payload = Payload::Cluster(event);
+        self.builder.finish(payload)
+    }
+
+    /// Build a `ClusterEvent::HealthChanged` event.
+    pub fn health_changed(self, before: Option<ClusterMeta>, after: ClusterMeta) -> Event {
+        let event = ClusterEvent::HealthChanged(ClusterHealthChanged {
+            cluster_id: after.cluster_id.clone(),
+            before,
+            after,
+        });
+        let payload = Payload::Cluster(event);
+        self.builder.finish(payload)
+    }
+
     /// Build a `ClusterEvent::New` event.
     pub fn new_cluster(self, discovery: ClusterDiscovery) -> Event {
         let event = ClusterEvent::New(discovery);
@@ -83,6 +134,13 @@ impl ClusterEventBuilder {
         self.builder.finish(payload)
     }
 
+    /// Build a `ClusterEvent::SettingsApply` event.
+    pub fn apply_settings(self, settings: ClusterSettings) -> Event {
+        let event = ClusterEvent::SettingsApply(settings);
+        let payload = Payload::Cluster(event);
+        self.builder.finish(payload)
+    }
+
     /// Build a `ClusterEvent::SettingsSynthetic` event.
     pub fn synthetic_settings(self, settings: ClusterSettings) -> Event {
         let event = ClusterEvent::SettingsSynthetic(settings);
@@ -95,9 +153,23 @@ impl ClusterEventBuilder {
 mod tests {
     use super::ClusterChanged;
     use super::ClusterEvent;
+    use super::ClusterHealthChanged;
     use super::Event;
     use super::Payload;
     use crate::cluster::discovery::ClusterDiscovery;
+    use crate::cluster::ClusterHealth;
+    use crate::cluster::ClusterMeta;
+    use crate::cluster::ClusterSettings;
+
+    #[test]
+    fn apply_settings() {
+        let settings = ClusterSettings::new("default", "test", true);
+        let event = Event::builder().cluster().apply_settings(settings.clone());
+        let expected = Payload::Cluster(ClusterEvent::SettingsApply(settings));
+        assert_eq!(event.payload, expected);
+        assert_eq!(event.code(), "CLUSTER_SETTINGS_APPLY");
+        assert_eq!(event.stream_key(), "test");
+    }
 
     #[test]
     fn changed() {
@@ -114,6 +186,34 @@ mod tests {
         assert_eq!(event.payload, expected);
     }
 
+    #[test]
+    fn disappeared() {
+        let discovery = ClusterDiscovery::new("test", vec![]);
+        let event = Event::builder().cluster().disappeared(discovery.clone());
+        let expected = Payload::Cluster(ClusterEvent::Disappeared(discovery));
+        assert_eq!(event.payload, expected);
+        assert_eq!(event.code(), "CLUSTER_DISAPPEARED");
+        assert_eq!(event.stream_key(), "test");
+    }
+
+    #[test]
+    fn health_changed() {
+        let before = ClusterMeta::new("test", "test");
+        let mut after = before.clone();
+        after.health = ClusterHealth::Degraded;
+        let event = Event::builder()
+            .cluster()
+            .health_changed(Some(before.clone()), after.clone());
+        let expected = Payload::Cluster(ClusterEvent::HealthChanged(ClusterHealthChanged {
+            after,
+            before: Some(before),
+            cluster_id: "test".into(),
+        }));
+        assert_eq!(event.payload, expected);
+        assert_eq!(event.code(), "CLUSTER_HEALTH_CHANGED");
+        assert_eq!(event.stream_key(), "test");
+    }
+
     #[test]
     fn new_cluster() {
         let discovery = ClusterDiscovery::new("test", vec![]);
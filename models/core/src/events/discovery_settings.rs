@@ -6,6 +6,55 @@ use super::EventBuilder;
 use super::Payload;
 use crate::cluster::discovery::DiscoverySettings;
 
+/// Metadata attached to discovery settings apply events.
+///
+/// Events emitted before revisions were tracked carry only the applied settings.
+/// These are decoded as new settings with no `before` state.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(from = "DiscoverySettingsApplyFormat")]
+pub struct DiscoverySettingsApply {
+    /// Settings as they are after the apply operation.
+    pub after: DiscoverySettings,
+
+    /// Settings as they were before the apply operation, if any existed.
+    pub before: Option<DiscoverySettings>,
+}
+
+/// Supported encodings of `DiscoverySettingsApply` payloads.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum DiscoverySettingsApplyFormat {
+    Current {
+        after: DiscoverySettings,
+        before: Option<DiscoverySettings>,
+    },
+    Legacy(DiscoverySettings),
+}
+
+impl From<DiscoverySettingsApplyFormat> for DiscoverySettingsApply {
+    fn from(format: DiscoverySettingsApplyFormat) -> DiscoverySettingsApply {
+        match format {
+            DiscoverySettingsApplyFormat::Current { after, before } => {
+                DiscoverySettingsApply { after, before }
+            }
+            DiscoverySettingsApplyFormat::Legacy(after) => DiscoverySettingsApply {
+                after,
+                before: None,
+            },
+        }
+    }
+}
+
+impl DiscoverySettingsApply {
+    /// Check if the apply operation changed the settings, including new settings.
+    pub fn changed(&self) -> bool {
+        match &self.before {
+            None => true,
+            Some(before) => before.revision != self.after.revision,
+        }
+    }
+}
+
 /// Enumerates all possible discovery settings events emitted by the system.
 #[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
 #[serde(tag = "event", content = "payload")]
@@ -15,7 +64,7 @@ pub enum DiscoverySettingsEvent {
     ///
     /// This event is emitted even if the object already exists and was not changed.
     #[serde(rename = "DISCOVERY_SETTINGS_APPLY")]
-    Apply(DiscoverySettings),
+    Apply(DiscoverySettingsApply),
 
     /// A DiscoverySettings object was deleted.
     ///
@@ -36,7 +85,7 @@ impl DiscoverySettingsEvent {
     /// Returns the "ordering ID" for correctly streaming the event.
     pub fn stream_key(&self) -> &str {
         match self {
-            DiscoverySettingsEvent::Apply(settings) => &settings.namespace,
+            DiscoverySettingsEvent::Apply(apply) => &apply.after.namespace,
             DiscoverySettingsEvent::Delete(id) => &id.namespace,
         }
     }
@@ -49,8 +98,8 @@ pub struct DiscoverySettingsEventBuilder {
 
 impl DiscoverySettingsEventBuilder {
     /// Build a `DiscoverySettingsEvent::Apply` event.
-    pub fn apply(self, settings: DiscoverySettings) -> Event {
-        let event = DiscoverySettingsEvent::Apply(settings);
+    pub fn apply(self, before: Option<DiscoverySettings>, after: DiscoverySettings) -> Event {
+        let event = DiscoverySettingsEvent::Apply(DiscoverySettingsApply { after, before });
         let payload = Payload::DiscoverySettings(event);
         self.builder.finish(payload)
     }
@@ -70,3 +119,89 @@ pub struct DiscoverySettingsDeleted {
     pub namespace: String,
     pub name: String,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::DiscoverySettingsApply;
+    use super::DiscoverySettingsEvent;
+    use super::Event;
+    use super::Payload;
+    use crate::cluster::discovery::DiscoverySettings;
+
+    fn settings() -> DiscoverySettings {
+        let payload = r#"{
+            "backend": "http",
+            "url": "http://localhost:8000/",
+            "interval": 60,
+            "name": "test",
+            "namespace": "default"
+        }"#;
+        serde_json::from_str(&payload).unwrap()
+    }
+
+    #[test]
+    fn apply_changed() {
+        let before = settings().revise(None);
+        let mut after = settings();
+        after.interval = 30;
+        let after = after.revise(Some(&before));
+        let event = Event::builder()
+            .discovery_settings()
+            .apply(Some(before.clone()), after.clone());
+        let apply = DiscoverySettingsApply {
+            after,
+            before: Some(before),
+        };
+        assert!(apply.changed());
+        let expected = Payload::DiscoverySettings(DiscoverySettingsEvent::Apply(apply));
+        assert_eq!(event.payload, expected);
+        assert_eq!(event.code(), "DISCOVERY_SETTINGS_APPLY");
+        assert_eq!(event.stream_key(), "default");
+    }
+
+    #[test]
+    fn apply_decodes_legacy_payload() {
+        let payload = serde_json::json!({
+            "event": "DISCOVERY_SETTINGS_APPLY",
+            "payload": {
+                "backend": "http",
+                "url": "http://localhost:8000/",
+                "interval": 60,
+                "name": "test",
+                "namespace": "default",
+            },
+        });
+        let event: DiscoverySettingsEvent = serde_json::from_value(payload).unwrap();
+        let apply = DiscoverySettingsApply {
+            after: settings(),
+            before: None,
+        };
+        assert_eq!(event, DiscoverySettingsEvent::Apply(apply));
+    }
+
+    #[test]
+    fn apply_round_trip() {
+        let before = settings().revise(None);
+        let mut after = settings();
+        after.interval = 30;
+        let after = after.revise(Some(&before));
+        let event = DiscoverySettingsEvent::Apply(DiscoverySettingsApply {
+            after,
+            before: Some(before),
+        });
+        let payload = serde_json::to_value(&event).unwrap();
+        let decoded: DiscoverySettingsEvent = serde_json::from_value(payload).unwrap();
+        assert_eq!(decoded, event);
+    }
+
+    #[test]
+    fn apply_unchanged() {
+        let before = settings().revise(None);
+        let after = settings().revise(Some(&before));
+        let apply = DiscoverySettingsApply {
+            after,
+            before: Some(before),
+        };
+        assert!(!apply.changed());
+    }
+}
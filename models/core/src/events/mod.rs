@@ -5,8 +5,10 @@ use serde_derive::Serialize;
 
 pub mod action;
 pub mod agent;
+pub mod alert;
 pub mod cluster;
 pub mod discovery_settings;
+pub mod namespace;
 pub mod node;
 pub mod shard;
 
@@ -59,8 +61,10 @@ impl Event {
         match &self.payload {
             Payload::Action(event) => event.cluster_id(),
             Payload::Agent(event) => event.cluster_id(),
+            Payload::Alert(event) => event.cluster_id(),
             Payload::Cluster(event) => event.cluster_id(),
             Payload::DiscoverySettings(_) => None,
+            Payload::Namespace(_) => None,
             Payload::Node(event) => event.cluster_id(),
             Payload::Shard(event) => event.cluster_id(),
             #[cfg(test)]
@@ -73,8 +77,10 @@ impl Event {
         match &self.payload {
             Payload::Action(event) => event.code(),
             Payload::Agent(event) => event.code(),
+            Payload::Alert(event) => event.code(),
             Payload::Cluster(event) => event.code(),
             Payload::DiscoverySettings(event) => event.code(),
+            Payload::Namespace(event) => event.code(),
             Payload::Node(event) => event.code(),
             Payload::Shard(event) => event.code(),
             #[cfg(test)]
@@ -92,8 +98,10 @@ impl Event {
         match &self.payload {
             Payload::Action(event) => event.stream_key(),
             Payload::Agent(event) => event.stream_key(),
+            Payload::Alert(event) => event.stream_key(),
             Payload::Cluster(event) => event.stream_key(),
             Payload::DiscoverySettings(event) => event.stream_key(),
+            Payload::Namespace(event) => event.stream_key(),
             Payload::Node(event) => event.stream_key(),
             Payload::Shard(event) => event.stream_key(),
             #[cfg(test)]
@@ -124,6 +132,11 @@ impl EventBuilder {
         self::agent::AgentEventBuilder { builder: self }
     }
 
+    /// Build alert events.
+    pub fn alert(self) -> self::alert::AlertEventBuilder {
+        self::alert::AlertEventBuilder { builder: self }
+    }
+
     /// Build cluster events.
     pub fn cluster(self) -> self::cluster::ClusterEventBuilder {
         self::cluster::ClusterEventBuilder { builder: self }
@@ -134,6 +147,11 @@ impl EventBuilder {
         self::discovery_settings::DiscoverySettingsEventBuilder { builder: self }
     }
 
+    /// Build namespace events.
+    pub fn namespace(self) -> self::namespace::NamespaceEventBuilder {
+        self::namespace::NamespaceEventBuilder { builder: self }
+    }
+
     /// Build node events.
     pub fn node(self) -> self::node::NodeEventBuilder {
         self::node::NodeEventBuilder { builder: self }
@@ -169,6 +187,38 @@ pub struct EventCode {
     pub event: String,
 }
 
+/// Match event codes by value, glob pattern or regular expression.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum EventCodeMatch {
+    /// Match the event code exactly.
+    Code(String),
+
+    /// Match event codes with a glob pattern (`*` matches any sequence, `?` any character).
+    Glob(String),
+
+    /// Match event codes with a regular expression.
+    Regex(String),
+}
+
+impl EventCodeMatch {
+    /// Match all events in a category, the event code prefix (for example `ACTION` or `SHARD`).
+    pub fn category(category: &str) -> EventCodeMatch {
+        EventCodeMatch::Glob(format!("{}_*", category.to_uppercase()))
+    }
+
+    /// Return the regular expression that matches event codes, if a pattern is used.
+    pub fn regex(&self) -> Option<String> {
+        match self {
+            EventCodeMatch::Code(_) => None,
+            EventCodeMatch::Glob(glob) => {
+                let regex = regex::escape(glob).replace(r"\*", ".*").replace(r"\?", ".");
+                Some(format!("^{}$", regex))
+            }
+            EventCodeMatch::Regex(regex) => Some(regex.clone()),
+        }
+    }
+}
+
 /// Result of a `deserialize_event` operation.
 #[allow(clippy::large_enum_variant)]
 pub enum DeserializeResult<E> {
@@ -195,6 +245,10 @@ pub enum Payload {
     #[serde(rename = "AGENT")]
     Agent(self::agent::AgentEvent),
 
+    /// Alert related events.
+    #[serde(rename = "ALERT")]
+    Alert(self::alert::AlertEvent),
+
     /// Cluster related events.
     #[serde(rename = "CLUSTER")]
     Cluster(self::cluster::ClusterEvent),
@@ -202,6 +256,10 @@ pub enum Payload {
     #[serde(rename = "DISCOVERY_SETTINGS")]
     DiscoverySettings(self::discovery_settings::DiscoverySettingsEvent),
 
+    /// Namespace related events.
+    #[serde(rename = "NAMESPACE")]
+    Namespace(self::namespace::NamespaceEvent),
+
     /// Node related events.
     #[serde(rename = "NODE")]
     Node(self::node::NodeEvent),
@@ -250,10 +308,20 @@ mod tests {
     use super::DeserializeResult;
     use super::Event;
     use super::EventCode;
+    use super::EventCodeMatch;
     use super::Payload;
     use super::TestEvent;
     use crate::deserialize_event;
 
+    #[test]
+    fn event_code_glob_regex() {
+        let glob = EventCodeMatch::Glob("ACTION_*".into());
+        assert_eq!(glob.regex(), Some("^ACTION_.*$".into()));
+        let glob = EventCodeMatch::Glob("NODE.?".into());
+        assert_eq!(glob.regex(), Some(r"^NODE\..$".into()));
+        assert_eq!(EventCodeMatch::Code("NODE_NEW".into()).regex(), None);
+    }
+
     #[test]
     fn flatten_as_expected() {
         let event = Event {
@@ -0,0 +1,101 @@
+use serde_derive::Deserialize;
+use serde_derive::Serialize;
+
+use super::Event;
+use super::EventBuilder;
+use super::Payload;
+use crate::scope::Namespace;
+
+/// Enumerates all possible namespace events emitted by the system.
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
+#[serde(tag = "event", content = "payload")]
+// TODO: use when possible #[non_exhaustive]
+pub enum NamespaceEvent {
+    /// A Namespace object was applied.
+    ///
+    /// This event is emitted even if the object already exists and was not changed.
+    #[serde(rename = "NAMESPACE_APPLY")]
+    Apply(Namespace),
+
+    /// A Namespace object was deleted.
+    ///
+    /// This event is emitted even if no matching object exists.
+    #[serde(rename = "NAMESPACE_DELETE")]
+    Delete(NamespaceDeleted),
+}
+
+impl NamespaceEvent {
+    /// Returns the event "code", the string that represents the event type.
+    pub fn code(&self) -> &'static str {
+        match self {
+            NamespaceEvent::Apply(_) => "NAMESPACE_APPLY",
+            NamespaceEvent::Delete(_) => "NAMESPACE_DELETE",
+        }
+    }
+
+    /// Returns the "ordering ID" for correctly streaming the event.
+    pub fn stream_key(&self) -> &str {
+        match self {
+            NamespaceEvent::Apply(ns) => &ns.ns_id,
+            NamespaceEvent::Delete(id) => &id.ns_id,
+        }
+    }
+}
+
+/// Build `NamespaceEvent`s, validating inputs.
+pub struct NamespaceEventBuilder {
+    pub(super) builder: EventBuilder,
+}
+
+impl NamespaceEventBuilder {
+    /// Build a `NamespaceEvent::Apply` event.
+    pub fn apply(self, ns: Namespace) -> Event {
+        let event = NamespaceEvent::Apply(ns);
+        let payload = Payload::Namespace(event);
+        self.builder.finish(payload)
+    }
+
+    /// Build a `NamespaceEvent::Delete` event.
+    pub fn delete(self, ns_id: String) -> Event {
+        let event = NamespaceEvent::Delete(NamespaceDeleted { ns_id });
+        let payload = Payload::Namespace(event);
+        self.builder.finish(payload)
+    }
+}
+
+/// Identification attributes of the Namespace object that was deleted.
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
+pub struct NamespaceDeleted {
+    pub ns_id: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Event;
+    use super::NamespaceDeleted;
+    use super::NamespaceEvent;
+    use super::Payload;
+    use crate::scope::Namespace;
+
+    #[test]
+    fn apply() {
+        let ns: Namespace = serde_json::from_str(r#"{"ns_id":"test"}"#).unwrap();
+        let event = Event::builder().namespace().apply(ns.clone());
+        let expected = Payload::Namespace(NamespaceEvent::Apply(ns));
+        assert_eq!(event.payload, expected);
+        assert_eq!(event.code(), "NAMESPACE_APPLY");
+        assert_eq!(event.stream_key(), "test");
+    }
+
+    #[test]
+    fn delete() {
+        let event = Event::builder().namespace().delete("test".into());
+        let id = NamespaceDeleted {
+            ns_id: "test".into(),
+        };
+        let expected = Payload::Namespace(NamespaceEvent::Delete(id));
+        assert_eq!(event.payload, expected);
+        assert_eq!(event.code(), "NAMESPACE_DELETE");
+        assert_eq!(event.stream_key(), "test");
+    }
+}
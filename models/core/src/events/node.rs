@@ -33,6 +33,10 @@ pub enum NodeEvent {
     #[serde(rename = "NODE_NEW")]
     New(Node),
 
+    /// A datastore node is no longer part of the cluster.
+    #[serde(rename = "NODE_REMOVED")]
+    Removed(Node),
+
     /// A datastore node was found to be up.
     #[serde(rename = "NODE_UP")]
     Up(StatusChange),
@@ -45,6 +49,7 @@ impl NodeEvent {
             NodeEvent::Changed(change) => &change.cluster_id,
             NodeEvent::Down(change) => &change.cluster_id,
             NodeEvent::New(node) => &node.cluster_id,
+            NodeEvent::Removed(node) => &node.cluster_id,
             NodeEvent::Up(change) => &change.cluster_id,
         };
         Some(cluster_id)
@@ -56,6 +61,7 @@ impl NodeEvent {
             NodeEvent::Changed(_) => "NODE_CHANGED",
             NodeEvent::Down(_) => "NODE_DOWN",
             NodeEvent::New(_) => "NODE_NEW",
+            NodeEvent::Removed(_) => "NODE_REMOVED",
             NodeEvent::Up(_) => "NODE_UP",
         }
     }
@@ -92,6 +98,13 @@ impl NodeEventBuilder {
         let payload = Payload::Node(event);
         self.builder.finish(payload)
     }
+
+    /// Build a `NodeEvent::Removed` event.
+    pub fn removed(self, node: Node) -> Event {
+        let event = NodeEvent::Removed(node);
+        let payload = Payload::Node(event);
+        self.builder.finish(payload)
+    }
 }
 
 #[cfg(test)]
@@ -143,4 +156,18 @@ mod tests {
         let expected = Payload::Node(NodeEvent::New(node));
         assert_eq!(event.payload, expected);
     }
+
+    #[test]
+    fn removed() {
+        let node = Node {
+            cluster_display_name: None,
+            cluster_id: "cluster".into(),
+            kind: "TestDB".into(),
+            node_id: "node".into(),
+            version: "1.2.3".into(),
+        };
+        let event = Event::builder().node().removed(node.clone());
+        let expected = Payload::Node(NodeEvent::Removed(node));
+        assert_eq!(event.payload, expected);
+    }
 }
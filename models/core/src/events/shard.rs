@@ -28,6 +28,16 @@ pub enum ShardEvent {
     /// A shard was found for the first time on a node.
     #[serde(rename = "SHARD_ALLOCATION_NEW")]
     AllocationNew(Shard),
+
+    /// A shard is no longer found on a node.
+    #[serde(rename = "SHARD_ALLOCATION_REMOVED")]
+    AllocationRemoved(Shard),
+
+    /// The commit offset or replication lag of a shard on a node changed.
+    ///
+    /// Only emitted when no other shard attribute changed.
+    #[serde(rename = "SHARD_LAG_SAMPLE")]
+    LagSample(Shard),
 }
 
 impl ShardEvent {
@@ -36,6 +46,8 @@ impl ShardEvent {
         let cluster_id = match self {
             ShardEvent::AllocationChanged(change) => &change.cluster_id,
             ShardEvent::AllocationNew(shard) => &shard.cluster_id,
+            ShardEvent::AllocationRemoved(shard) => &shard.cluster_id,
+            ShardEvent::LagSample(shard) => &shard.cluster_id,
         };
         Some(cluster_id)
     }
@@ -45,6 +57,8 @@ impl ShardEvent {
         match self {
             ShardEvent::AllocationChanged(_) => "SHARD_ALLOCATION_CHANGED",
             ShardEvent::AllocationNew(_) => "SHARD_ALLOCATION_NEW",
+            ShardEvent::AllocationRemoved(_) => "SHARD_ALLOCATION_REMOVED",
+            ShardEvent::LagSample(_) => "SHARD_LAG_SAMPLE",
         }
     }
 
@@ -76,6 +90,20 @@ impl ShardEventBuilder {
         self.builder.finish(payload)
     }
 
+    /// Build a `ShardEvent::AllocationRemoved` event.
+    pub fn allocation_removed(self, shard: Shard) -> Event {
+        let event = ShardEvent::AllocationRemoved(shard);
+        let payload = Payload::Shard(event);
+        self.builder.finish(payload)
+    }
+
+    /// Build a `ShardEvent::LagSample` event.
+    pub fn lag_sample(self, shard: Shard) -> Event {
+        let event = ShardEvent::LagSample(shard);
+        let payload = Payload::Shard(event);
+        self.builder.finish(payload)
+    }
+
     /// Build a `ShardEvent::AllocationNew` event.
     pub fn new_allocation(self, shard: Shard) -> Event {
         let event = ShardEvent::AllocationNew(shard);
@@ -138,4 +166,35 @@ mod tests {
         let expected = Payload::Shard(ShardEvent::AllocationNew(shard));
         assert_eq!(event.payload, expected);
     }
+
+    #[test]
+    fn allocation_removed() {
+        let shard = Shard {
+            cluster_id: "cluster".into(),
+            commit_offset: None,
+            lag: None,
+            node_id: "node".into(),
+            role: ShardRole::Primary,
+            shard_id: "shard".into(),
+        };
+        let event = Event::builder().shard().allocation_removed(shard.clone());
+        let expected = Payload::Shard(ShardEvent::AllocationRemoved(shard));
+        assert_eq!(event.payload, expected);
+    }
+
+    #[test]
+    fn lag_sample() {
+        let shard = Shard {
+            cluster_id: "cluster".into(),
+            commit_offset: None,
+            lag: None,
+            node_id: "node".into(),
+            role: ShardRole::Secondary,
+            shard_id: "shard".into(),
+        };
+        let event = Event::builder().shard().lag_sample(shard.clone());
+        let expected = Payload::Shard(ShardEvent::LagSample(shard));
+        assert_eq!(event.payload, expected);
+        assert_eq!(event.code(), "SHARD_LAG_SAMPLE");
+    }
 }
@@ -1,7 +1,9 @@
 pub mod actions;
 pub mod admin;
 pub mod agent;
+pub mod alerts;
 pub mod api;
 pub mod cluster;
 pub mod events;
 pub mod scope;
+pub mod webhooks;
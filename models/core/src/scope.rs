@@ -9,21 +9,90 @@ use serde_derive::Serialize;
 /// Resources includes all concepts in replicante outside users and organisations.
 /// This means all clusters, playbooks, roles, and more.
 // NOTE: this model jumps the gun a bit as organizations are not a thing yet.
-//       This is currently defined to avoid introducing some artifact to pass
-//       namespace settings around just to replace it soon after.
-//       The current solution is to use the config file and create a "default"
-//       namespace model for the code that wants to deal with model and slowly
-//       evolve that into a more complex entity at a later date.
+//       Namespaces are stored in the primary store and managed with `apply`.
+//       Namespaces that are not stored fall back to settings in the config file.
 #[derive(Clone, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
 pub struct Namespace {
     /// Unique ID of the namespace.
     pub ns_id: String,
 
+    /// Actions settings for clusters in the namespace.
+    #[serde(default)]
+    pub actions: NsActions,
+
+    /// Cluster health classification settings.
+    #[serde(default)]
+    pub health: NsHealth,
+
     /// HTTPS Agent Transport settings.
     #[serde(default)]
     pub https_transport: NsHttpsTransport,
 }
 
+impl Namespace {
+    /// Create a `Namespace` from an apply API object.
+    pub fn from_object(ns_id: String, object: crate::api::objects::Namespace) -> Namespace {
+        Namespace {
+            ns_id,
+            actions: object.actions,
+            health: object.health,
+            https_transport: object.https_transport,
+        }
+    }
+}
+
+/// Actions settings for a namespace.
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
+pub struct NsActions {
+    /// Number of times scheduling an action with an agent is attempted before it is failed.
+    #[serde(default = "NsActions::default_max_schedule_attempts")]
+    pub max_schedule_attempts: i32,
+
+    /// Default number of seconds actions have to finish before they are failed.
+    ///
+    /// Actions can override this default with a `timeout` in their apply metadata.
+    /// Actions without a timeout can stay unfinished indefinitely.
+    #[serde(default)]
+    pub timeout: Option<i64>,
+}
+
+impl NsActions {
+    fn default_max_schedule_attempts() -> i32 {
+        10
+    }
+}
+
+impl Default for NsActions {
+    fn default() -> NsActions {
+        NsActions {
+            max_schedule_attempts: NsActions::default_max_schedule_attempts(),
+            timeout: None,
+        }
+    }
+}
+
+/// Cluster health classification settings for a namespace.
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
+pub struct NsHealth {
+    /// Replication lag (in seconds) above which a cluster is considered degraded.
+    #[serde(default = "NsHealth::default_degraded_lag")]
+    pub degraded_lag: i64,
+}
+
+impl NsHealth {
+    fn default_degraded_lag() -> i64 {
+        60
+    }
+}
+
+impl Default for NsHealth {
+    fn default() -> NsHealth {
+        NsHealth {
+            degraded_lag: NsHealth::default_degraded_lag(),
+        }
+    }
+}
+
 /// HTTPS Agent Transport settings for a namespace.
 #[derive(Clone, Default, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
 pub struct NsHttpsTransport {
@@ -36,3 +105,33 @@ pub struct NsHttpsTransport {
     #[serde(default)]
     pub client_key_id: Option<String>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Namespace;
+
+    #[test]
+    fn from_json_defaults() {
+        let payload = r#"{"ns_id":"default"}"#;
+        let ns: Namespace = serde_json::from_str(payload).unwrap();
+        assert_eq!(ns.ns_id, "default");
+        assert_eq!(ns.actions.max_schedule_attempts, 10);
+        assert_eq!(ns.actions.timeout, None);
+        assert_eq!(ns.health.degraded_lag, 60);
+        assert_eq!(ns.https_transport.ca_bundle, None);
+    }
+
+    #[test]
+    fn from_object() {
+        let payload = r#"{
+            "actions": {"max_schedule_attempts": 3, "timeout": 600},
+            "health": {"degraded_lag": 300}
+        }"#;
+        let object = serde_json::from_str(payload).unwrap();
+        let ns = Namespace::from_object("test".into(), object);
+        assert_eq!(ns.ns_id, "test");
+        assert_eq!(ns.actions.max_schedule_attempts, 3);
+        assert_eq!(ns.actions.timeout, Some(600));
+        assert_eq!(ns.health.degraded_lag, 300);
+    }
+}
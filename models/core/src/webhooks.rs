@@ -0,0 +1,35 @@
+use chrono::DateTime;
+use chrono::Utc;
+use serde_derive::Deserialize;
+use serde_derive::Serialize;
+
+/// Record of an event that could not be delivered to a webhook endpoint.
+///
+/// Dead letters are stored once all delivery attempts failed so that
+/// operators can inspect them and replay the original request if needed.
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
+pub struct WebhookDeadLetter {
+    /// Number of delivery attempts made before giving up.
+    pub attempts: u32,
+
+    /// ID of the cluster the event is about, if any.
+    pub cluster_id: Option<String>,
+
+    /// Name of the webhook endpoint the event was sent to.
+    pub endpoint: String,
+
+    /// Description of the error returned by the last attempt.
+    pub error: String,
+
+    /// Code of the event that could not be delivered.
+    pub event_code: String,
+
+    /// Time the last delivery attempt failed.
+    pub failed_ts: DateTime<Utc>,
+
+    /// JSON encoded request body that could not be delivered.
+    pub payload: String,
+
+    /// URL of the webhook endpoint the event was sent to.
+    pub url: String,
+}
@@ -8,10 +8,29 @@ use replicante_models_core::cluster::discovery::ClusterDiscovery;
 pub struct ClusterRefreshPayload {
     pub cluster: ClusterDiscovery,
     pub snapshot: bool,
+
+    /// ID of the namespace the cluster belongs to, if known.
+    ///
+    /// Clusters without a namespace are refreshed with the default namespace settings.
+    #[serde(default)]
+    pub namespace: Option<String>,
 }
 
 impl ClusterRefreshPayload {
     pub fn new(cluster: ClusterDiscovery, snapshot: bool) -> ClusterRefreshPayload {
-        ClusterRefreshPayload { cluster, snapshot }
+        ClusterRefreshPayload {
+            cluster,
+            snapshot,
+            namespace: None,
+        }
+    }
+
+    /// Set the ID of the namespace the cluster belongs to.
+    pub fn with_namespace<S>(mut self, namespace: S) -> ClusterRefreshPayload
+    where
+        S: Into<String>,
+    {
+        self.namespace = Some(namespace.into());
+        self
     }
 }
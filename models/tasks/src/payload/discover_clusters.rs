@@ -1,16 +1,46 @@
 use serde_derive::Deserialize;
 use serde_derive::Serialize;
 
+use replicante_models_core::cluster::discovery::DiscoveryRetry;
 use replicante_models_core::cluster::discovery::DiscoverySettings;
 
 /// Clusters discovery task parameters.
 #[derive(Clone, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
 pub struct DiscoverClustersPayload {
+    /// Number of consecutive failed attempts to fetch the page at `cursor`.
+    #[serde(default)]
+    pub attempt: u32,
+
+    /// Resume a previously failed discovery from this backend cursor.
+    #[serde(default)]
+    pub cursor: Option<String>,
     pub settings: DiscoverySettings,
 }
 
 impl DiscoverClustersPayload {
     pub fn new(settings: DiscoverySettings) -> DiscoverClustersPayload {
-        DiscoverClustersPayload { settings }
+        DiscoverClustersPayload {
+            attempt: 0,
+            cursor: None,
+            settings,
+        }
+    }
+
+    /// Continue a partially completed discovery from the given cursor.
+    pub fn resume(settings: DiscoverySettings, cursor: String) -> DiscoverClustersPayload {
+        DiscoverClustersPayload {
+            attempt: 0,
+            cursor: Some(cursor),
+            settings,
+        }
+    }
+
+    /// Retry a failed discovery run that was scheduled for later.
+    pub fn retry(settings: DiscoverySettings, retry: DiscoveryRetry) -> DiscoverClustersPayload {
+        DiscoverClustersPayload {
+            attempt: retry.attempt,
+            cursor: retry.cursor,
+            settings,
+        }
     }
 }
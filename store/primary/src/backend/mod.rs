@@ -18,10 +18,17 @@ use replicante_models_core::agent::Agent;
 use replicante_models_core::agent::AgentInfo;
 use replicante_models_core::agent::Node;
 use replicante_models_core::agent::Shard;
+use replicante_models_core::alerts::Alert;
+use replicante_models_core::alerts::AlertRule;
 use replicante_models_core::cluster::discovery::ClusterDiscovery;
+use replicante_models_core::cluster::discovery::DiscoveryRetry;
+use replicante_models_core::cluster::discovery::DiscoveryRun;
 use replicante_models_core::cluster::discovery::DiscoverySettings;
 use replicante_models_core::cluster::ClusterMeta;
 use replicante_models_core::cluster::ClusterSettings;
+use replicante_models_core::cluster::OrchestrateReport;
+use replicante_models_core::scope::Namespace;
+use replicante_models_core::webhooks::WebhookDeadLetter;
 use replicante_service_healthcheck::HealthChecks;
 
 use crate::store::actions::ActionSyncState;
@@ -29,8 +36,10 @@ use crate::store::actions::ActionsAttributes;
 use crate::store::agent::AgentAttribures;
 use crate::store::agents::AgentsAttribures;
 use crate::store::agents::AgentsCounts;
+use crate::store::alert_rules::AlertRulesAttributes;
 use crate::store::cluster::ClusterAttribures;
 use crate::store::discovery_settings::DiscoverySettingsAttributes;
+use crate::store::namespace::NamespaceAttributes;
 use crate::store::node::NodeAttribures;
 use crate::store::nodes::NodesAttribures;
 use crate::store::shard::ShardAttribures;
@@ -169,10 +178,12 @@ arc_interface! {
         fn actions(&self) -> ActionsImpl;
         fn agent(&self) -> AgentImpl;
         fn agents(&self) -> AgentsImpl;
+        fn alert_rules(&self) -> AlertRulesImpl;
         fn cluster(&self) -> ClusterImpl;
         fn discovery_settings(&self) -> DiscoverySettingsImpl;
         fn global_search(&self) -> GlobalSearchImpl;
         fn legacy(&self) -> LegacyImpl;
+        fn namespace(&self) -> NamespaceImpl;
         fn node(&self) -> NodeImpl;
         fn nodes(&self) -> NodesImpl;
         fn persist(&self) -> PersistImpl;
@@ -191,12 +202,24 @@ box_interface! {
     trait ActionsInterface,
 
     interface {
+        fn action(
+            &self,
+            attrs: &ActionsAttributes,
+            action_id: Uuid,
+            span: Option<SpanContext>,
+        ) -> Result<Option<Action>>;
         fn approve(
             &self,
             attrs: &ActionsAttributes,
             action_id: Uuid,
             span: Option<SpanContext>,
         ) -> Result<()>;
+        fn cancel(
+            &self,
+            attrs: &ActionsAttributes,
+            action_id: Uuid,
+            span: Option<SpanContext>,
+        ) -> Result<bool>;
         fn disapprove(
             &self,
             attrs: &ActionsAttributes,
@@ -245,6 +268,7 @@ box_interface! {
     trait AgentInterface,
 
     interface {
+        fn delete(&self, attrs: &AgentAttribures, span: Option<SpanContext>) -> Result<()>;
         fn get(&self, attrs: &AgentAttribures, span: Option<SpanContext>) -> Result<Option<Agent>>;
         fn info(&self, attrs: &AgentAttribures, span: Option<SpanContext>)
             -> Result<Option<AgentInfo>>;
@@ -279,6 +303,39 @@ box_interface! {
     }
 }
 
+box_interface! {
+    /// Dynamic dispatch alert rules operations to a backend-specific implementation.
+    struct AlertRulesImpl,
+
+    /// Definition of supported alert rules operations.
+    ///
+    /// See `store::alert_rules::AlertRules` for descriptions of methods.
+    trait AlertRulesInterface,
+
+    interface {
+        fn alert(
+            &self,
+            attrs: &AlertRulesAttributes,
+            name: &str,
+            cluster_id: &str,
+            span: Option<SpanContext>,
+        ) -> Result<Option<Alert>>;
+        fn get(
+            &self,
+            attrs: &AlertRulesAttributes,
+            name: &str,
+            span: Option<SpanContext>,
+        ) -> Result<Option<AlertRule>>;
+        fn update_unchanged(
+            &self,
+            attrs: &AlertRulesAttributes,
+            alert: Alert,
+            updated_ts: DateTime<Utc>,
+            span: Option<SpanContext>,
+        ) -> Result<bool>;
+    }
+}
+
 box_interface! {
     /// Dynamic dispatch all cluster operations to a backend-specific implementation.
     struct ClusterImpl,
@@ -289,12 +346,33 @@ box_interface! {
     trait ClusterInterface,
 
     interface {
+        fn delete_discovery(
+            &self,
+            attrs: &ClusterAttribures,
+            span: Option<SpanContext>,
+        ) -> Result<()>;
+        fn delete_settings(
+            &self,
+            attrs: &ClusterAttribures,
+            span: Option<SpanContext>,
+        ) -> Result<()>;
+        fn delete_state(&self, attrs: &ClusterAttribures, span: Option<SpanContext>) -> Result<()>;
         fn discovery(
             &self,
             attrs: &ClusterAttribures,
             span: Option<SpanContext>,
         ) -> Result<Option<ClusterDiscovery>>;
         fn mark_stale(&self, attrs: &ClusterAttribures, span: Option<SpanContext>) -> Result<()>;
+        fn namespace(
+            &self,
+            attrs: &ClusterAttribures,
+            span: Option<SpanContext>,
+        ) -> Result<Option<String>>;
+        fn orchestrate_report(
+            &self,
+            attrs: &ClusterAttribures,
+            span: Option<SpanContext>,
+        ) -> Result<Option<OrchestrateReport>>;
         fn settings(
             &self,
             attrs: &ClusterAttribures,
@@ -339,11 +417,37 @@ box_interface! {
             name: &str,
             span: Option<SpanContext>,
         ) -> Result<()>;
+        fn get(
+            &self,
+            attrs: &DiscoverySettingsAttributes,
+            name: &str,
+            span: Option<SpanContext>,
+        ) -> Result<Option<DiscoverySettings>>;
+        fn history(
+            &self,
+            attrs: &DiscoverySettingsAttributes,
+            name: &str,
+            span: Option<SpanContext>,
+        ) -> Result<Cursor<DiscoverySettings>>;
+        fn iter_missing_clusters(
+            &self,
+            attrs: &DiscoverySettingsAttributes,
+            name: &str,
+            seen_before: DateTime<Utc>,
+            span: Option<SpanContext>,
+        ) -> Result<Cursor<ClusterDiscovery>>;
         fn iter_names(
             &self,
             attrs: &DiscoverySettingsAttributes,
             span: Option<SpanContext>,
         ) -> Result<Cursor<String>>;
+        fn mark_clusters_seen(
+            &self,
+            attrs: &DiscoverySettingsAttributes,
+            name: &str,
+            cluster_ids: &[String],
+            span: Option<SpanContext>,
+        ) -> Result<()>;
     }
 }
 
@@ -357,7 +461,14 @@ box_interface! {
     trait GlobalSearchInterface,
 
     interface {
-        fn discoveries_to_run(&self, span: Option<SpanContext>) -> Result<Cursor<DiscoverySettings>>;
+        fn active_alerts(&self, span: Option<SpanContext>) -> Result<Cursor<Alert>>;
+        fn alert_rules(&self, span: Option<SpanContext>) -> Result<Cursor<AlertRule>>;
+        fn clusters_to_orchestrate(
+            &self,
+            span: Option<SpanContext>,
+        ) -> Result<Cursor<ClusterSettings>>;
+        fn discoveries_to_run(&self, span: Option<SpanContext>) -> Result<Cursor<DiscoveryRun>>;
+        fn namespaces(&self, span: Option<SpanContext>) -> Result<Cursor<Namespace>>;
     }
 }
 
@@ -387,6 +498,30 @@ box_interface! {
     }
 }
 
+box_interface! {
+    /// Dynamic dispatch namespace operations to a backend-specific implementation.
+    struct NamespaceImpl,
+
+    /// Definition of supported operations on namespaces.
+    ///
+    /// See `store::namespace::Namespace` for descriptions of methods.
+    trait NamespaceInterface,
+
+    interface {
+        fn delete(&self, attrs: &NamespaceAttributes, span: Option<SpanContext>) -> Result<()>;
+        fn get(
+            &self,
+            attrs: &NamespaceAttributes,
+            span: Option<SpanContext>,
+        ) -> Result<Option<Namespace>>;
+        fn iter_clusters(
+            &self,
+            attrs: &NamespaceAttributes,
+            span: Option<SpanContext>,
+        ) -> Result<Cursor<String>>;
+    }
+}
+
 box_interface! {
     /// Dynamic dispatch node operations to a backend-specific implementation.
     struct NodeImpl,
@@ -397,6 +532,7 @@ box_interface! {
     trait NodeInterface,
 
     interface {
+        fn delete(&self, attrs: &NodeAttribures, span: Option<SpanContext>) -> Result<()>;
         fn get(&self, attrs: &NodeAttribures, span: Option<SpanContext>) -> Result<Option<Node>>;
     }
 }
@@ -412,6 +548,11 @@ box_interface! {
 
     interface {
         fn iter(&self, attrs: &NodesAttribures, span: Option<SpanContext>) -> Result<Cursor<Node>>;
+        fn iter_stale(
+            &self,
+            attrs: &NodesAttribures,
+            span: Option<SpanContext>,
+        ) -> Result<Cursor<Node>>;
         fn kinds(
             &self,
             attrs: &NodesAttribures,
@@ -433,21 +574,41 @@ box_interface! {
         fn action(&self, action: Action, span: Option<SpanContext>) -> Result<()>;
         fn agent(&self, agent: Agent, span: Option<SpanContext>) -> Result<()>;
         fn agent_info(&self, agent: AgentInfo, span: Option<SpanContext>) -> Result<()>;
+        fn alert(&self, alert: Alert, span: Option<SpanContext>) -> Result<()>;
+        fn alert_rule(&self, rule: AlertRule, span: Option<SpanContext>) -> Result<()>;
         fn cluster_discovery(
             &self,
             discovery: ClusterDiscovery,
             span: Option<SpanContext>,
         ) -> Result<()>;
+        fn cluster_orchestrate_report(
+            &self,
+            report: OrchestrateReport,
+            span: Option<SpanContext>,
+        ) -> Result<()>;
         fn cluster_settings(
             &self,
             settings: ClusterSettings,
             span: Option<SpanContext>,
         ) -> Result<()>;
+        fn discovery_retry(
+            &self,
+            settings: DiscoverySettings,
+            retry: DiscoveryRetry,
+            retry_at: DateTime<Utc>,
+            span: Option<SpanContext>,
+        ) -> Result<()>;
         fn discovery_settings(
             &self,
             settings: DiscoverySettings,
             span: Option<SpanContext>,
         ) -> Result<()>;
+        fn namespace(&self, namespace: Namespace, span: Option<SpanContext>) -> Result<()>;
+        fn next_cluster_orchestrate(
+            &self,
+            settings: ClusterSettings,
+            span: Option<SpanContext>,
+        ) -> Result<()>;
         fn next_discovery_run(
             &self,
             settings: DiscoverySettings,
@@ -455,6 +616,11 @@ box_interface! {
         ) -> Result<()>;
         fn node(&self, node: Node, span: Option<SpanContext>) -> Result<()>;
         fn shard(&self, shard: Shard, span: Option<SpanContext>) -> Result<()>;
+        fn webhook_dead_letter(
+            &self,
+            letter: WebhookDeadLetter,
+            span: Option<SpanContext>,
+        ) -> Result<()>;
     }
 }
 
@@ -468,6 +634,7 @@ box_interface! {
     trait ShardInterface,
 
     interface {
+        fn delete(&self, attrs: &ShardAttribures, span: Option<SpanContext>) -> Result<()>;
         fn get(&self, attrs: &ShardAttribures, span: Option<SpanContext>) -> Result<Option<Shard>>;
     }
 }
@@ -492,6 +659,11 @@ box_interface! {
             attrs: &ShardsAttribures,
             span: Option<SpanContext>,
         ) -> Result<Cursor<Shard>>;
+        fn iter_stale(
+            &self,
+            attrs: &ShardsAttribures,
+            span: Option<SpanContext>,
+        ) -> Result<Cursor<Shard>>;
     }
 }
 
@@ -15,6 +15,7 @@ use opentracingrust::Tracer;
 use uuid::Uuid;
 
 use replicante_externals_mongodb::operations::find;
+use replicante_externals_mongodb::operations::find_one;
 use replicante_externals_mongodb::operations::update_many;
 use replicante_externals_mongodb::operations::update_one;
 use replicante_models_core::actions::Action;
@@ -68,6 +69,26 @@ impl Actions {
 }
 
 impl ActionsInterface for Actions {
+    fn action(
+        &self,
+        attrs: &ActionsAttributes,
+        action_id: Uuid,
+        span: Option<SpanContext>,
+    ) -> Result<Option<Action>> {
+        let filter = doc! {
+            "cluster_id": &attrs.cluster_id,
+            "action_id": action_id.to_string(),
+        };
+        let collection = self
+            .client
+            .database(&self.db)
+            .collection(COLLECTION_ACTIONS);
+        let action: Option<ActionDocument> =
+            find_one(collection, filter, span, self.tracer.as_deref())
+                .with_context(|_| ErrorKind::MongoDBOperation)?;
+        Ok(action.map(Action::from))
+    }
+
     fn approve(
         &self,
         attrs: &ActionsAttributes,
@@ -91,6 +112,36 @@ impl ActionsInterface for Actions {
         Ok(())
     }
 
+    fn cancel(
+        &self,
+        attrs: &ActionsAttributes,
+        action_id: Uuid,
+        span: Option<SpanContext>,
+    ) -> Result<bool> {
+        let filter = doc! {
+            "cluster_id": &attrs.cluster_id,
+            "action_id": action_id.to_string(),
+            "state": { "$in": [
+                "PENDING_APPROVE",
+                "PENDING_SCHEDULE",
+            ] },
+        };
+        let finished_ts = UtcDateTime::from(Utc::now());
+        let update = doc! {
+            "$set": {
+                "finished_ts": bson::to_bson(&finished_ts).unwrap(),
+                "state": bson::to_bson(&ActionState::Cancelled).unwrap(),
+            }
+        };
+        let collection = self
+            .client
+            .database(&self.db)
+            .collection(COLLECTION_ACTIONS);
+        let result = update_one(collection, filter, update, span, self.tracer.as_deref())
+            .with_context(|_| ErrorKind::MongoDBOperation)?;
+        Ok(result.matched_count == 1)
+    }
+
     fn disapprove(
         &self,
         attrs: &ActionsAttributes,
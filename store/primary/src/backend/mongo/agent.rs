@@ -6,6 +6,7 @@ use mongodb::sync::Client;
 use opentracingrust::SpanContext;
 use opentracingrust::Tracer;
 
+use replicante_externals_mongodb::operations::delete_one;
 use replicante_externals_mongodb::operations::find_one;
 use replicante_models_core::agent::Agent as AgentModel;
 use replicante_models_core::agent::AgentInfo as AgentInfoModel;
@@ -36,6 +37,25 @@ impl Agent {
 }
 
 impl AgentInterface for Agent {
+    fn delete(&self, attrs: &AgentAttribures, span: Option<SpanContext>) -> Result<()> {
+        let filter = doc! {
+            "cluster_id": &attrs.cluster_id,
+            "host": &attrs.host,
+        };
+        let database = self.client.database(&self.db);
+        for name in &[COLLECTION_AGENTS, COLLECTION_AGENTS_INFO] {
+            let collection = database.collection(name);
+            delete_one(
+                collection,
+                filter.clone(),
+                span.clone(),
+                self.tracer.as_deref(),
+            )
+            .with_context(|_| ErrorKind::MongoDBOperation)?;
+        }
+        Ok(())
+    }
+
     fn get(
         &self,
         attrs: &AgentAttribures,
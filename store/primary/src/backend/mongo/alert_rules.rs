@@ -0,0 +1,106 @@
+use std::sync::Arc;
+
+use bson::doc;
+use bson::Bson;
+use chrono::DateTime;
+use chrono::Utc;
+use failure::ResultExt;
+use mongodb::sync::Client;
+use opentracingrust::SpanContext;
+use opentracingrust::Tracer;
+
+use replicante_externals_mongodb::operations::find_one;
+use replicante_externals_mongodb::operations::update_one;
+use replicante_models_core::alerts::Alert;
+use replicante_models_core::alerts::AlertRule;
+
+use super::super::AlertRulesInterface;
+use super::constants::COLLECTION_ALERTS;
+use super::constants::COLLECTION_ALERT_RULES;
+use super::document::AlertDocument;
+use crate::store::alert_rules::AlertRulesAttributes;
+use crate::ErrorKind;
+use crate::Result;
+
+/// Alert rules operations implementation using MongoDB.
+pub struct AlertRules {
+    client: Client,
+    db: String,
+    tracer: Option<Arc<Tracer>>,
+}
+
+impl AlertRules {
+    pub fn new<T>(client: Client, db: String, tracer: T) -> AlertRules
+    where
+        T: Into<Option<Arc<Tracer>>>,
+    {
+        let tracer = tracer.into();
+        AlertRules { client, db, tracer }
+    }
+}
+
+impl AlertRulesInterface for AlertRules {
+    fn alert(
+        &self,
+        attrs: &AlertRulesAttributes,
+        name: &str,
+        cluster_id: &str,
+        span: Option<SpanContext>,
+    ) -> Result<Option<Alert>> {
+        let filter = doc! {
+            "namespace": &attrs.namespace,
+            "rule": name,
+            "cluster_id": cluster_id,
+        };
+        let collection = self.client.database(&self.db).collection(COLLECTION_ALERTS);
+        let document: Option<AlertDocument> =
+            find_one(collection, filter, span, self.tracer.as_deref())
+                .with_context(|_| ErrorKind::MongoDBOperation)?;
+        Ok(document.map(Alert::from))
+    }
+
+    fn get(
+        &self,
+        attrs: &AlertRulesAttributes,
+        name: &str,
+        span: Option<SpanContext>,
+    ) -> Result<Option<AlertRule>> {
+        let filter = doc! {
+            "namespace": &attrs.namespace,
+            "name": name,
+        };
+        let collection = self
+            .client
+            .database(&self.db)
+            .collection(COLLECTION_ALERT_RULES);
+        let rule = find_one(collection, filter, span, self.tracer.as_deref())
+            .with_context(|_| ErrorKind::MongoDBOperation)?;
+        Ok(rule)
+    }
+
+    fn update_unchanged(
+        &self,
+        attrs: &AlertRulesAttributes,
+        alert: Alert,
+        updated_ts: DateTime<Utc>,
+        span: Option<SpanContext>,
+    ) -> Result<bool> {
+        let filter = doc! {
+            "namespace": &attrs.namespace,
+            "rule": &alert.rule,
+            "cluster_id": &alert.cluster_id,
+            "updated_ts": updated_ts,
+        };
+        let alert = AlertDocument::from(alert);
+        let document = bson::to_bson(&alert).with_context(|_| ErrorKind::MongoDBBsonEncode)?;
+        let document = match document {
+            Bson::Document(document) => document,
+            _ => panic!("Alert failed to encode as BSON document"),
+        };
+        let update = doc! {"$set": document};
+        let collection = self.client.database(&self.db).collection(COLLECTION_ALERTS);
+        let result = update_one(collection, filter, update, span, self.tracer.as_deref())
+            .with_context(|_| ErrorKind::MongoDBOperation)?;
+        Ok(result.matched_count == 1)
+    }
+}
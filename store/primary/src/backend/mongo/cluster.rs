@@ -8,15 +8,20 @@ use opentracingrust::Tracer;
 use slog::debug;
 use slog::Logger;
 
+use replicante_externals_mongodb::operations::delete_many;
+use replicante_externals_mongodb::operations::delete_one;
 use replicante_externals_mongodb::operations::find_one;
 use replicante_externals_mongodb::operations::update_many;
 use replicante_models_core::cluster::discovery::ClusterDiscovery;
 use replicante_models_core::cluster::ClusterSettings;
+use replicante_models_core::cluster::OrchestrateReport;
 
 use super::super::ClusterInterface;
 use super::constants::COLLECTION_CLUSTER_SETTINGS;
 use super::constants::COLLECTION_DISCOVERIES;
 use super::constants::STALE_COLLECTIONS;
+use super::constants::STATE_COLLECTIONS;
+use super::document::ClusterSettingsDocument;
 use crate::store::cluster::ClusterAttribures;
 use crate::ErrorKind;
 use crate::Result;
@@ -45,6 +50,41 @@ impl Cluster {
 }
 
 impl ClusterInterface for Cluster {
+    fn delete_discovery(&self, attrs: &ClusterAttribures, span: Option<SpanContext>) -> Result<()> {
+        let filter = doc! {"cluster_id": &attrs.cluster_id};
+        let collection = self
+            .client
+            .database(&self.db)
+            .collection(COLLECTION_DISCOVERIES);
+        delete_one(collection, filter, span, self.tracer.as_deref())
+            .with_context(|_| ErrorKind::MongoDBOperation)?;
+        Ok(())
+    }
+
+    fn delete_settings(&self, attrs: &ClusterAttribures, span: Option<SpanContext>) -> Result<()> {
+        let filter = doc! {
+            "namespace": &attrs.namespace,
+            "cluster_id": &attrs.cluster_id,
+        };
+        let collection = self
+            .client
+            .database(&self.db)
+            .collection(COLLECTION_CLUSTER_SETTINGS);
+        delete_one(collection, filter, span, self.tracer.as_deref())
+            .with_context(|_| ErrorKind::MongoDBOperation)?;
+        Ok(())
+    }
+
+    fn delete_state(&self, attrs: &ClusterAttribures, span: Option<SpanContext>) -> Result<()> {
+        for name in STATE_COLLECTIONS.iter() {
+            let collection = self.client.database(&self.db).collection(name);
+            let filter = doc! {"cluster_id": &attrs.cluster_id};
+            delete_many(collection, filter, span.clone(), self.tracer.as_deref())
+                .with_context(|_| ErrorKind::MongoDBOperation)?;
+        }
+        Ok(())
+    }
+
     fn discovery(
         &self,
         attrs: &ClusterAttribures,
@@ -84,6 +124,45 @@ impl ClusterInterface for Cluster {
         Ok(())
     }
 
+    fn namespace(
+        &self,
+        attrs: &ClusterAttribures,
+        span: Option<SpanContext>,
+    ) -> Result<Option<String>> {
+        let filter = doc! {"cluster_id": &attrs.cluster_id};
+        let collection = self
+            .client
+            .database(&self.db)
+            .collection(COLLECTION_CLUSTER_SETTINGS);
+        let settings: Option<ClusterSettingsDocument> =
+            find_one(collection, filter, span, self.tracer.as_deref())
+                .with_context(|_| ErrorKind::MongoDBOperation)?;
+        let namespace = settings.map(|settings| settings.settings.namespace);
+        Ok(namespace)
+    }
+
+    fn orchestrate_report(
+        &self,
+        attrs: &ClusterAttribures,
+        span: Option<SpanContext>,
+    ) -> Result<Option<OrchestrateReport>> {
+        let filter = doc! {
+            "namespace": &attrs.namespace,
+            "cluster_id": &attrs.cluster_id,
+        };
+        let collection = self
+            .client
+            .database(&self.db)
+            .collection(COLLECTION_CLUSTER_SETTINGS);
+        let settings: Option<ClusterSettingsDocument> =
+            find_one(collection, filter, span, self.tracer.as_deref())
+                .with_context(|_| ErrorKind::MongoDBOperation)?;
+        let report = settings
+            .and_then(|settings| settings.orchestrate_report)
+            .map(OrchestrateReport::from);
+        Ok(report)
+    }
+
     fn settings(
         &self,
         attrs: &ClusterAttribures,
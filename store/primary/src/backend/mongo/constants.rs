@@ -5,12 +5,17 @@ use lazy_static::lazy_static;
 pub const COLLECTION_ACTIONS: &str = "actions";
 pub const COLLECTION_AGENTS: &str = "agents";
 pub const COLLECTION_AGENTS_INFO: &str = "agents_info";
+pub const COLLECTION_ALERTS: &str = "alerts";
+pub const COLLECTION_ALERT_RULES: &str = "alert_rules";
 pub const COLLECTION_CLUSTER_META: &str = "clusters_meta";
 pub const COLLECTION_CLUSTER_SETTINGS: &str = "cluster_settings";
 pub const COLLECTION_DISCOVERIES: &str = "discoveries";
 pub const COLLECTION_DISCOVERY_SETTINGS: &str = "discovery_settings";
+pub const COLLECTION_DISCOVERY_SETTINGS_HISTORY: &str = "discovery_settings_history";
+pub const COLLECTION_NAMESPACES: &str = "namespaces";
 pub const COLLECTION_NODES: &str = "nodes";
 pub const COLLECTION_SHARDS: &str = "shards";
+pub const COLLECTION_WEBHOOKS_DEAD_LETTERS: &str = "webhooks_dead_letters";
 
 pub const TOP_CLUSTERS_LIMIT: u32 = 10;
 
@@ -20,6 +25,15 @@ lazy_static! {
         set.insert("events");
         set
     };
+    pub static ref STATE_COLLECTIONS: HashSet<&'static str> = {
+        let mut set = HashSet::new();
+        set.insert(COLLECTION_AGENTS);
+        set.insert(COLLECTION_AGENTS_INFO);
+        set.insert(COLLECTION_CLUSTER_META);
+        set.insert(COLLECTION_NODES);
+        set.insert(COLLECTION_SHARDS);
+        set
+    };
     pub static ref STALE_COLLECTIONS: HashSet<&'static str> = {
         let mut set = HashSet::new();
         set.insert(COLLECTION_AGENTS_INFO);
@@ -32,12 +46,17 @@ lazy_static! {
         set.insert(COLLECTION_ACTIONS);
         set.insert(COLLECTION_AGENTS);
         set.insert(COLLECTION_AGENTS_INFO);
+        set.insert(COLLECTION_ALERTS);
+        set.insert(COLLECTION_ALERT_RULES);
         set.insert(COLLECTION_CLUSTER_META);
         set.insert(COLLECTION_CLUSTER_SETTINGS);
         set.insert(COLLECTION_DISCOVERIES);
         set.insert(COLLECTION_DISCOVERY_SETTINGS);
+        set.insert(COLLECTION_DISCOVERY_SETTINGS_HISTORY);
+        set.insert(COLLECTION_NAMESPACES);
         set.insert(COLLECTION_NODES);
         set.insert(COLLECTION_SHARDS);
+        set.insert(COLLECTION_WEBHOOKS_DEAD_LETTERS);
         set
     };
 }
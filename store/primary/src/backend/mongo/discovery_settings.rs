@@ -1,17 +1,27 @@
 use std::sync::Arc;
 
 use bson::doc;
+use chrono::DateTime;
+use chrono::Utc;
 use failure::ResultExt;
 use mongodb::options::FindOptions;
 use mongodb::sync::Client;
 use opentracingrust::SpanContext;
 use opentracingrust::Tracer;
 
+use replicante_externals_mongodb::operations::delete_many;
 use replicante_externals_mongodb::operations::delete_one;
+use replicante_externals_mongodb::operations::find;
+use replicante_externals_mongodb::operations::find_one;
 use replicante_externals_mongodb::operations::find_with_options;
+use replicante_externals_mongodb::operations::update_many;
+use replicante_models_core::cluster::discovery::ClusterDiscovery;
+use replicante_models_core::cluster::discovery::DiscoverySettings as DiscoverySettingsModel;
 
 use super::super::DiscoverySettingsInterface;
+use super::constants::COLLECTION_DISCOVERIES;
 use super::constants::COLLECTION_DISCOVERY_SETTINGS;
+use super::constants::COLLECTION_DISCOVERY_SETTINGS_HISTORY;
 use super::document::DiscoverySettingsDocument;
 use crate::store::discovery_settings::DiscoverySettingsAttributes;
 use crate::Cursor;
@@ -50,11 +60,86 @@ impl DiscoverySettingsInterface for DiscoverySettings {
             .client
             .database(&self.db)
             .collection(COLLECTION_DISCOVERY_SETTINGS);
-        delete_one(collection, filter, span, self.tracer.as_deref())
+        delete_one(
+            collection,
+            filter.clone(),
+            span.clone(),
+            self.tracer.as_deref(),
+        )
+        .with_context(|_| ErrorKind::MongoDBOperation)?;
+        let collection = self
+            .client
+            .database(&self.db)
+            .collection(COLLECTION_DISCOVERY_SETTINGS_HISTORY);
+        delete_many(collection, filter, span, self.tracer.as_deref())
             .with_context(|_| ErrorKind::MongoDBOperation)?;
         Ok(())
     }
 
+    fn get(
+        &self,
+        attrs: &DiscoverySettingsAttributes,
+        name: &str,
+        span: Option<SpanContext>,
+    ) -> Result<Option<DiscoverySettingsModel>> {
+        let filter = doc! {
+            "namespace": &attrs.namespace,
+            "name": name,
+        };
+        let collection = self
+            .client
+            .database(&self.db)
+            .collection(COLLECTION_DISCOVERY_SETTINGS);
+        let document: Option<DiscoverySettingsDocument> =
+            find_one(collection, filter, span, self.tracer.as_deref())
+                .with_context(|_| ErrorKind::MongoDBOperation)?;
+        Ok(document.map(DiscoverySettingsModel::from))
+    }
+
+    fn history(
+        &self,
+        attrs: &DiscoverySettingsAttributes,
+        name: &str,
+        span: Option<SpanContext>,
+    ) -> Result<Cursor<DiscoverySettingsModel>> {
+        let filter = doc! {
+            "namespace": &attrs.namespace,
+            "name": name,
+        };
+        let mut options = FindOptions::default();
+        options.sort = Some(doc! {"revision": -1});
+        let collection = self
+            .client
+            .database(&self.db)
+            .collection(COLLECTION_DISCOVERY_SETTINGS_HISTORY);
+        let cursor = find_with_options(collection, filter, options, span, self.tracer.as_deref())
+            .with_context(|_| ErrorKind::MongoDBOperation)?
+            .map(|item| item.map_err(|error| error.context(ErrorKind::MongoDBCursor).into()));
+        Ok(Cursor::new(cursor))
+    }
+
+    fn iter_missing_clusters(
+        &self,
+        attrs: &DiscoverySettingsAttributes,
+        name: &str,
+        seen_before: DateTime<Utc>,
+        span: Option<SpanContext>,
+    ) -> Result<Cursor<ClusterDiscovery>> {
+        let filter = doc! {
+            "discovery_namespace": &attrs.namespace,
+            "discovery_name": name,
+            "last_seen": {"$lt": seen_before},
+        };
+        let collection = self
+            .client
+            .database(&self.db)
+            .collection(COLLECTION_DISCOVERIES);
+        let cursor = find(collection, filter, span, self.tracer.as_deref())
+            .with_context(|_| ErrorKind::MongoDBOperation)?
+            .map(|item| item.map_err(|error| error.context(ErrorKind::MongoDBCursor).into()));
+        Ok(Cursor::new(cursor))
+    }
+
     fn iter_names(
         &self,
         attrs: &DiscoverySettingsAttributes,
@@ -76,4 +161,26 @@ impl DiscoverySettingsInterface for DiscoverySettings {
         });
         Ok(Cursor::new(cursor))
     }
+
+    fn mark_clusters_seen(
+        &self,
+        attrs: &DiscoverySettingsAttributes,
+        name: &str,
+        cluster_ids: &[String],
+        span: Option<SpanContext>,
+    ) -> Result<()> {
+        let filter = doc! {"cluster_id": {"$in": cluster_ids.to_vec()}};
+        let update = doc! {"$set": {
+            "discovery_namespace": &attrs.namespace,
+            "discovery_name": name,
+            "last_seen": Utc::now(),
+        }};
+        let collection = self
+            .client
+            .database(&self.db)
+            .collection(COLLECTION_DISCOVERIES);
+        update_many(collection, filter, update, span, self.tracer.as_deref())
+            .with_context(|_| ErrorKind::MongoDBOperation)?;
+        Ok(())
+    }
 }
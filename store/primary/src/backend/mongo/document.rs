@@ -10,8 +10,15 @@ use replicante_models_core::actions::ActionState;
 use replicante_models_core::agent::AgentInfo;
 use replicante_models_core::agent::Node;
 use replicante_models_core::agent::Shard;
+use replicante_models_core::alerts::Alert;
+use replicante_models_core::alerts::AlertState;
+use replicante_models_core::cluster::discovery::DiscoveryRetry;
+use replicante_models_core::cluster::discovery::DiscoveryRun;
 use replicante_models_core::cluster::discovery::DiscoverySettings;
 use replicante_models_core::cluster::ClusterSettings;
+use replicante_models_core::cluster::OrchestrateOutcome;
+use replicante_models_core::cluster::OrchestrateReport;
+use replicante_models_core::webhooks::WebhookDeadLetter;
 
 /// Wrap an `Action` with store only fields and MongoDB specific types.
 #[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
@@ -23,6 +30,8 @@ pub struct ActionDocument {
 
     // Record attributes.
     pub created_ts: DateTime,
+    #[serde(default)]
+    pub deadline_ts: Option<DateTime>,
     pub finished_ts: Option<DateTime>,
     pub headers: HashMap<String, String>,
     pub kind: String,
@@ -50,6 +59,7 @@ impl From<Action> for ActionDocument {
             args,
             cluster_id: action.cluster_id,
             created_ts: DateTime::from(action.created_ts),
+            deadline_ts: action.deadline_ts.map(DateTime::from),
             finished_ts: action.finished_ts.map(DateTime::from),
             headers: action.headers,
             kind: action.kind,
@@ -80,6 +90,7 @@ impl From<ActionDocument> for Action {
             args,
             cluster_id: action.cluster_id,
             created_ts: action.created_ts.0,
+            deadline_ts: action.deadline_ts.map(|ts| ts.0),
             finished_ts: action.finished_ts.map(|ts| ts.0),
             headers: action.headers,
             kind: action.kind,
@@ -119,14 +130,66 @@ impl From<AgentInfoDocument> for AgentInfo {
     }
 }
 
+/// Wraps an `Alert` with MongoDB specific types.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct AlertDocument {
+    pub cluster_id: String,
+    #[serde(default)]
+    pub event_pending: bool,
+    pub fired_ts: Option<DateTime>,
+    #[serde(default)]
+    pub matches: Vec<DateTime>,
+    pub namespace: String,
+    pub resolved_ts: Option<DateTime>,
+    pub rule: String,
+    pub state: AlertState,
+    pub updated_ts: DateTime,
+}
+
+impl From<Alert> for AlertDocument {
+    fn from(alert: Alert) -> AlertDocument {
+        AlertDocument {
+            cluster_id: alert.cluster_id,
+            event_pending: alert.event_pending,
+            fired_ts: alert.fired_ts.map(DateTime::from),
+            matches: alert.matches.into_iter().map(DateTime::from).collect(),
+            namespace: alert.namespace,
+            resolved_ts: alert.resolved_ts.map(DateTime::from),
+            rule: alert.rule,
+            state: alert.state,
+            updated_ts: DateTime::from(alert.updated_ts),
+        }
+    }
+}
+
+impl From<AlertDocument> for Alert {
+    fn from(alert: AlertDocument) -> Alert {
+        Alert {
+            cluster_id: alert.cluster_id,
+            event_pending: alert.event_pending,
+            fired_ts: alert.fired_ts.map(|ts| ts.0),
+            matches: alert.matches.into_iter().map(|ts| ts.0).collect(),
+            namespace: alert.namespace,
+            resolved_ts: alert.resolved_ts.map(|ts| ts.0),
+            rule: alert.rule,
+            state: alert.state,
+            updated_ts: alert.updated_ts.0,
+        }
+    }
+}
+
 /// Wraps a `ClusterSettings` with store only fields.
 #[derive(Clone, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
 pub struct ClusterSettingsDocument {
     #[serde(flatten)]
     pub settings: ClusterSettings,
 
-    /// Timestamp for the next expected discovery run.
+    /// Timestamp for the next expected orchestration run.
     pub next_orchestrate: Option<DateTime>,
+
+    /// Report of the last orchestration run, if any.
+    #[serde(default)]
+    pub orchestrate_report: Option<OrchestrateReportDocument>,
 }
 
 impl From<ClusterSettings> for ClusterSettingsDocument {
@@ -134,6 +197,7 @@ impl From<ClusterSettings> for ClusterSettingsDocument {
         ClusterSettingsDocument {
             settings,
             next_orchestrate: None,
+            orchestrate_report: None,
         }
     }
 }
@@ -144,6 +208,43 @@ impl From<ClusterSettingsDocument> for ClusterSettings {
     }
 }
 
+/// Wraps an `OrchestrateReport` with MongoDB specific types.
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
+pub struct OrchestrateReportDocument {
+    pub cluster_id: String,
+    pub namespace: String,
+    pub duration: i64,
+    pub error: Option<String>,
+    pub outcome: OrchestrateOutcome,
+    pub start_time: DateTime,
+}
+
+impl From<OrchestrateReport> for OrchestrateReportDocument {
+    fn from(report: OrchestrateReport) -> OrchestrateReportDocument {
+        OrchestrateReportDocument {
+            cluster_id: report.cluster_id,
+            namespace: report.namespace,
+            duration: report.duration,
+            error: report.error,
+            outcome: report.outcome,
+            start_time: DateTime::from(report.start_time),
+        }
+    }
+}
+
+impl From<OrchestrateReportDocument> for OrchestrateReport {
+    fn from(report: OrchestrateReportDocument) -> OrchestrateReport {
+        OrchestrateReport {
+            cluster_id: report.cluster_id,
+            namespace: report.namespace,
+            duration: report.duration,
+            error: report.error,
+            outcome: report.outcome,
+            start_time: report.start_time.0,
+        }
+    }
+}
+
 /// Wraps a `DiscoverySettings` with store only fields.
 #[derive(Clone, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
 pub struct DiscoverySettingsDocument {
@@ -152,6 +253,10 @@ pub struct DiscoverySettingsDocument {
 
     /// Timestamp for the next expected discovery run.
     pub next_run: Option<DateTime>,
+
+    /// Retry state of the last discovery run, if it failed and should be retried.
+    #[serde(default)]
+    pub retry: Option<DiscoveryRetry>,
 }
 
 impl From<DiscoverySettings> for DiscoverySettingsDocument {
@@ -159,6 +264,7 @@ impl From<DiscoverySettings> for DiscoverySettingsDocument {
         DiscoverySettingsDocument {
             settings,
             next_run: None,
+            retry: None,
         }
     }
 }
@@ -169,6 +275,15 @@ impl From<DiscoverySettingsDocument> for DiscoverySettings {
     }
 }
 
+impl From<DiscoverySettingsDocument> for DiscoveryRun {
+    fn from(document: DiscoverySettingsDocument) -> DiscoveryRun {
+        DiscoveryRun {
+            retry: document.retry,
+            settings: document.settings,
+        }
+    }
+}
+
 /// Wraps a `Node` with store only and MongoDB specific fields.
 #[derive(Clone, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
 pub struct NodeDocument {
@@ -215,3 +330,46 @@ impl From<ShardDocument> for Shard {
         wrapper.shard
     }
 }
+
+/// Wraps a `WebhookDeadLetter` with MongoDB specific types.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct WebhookDeadLetterDocument {
+    pub attempts: u32,
+    pub cluster_id: Option<String>,
+    pub endpoint: String,
+    pub error: String,
+    pub event_code: String,
+    pub failed_ts: DateTime,
+    pub payload: String,
+    pub url: String,
+}
+
+impl From<WebhookDeadLetter> for WebhookDeadLetterDocument {
+    fn from(letter: WebhookDeadLetter) -> WebhookDeadLetterDocument {
+        WebhookDeadLetterDocument {
+            attempts: letter.attempts,
+            cluster_id: letter.cluster_id,
+            endpoint: letter.endpoint,
+            error: letter.error,
+            event_code: letter.event_code,
+            failed_ts: DateTime::from(letter.failed_ts),
+            payload: letter.payload,
+            url: letter.url,
+        }
+    }
+}
+
+impl From<WebhookDeadLetterDocument> for WebhookDeadLetter {
+    fn from(letter: WebhookDeadLetterDocument) -> WebhookDeadLetter {
+        WebhookDeadLetter {
+            attempts: letter.attempts,
+            cluster_id: letter.cluster_id,
+            endpoint: letter.endpoint,
+            error: letter.error,
+            event_code: letter.event_code,
+            failed_ts: letter.failed_ts.0,
+            payload: letter.payload,
+            url: letter.url,
+        }
+    }
+}
@@ -4,15 +4,27 @@ use bson::doc;
 use chrono::Utc;
 use failure::Fail;
 use failure::ResultExt;
+use mongodb::options::FindOptions;
 use mongodb::sync::Client;
 use opentracingrust::SpanContext;
 use opentracingrust::Tracer;
 
 use replicante_externals_mongodb::operations::find;
-use replicante_models_core::cluster::discovery::DiscoverySettings;
+use replicante_externals_mongodb::operations::find_with_options;
+use replicante_models_core::alerts::Alert;
+use replicante_models_core::alerts::AlertRule;
+use replicante_models_core::cluster::discovery::DiscoveryRun;
+use replicante_models_core::cluster::ClusterSettings;
+use replicante_models_core::scope::Namespace;
 
 use super::super::GlobalSearchInterface;
+use super::constants::COLLECTION_ALERTS;
+use super::constants::COLLECTION_ALERT_RULES;
+use super::constants::COLLECTION_CLUSTER_SETTINGS;
 use super::constants::COLLECTION_DISCOVERY_SETTINGS;
+use super::constants::COLLECTION_NAMESPACES;
+use super::document::AlertDocument;
+use super::document::ClusterSettingsDocument;
 use super::document::DiscoverySettingsDocument;
 use crate::Cursor;
 use crate::ErrorKind;
@@ -36,7 +48,54 @@ impl GlobalSearch {
 }
 
 impl GlobalSearchInterface for GlobalSearch {
-    fn discoveries_to_run(&self, span: Option<SpanContext>) -> Result<Cursor<DiscoverySettings>> {
+    fn active_alerts(&self, span: Option<SpanContext>) -> Result<Cursor<Alert>> {
+        let filter = doc! {"$or": [
+            {"state": "FIRING"},
+            {"event_pending": true},
+        ]};
+        let collection = self.client.database(&self.db).collection(COLLECTION_ALERTS);
+        let cursor = find(collection, filter, span, self.tracer.as_deref())
+            .with_context(|_| ErrorKind::MongoDBOperation)?
+            .map(|item| item.map_err(|error| error.context(ErrorKind::MongoDBCursor).into()))
+            .map(|result: Result<AlertDocument>| result.map(Alert::from));
+        Ok(Cursor::new(cursor))
+    }
+
+    fn alert_rules(&self, span: Option<SpanContext>) -> Result<Cursor<AlertRule>> {
+        let filter = doc! {};
+        let collection = self
+            .client
+            .database(&self.db)
+            .collection(COLLECTION_ALERT_RULES);
+        let cursor = find(collection, filter, span, self.tracer.as_deref())
+            .with_context(|_| ErrorKind::MongoDBOperation)?
+            .map(|item| item.map_err(|error| error.context(ErrorKind::MongoDBCursor).into()));
+        Ok(Cursor::new(cursor))
+    }
+
+    fn clusters_to_orchestrate(
+        &self,
+        span: Option<SpanContext>,
+    ) -> Result<Cursor<ClusterSettings>> {
+        let filter = doc! {"$and": [
+            {"enabled": true},
+            {"$or": [
+                {"next_orchestrate": null},
+                {"next_orchestrate": {"$lte": Utc::now()}},
+            ]},
+        ]};
+        let collection = self
+            .client
+            .database(&self.db)
+            .collection(COLLECTION_CLUSTER_SETTINGS);
+        let cursor = find(collection, filter, span, self.tracer.as_deref())
+            .with_context(|_| ErrorKind::MongoDBOperation)?
+            .map(|item| item.map_err(|error| error.context(ErrorKind::MongoDBCursor).into()))
+            .map(|result: Result<ClusterSettingsDocument>| result.map(ClusterSettings::from));
+        Ok(Cursor::new(cursor))
+    }
+
+    fn discoveries_to_run(&self, span: Option<SpanContext>) -> Result<Cursor<DiscoveryRun>> {
         let filter = doc! {"$and": [
             {"enabled": true},
             {"$or": [
@@ -51,7 +110,21 @@ impl GlobalSearchInterface for GlobalSearch {
         let cursor = find(collection, filter, span, self.tracer.as_deref())
             .with_context(|_| ErrorKind::MongoDBOperation)?
             .map(|item| item.map_err(|error| error.context(ErrorKind::MongoDBCursor).into()))
-            .map(|result: Result<DiscoverySettingsDocument>| result.map(DiscoverySettings::from));
+            .map(|result: Result<DiscoverySettingsDocument>| result.map(DiscoveryRun::from));
+        Ok(Cursor::new(cursor))
+    }
+
+    fn namespaces(&self, span: Option<SpanContext>) -> Result<Cursor<Namespace>> {
+        let filter = doc! {};
+        let mut options = FindOptions::default();
+        options.sort = Some(doc! {"ns_id": 1});
+        let collection = self
+            .client
+            .database(&self.db)
+            .collection(COLLECTION_NAMESPACES);
+        let cursor = find_with_options(collection, filter, options, span, self.tracer.as_deref())
+            .with_context(|_| ErrorKind::MongoDBOperation)?
+            .map(|item| item.map_err(|error| error.context(ErrorKind::MongoDBCursor).into()));
         Ok(Cursor::new(cursor))
     }
 }
@@ -15,11 +15,13 @@ use super::ActionsImpl;
 use super::AdminInterface;
 use super::AgentImpl;
 use super::AgentsImpl;
+use super::AlertRulesImpl;
 use super::ClusterImpl;
 use super::DataImpl;
 use super::DiscoverySettingsImpl;
 use super::GlobalSearchImpl;
 use super::LegacyImpl;
+use super::NamespaceImpl;
 use super::NodeImpl;
 use super::NodesImpl;
 use super::PersistImpl;
@@ -34,6 +36,7 @@ use crate::Result;
 mod actions;
 mod agent;
 mod agents;
+mod alert_rules;
 mod cluster;
 mod constants;
 mod data;
@@ -41,6 +44,7 @@ mod discovery_settings;
 mod document;
 mod global_search;
 mod legacy;
+mod namespace;
 mod node;
 mod nodes;
 mod persist;
@@ -90,11 +94,18 @@ impl AdminInterface for Admin {
 ///
 /// # Expected indexes
 ///
+///   * Index on `alerts`: `state: 1`
+///   * Index on `cluster_settings`: `next_orchestrate: 1`
 ///   * Index on `clusters_meta`: `(shards: -1, nodes: -1, cluster_id: 1)`
+///   * Index on `discoveries`: `(discovery_namespace: 1, discovery_name: 1, last_seen: 1)`
 ///   * Unique index on `agents`: `(cluster_id: 1, host: 1)`
 ///   * Unique index on `agents_info`: `(cluster_id: 1, host: 1)`
+///   * Unique index on `alert_rules`: `(namespace: 1, name: 1)`
+///   * Unique index on `alerts`: `(namespace: 1, rule: 1, cluster_id: 1)`
+///   * Unique index on `cluster_settings`: `(namespace: 1, cluster_id: 1)`
 ///   * Unique index on `clusters_meta`: `cluster_id: 1`
 ///   * Unique index on `discoveries`: `cluster_id: 1`
+///   * Unique index on `namespaces`: `ns_id: 1`
 ///   * Unique index on `nodes`: `(cluster_id: 1, node_id: 1)`
 ///   * Unique index on `shards`: `(cluster_id: 1, shard_id: 1, node_id: 1)`
 pub struct Store {
@@ -150,6 +161,15 @@ impl StoreInterface for Store {
         AgentsImpl::new(agents)
     }
 
+    fn alert_rules(&self) -> AlertRulesImpl {
+        let rules = self::alert_rules::AlertRules::new(
+            self.client.clone(),
+            self.db.clone(),
+            self.tracer.clone(),
+        );
+        AlertRulesImpl::new(rules)
+    }
+
     fn cluster(&self) -> ClusterImpl {
         let cluster = self::cluster::Cluster::new(
             self.client.clone(),
@@ -184,6 +204,15 @@ impl StoreInterface for Store {
         LegacyImpl::new(legacy)
     }
 
+    fn namespace(&self) -> NamespaceImpl {
+        let namespace = self::namespace::Namespace::new(
+            self.client.clone(),
+            self.db.clone(),
+            self.tracer.clone(),
+        );
+        NamespaceImpl::new(namespace)
+    }
+
     fn node(&self) -> NodeImpl {
         let node = self::node::Node::new(self.client.clone(), self.db.clone(), self.tracer.clone());
         NodeImpl::new(node)
@@ -0,0 +1,89 @@
+use std::sync::Arc;
+
+use bson::doc;
+use failure::ResultExt;
+use mongodb::options::FindOptions;
+use mongodb::sync::Client;
+use opentracingrust::SpanContext;
+use opentracingrust::Tracer;
+
+use replicante_externals_mongodb::operations::delete_one;
+use replicante_externals_mongodb::operations::find_one;
+use replicante_externals_mongodb::operations::find_with_options;
+use replicante_models_core::scope::Namespace as NamespaceModel;
+
+use super::super::NamespaceInterface;
+use super::constants::COLLECTION_CLUSTER_SETTINGS;
+use super::constants::COLLECTION_NAMESPACES;
+use super::document::ClusterSettingsDocument;
+use crate::store::namespace::NamespaceAttributes;
+use crate::Cursor;
+use crate::ErrorKind;
+use crate::Result;
+
+/// Namespace operations implementation using MongoDB.
+pub struct Namespace {
+    client: Client,
+    db: String,
+    tracer: Option<Arc<Tracer>>,
+}
+
+impl Namespace {
+    pub fn new<T>(client: Client, db: String, tracer: T) -> Namespace
+    where
+        T: Into<Option<Arc<Tracer>>>,
+    {
+        let tracer = tracer.into();
+        Namespace { client, db, tracer }
+    }
+}
+
+impl NamespaceInterface for Namespace {
+    fn delete(&self, attrs: &NamespaceAttributes, span: Option<SpanContext>) -> Result<()> {
+        let filter = doc! {"ns_id": &attrs.ns_id};
+        let collection = self
+            .client
+            .database(&self.db)
+            .collection(COLLECTION_NAMESPACES);
+        delete_one(collection, filter, span, self.tracer.as_deref())
+            .with_context(|_| ErrorKind::MongoDBOperation)?;
+        Ok(())
+    }
+
+    fn get(
+        &self,
+        attrs: &NamespaceAttributes,
+        span: Option<SpanContext>,
+    ) -> Result<Option<NamespaceModel>> {
+        let filter = doc! {"ns_id": &attrs.ns_id};
+        let collection = self
+            .client
+            .database(&self.db)
+            .collection(COLLECTION_NAMESPACES);
+        let namespace = find_one(collection, filter, span, self.tracer.as_deref())
+            .with_context(|_| ErrorKind::MongoDBOperation)?;
+        Ok(namespace)
+    }
+
+    fn iter_clusters(
+        &self,
+        attrs: &NamespaceAttributes,
+        span: Option<SpanContext>,
+    ) -> Result<Cursor<String>> {
+        let filter = doc! {"namespace": &attrs.ns_id};
+        let mut options = FindOptions::default();
+        options.sort = Some(doc! {"cluster_id": 1});
+        let collection = self
+            .client
+            .database(&self.db)
+            .collection(COLLECTION_CLUSTER_SETTINGS);
+        let cursor = find_with_options(collection, filter, options, span, self.tracer.as_deref())
+            .with_context(|_| ErrorKind::MongoDBOperation)?;
+        let cursor = cursor.map(|document| {
+            let document: ClusterSettingsDocument =
+                document.with_context(|_| ErrorKind::MongoDBCursor)?;
+            Ok(document.settings.cluster_id)
+        });
+        Ok(Cursor::new(cursor))
+    }
+}
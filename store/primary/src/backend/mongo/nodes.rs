@@ -49,6 +49,23 @@ impl NodesInterface for Nodes {
         Ok(Cursor::new(cursor))
     }
 
+    fn iter_stale(
+        &self,
+        attrs: &NodesAttribures,
+        span: Option<SpanContext>,
+    ) -> Result<Cursor<Node>> {
+        let filter = doc! {
+            "cluster_id": &attrs.cluster_id,
+            "stale": true,
+        };
+        let collection = self.client.database(&self.db).collection(COLLECTION_NODES);
+        let cursor = find(collection, filter, span, self.tracer.as_deref())
+            .with_context(|_| ErrorKind::MongoDBOperation)?
+            .map(|item| item.map_err(|error| error.context(ErrorKind::MongoDBCursor).into()))
+            .map(|result: Result<NodeDocument>| result.map(Node::from));
+        Ok(Cursor::new(cursor))
+    }
+
     fn kinds(&self, attrs: &NodesAttribures, span: Option<SpanContext>) -> Result<HashSet<String>> {
         // Let mongo figure out the kinds with an aggregation.
         let filter = doc! {"$match": {
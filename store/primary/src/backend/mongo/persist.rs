@@ -2,38 +2,56 @@ use std::sync::Arc;
 
 use bson::doc;
 use bson::Bson;
+use bson::Document;
+use chrono::DateTime;
 use chrono::Utc;
 use failure::ResultExt;
+use mongodb::options::UpdateOptions;
 use mongodb::sync::Client;
 use opentracingrust::SpanContext;
 use opentracingrust::Tracer;
 
+use replicante_externals_mongodb::operations::insert_one;
 use replicante_externals_mongodb::operations::replace_one;
 use replicante_externals_mongodb::operations::update_one;
+use replicante_externals_mongodb::operations::update_one_with_options;
 use replicante_models_core::actions::Action as ActionModel;
 use replicante_models_core::agent::Agent as AgentModel;
 use replicante_models_core::agent::AgentInfo as AgentInfoModel;
 use replicante_models_core::agent::Node as NodeModel;
 use replicante_models_core::agent::Shard as ShardModel;
+use replicante_models_core::alerts::Alert as AlertModel;
+use replicante_models_core::alerts::AlertRule as AlertRuleModel;
 use replicante_models_core::cluster::discovery::ClusterDiscovery as ClusterDiscoveryModel;
+use replicante_models_core::cluster::discovery::DiscoveryRetry as DiscoveryRetryModel;
 use replicante_models_core::cluster::discovery::DiscoverySettings as DiscoverySettingsModel;
 use replicante_models_core::cluster::ClusterSettings as ClusterSettingsModel;
+use replicante_models_core::cluster::OrchestrateReport as OrchestrateReportModel;
+use replicante_models_core::scope::Namespace as NamespaceModel;
+use replicante_models_core::webhooks::WebhookDeadLetter as WebhookDeadLetterModel;
 
 use super::super::PersistInterface;
 use super::constants::COLLECTION_ACTIONS;
 use super::constants::COLLECTION_AGENTS;
 use super::constants::COLLECTION_AGENTS_INFO;
+use super::constants::COLLECTION_ALERTS;
+use super::constants::COLLECTION_ALERT_RULES;
 use super::constants::COLLECTION_CLUSTER_SETTINGS;
 use super::constants::COLLECTION_DISCOVERIES;
 use super::constants::COLLECTION_DISCOVERY_SETTINGS;
+use super::constants::COLLECTION_DISCOVERY_SETTINGS_HISTORY;
+use super::constants::COLLECTION_NAMESPACES;
 use super::constants::COLLECTION_NODES;
 use super::constants::COLLECTION_SHARDS;
+use super::constants::COLLECTION_WEBHOOKS_DEAD_LETTERS;
 use super::document::ActionDocument;
 use super::document::AgentInfoDocument;
-use super::document::ClusterSettingsDocument;
+use super::document::AlertDocument;
 use super::document::DiscoverySettingsDocument;
 use super::document::NodeDocument;
+use super::document::OrchestrateReportDocument;
 use super::document::ShardDocument;
+use super::document::WebhookDeadLetterDocument;
 use crate::ErrorKind;
 use crate::Result;
 
@@ -112,6 +130,43 @@ impl PersistInterface for Persist {
         Ok(())
     }
 
+    fn alert(&self, alert: AlertModel, span: Option<SpanContext>) -> Result<()> {
+        let filter = doc! {
+            "namespace": &alert.namespace,
+            "rule": &alert.rule,
+            "cluster_id": &alert.cluster_id,
+        };
+        let alert = AlertDocument::from(alert);
+        let collection = self.client.database(&self.db).collection(COLLECTION_ALERTS);
+        let document = bson::to_bson(&alert).with_context(|_| ErrorKind::MongoDBBsonEncode)?;
+        let document = match document {
+            Bson::Document(document) => document,
+            _ => panic!("Alert failed to encode as BSON document"),
+        };
+        replace_one(collection, filter, document, span, self.tracer.as_deref())
+            .with_context(|_| ErrorKind::MongoDBOperation)?;
+        Ok(())
+    }
+
+    fn alert_rule(&self, rule: AlertRuleModel, span: Option<SpanContext>) -> Result<()> {
+        let filter = doc! {
+            "namespace": &rule.namespace,
+            "name": &rule.name,
+        };
+        let collection = self
+            .client
+            .database(&self.db)
+            .collection(COLLECTION_ALERT_RULES);
+        let document = bson::to_bson(&rule).with_context(|_| ErrorKind::MongoDBBsonEncode)?;
+        let document = match document {
+            Bson::Document(document) => document,
+            _ => panic!("AlertRule failed to encode as BSON document"),
+        };
+        replace_one(collection, filter, document, span, self.tracer.as_deref())
+            .with_context(|_| ErrorKind::MongoDBOperation)?;
+        Ok(())
+    }
+
     fn cluster_discovery(
         &self,
         discovery: ClusterDiscoveryModel,
@@ -122,12 +177,38 @@ impl PersistInterface for Persist {
             .client
             .database(&self.db)
             .collection(COLLECTION_DISCOVERIES);
-        let document = bson::to_bson(&discovery).with_context(|_| ErrorKind::MongoDBBsonEncode)?;
-        let document = match document {
-            Bson::Document(document) => document,
-            _ => panic!("ClusterDiscovery failed to encode as BSON document"),
+        let update = cluster_discovery_update(discovery)?;
+        let mut options = UpdateOptions::default();
+        options.upsert = Some(true);
+        update_one_with_options(
+            collection,
+            filter,
+            update,
+            options,
+            span,
+            self.tracer.as_deref(),
+        )
+        .with_context(|_| ErrorKind::MongoDBOperation)?;
+        Ok(())
+    }
+
+    fn cluster_orchestrate_report(
+        &self,
+        report: OrchestrateReportModel,
+        span: Option<SpanContext>,
+    ) -> Result<()> {
+        let filter = doc! {
+            "namespace": &report.namespace,
+            "cluster_id": &report.cluster_id,
         };
-        replace_one(collection, filter, document, span, self.tracer.as_deref())
+        let collection = self
+            .client
+            .database(&self.db)
+            .collection(COLLECTION_CLUSTER_SETTINGS);
+        let report = OrchestrateReportDocument::from(report);
+        let report = bson::to_bson(&report).with_context(|_| ErrorKind::MongoDBBsonEncode)?;
+        let update = doc! {"$set": {"orchestrate_report": report}};
+        update_one(collection, filter, update, span, self.tracer.as_deref())
             .with_context(|_| ErrorKind::MongoDBOperation)?;
         Ok(())
     }
@@ -145,13 +226,42 @@ impl PersistInterface for Persist {
             .client
             .database(&self.db)
             .collection(COLLECTION_CLUSTER_SETTINGS);
-        let document = ClusterSettingsDocument::from(settings);
-        let document = bson::to_bson(&document).with_context(|_| ErrorKind::MongoDBBsonEncode)?;
-        let document = match document {
-            Bson::Document(document) => document,
-            _ => panic!("ClusterSettings failed to encode as BSON document"),
+        let update = cluster_settings_update(settings)?;
+        let mut options = UpdateOptions::default();
+        options.upsert = Some(true);
+        update_one_with_options(
+            collection,
+            filter,
+            update,
+            options,
+            span,
+            self.tracer.as_deref(),
+        )
+        .with_context(|_| ErrorKind::MongoDBOperation)?;
+        Ok(())
+    }
+
+    fn discovery_retry(
+        &self,
+        settings: DiscoverySettingsModel,
+        retry: DiscoveryRetryModel,
+        retry_at: DateTime<Utc>,
+        span: Option<SpanContext>,
+    ) -> Result<()> {
+        let filter = doc! {
+            "namespace": &settings.namespace,
+            "name": &settings.name,
         };
-        replace_one(collection, filter, document, span, self.tracer.as_deref())
+        let retry = bson::to_bson(&retry).with_context(|_| ErrorKind::MongoDBBsonEncode)?;
+        let update = doc! {"$set": {
+            "next_run": retry_at,
+            "retry": retry,
+        }};
+        let collection = self
+            .client
+            .database(&self.db)
+            .collection(COLLECTION_DISCOVERY_SETTINGS);
+        update_one(collection, filter, update, span, self.tracer.as_deref())
             .with_context(|_| ErrorKind::MongoDBOperation)?;
         Ok(())
     }
@@ -165,6 +275,16 @@ impl PersistInterface for Persist {
             "namespace": &settings.namespace,
             "name": &settings.name,
         };
+        let revision_filter = doc! {
+            "namespace": &settings.namespace,
+            "name": &settings.name,
+            "revision": settings.revision as i64,
+        };
+        let revision = bson::to_bson(&settings).with_context(|_| ErrorKind::MongoDBBsonEncode)?;
+        let revision = match revision {
+            Bson::Document(revision) => revision,
+            _ => panic!("DiscoverySettings failed to encode as BSON document"),
+        };
         let collection = self
             .client
             .database(&self.db)
@@ -175,11 +295,67 @@ impl PersistInterface for Persist {
             Bson::Document(document) => document,
             _ => panic!("DiscoverySettings failed to encode as BSON document"),
         };
+        replace_one(
+            collection,
+            filter,
+            document,
+            span.clone(),
+            self.tracer.as_deref(),
+        )
+        .with_context(|_| ErrorKind::MongoDBOperation)?;
+
+        // Record the revision in the history, re-applying a revision overwrites the same record.
+        let collection = self
+            .client
+            .database(&self.db)
+            .collection(COLLECTION_DISCOVERY_SETTINGS_HISTORY);
+        replace_one(
+            collection,
+            revision_filter,
+            revision,
+            span,
+            self.tracer.as_deref(),
+        )
+        .with_context(|_| ErrorKind::MongoDBOperation)?;
+        Ok(())
+    }
+
+    fn namespace(&self, namespace: NamespaceModel, span: Option<SpanContext>) -> Result<()> {
+        let filter = doc! {"ns_id": &namespace.ns_id};
+        let collection = self
+            .client
+            .database(&self.db)
+            .collection(COLLECTION_NAMESPACES);
+        let document = bson::to_bson(&namespace).with_context(|_| ErrorKind::MongoDBBsonEncode)?;
+        let document = match document {
+            Bson::Document(document) => document,
+            _ => panic!("Namespace failed to encode as BSON document"),
+        };
         replace_one(collection, filter, document, span, self.tracer.as_deref())
             .with_context(|_| ErrorKind::MongoDBOperation)?;
         Ok(())
     }
 
+    fn next_cluster_orchestrate(
+        &self,
+        settings: ClusterSettingsModel,
+        span: Option<SpanContext>,
+    ) -> Result<()> {
+        let filter = doc! {
+            "namespace": &settings.namespace,
+            "cluster_id": &settings.cluster_id,
+        };
+        let next_orchestrate = Utc::now() + chrono::Duration::seconds(settings.interval);
+        let update = doc! {"$set": {"next_orchestrate": next_orchestrate}};
+        let collection = self
+            .client
+            .database(&self.db)
+            .collection(COLLECTION_CLUSTER_SETTINGS);
+        update_one(collection, filter, update, span, self.tracer.as_deref())
+            .with_context(|_| ErrorKind::MongoDBOperation)?;
+        Ok(())
+    }
+
     fn next_discovery_run(
         &self,
         settings: DiscoverySettingsModel,
@@ -190,7 +366,10 @@ impl PersistInterface for Persist {
             "name": &settings.name,
         };
         let next_run = Utc::now() + chrono::Duration::seconds(settings.interval);
-        let update = doc! {"$set": {"next_run": next_run}};
+        let update = doc! {
+            "$set": {"next_run": next_run},
+            "$unset": {"retry": ""},
+        };
         let collection = self
             .client
             .database(&self.db)
@@ -234,4 +413,102 @@ impl PersistInterface for Persist {
             .with_context(|_| ErrorKind::MongoDBOperation)?;
         Ok(())
     }
+
+    fn webhook_dead_letter(
+        &self,
+        letter: WebhookDeadLetterModel,
+        span: Option<SpanContext>,
+    ) -> Result<()> {
+        let letter = WebhookDeadLetterDocument::from(letter);
+        let collection = self
+            .client
+            .database(&self.db)
+            .collection(COLLECTION_WEBHOOKS_DEAD_LETTERS);
+        let document = bson::to_bson(&letter).with_context(|_| ErrorKind::MongoDBBsonEncode)?;
+        let document = match document {
+            Bson::Document(document) => document,
+            _ => panic!("WebhookDeadLetter failed to encode as BSON document"),
+        };
+        insert_one(collection, document, span, self.tracer.as_deref())
+            .with_context(|_| ErrorKind::MongoDBOperation)?;
+        Ok(())
+    }
+}
+
+/// Update `ClusterDiscovery` fields, creating the record if needed.
+///
+/// Discovery tracking fields (`discovery_namespace`, `discovery_name` and `last_seen`)
+/// are managed by `DiscoverySettings::mark_clusters_seen` and must survive updates.
+fn cluster_discovery_update(discovery: ClusterDiscoveryModel) -> Result<Document> {
+    let discovery = bson::to_bson(&discovery).with_context(|_| ErrorKind::MongoDBBsonEncode)?;
+    let discovery = match discovery {
+        Bson::Document(discovery) => discovery,
+        _ => panic!("ClusterDiscovery failed to encode as BSON document"),
+    };
+    Ok(doc! {"$set": discovery})
+}
+
+/// Update user-owned `ClusterSettings` fields, creating the record if needed.
+///
+/// Orchestration scheduling state (`next_orchestrate` and `orchestrate_report`) is
+/// managed by Core and must survive settings being applied again.
+fn cluster_settings_update(settings: ClusterSettingsModel) -> Result<Document> {
+    let settings = bson::to_bson(&settings).with_context(|_| ErrorKind::MongoDBBsonEncode)?;
+    let settings = match settings {
+        Bson::Document(settings) => settings,
+        _ => panic!("ClusterSettings failed to encode as BSON document"),
+    };
+    Ok(doc! {"$set": settings})
+}
+
+#[cfg(test)]
+mod tests {
+    use bson::Bson;
+    use bson::DateTime;
+    use chrono::TimeZone;
+    use chrono::Utc;
+
+    use replicante_models_core::cluster::discovery::ClusterDiscovery;
+    use replicante_models_core::cluster::ClusterSettings;
+
+    use super::cluster_discovery_update;
+    use super::cluster_settings_update;
+    use crate::backend::mongo::document::ClusterSettingsDocument;
+
+    #[test]
+    fn cluster_discovery_update_keeps_tracking() {
+        let discovery = ClusterDiscovery::new("c1", vec!["https://node1:37000".into()]);
+        let update = cluster_discovery_update(discovery).unwrap();
+        let set = update.get_document("$set").unwrap();
+        assert_eq!(set.get_str("cluster_id").unwrap(), "c1");
+        assert!(!set.contains_key("discovery_namespace"));
+        assert!(!set.contains_key("discovery_name"));
+        assert!(!set.contains_key("last_seen"));
+    }
+
+    #[test]
+    fn cluster_settings_apply_keeps_schedule() {
+        let next_orchestrate = DateTime(Utc.ymd(2020, 6, 1).and_hms(12, 0, 0));
+        let mut stored = ClusterSettingsDocument::from(ClusterSettings::new("ns", "c1", true));
+        stored.next_orchestrate = Some(next_orchestrate);
+        let mut stored = match bson::to_bson(&stored).unwrap() {
+            Bson::Document(stored) => stored,
+            _ => panic!("ClusterSettingsDocument failed to encode as BSON document"),
+        };
+
+        // Apply the update the same way MongoDB would for a $set operation.
+        let mut settings = ClusterSettings::new("ns", "c1", false);
+        settings.interval = 30;
+        let update = cluster_settings_update(settings.clone()).unwrap();
+        let set = update.get_document("$set").unwrap();
+        assert!(!set.contains_key("next_orchestrate"));
+        assert!(!set.contains_key("orchestrate_report"));
+        for (key, value) in set {
+            stored.insert(key.clone(), value.clone());
+        }
+
+        let stored: ClusterSettingsDocument = bson::from_bson(Bson::Document(stored)).unwrap();
+        assert_eq!(stored.settings, settings);
+        assert_eq!(stored.next_orchestrate, Some(next_orchestrate));
+    }
 }
@@ -6,6 +6,7 @@ use mongodb::sync::Client;
 use opentracingrust::SpanContext;
 use opentracingrust::Tracer;
 
+use replicante_externals_mongodb::operations::delete_one;
 use replicante_externals_mongodb::operations::find_one;
 use replicante_models_core::agent::Shard as ShardModel;
 
@@ -34,6 +35,18 @@ impl Shard {
 }
 
 impl ShardInterface for Shard {
+    fn delete(&self, attrs: &ShardAttribures, span: Option<SpanContext>) -> Result<()> {
+        let filter = doc! {
+            "cluster_id": &attrs.cluster_id,
+            "node_id": &attrs.node_id,
+            "shard_id": &attrs.shard_id,
+        };
+        let collection = self.client.database(&self.db).collection(COLLECTION_SHARDS);
+        delete_one(collection, filter, span, self.tracer.as_deref())
+            .with_context(|_| ErrorKind::MongoDBOperation)?;
+        Ok(())
+    }
+
     fn get(
         &self,
         attrs: &ShardAttribures,
@@ -106,4 +106,21 @@ impl ShardsInterface for Shards {
             .map(|result: Result<ShardDocument>| result.map(Shard::from));
         Ok(Cursor::new(cursor))
     }
+
+    fn iter_stale(
+        &self,
+        attrs: &ShardsAttribures,
+        span: Option<SpanContext>,
+    ) -> Result<Cursor<Shard>> {
+        let filter = doc! {
+            "cluster_id": &attrs.cluster_id,
+            "stale": true,
+        };
+        let collection = self.client.database(&self.db).collection(COLLECTION_SHARDS);
+        let cursor = find(collection, filter, span, self.tracer.as_deref())
+            .with_context(|_| ErrorKind::MongoDBOperation)?
+            .map(|item| item.map_err(|error| error.context(ErrorKind::MongoDBCursor).into()))
+            .map(|result: Result<ShardDocument>| result.map(Shard::from));
+        Ok(Cursor::new(cursor))
+    }
 }
@@ -2,6 +2,8 @@ use std::collections::HashMap;
 use std::sync::Arc;
 use std::sync::Mutex;
 
+use chrono::DateTime;
+use chrono::Utc;
 use uuid::Uuid;
 
 use replicante_models_core::actions::Action;
@@ -11,7 +13,10 @@ use replicante_models_core::agent::Node;
 use replicante_models_core::agent::Shard;
 use replicante_models_core::cluster::discovery::ClusterDiscovery;
 use replicante_models_core::cluster::ClusterMeta;
+use replicante_models_core::cluster::ClusterSettings;
 use replicante_models_core::events::Event;
+use replicante_models_core::scope::Namespace;
+use replicante_models_core::webhooks::WebhookDeadLetter;
 
 use crate::admin::Admin;
 use crate::store::Store;
@@ -45,9 +50,21 @@ pub struct MockState {
     pub actions: HashMap<(String, String, Uuid), Action>,
     pub agents: HashMap<(String, String), Agent>,
     pub agents_info: HashMap<(String, String), AgentInfo>,
+    pub cluster_settings: HashMap<(String, String), ClusterSettings>,
     pub clusters_meta: HashMap<String, ClusterMeta>,
     pub discoveries: HashMap<String, ClusterDiscovery>,
+    pub discoveries_seen: HashMap<String, DiscoverySeen>,
     pub events: Vec<Event>,
+    pub namespaces: HashMap<String, Namespace>,
     pub nodes: HashMap<(String, String), Node>,
     pub shards: HashMap<(String, String, String), Shard>,
+    pub webhook_dead_letters: Vec<WebhookDeadLetter>,
+}
+
+/// Discovery tracking information for a `ClusterDiscovery` record.
+#[derive(Clone, Debug)]
+pub struct DiscoverySeen {
+    pub discovery_namespace: String,
+    pub discovery_name: String,
+    pub last_seen: DateTime<Utc>,
 }
@@ -13,21 +13,33 @@ use replicante_models_core::agent::Agent;
 use replicante_models_core::agent::AgentInfo;
 use replicante_models_core::agent::Node;
 use replicante_models_core::agent::Shard;
+use replicante_models_core::alerts::Alert;
+use replicante_models_core::alerts::AlertRule;
 use replicante_models_core::cluster::discovery::ClusterDiscovery;
-use replicante_models_core::cluster::discovery::DiscoverySettings;
+use replicante_models_core::cluster::discovery::DiscoveryRetry;
+use replicante_models_core::cluster::discovery::DiscoverySettings as DiscoverySettingsModel;
 use replicante_models_core::cluster::ClusterMeta;
 use replicante_models_core::cluster::ClusterSettings;
+use replicante_models_core::cluster::OrchestrateReport;
+use replicante_models_core::scope::Namespace as NamespaceModel;
+use replicante_models_core::webhooks::WebhookDeadLetter;
 
+use super::DiscoverySeen;
 use super::MockState;
 use crate::backend::ActionsImpl;
 use crate::backend::ActionsInterface;
 use crate::backend::AgentImpl;
 use crate::backend::AgentsImpl;
+use crate::backend::AlertRulesImpl;
 use crate::backend::ClusterImpl;
+use crate::backend::ClusterInterface;
 use crate::backend::DiscoverySettingsImpl;
+use crate::backend::DiscoverySettingsInterface;
 use crate::backend::GlobalSearchImpl;
 use crate::backend::LegacyImpl;
 use crate::backend::LegacyInterface;
+use crate::backend::NamespaceImpl;
+use crate::backend::NamespaceInterface;
 use crate::backend::NodeImpl;
 use crate::backend::NodesImpl;
 use crate::backend::PersistImpl;
@@ -38,6 +50,9 @@ use crate::backend::StoreImpl;
 use crate::backend::StoreInterface;
 use crate::store::actions::ActionSyncState;
 use crate::store::actions::ActionsAttributes;
+use crate::store::cluster::ClusterAttribures;
+use crate::store::discovery_settings::DiscoverySettingsAttributes;
+use crate::store::namespace::NamespaceAttributes;
 use crate::store::Store;
 use crate::Cursor;
 use crate::Result;
@@ -63,12 +78,22 @@ impl StoreInterface for StoreMock {
         panic!("TODO: StoreMock::agents");
     }
 
+    fn alert_rules(&self) -> AlertRulesImpl {
+        panic!("TODO: StoreMock::alert_rules");
+    }
+
     fn cluster(&self) -> ClusterImpl {
-        panic!("TODO: StoreMock::cluster");
+        let cluster = Cluster {
+            state: Arc::clone(&self.state),
+        };
+        ClusterImpl::new(cluster)
     }
 
     fn discovery_settings(&self) -> DiscoverySettingsImpl {
-        panic!("TODO: StoreMock::discovery_settings");
+        let discovery_settings = DiscoverySettings {
+            state: Arc::clone(&self.state),
+        };
+        DiscoverySettingsImpl::new(discovery_settings)
     }
 
     fn global_search(&self) -> GlobalSearchImpl {
@@ -82,6 +107,13 @@ impl StoreInterface for StoreMock {
         LegacyImpl::new(legacy)
     }
 
+    fn namespace(&self) -> NamespaceImpl {
+        let namespace = Namespace {
+            state: Arc::clone(&self.state),
+        };
+        NamespaceImpl::new(namespace)
+    }
+
     fn node(&self) -> NodeImpl {
         panic!("TODO: StoreMock::node");
     }
@@ -119,6 +151,21 @@ struct Actions {
 }
 
 impl ActionsInterface for Actions {
+    fn action(
+        &self,
+        attrs: &ActionsAttributes,
+        action_id: Uuid,
+        _: Option<SpanContext>,
+    ) -> Result<Option<Action>> {
+        let store = self.state.lock().expect("MockStore state lock is poisoned");
+        let action = store
+            .actions
+            .iter()
+            .find(|(key, _)| key.0 == attrs.cluster_id && key.2 == action_id)
+            .map(|(_, action)| action.clone());
+        Ok(action)
+    }
+
     fn approve(
         &self,
         _attrs: &ActionsAttributes,
@@ -128,6 +175,29 @@ impl ActionsInterface for Actions {
         panic!("TODO: MockStore::Actions::approve")
     }
 
+    fn cancel(
+        &self,
+        attrs: &ActionsAttributes,
+        action_id: Uuid,
+        _: Option<SpanContext>,
+    ) -> Result<bool> {
+        let mut store = self.state.lock().expect("MockStore state lock is poisoned");
+        let action = store.actions.iter_mut().find(|(key, action)| {
+            key.0 == attrs.cluster_id
+                && key.2 == action_id
+                && (action.state == ActionState::PendingApprove
+                    || action.state == ActionState::PendingSchedule)
+        });
+        match action {
+            None => Ok(false),
+            Some((_, action)) => {
+                action.state = ActionState::Cancelled;
+                action.finished_ts = Some(Utc::now());
+                Ok(true)
+            }
+        }
+    }
+
     fn disapprove(
         &self,
         _attrs: &ActionsAttributes,
@@ -238,6 +308,187 @@ impl ActionsInterface for Actions {
     }
 }
 
+/// Mock implementation of the `ClusterInterface`.
+struct Cluster {
+    state: Arc<Mutex<MockState>>,
+}
+
+impl ClusterInterface for Cluster {
+    fn delete_discovery(&self, attrs: &ClusterAttribures, _: Option<SpanContext>) -> Result<()> {
+        let mut state = self.state.lock().expect("MockStore state lock poisoned");
+        state.discoveries.remove(&attrs.cluster_id);
+        state.discoveries_seen.remove(&attrs.cluster_id);
+        Ok(())
+    }
+
+    fn delete_settings(&self, attrs: &ClusterAttribures, _: Option<SpanContext>) -> Result<()> {
+        let key = (attrs.namespace.clone(), attrs.cluster_id.clone());
+        self.state
+            .lock()
+            .expect("MockStore state lock poisoned")
+            .cluster_settings
+            .remove(&key);
+        Ok(())
+    }
+
+    fn delete_state(&self, attrs: &ClusterAttribures, _: Option<SpanContext>) -> Result<()> {
+        let mut state = self.state.lock().expect("MockStore state lock poisoned");
+        let cluster_id = &attrs.cluster_id;
+        state.agents.retain(|key, _| &key.0 != cluster_id);
+        state.agents_info.retain(|key, _| &key.0 != cluster_id);
+        state.clusters_meta.remove(cluster_id);
+        state.nodes.retain(|key, _| &key.0 != cluster_id);
+        state.shards.retain(|key, _| &key.0 != cluster_id);
+        Ok(())
+    }
+
+    fn discovery(
+        &self,
+        attrs: &ClusterAttribures,
+        _: Option<SpanContext>,
+    ) -> Result<Option<ClusterDiscovery>> {
+        let discovery = self
+            .state
+            .lock()
+            .expect("MockStore state lock poisoned")
+            .discoveries
+            .get(&attrs.cluster_id)
+            .cloned();
+        Ok(discovery)
+    }
+
+    fn mark_stale(&self, _attrs: &ClusterAttribures, _: Option<SpanContext>) -> Result<()> {
+        panic!("TODO: MockStore::Cluster::mark_stale")
+    }
+
+    fn namespace(
+        &self,
+        attrs: &ClusterAttribures,
+        _: Option<SpanContext>,
+    ) -> Result<Option<String>> {
+        let namespace = self
+            .state
+            .lock()
+            .expect("MockStore state lock poisoned")
+            .cluster_settings
+            .keys()
+            .find(|(_, cluster_id)| cluster_id == &attrs.cluster_id)
+            .map(|(namespace, _)| namespace.clone());
+        Ok(namespace)
+    }
+
+    fn orchestrate_report(
+        &self,
+        _attrs: &ClusterAttribures,
+        _: Option<SpanContext>,
+    ) -> Result<Option<OrchestrateReport>> {
+        panic!("TODO: MockStore::Cluster::orchestrate_report")
+    }
+
+    fn settings(
+        &self,
+        attrs: &ClusterAttribures,
+        _: Option<SpanContext>,
+    ) -> Result<Option<ClusterSettings>> {
+        let key = (attrs.namespace.clone(), attrs.cluster_id.clone());
+        let settings = self
+            .state
+            .lock()
+            .expect("MockStore state lock poisoned")
+            .cluster_settings
+            .get(&key)
+            .cloned();
+        Ok(settings)
+    }
+}
+
+/// Mock implementation of the `DiscoverySettingsInterface`.
+struct DiscoverySettings {
+    state: Arc<Mutex<MockState>>,
+}
+
+impl DiscoverySettingsInterface for DiscoverySettings {
+    fn delete(
+        &self,
+        _attrs: &DiscoverySettingsAttributes,
+        _name: &str,
+        _: Option<SpanContext>,
+    ) -> Result<()> {
+        panic!("TODO: MockStore::DiscoverySettings::delete")
+    }
+
+    fn get(
+        &self,
+        _attrs: &DiscoverySettingsAttributes,
+        _name: &str,
+        _: Option<SpanContext>,
+    ) -> Result<Option<DiscoverySettingsModel>> {
+        panic!("TODO: MockStore::DiscoverySettings::get")
+    }
+
+    fn history(
+        &self,
+        _attrs: &DiscoverySettingsAttributes,
+        _name: &str,
+        _: Option<SpanContext>,
+    ) -> Result<Cursor<DiscoverySettingsModel>> {
+        panic!("TODO: MockStore::DiscoverySettings::history")
+    }
+
+    fn iter_missing_clusters(
+        &self,
+        attrs: &DiscoverySettingsAttributes,
+        name: &str,
+        seen_before: DateTime<Utc>,
+        _: Option<SpanContext>,
+    ) -> Result<Cursor<ClusterDiscovery>> {
+        let state = self.state.lock().expect("MockStore state lock poisoned");
+        let missing: Vec<Result<ClusterDiscovery>> = state
+            .discoveries_seen
+            .iter()
+            .filter(|(_, seen)| {
+                seen.discovery_namespace == attrs.namespace
+                    && seen.discovery_name == name
+                    && seen.last_seen < seen_before
+            })
+            .filter_map(|(cluster_id, _)| state.discoveries.get(cluster_id).cloned())
+            .map(Ok)
+            .collect();
+        Ok(Cursor(Box::new(missing.into_iter())))
+    }
+
+    fn iter_names(
+        &self,
+        _attrs: &DiscoverySettingsAttributes,
+        _: Option<SpanContext>,
+    ) -> Result<Cursor<String>> {
+        panic!("TODO: MockStore::DiscoverySettings::iter_names")
+    }
+
+    fn mark_clusters_seen(
+        &self,
+        attrs: &DiscoverySettingsAttributes,
+        name: &str,
+        cluster_ids: &[String],
+        _: Option<SpanContext>,
+    ) -> Result<()> {
+        let mut state = self.state.lock().expect("MockStore state lock poisoned");
+        for cluster_id in cluster_ids {
+            // Like the MongoDB update, only existing discovery records are marked.
+            if !state.discoveries.contains_key(cluster_id) {
+                continue;
+            }
+            let seen = DiscoverySeen {
+                discovery_namespace: attrs.namespace.clone(),
+                discovery_name: name.to_string(),
+                last_seen: Utc::now(),
+            };
+            state.discoveries_seen.insert(cluster_id.clone(), seen);
+        }
+        Ok(())
+    }
+}
+
 /// Mock implementation of the `LegacyInterface`.
 struct Legacy {
     state: Arc<Mutex<MockState>>,
@@ -276,6 +527,53 @@ impl LegacyInterface for Legacy {
     }
 }
 
+/// Mock implementation of the `NamespaceInterface`.
+struct Namespace {
+    state: Arc<Mutex<MockState>>,
+}
+
+impl NamespaceInterface for Namespace {
+    fn delete(&self, attrs: &NamespaceAttributes, _: Option<SpanContext>) -> Result<()> {
+        self.state
+            .lock()
+            .expect("MockStore state lock poisoned")
+            .namespaces
+            .remove(&attrs.ns_id);
+        Ok(())
+    }
+
+    fn get(
+        &self,
+        attrs: &NamespaceAttributes,
+        _: Option<SpanContext>,
+    ) -> Result<Option<NamespaceModel>> {
+        let namespace = self
+            .state
+            .lock()
+            .expect("MockStore state lock poisoned")
+            .namespaces
+            .get(&attrs.ns_id)
+            .cloned();
+        Ok(namespace)
+    }
+
+    fn iter_clusters(
+        &self,
+        attrs: &NamespaceAttributes,
+        _: Option<SpanContext>,
+    ) -> Result<Cursor<String>> {
+        let state = self.state.lock().expect("MockStore state lock poisoned");
+        let mut cursor: Vec<String> = state
+            .cluster_settings
+            .keys()
+            .filter(|(namespace, _)| namespace == &attrs.ns_id)
+            .map(|(_, cluster_id)| cluster_id.clone())
+            .collect();
+        cursor.sort();
+        Ok(Cursor::new(cursor.into_iter().map(Ok)))
+    }
+}
+
 /// Mock implementation of the `PersistInterface`.
 struct Persist {
     state: Arc<Mutex<MockState>>,
@@ -304,34 +602,84 @@ impl PersistInterface for Persist {
         panic!("TODO: MockStore::Persist::agent_info")
     }
 
-    fn cluster_discovery(
+    fn alert(&self, _alert: Alert, _: Option<SpanContext>) -> Result<()> {
+        panic!("TODO: MockStore::Persist::alert")
+    }
+
+    fn alert_rule(&self, _rule: AlertRule, _: Option<SpanContext>) -> Result<()> {
+        panic!("TODO: MockStore::Persist::alert_rule")
+    }
+
+    fn cluster_discovery(&self, discovery: ClusterDiscovery, _: Option<SpanContext>) -> Result<()> {
+        self.state
+            .lock()
+            .expect("MockStore state lock poisoned")
+            .discoveries
+            .insert(discovery.cluster_id.clone(), discovery);
+        Ok(())
+    }
+
+    fn cluster_orchestrate_report(
         &self,
-        _discovery: ClusterDiscovery,
+        _report: OrchestrateReport,
         _: Option<SpanContext>,
     ) -> Result<()> {
-        panic!("TODO: MockStore::Persist::cluster_discovery")
+        panic!("TODO: MockStore::Persist::cluster_orchestrate_report")
+    }
+
+    fn cluster_settings(&self, settings: ClusterSettings, _: Option<SpanContext>) -> Result<()> {
+        let key = (settings.namespace.clone(), settings.cluster_id.clone());
+        self.state
+            .lock()
+            .expect("MockStore state lock poisoned")
+            .cluster_settings
+            .insert(key, settings);
+        Ok(())
     }
 
-    fn cluster_settings(&self, _settings: ClusterSettings, _: Option<SpanContext>) -> Result<()> {
-        panic!("TODO: MockStore::Persist::cluster_settings")
+    fn discovery_retry(
+        &self,
+        _settings: DiscoverySettingsModel,
+        _retry: DiscoveryRetry,
+        _retry_at: DateTime<Utc>,
+        _: Option<SpanContext>,
+    ) -> Result<()> {
+        panic!("TODO: MockStore::Persist::discovery_retry")
     }
 
     fn discovery_settings(
         &self,
-        _settings: DiscoverySettings,
+        _settings: DiscoverySettingsModel,
         _: Option<SpanContext>,
     ) -> Result<()> {
         panic!("TODO: MockStore::Persist::discovery_settings")
     }
 
+    fn next_cluster_orchestrate(
+        &self,
+        _settings: ClusterSettings,
+        _: Option<SpanContext>,
+    ) -> Result<()> {
+        panic!("TODO: MockStore::Persist::next_cluster_orchestrate")
+    }
+
     fn next_discovery_run(
         &self,
-        _settings: DiscoverySettings,
+        _settings: DiscoverySettingsModel,
         _: Option<SpanContext>,
     ) -> Result<()> {
         panic!("TODO: MockStore::Persist::next_discovery_run")
     }
 
+    fn namespace(&self, namespace: NamespaceModel, _: Option<SpanContext>) -> Result<()> {
+        self.state
+            .lock()
+            .expect("MockStore state lock poisoned")
+            .namespaces
+            .insert(namespace.ns_id.clone(), namespace);
+        Ok(())
+    }
+
     fn node(&self, _node: Node, _: Option<SpanContext>) -> Result<()> {
         panic!("TODO: MockStore::Persist::node")
     }
@@ -339,4 +687,13 @@ impl PersistInterface for Persist {
     fn shard(&self, _shard: Shard, _: Option<SpanContext>) -> Result<()> {
         panic!("TODO: MockStore::Persist::shard")
     }
+
+    fn webhook_dead_letter(&self, letter: WebhookDeadLetter, _: Option<SpanContext>) -> Result<()> {
+        self.state
+            .lock()
+            .expect("MockStore state lock poisoned")
+            .webhook_dead_letters
+            .push(letter);
+        Ok(())
+    }
 }
@@ -24,6 +24,14 @@ impl Actions {
         Actions { actions, attrs }
     }
 
+    /// Lookup an action by ID.
+    pub fn action<S>(&self, action_id: Uuid, span: S) -> Result<Option<Action>>
+    where
+        S: Into<Option<SpanContext>>,
+    {
+        self.actions.action(&self.attrs, action_id, span.into())
+    }
+
     /// Approve a PENDING_APPROVE action for scheduling.
     pub fn approve<S>(&self, action_id: Uuid, span: S) -> Result<()>
     where
@@ -32,6 +40,18 @@ impl Actions {
         self.actions.approve(&self.attrs, action_id, span.into())
     }
 
+    /// Cancel a PENDING_APPROVE or PENDING_SCHEDULE action so it won't be scheduled.
+    ///
+    /// Actions already sent to agents are not changed by this method.
+    /// Returns `false` if the action was not found in a pending state.
+    /// The method does NOT generate an action transition history record for the event.
+    pub fn cancel<S>(&self, action_id: Uuid, span: S) -> Result<bool>
+    where
+        S: Into<Option<SpanContext>>,
+    {
+        self.actions.cancel(&self.attrs, action_id, span.into())
+    }
+
     /// Disapprove a PENDING_APPROVE action so it won't be scheduled.
     pub fn disapprove<S>(&self, action_id: Uuid, span: S) -> Result<()>
     where
@@ -17,6 +17,14 @@ impl Agent {
         Agent { agent, attrs }
     }
 
+    /// Delete the `Agent` and `AgentInfo` records, if any are stored.
+    pub fn delete<S>(&self, span: S) -> Result<()>
+    where
+        S: Into<Option<SpanContext>>,
+    {
+        self.agent.delete(&self.attrs, span.into())
+    }
+
     /// Query the `Agent` record, if any is stored.
     pub fn get<S>(&self, span: S) -> Result<Option<AgentModel>>
     where
@@ -0,0 +1,60 @@
+use chrono::DateTime;
+use chrono::Utc;
+use opentracingrust::SpanContext;
+
+use replicante_models_core::alerts::Alert;
+use replicante_models_core::alerts::AlertRule;
+
+use crate::backend::AlertRulesImpl;
+use crate::Result;
+
+/// Operate on alert rules and their alerts.
+pub struct AlertRules {
+    attrs: AlertRulesAttributes,
+    rules: AlertRulesImpl,
+}
+
+impl AlertRules {
+    pub(crate) fn new(rules: AlertRulesImpl, attrs: AlertRulesAttributes) -> AlertRules {
+        AlertRules { attrs, rules }
+    }
+
+    /// Query the alert for the named rule and cluster, if any is stored.
+    pub fn alert<S>(&self, name: &str, cluster_id: &str, span: S) -> Result<Option<Alert>>
+    where
+        S: Into<Option<SpanContext>>,
+    {
+        self.rules.alert(&self.attrs, name, cluster_id, span.into())
+    }
+
+    /// Query the named AlertRule object, if any is stored.
+    pub fn get<S>(&self, name: &str, span: S) -> Result<Option<AlertRule>>
+    where
+        S: Into<Option<SpanContext>>,
+    {
+        self.rules.get(&self.attrs, name, span.into())
+    }
+
+    /// Persist an alert only if its record was not updated since `updated_ts`.
+    ///
+    /// Returns `true` if the alert record was updated.
+    /// This allows the events follower and background resolver to update alerts
+    /// without overriding changes made concurrently by each other.
+    pub fn update_unchanged<S>(
+        &self,
+        alert: Alert,
+        updated_ts: DateTime<Utc>,
+        span: S,
+    ) -> Result<bool>
+    where
+        S: Into<Option<SpanContext>>,
+    {
+        self.rules
+            .update_unchanged(&self.attrs, alert, updated_ts, span.into())
+    }
+}
+
+/// Attributes attached to all alert rules operations.
+pub struct AlertRulesAttributes {
+    pub namespace: String,
+}
@@ -2,6 +2,7 @@ use opentracingrust::SpanContext;
 
 use replicante_models_core::cluster::discovery::ClusterDiscovery;
 use replicante_models_core::cluster::ClusterSettings;
+use replicante_models_core::cluster::OrchestrateReport;
 
 use crate::backend::ClusterImpl;
 use crate::Result;
@@ -17,6 +18,40 @@ impl Cluster {
         Cluster { cluster, attrs }
     }
 
+    /// Delete the `ClusterDiscovery` record, if any is stored.
+    ///
+    /// Clusters without a discovery record are no longer scheduled for refresh.
+    pub fn delete_discovery<S>(&self, span: S) -> Result<()>
+    where
+        S: Into<Option<SpanContext>>,
+    {
+        self.cluster.delete_discovery(&self.attrs, span.into())
+    }
+
+    /// Delete the `ClusterSettings` record, if any is stored.
+    pub fn delete_settings<S>(&self, span: S) -> Result<()>
+    where
+        S: Into<Option<SpanContext>>,
+    {
+        self.cluster.delete_settings(&self.attrs, span.into())
+    }
+
+    /// Delete all records describing the state of the cluster.
+    ///
+    /// List of models that are deleted:
+    ///
+    ///   * Agent
+    ///   * AgentInfo
+    ///   * ClusterMeta
+    ///   * Node
+    ///   * Shard
+    pub fn delete_state<S>(&self, span: S) -> Result<()>
+    where
+        S: Into<Option<SpanContext>>,
+    {
+        self.cluster.delete_state(&self.attrs, span.into())
+    }
+
     /// Query a `ClusterDiscovery` record, if any is stored.
     pub fn discovery<S>(&self, span: S) -> Result<Option<ClusterDiscovery>>
     where
@@ -44,6 +79,9 @@ impl Cluster {
     /// but we still have the state the node was in before the agent failed.
     /// Instead of deliting this state, which would otherwise make the node
     /// appear new when it comes back online, we mark it as stale.
+    ///
+    /// Records that are still stale once a refresh reached all relevant agents
+    /// are no longer part of the cluster and are deleted by the cluster fetcher.
     pub fn mark_stale<S>(&self, span: S) -> Result<()>
     where
         S: Into<Option<SpanContext>>,
@@ -51,6 +89,25 @@ impl Cluster {
         self.cluster.mark_stale(&self.attrs, span.into())
     }
 
+    /// Query the ID of the namespace the cluster is configured in, if settings are stored.
+    ///
+    /// Clusters are looked up by ID only so callers that don't know the namespace yet
+    /// can find out which one to operate in.
+    pub fn namespace<S>(&self, span: S) -> Result<Option<String>>
+    where
+        S: Into<Option<SpanContext>>,
+    {
+        self.cluster.namespace(&self.attrs, span.into())
+    }
+
+    /// Query the report of the last orchestration run for the cluster, if any is stored.
+    pub fn orchestrate_report<S>(&self, span: S) -> Result<Option<OrchestrateReport>>
+    where
+        S: Into<Option<SpanContext>>,
+    {
+        self.cluster.orchestrate_report(&self.attrs, span.into())
+    }
+
     /// Query a `ClusterSettings` record, if any is stored.
     pub fn settings<S>(&self, span: S) -> Result<Option<ClusterSettings>>
     where
@@ -1,5 +1,10 @@
+use chrono::DateTime;
+use chrono::Utc;
 use opentracingrust::SpanContext;
 
+use replicante_models_core::cluster::discovery::ClusterDiscovery;
+use replicante_models_core::cluster::discovery::DiscoverySettings as DiscoverySettingsModel;
+
 use crate::backend::DiscoverySettingsImpl;
 use crate::Cursor;
 use crate::Result;
@@ -18,7 +23,7 @@ impl DiscoverySettings {
         DiscoverySettings { attrs, settings }
     }
 
-    /// Delete the named DiscoverySettings object.
+    /// Delete the named DiscoverySettings object, along with its revision history.
     pub fn delete<S>(&self, name: &str, span: S) -> Result<()>
     where
         S: Into<Option<SpanContext>>,
@@ -26,6 +31,40 @@ impl DiscoverySettings {
         self.settings.delete(&self.attrs, name, span.into())
     }
 
+    /// Query the named DiscoverySettings object, if any is stored.
+    pub fn get<S>(&self, name: &str, span: S) -> Result<Option<DiscoverySettingsModel>>
+    where
+        S: Into<Option<SpanContext>>,
+    {
+        self.settings.get(&self.attrs, name, span.into())
+    }
+
+    /// Iterate over all stored revisions of the named DiscoverySettings object.
+    ///
+    /// Revisions are returned from the newest to the oldest.
+    pub fn history<S>(&self, name: &str, span: S) -> Result<Cursor<DiscoverySettingsModel>>
+    where
+        S: Into<Option<SpanContext>>,
+    {
+        self.settings.history(&self.attrs, name, span.into())
+    }
+
+    /// Iterate over clusters discovered by the named DiscoverySettings but not seen since a time.
+    ///
+    /// Only clusters previously marked as seen with `mark_clusters_seen` are returned.
+    pub fn iter_missing_clusters<S>(
+        &self,
+        name: &str,
+        seen_before: DateTime<Utc>,
+        span: S,
+    ) -> Result<Cursor<ClusterDiscovery>>
+    where
+        S: Into<Option<SpanContext>>,
+    {
+        self.settings
+            .iter_missing_clusters(&self.attrs, name, seen_before, span.into())
+    }
+
     /// Iterate over the names of DiscoverySettings objects in the namespace.
     ///
     /// Names are returned in ascending alphabetical order.
@@ -35,6 +74,15 @@ impl DiscoverySettings {
     {
         self.settings.iter_names(&self.attrs, span.into())
     }
+
+    /// Record that the named DiscoverySettings returned the given clusters just now.
+    pub fn mark_clusters_seen<S>(&self, name: &str, cluster_ids: &[String], span: S) -> Result<()>
+    where
+        S: Into<Option<SpanContext>>,
+    {
+        self.settings
+            .mark_clusters_seen(&self.attrs, name, cluster_ids, span.into())
+    }
 }
 
 /// Attributes attached to all discovery settings operations.
@@ -1,6 +1,10 @@
 use opentracingrust::SpanContext;
 
-use replicante_models_core::cluster::discovery::DiscoverySettings;
+use replicante_models_core::alerts::Alert;
+use replicante_models_core::alerts::AlertRule;
+use replicante_models_core::cluster::discovery::DiscoveryRun;
+use replicante_models_core::cluster::ClusterSettings;
+use replicante_models_core::scope::Namespace;
 
 use crate::backend::GlobalSearchImpl;
 use crate::Cursor;
@@ -15,11 +19,43 @@ impl GlobalSearch {
         GlobalSearch { search }
     }
 
-    /// Iterate over `DiscoverySettings` waiting to be scheduled.
-    pub fn discoveries_to_run<S>(&self, span: S) -> Result<Cursor<DiscoverySettings>>
+    /// Iterate over all `Alert`s that are firing or have a state change event to emit.
+    pub fn active_alerts<S>(&self, span: S) -> Result<Cursor<Alert>>
+    where
+        S: Into<Option<SpanContext>>,
+    {
+        self.search.active_alerts(span.into())
+    }
+
+    /// Iterate over all `AlertRule`s across all namespaces.
+    pub fn alert_rules<S>(&self, span: S) -> Result<Cursor<AlertRule>>
+    where
+        S: Into<Option<SpanContext>>,
+    {
+        self.search.alert_rules(span.into())
+    }
+
+    /// Iterate over `ClusterSettings` waiting to be orchestrated.
+    pub fn clusters_to_orchestrate<S>(&self, span: S) -> Result<Cursor<ClusterSettings>>
+    where
+        S: Into<Option<SpanContext>>,
+    {
+        self.search.clusters_to_orchestrate(span.into())
+    }
+
+    /// Iterate over `DiscoverySettings` waiting to be scheduled, with any pending retry state.
+    pub fn discoveries_to_run<S>(&self, span: S) -> Result<Cursor<DiscoveryRun>>
     where
         S: Into<Option<SpanContext>>,
     {
         self.search.discoveries_to_run(span.into())
     }
+
+    /// Iterate over all `Namespace`s, sorted by ID.
+    pub fn namespaces<S>(&self, span: S) -> Result<Cursor<Namespace>>
+    where
+        S: Into<Option<SpanContext>>,
+    {
+        self.search.namespaces(span.into())
+    }
 }
@@ -13,10 +13,12 @@ use crate::Result;
 pub mod actions;
 pub mod agent;
 pub mod agents;
+pub mod alert_rules;
 pub mod cluster;
 pub mod discovery_settings;
 pub mod global_search;
 pub mod legacy;
+pub mod namespace;
 pub mod node;
 pub mod nodes;
 pub mod persist;
@@ -26,10 +28,12 @@ pub mod shards;
 use self::actions::Actions;
 use self::agent::Agent;
 use self::agents::Agents;
+use self::alert_rules::AlertRules;
 use self::cluster::Cluster;
 use self::discovery_settings::DiscoverySettings;
 use self::global_search::GlobalSearch;
 use self::legacy::Legacy;
+use self::namespace::Namespace;
 use self::node::Node;
 use self::nodes::Nodes;
 use self::persist::Persist;
@@ -102,6 +106,13 @@ impl Store {
         Agents::new(agents, attrs)
     }
 
+    /// Operate on AlertRule objects, and their alerts, in a namespace.
+    pub fn alert_rules(&self, namespace: String) -> AlertRules {
+        let rules = self.store.alert_rules();
+        let attrs = self::alert_rules::AlertRulesAttributes { namespace };
+        AlertRules::new(rules, attrs)
+    }
+
     /// Operate on cluster-level models.
     pub fn cluster(&self, namespace: String, cluster_id: String) -> Cluster {
         let cluster = self.store.cluster();
@@ -131,6 +142,13 @@ impl Store {
         Legacy::new(legacy)
     }
 
+    /// Operate on the namespace identified by the provided ns_id.
+    pub fn namespace(&self, ns_id: String) -> Namespace {
+        let namespace = self.store.namespace();
+        let attrs = self::namespace::NamespaceAttributes { ns_id };
+        Namespace::new(namespace, attrs)
+    }
+
     /// Operate on the node identified by the provided cluster_id and node_id.
     pub fn node(&self, cluster_id: String, node_id: String) -> Node {
         let node = self.store.node();
@@ -0,0 +1,53 @@
+use opentracingrust::SpanContext;
+
+use replicante_models_core::scope::Namespace as NamespaceModel;
+
+use crate::backend::NamespaceImpl;
+use crate::Cursor;
+use crate::Result;
+
+/// Operate on the namespace identified by the provided ns_id.
+pub struct Namespace {
+    attrs: NamespaceAttributes,
+    namespace: NamespaceImpl,
+}
+
+impl Namespace {
+    pub(crate) fn new(namespace: NamespaceImpl, attrs: NamespaceAttributes) -> Namespace {
+        Namespace { attrs, namespace }
+    }
+
+    /// Delete the `Namespace` record.
+    ///
+    /// Objects scoped to the namespace (cluster and discovery settings, ...) are not deleted.
+    /// Callers should check no cluster is in the namespace with `iter_clusters` first.
+    pub fn delete<S>(&self, span: S) -> Result<()>
+    where
+        S: Into<Option<SpanContext>>,
+    {
+        self.namespace.delete(&self.attrs, span.into())
+    }
+
+    /// Query the `Namespace` record, if any is stored.
+    pub fn get<S>(&self, span: S) -> Result<Option<NamespaceModel>>
+    where
+        S: Into<Option<SpanContext>>,
+    {
+        self.namespace.get(&self.attrs, span.into())
+    }
+
+    /// Iterate over the IDs of clusters with `ClusterSettings` in the namespace.
+    ///
+    /// IDs are returned in ascending alphabetical order.
+    pub fn iter_clusters<S>(&self, span: S) -> Result<Cursor<String>>
+    where
+        S: Into<Option<SpanContext>>,
+    {
+        self.namespace.iter_clusters(&self.attrs, span.into())
+    }
+}
+
+/// Attributes attached to all namespace operations.
+pub struct NamespaceAttributes {
+    pub ns_id: String,
+}
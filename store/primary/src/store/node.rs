@@ -16,6 +16,14 @@ impl Node {
         Node { node, attrs }
     }
 
+    /// Delete the `Node` record, if any is stored.
+    pub fn delete<S>(&self, span: S) -> Result<()>
+    where
+        S: Into<Option<SpanContext>>,
+    {
+        self.node.delete(&self.attrs, span.into())
+    }
+
     /// Query the `Node` record, if any is stored.
     pub fn get<S>(&self, span: S) -> Result<Option<NodeModel>>
     where
@@ -27,6 +27,16 @@ impl Nodes {
         self.nodes.iter(&self.attrs, span.into())
     }
 
+    /// Iterate over *stale* nodes in a cluster.
+    ///
+    /// See `Store::cluster::mark_stale` for why nodes are marked stale.
+    pub fn iter_stale<S>(&self, span: S) -> Result<Cursor<NodeModel>>
+    where
+        S: Into<Option<SpanContext>>,
+    {
+        self.nodes.iter_stale(&self.attrs, span.into())
+    }
+
     /// Enumerate the different kinds of *active* nodes in the cluster.
     ///
     /// Active nodes are those not stale.
@@ -1,3 +1,5 @@
+use chrono::DateTime;
+use chrono::Utc;
 use opentracingrust::SpanContext;
 
 use replicante_models_core::actions::Action as ActionModel;
@@ -5,9 +7,15 @@ use replicante_models_core::agent::Agent as AgentModel;
 use replicante_models_core::agent::AgentInfo as AgentInfoModel;
 use replicante_models_core::agent::Node as NodeModel;
 use replicante_models_core::agent::Shard as ShardModel;
+use replicante_models_core::alerts::Alert as AlertModel;
+use replicante_models_core::alerts::AlertRule as AlertRuleModel;
 use replicante_models_core::cluster::discovery::ClusterDiscovery as ClusterDiscoveryModel;
+use replicante_models_core::cluster::discovery::DiscoveryRetry;
 use replicante_models_core::cluster::discovery::DiscoverySettings;
 use replicante_models_core::cluster::ClusterSettings;
+use replicante_models_core::cluster::OrchestrateReport;
+use replicante_models_core::scope::Namespace as NamespaceModel;
+use replicante_models_core::webhooks::WebhookDeadLetter;
 
 use crate::backend::PersistImpl;
 use crate::Result;
@@ -46,6 +54,22 @@ impl Persist {
         self.persist.agent_info(agent, span.into())
     }
 
+    /// Create or update an Alert record.
+    pub fn alert<S>(&self, alert: AlertModel, span: S) -> Result<()>
+    where
+        S: Into<Option<SpanContext>>,
+    {
+        self.persist.alert(alert, span.into())
+    }
+
+    /// Create or update an AlertRule record.
+    pub fn alert_rule<S>(&self, rule: AlertRuleModel, span: S) -> Result<()>
+    where
+        S: Into<Option<SpanContext>>,
+    {
+        self.persist.alert_rule(rule, span.into())
+    }
+
     /// Create or update a ClusterDiscovery record.
     pub fn cluster_discovery<S>(&self, discovery: ClusterDiscoveryModel, span: S) -> Result<()>
     where
@@ -62,7 +86,35 @@ impl Persist {
         self.persist.cluster_settings(settings, span.into())
     }
 
+    /// Update the last orchestration report of a ClusterSettings record.
+    pub fn cluster_orchestrate_report<S>(&self, report: OrchestrateReport, span: S) -> Result<()>
+    where
+        S: Into<Option<SpanContext>>,
+    {
+        self.persist.cluster_orchestrate_report(report, span.into())
+    }
+
+    /// Schedule a retry of a failed discovery run at the given time.
+    ///
+    /// The retry state is returned by `GlobalSearch::discoveries_to_run` once the retry is due
+    /// and is cleared when the discovery is next scheduled.
+    pub fn discovery_retry<S>(
+        &self,
+        settings: DiscoverySettings,
+        retry: DiscoveryRetry,
+        retry_at: DateTime<Utc>,
+        span: S,
+    ) -> Result<()>
+    where
+        S: Into<Option<SpanContext>>,
+    {
+        self.persist
+            .discovery_retry(settings, retry, retry_at, span.into())
+    }
+
     /// Create or update a cluster DiscoverySettings record.
+    ///
+    /// The settings are also recorded in the revision history under their current revision.
     pub fn discovery_settings<S>(&self, settings: DiscoverySettings, span: S) -> Result<()>
     where
         S: Into<Option<SpanContext>>,
@@ -70,9 +122,18 @@ impl Persist {
         self.persist.discovery_settings(settings, span.into())
     }
 
+    /// Create or update a Namespace record.
+    pub fn namespace<S>(&self, namespace: NamespaceModel, span: S) -> Result<()>
+    where
+        S: Into<Option<SpanContext>>,
+    {
+        self.persist.namespace(namespace, span.into())
+    }
+
     /// Update the next_run of a cluster DiscoverySettings record.
     ///
     /// The new value is based on the current time + settings.interval.
+    /// Any pending retry state is cleared as it is consumed by the scheduled run.
     pub fn next_discovery_run<S>(&self, settings: DiscoverySettings, span: S) -> Result<()>
     where
         S: Into<Option<SpanContext>>,
@@ -80,6 +141,16 @@ impl Persist {
         self.persist.next_discovery_run(settings, span.into())
     }
 
+    /// Update the next_orchestrate of a ClusterSettings record.
+    ///
+    /// The new value is based on the current time + settings.interval.
+    pub fn next_cluster_orchestrate<S>(&self, settings: ClusterSettings, span: S) -> Result<()>
+    where
+        S: Into<Option<SpanContext>>,
+    {
+        self.persist.next_cluster_orchestrate(settings, span.into())
+    }
+
     /// Creat or update a Node record.
     pub fn node<S>(&self, node: NodeModel, span: S) -> Result<()>
     where
@@ -95,4 +166,12 @@ impl Persist {
     {
         self.persist.shard(shard, span.into())
     }
+
+    /// Record an event that could not be delivered to a webhook endpoint.
+    pub fn webhook_dead_letter<S>(&self, letter: WebhookDeadLetter, span: S) -> Result<()>
+    where
+        S: Into<Option<SpanContext>>,
+    {
+        self.persist.webhook_dead_letter(letter, span.into())
+    }
 }
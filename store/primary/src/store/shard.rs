@@ -16,6 +16,14 @@ impl Shard {
         Shard { shard, attrs }
     }
 
+    /// Delete the `Shard` record, if any is stored.
+    pub fn delete<S>(&self, span: S) -> Result<()>
+    where
+        S: Into<Option<SpanContext>>,
+    {
+        self.shard.delete(&self.attrs, span.into())
+    }
+
     /// Query the `Shard` record, if any is stored.
     pub fn get<S>(&self, span: S) -> Result<Option<ShardModel>>
     where
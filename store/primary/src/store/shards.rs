@@ -37,6 +37,16 @@ impl Shards {
     {
         self.shards.iter(&self.attrs, span.into())
     }
+
+    /// Iterate over *stale* shards in a cluster.
+    ///
+    /// See `Store::cluster::mark_stale` for why shards are marked stale.
+    pub fn iter_stale<S>(&self, span: S) -> Result<Cursor<ShardModel>>
+    where
+        S: Into<Option<SpanContext>>,
+    {
+        self.shards.iter_stale(&self.attrs, span.into())
+    }
 }
 
 /// Attributes attached to all shards operations.
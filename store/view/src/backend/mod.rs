@@ -13,12 +13,14 @@ use replicante_externals_mongodb::admin::ValidationResult;
 use replicante_models_core::actions::Action;
 use replicante_models_core::actions::ActionHistory;
 use replicante_models_core::admin::Version;
+use replicante_models_core::agent::ShardLagSample;
 use replicante_models_core::events::Event;
 use replicante_service_healthcheck::HealthChecks;
 
 use crate::store::actions::SearchFilters as ActionsSearchFilters;
 use crate::store::events::EventsFilters;
 use crate::store::events::EventsOptions;
+use crate::store::IntervalCount;
 use crate::Config;
 use crate::Cursor;
 use crate::Result;
@@ -151,6 +153,7 @@ arc_interface! {
         fn actions(&self, cluster_id: String) -> ActionsImpl;
         fn events(&self) -> EventsImpl;
         fn persist(&self) -> PersistImpl;
+        fn shards(&self, cluster_id: String) -> ShardsImpl;
     }
 }
 
@@ -165,6 +168,12 @@ box_interface! {
 
     interface {
         fn action(&self, action_id: Uuid, span: Option<SpanContext>) -> Result<Option<Action>>;
+        fn count_by_state(
+            &self,
+            filters: ActionsSearchFilters,
+            interval_ms: i64,
+            span: Option<SpanContext>,
+        ) -> Result<Vec<IntervalCount>>;
         fn finish_history(
             &self,
             action_id: Uuid,
@@ -194,12 +203,19 @@ box_interface! {
     trait EventsInterface,
 
     interface {
+        fn count_by_code(
+            &self,
+            filters: EventsFilters,
+            interval_ms: i64,
+            span: Option<SpanContext>,
+        ) -> Result<Vec<IntervalCount>>;
         fn range(
             &self,
             filters: EventsFilters,
             options: EventsOptions,
             span: Option<SpanContext>,
         ) -> Result<Cursor<Event>>;
+        fn valid_code_regex(&self, regex: String, span: Option<SpanContext>) -> Result<bool>;
     }
 }
 
@@ -240,6 +256,31 @@ box_interface! {
             span: Option<SpanContext>,
         ) -> Result<()>;
         fn event(&self, event: Event, span: Option<SpanContext>) -> Result<()>;
+        fn shard_lag_sample(
+            &self,
+            sample: ShardLagSample,
+            span: Option<SpanContext>,
+        ) -> Result<()>;
+    }
+}
+
+box_interface! {
+    /// Dynamic dispatch shards operations to a backend-specific implementation.
+    struct ShardsImpl,
+
+    /// Definition of shards operations.
+    ///
+    /// See `store::shards::Shards` for descriptions of methods.
+    trait ShardsInterface,
+
+    interface {
+        fn lag(
+            &self,
+            shard_id: String,
+            from: DateTime<Utc>,
+            until: DateTime<Utc>,
+            span: Option<SpanContext>,
+        ) -> Result<Cursor<ShardLagSample>>;
     }
 }
 
@@ -3,6 +3,7 @@ use std::sync::Arc;
 use bson::doc;
 use bson::Bson;
 use bson::DateTime as UtcDateTime;
+use bson::Document;
 use chrono::DateTime;
 use chrono::Utc;
 use failure::Fail;
@@ -13,6 +14,7 @@ use opentracingrust::SpanContext;
 use opentracingrust::Tracer;
 use uuid::Uuid;
 
+use replicante_externals_mongodb::operations::aggregate;
 use replicante_externals_mongodb::operations::find_one;
 use replicante_externals_mongodb::operations::find_with_options;
 use replicante_externals_mongodb::operations::update_many;
@@ -25,11 +27,38 @@ use super::constants::COLLECTION_ACTIONS_HISTORY;
 use super::constants::MAX_ACTIONS_SEARCH;
 use super::document::ActionDocument;
 use super::document::ActionHistoryDocument;
+use super::interval::count_pipeline;
+use super::interval::IntervalCountDocument;
 use crate::store::actions::SearchFilters;
+use crate::store::IntervalCount;
 use crate::Cursor;
 use crate::ErrorKind;
 use crate::Result;
 
+/// Build the MongoDB filter document matching the given search filters.
+fn search_filter(cluster_id: &str, search: SearchFilters) -> Document {
+    let from = search.from;
+    let until = search.until;
+    let mut filters = vec![
+        Bson::from(doc! {"cluster_id": {"$eq": cluster_id}}),
+        Bson::from(doc! {"created_ts": {"$gte": from}}),
+        Bson::from(doc! {"created_ts": {"$lte": until}}),
+    ];
+    if let Some(action_kind) = search.action_kind {
+        let action_kind = regex::escape(&action_kind);
+        filters.push(Bson::from(doc! {"kind": {"$regex": action_kind}}));
+    }
+    if let Some(action_state) = search.action_state {
+        let action_state = regex::escape(&action_state);
+        filters.push(Bson::from(doc! {"state": {"$regex": action_state}}));
+    }
+    if let Some(node_id) = search.node_id {
+        let node_id = regex::escape(&node_id);
+        filters.push(Bson::from(doc! {"node_id": {"$regex": node_id}}));
+    }
+    doc! {"$and": filters}
+}
+
 /// Actions operations implementation using MongoDB.
 pub struct Actions {
     client: Client,
@@ -69,6 +98,29 @@ impl ActionsInterface for Actions {
         Ok(action.map(Action::from))
     }
 
+    fn count_by_state(
+        &self,
+        search: SearchFilters,
+        interval_ms: i64,
+        span: Option<SpanContext>,
+    ) -> Result<Vec<IntervalCount>> {
+        let origin = search.from;
+        let filter = search_filter(&self.cluster_id, search);
+        let pipeline = count_pipeline(filter, "state", "created_ts", origin, interval_ms);
+        let collection = self
+            .client
+            .database(&self.db)
+            .collection(COLLECTION_ACTIONS);
+        let cursor = aggregate(collection, pipeline, span, self.tracer.as_deref())
+            .with_context(|_| ErrorKind::MongoDBOperation)?;
+        let mut counts = Vec::new();
+        for count in cursor {
+            let count: IntervalCountDocument = count.with_context(|_| ErrorKind::MongoDBCursor)?;
+            counts.push(count.into());
+        }
+        Ok(counts)
+    }
+
     fn finish_history(
         &self,
         action_id: Uuid,
@@ -127,29 +179,8 @@ impl ActionsInterface for Actions {
         options.limit = Some(MAX_ACTIONS_SEARCH);
         options.sort = Some(doc! {"created_ts": -1});
 
-        // Apply filters.
-        let from = search.from;
-        let until = search.until;
-        let mut filters = vec![
-            Bson::from(doc! {"cluster_id": {"$eq": &self.cluster_id}}),
-            Bson::from(doc! {"created_ts": {"$gte": from}}),
-            Bson::from(doc! {"created_ts": {"$lte": until}}),
-        ];
-        if let Some(action_kind) = search.action_kind {
-            let action_kind = regex::escape(&action_kind);
-            filters.push(Bson::from(doc! {"kind": {"$regex": action_kind}}));
-        }
-        if let Some(action_state) = search.action_state {
-            let action_state = regex::escape(&action_state);
-            filters.push(Bson::from(doc! {"state": {"$regex": action_state}}));
-        }
-        if let Some(node_id) = search.node_id {
-            let node_id = regex::escape(&node_id);
-            filters.push(Bson::from(doc! {"node_id": {"$regex": node_id}}));
-        }
-        let filters = doc! {"$and": filters};
-
         // Execute the query.
+        let filters = search_filter(&self.cluster_id, search);
         let collection = self
             .client
             .database(&self.db)
@@ -7,7 +7,9 @@ use lazy_static::lazy_static;
 pub const COLLECTION_ACTIONS: &str = "actions";
 pub const COLLECTION_ACTIONS_HISTORY: &str = "actions_history";
 pub const COLLECTION_EVENTS: &str = "events";
+pub const COLLECTION_SHARDS_LAG: &str = "shards_lag";
 pub const MAX_ACTIONS_SEARCH: i64 = 100;
+pub const MAX_SHARD_LAG_SAMPLES: i64 = 10000;
 
 lazy_static! {
     pub static ref EVENTS_FILTER_NOT_SNAPSHOT: Document = doc! {"$nin": [
@@ -16,12 +18,15 @@ lazy_static! {
         "SNAPSHOT_DISCOVERY",
         "SNAPSHOT_NODE",
         "SNAPSHOT_SHARD",
+        // High-volume sampling events are excluded along with snapshots.
+        "SHARD_LAG_SAMPLE",
     ]};
     pub static ref VALIDATE_EXPECTED_COLLECTIONS: HashSet<&'static str> = {
         let mut set = HashSet::new();
         set.insert(COLLECTION_ACTIONS);
         set.insert(COLLECTION_ACTIONS_HISTORY);
         set.insert(COLLECTION_EVENTS);
+        set.insert(COLLECTION_SHARDS_LAG);
         set
     };
 }
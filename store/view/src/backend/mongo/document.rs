@@ -9,6 +9,8 @@ use replicante_models_core::actions::ActionHistory;
 use replicante_models_core::actions::ActionHistoryOrigin;
 use replicante_models_core::actions::ActionRequester;
 use replicante_models_core::actions::ActionState;
+use replicante_models_core::agent::CommitOffset;
+use replicante_models_core::agent::ShardLagSample;
 use replicante_models_core::events::Event;
 use replicante_models_core::events::Payload;
 
@@ -22,6 +24,8 @@ pub struct ActionDocument {
 
     // Record attributes.
     pub created_ts: DateTime,
+    #[serde(default)]
+    pub deadline_ts: Option<DateTime>,
     pub finished_ts: Option<DateTime>,
     pub headers: HashMap<String, String>,
     pub kind: String,
@@ -49,6 +53,7 @@ impl From<Action> for ActionDocument {
             args,
             cluster_id: action.cluster_id,
             created_ts: DateTime::from(action.created_ts),
+            deadline_ts: action.deadline_ts.map(DateTime::from),
             finished_ts: action.finished_ts.map(DateTime::from),
             headers: action.headers,
             kind: action.kind,
@@ -79,6 +84,7 @@ impl From<ActionDocument> for Action {
             args,
             cluster_id: action.cluster_id,
             created_ts: action.created_ts.0,
+            deadline_ts: action.deadline_ts.map(|ts| ts.0),
             finished_ts: action.finished_ts.map(|ts| ts.0),
             headers: action.headers,
             kind: action.kind,
@@ -177,3 +183,43 @@ impl From<EventDocument> for Event {
         }
     }
 }
+
+/// Wrap a `ShardLagSample` with MongoDB specific types.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct ShardLagSampleDocument {
+    // ID attributes.
+    pub cluster_id: String,
+    pub shard_id: String,
+    pub node_id: String,
+
+    // Sample attributes.
+    pub commit_offset: Option<CommitOffset>,
+    pub lag: Option<CommitOffset>,
+    pub timestamp: DateTime,
+}
+
+impl From<ShardLagSample> for ShardLagSampleDocument {
+    fn from(sample: ShardLagSample) -> ShardLagSampleDocument {
+        ShardLagSampleDocument {
+            cluster_id: sample.cluster_id,
+            shard_id: sample.shard_id,
+            node_id: sample.node_id,
+            commit_offset: sample.commit_offset,
+            lag: sample.lag,
+            timestamp: DateTime::from(sample.timestamp),
+        }
+    }
+}
+
+impl From<ShardLagSampleDocument> for ShardLagSample {
+    fn from(sample: ShardLagSampleDocument) -> ShardLagSample {
+        ShardLagSample {
+            cluster_id: sample.cluster_id,
+            commit_offset: sample.commit_offset,
+            lag: sample.lag,
+            node_id: sample.node_id,
+            shard_id: sample.shard_id,
+            timestamp: sample.timestamp.0,
+        }
+    }
+}
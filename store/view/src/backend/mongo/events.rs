@@ -2,13 +2,20 @@ use std::sync::Arc;
 
 use bson::doc;
 use bson::Bson;
+use bson::Document;
+use chrono::TimeZone;
+use chrono::Utc;
 use failure::Fail;
 use failure::ResultExt;
+use mongodb::error::Error as MongoError;
+use mongodb::error::ErrorKind as MongoErrorKind;
 use mongodb::options::FindOptions;
 use mongodb::sync::Client;
 use opentracingrust::SpanContext;
 use opentracingrust::Tracer;
 
+use replicante_externals_mongodb::operations::aggregate;
+use replicante_externals_mongodb::operations::find_one;
 use replicante_externals_mongodb::operations::find_with_options;
 use replicante_models_core::events::Event;
 
@@ -16,12 +23,76 @@ use super::super::EventsInterface;
 use super::constants::COLLECTION_EVENTS;
 use super::constants::EVENTS_FILTER_NOT_SNAPSHOT;
 use super::document::EventDocument;
+use super::interval::count_pipeline;
+use super::interval::IntervalCountDocument;
+use crate::store::events::EventCodeMatch;
 use crate::store::events::EventsFilters;
 use crate::store::events::EventsOptions;
+use crate::store::events::SYSTEM_EVENTS_TAG;
+use crate::store::IntervalCount;
 use crate::Cursor;
 use crate::ErrorKind;
 use crate::Result;
 
+/// MongoDB error codes returned for invalid `$regex` expressions (`BadValue` and `51091`).
+const INVALID_REGEX_ERROR_CODES: &[i32] = &[2, 51091];
+
+/// Build the MongoDB filter document matching the given events filters.
+fn events_filter(filters: EventsFilters) -> Document {
+    let mut filter = Vec::new();
+    if !filters.cluster_ids.is_empty() {
+        // Include events without a cluster ID to support cmobined system events.
+        filter.push(Bson::from(doc! {"$or": [
+            {"payload.cluster_id": {"$in": filters.cluster_ids}},
+            {"payload.cluster_id": {"$exists": false}},
+        ]}));
+    }
+    if !filters.events.is_empty() {
+        let events: Vec<Bson> = filters
+            .events
+            .iter()
+            .map(|event| match event {
+                EventCodeMatch::Code(code) => Bson::from(doc! {"event": {"$eq": code}}),
+                _ => {
+                    let regex = event.regex().expect("event code patterns have a regex");
+                    Bson::from(doc! {"event": {"$regex": regex}})
+                }
+            })
+            .collect();
+        filter.push(Bson::from(doc! {"$or": events}));
+    }
+    if filters.exclude_snapshots {
+        filter.push(Bson::from(doc! {
+            "event": EVENTS_FILTER_NOT_SNAPSHOT.clone()
+        }));
+    }
+    if filters.exclude_system_events {
+        filter.push(Bson::from(doc! {"payload.cluster_id": {"$exists": false}}));
+    }
+    if let Some(start_from) = filters.start_from {
+        filter.push(Bson::from(doc! {"timestamp": {"$gte": start_from}}));
+    }
+    if let Some(stop_at) = filters.stop_at {
+        filter.push(Bson::from(doc! {"timestamp": {"$lte": stop_at}}));
+    }
+    for tag in filters.tags {
+        let cluster = if tag == SYSTEM_EVENTS_TAG {
+            doc! {"payload.cluster_id": {"$exists": false}}
+        } else {
+            doc! {"payload.cluster_id": {"$eq": &tag}}
+        };
+        filter.push(Bson::from(doc! {"$or": [
+            {"event": {"$eq": &tag}},
+            cluster,
+        ]}));
+    }
+    if !filter.is_empty() {
+        doc! {"$and": filter}
+    } else {
+        doc! {}
+    }
+}
+
 /// Events operations implementation using MongoDB.
 pub struct Events {
     client: Client,
@@ -40,6 +111,26 @@ impl Events {
 }
 
 impl EventsInterface for Events {
+    fn count_by_code(
+        &self,
+        filters: EventsFilters,
+        interval_ms: i64,
+        span: Option<SpanContext>,
+    ) -> Result<Vec<IntervalCount>> {
+        let origin = filters.start_from.unwrap_or_else(|| Utc.timestamp(0, 0));
+        let filter = events_filter(filters);
+        let pipeline = count_pipeline(filter, "event", "timestamp", origin, interval_ms);
+        let collection = self.client.database(&self.db).collection(COLLECTION_EVENTS);
+        let cursor = aggregate(collection, pipeline, span, self.tracer.as_deref())
+            .with_context(|_| ErrorKind::MongoDBOperation)?;
+        let mut counts = Vec::new();
+        for count in cursor {
+            let count: IntervalCountDocument = count.with_context(|_| ErrorKind::MongoDBCursor)?;
+            counts.push(count.into());
+        }
+        Ok(counts)
+    }
+
     fn range(
         &self,
         filters: EventsFilters,
@@ -50,36 +141,7 @@ impl EventsInterface for Events {
         options.limit = opts.limit;
         options.sort = Some(doc! {"$natural": if opts.reverse { -1 } else { 1 }});
 
-        let mut filter = Vec::new();
-        if let Some(cluster_id) = filters.cluster_id {
-            // Include events without a cluster ID to support cmobined system events.
-            filter.push(Bson::from(doc! {"$or": [
-                {"payload.cluster_id": {"$eq": cluster_id}},
-                {"payload.cluster_id": {"$exists": false}},
-            ]}));
-        }
-        if let Some(event) = filters.event {
-            filter.push(Bson::from(doc! {"event": {"$eq": event}}));
-        }
-        if filters.exclude_snapshots {
-            filter.push(Bson::from(doc! {
-                "event": EVENTS_FILTER_NOT_SNAPSHOT.clone()
-            }));
-        }
-        if filters.exclude_system_events {
-            filter.push(Bson::from(doc! {"payload.cluster_id": {"$exists": false}}));
-        }
-        if let Some(start_from) = filters.start_from {
-            filter.push(Bson::from(doc! {"timestamp": {"$gte": start_from}}));
-        }
-        if let Some(stop_at) = filters.stop_at {
-            filter.push(Bson::from(doc! {"timestamp": {"$lte": stop_at}}));
-        }
-        let filter = if !filter.is_empty() {
-            doc! {"$and": filter}
-        } else {
-            doc! {}
-        };
+        let filter = events_filter(filters);
         let collection = self.client.database(&self.db).collection(COLLECTION_EVENTS);
         let cursor = find_with_options(collection, filter, options, span, self.tracer.as_deref())
             .with_context(|_| ErrorKind::MongoDBOperation)?
@@ -87,4 +149,32 @@ impl EventsInterface for Events {
             .map(|result: Result<EventDocument>| result.map(Event::from));
         Ok(Cursor::new(cursor))
     }
+
+    fn valid_code_regex(&self, regex: String, span: Option<SpanContext>) -> Result<bool> {
+        // The filter can only match the (non-existent) event with a null ID so no events
+        // are scanned but MongoDB still parses the regular expression when planning the query.
+        let filter = doc! {
+            "_id": {"$eq": Bson::Null},
+            "event": {"$regex": regex},
+        };
+        let collection = self.client.database(&self.db).collection(COLLECTION_EVENTS);
+        let result = find_one::<Document>(collection, filter, span, self.tracer.as_deref());
+        let error = match result {
+            Ok(_) => return Ok(true),
+            Err(error) => error,
+        };
+        let rejected = error
+            .iter_chain()
+            .filter_map(|cause| cause.downcast_ref::<MongoError>())
+            .any(|error| match error.kind.as_ref() {
+                MongoErrorKind::CommandError(error) => {
+                    INVALID_REGEX_ERROR_CODES.contains(&error.code)
+                }
+                _ => false,
+            });
+        if rejected {
+            return Ok(false);
+        }
+        Err(error.context(ErrorKind::MongoDBOperation).into())
+    }
 }
@@ -0,0 +1,57 @@
+use bson::doc;
+use bson::DateTime as UtcDateTime;
+use bson::Document;
+use chrono::DateTime;
+use chrono::Utc;
+use serde_derive::Deserialize;
+
+use crate::store::IntervalCount;
+
+/// Aggregation pipeline counting documents by key in fixed time intervals.
+///
+/// Interval start times are computed by MongoDB so documents are never loaded by the store.
+pub fn count_pipeline(
+    filter: Document,
+    key: &str,
+    time: &str,
+    origin: DateTime<Utc>,
+    interval_ms: i64,
+) -> Vec<Document> {
+    let key = format!("${}", key);
+    let time = format!("${}", time);
+    let interval = doc! {"$subtract": [
+        time.as_str(),
+        {"$mod": [{"$subtract": [time.as_str(), origin]}, interval_ms]},
+    ]};
+    vec![
+        doc! {"$match": filter},
+        doc! {"$group": {
+            "_id": {"key": key, "interval": interval},
+            "count": {"$sum": 1},
+        }},
+    ]
+}
+
+/// Decode the result of a `count_pipeline` aggregation.
+#[derive(Clone, Debug, Deserialize)]
+pub struct IntervalCountDocument {
+    #[serde(rename = "_id")]
+    pub id: IntervalCountId,
+    pub count: i64,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct IntervalCountId {
+    pub interval: UtcDateTime,
+    pub key: String,
+}
+
+impl From<IntervalCountDocument> for IntervalCount {
+    fn from(document: IntervalCountDocument) -> IntervalCount {
+        IntervalCount {
+            count: document.count.max(0) as u64,
+            interval: document.id.interval.0,
+            key: document.id.key,
+        }
+    }
+}
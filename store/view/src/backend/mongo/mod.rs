@@ -20,6 +20,7 @@ use super::AdminInterface;
 use super::DataImpl;
 use super::EventsImpl;
 use super::PersistImpl;
+use super::ShardsImpl;
 use super::StoreInterface;
 use super::ValidateImpl;
 
@@ -28,7 +29,9 @@ mod constants;
 mod data;
 mod document;
 mod events;
+mod interval;
 mod persist;
+mod shards;
 mod validate;
 
 /// View store admin using MongoDB.
@@ -70,6 +73,7 @@ impl AdminInterface for Admin {
 /// # Special collection requirements
 ///
 ///   * `events`: is a capped or TTL indexed collection.
+///   * `shards_lag`: is a capped or TTL indexed collection.
 pub struct Store {
     client: Client,
     db: String,
@@ -120,4 +124,14 @@ impl StoreInterface for Store {
             self::persist::Persist::new(self.client.clone(), self.db.clone(), self.tracer.clone());
         PersistImpl::new(persist)
     }
+
+    fn shards(&self, cluster_id: String) -> ShardsImpl {
+        let shards = self::shards::Shards::new(
+            self.client.clone(),
+            self.db.clone(),
+            self.tracer.clone(),
+            cluster_id,
+        );
+        ShardsImpl::new(shards)
+    }
 }
@@ -12,15 +12,18 @@ use replicante_externals_mongodb::operations::insert_one;
 use replicante_externals_mongodb::operations::replace_one;
 use replicante_models_core::actions::Action;
 use replicante_models_core::actions::ActionHistory;
+use replicante_models_core::agent::ShardLagSample;
 use replicante_models_core::events::Event;
 
 use super::super::PersistInterface;
 use super::constants::COLLECTION_ACTIONS;
 use super::constants::COLLECTION_ACTIONS_HISTORY;
 use super::constants::COLLECTION_EVENTS;
+use super::constants::COLLECTION_SHARDS_LAG;
 use super::document::ActionDocument;
 use super::document::ActionHistoryDocument;
 use super::document::EventDocument;
+use super::document::ShardLagSampleDocument;
 use crate::Error;
 use crate::ErrorKind;
 use crate::Result;
@@ -97,4 +100,20 @@ impl PersistInterface for Persist {
             .with_context(|_| ErrorKind::MongoDBOperation)
             .map_err(Error::from)
     }
+
+    fn shard_lag_sample(&self, sample: ShardLagSample, span: Option<SpanContext>) -> Result<()> {
+        let collection = self
+            .client
+            .database(&self.db)
+            .collection(COLLECTION_SHARDS_LAG);
+        let sample = ShardLagSampleDocument::from(sample);
+        let document = bson::to_bson(&sample).with_context(|_| ErrorKind::MongoDBBsonEncode)?;
+        let document = match document {
+            Bson::Document(document) => document,
+            _ => panic!("ShardLagSample failed to encode as BSON document"),
+        };
+        insert_one(collection, document, span, self.tracer.as_deref())
+            .with_context(|_| ErrorKind::MongoDBOperation)
+            .map_err(Error::from)
+    }
 }
@@ -0,0 +1,80 @@
+use std::sync::Arc;
+
+use bson::doc;
+use bson::Bson;
+use chrono::DateTime;
+use chrono::Utc;
+use failure::Fail;
+use failure::ResultExt;
+use mongodb::options::FindOptions;
+use mongodb::sync::Client;
+use opentracingrust::SpanContext;
+use opentracingrust::Tracer;
+
+use replicante_externals_mongodb::operations::find_with_options;
+use replicante_models_core::agent::ShardLagSample;
+
+use super::super::ShardsInterface;
+use super::constants::COLLECTION_SHARDS_LAG;
+use super::constants::MAX_SHARD_LAG_SAMPLES;
+use super::document::ShardLagSampleDocument;
+use crate::Cursor;
+use crate::ErrorKind;
+use crate::Result;
+
+/// Shards operations implementation using MongoDB.
+pub struct Shards {
+    client: Client,
+    cluster_id: String,
+    db: String,
+    tracer: Option<Arc<Tracer>>,
+}
+
+impl Shards {
+    pub fn new<T>(client: Client, db: String, tracer: T, cluster_id: String) -> Shards
+    where
+        T: Into<Option<Arc<Tracer>>>,
+    {
+        let tracer = tracer.into();
+        Shards {
+            client,
+            cluster_id,
+            db,
+            tracer,
+        }
+    }
+}
+
+impl ShardsInterface for Shards {
+    fn lag(
+        &self,
+        shard_id: String,
+        from: DateTime<Utc>,
+        until: DateTime<Utc>,
+        span: Option<SpanContext>,
+    ) -> Result<Cursor<ShardLagSample>> {
+        // Fetch the most recent samples first so the limit drops the oldest ones.
+        let mut options = FindOptions::default();
+        options.limit = Some(MAX_SHARD_LAG_SAMPLES);
+        options.sort = Some(doc! {"timestamp": -1});
+        let filter = doc! {"$and": [
+            Bson::from(doc! {"cluster_id": {"$eq": &self.cluster_id}}),
+            Bson::from(doc! {"shard_id": {"$eq": shard_id}}),
+            Bson::from(doc! {"timestamp": {"$gte": from}}),
+            Bson::from(doc! {"timestamp": {"$lte": until}}),
+        ]};
+        let collection = self
+            .client
+            .database(&self.db)
+            .collection(COLLECTION_SHARDS_LAG);
+        let cursor = find_with_options(collection, filter, options, span, self.tracer.as_deref())
+            .with_context(|_| ErrorKind::MongoDBOperation)?
+            .map(|item| item.map_err(|error| error.context(ErrorKind::MongoDBCursor).into()))
+            .map(|item: Result<ShardLagSampleDocument>| item.map(ShardLagSample::from));
+
+        // Samples are returned oldest first so reverse the (bounded) result set.
+        let mut samples: Vec<Result<ShardLagSample>> = cursor.collect();
+        samples.reverse();
+        Ok(Cursor::new(samples.into_iter()))
+    }
+}
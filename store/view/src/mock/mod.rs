@@ -1,3 +1,8 @@
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use replicante_models_core::agent::ShardLagSample;
+
 use super::store::Store;
 
 mod store;
@@ -5,13 +10,21 @@ mod store;
 /// Manage a mocked store and admin interface.
 #[derive(Clone, Default)]
 pub struct Mock {
-    // TODO: implement state when needed.
+    pub state: Arc<Mutex<MockState>>,
 }
 
 impl Mock {
     /// Return a `Store` "view" into the mock.
     pub fn store(&self) -> Store {
-        let store = self::store::StoreMock {};
+        let store = self::store::StoreMock {
+            state: Arc::clone(&self.state),
+        };
         store.into()
     }
 }
+
+/// Internal mock state.
+#[derive(Default)]
+pub struct MockState {
+    pub shards_lag: Vec<ShardLagSample>,
+}
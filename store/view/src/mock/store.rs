@@ -1,3 +1,6 @@
+use std::sync::Arc;
+use std::sync::Mutex;
+
 use chrono::DateTime;
 use chrono::Utc;
 use opentracingrust::SpanContext;
@@ -5,23 +8,28 @@ use uuid::Uuid;
 
 use replicante_models_core::actions::Action;
 use replicante_models_core::actions::ActionHistory;
+use replicante_models_core::agent::ShardLagSample;
 use replicante_models_core::events::Event;
 
+use super::MockState;
 use crate::backend::ActionsImpl;
 use crate::backend::ActionsInterface;
 use crate::backend::EventsImpl;
 use crate::backend::PersistImpl;
 use crate::backend::PersistInterface;
+use crate::backend::ShardsImpl;
+use crate::backend::ShardsInterface;
 use crate::backend::StoreImpl;
 use crate::backend::StoreInterface;
 use crate::store::actions::SearchFilters as ActionsSearchFilters;
+use crate::store::IntervalCount;
 use crate::store::Store;
 use crate::Cursor;
 use crate::Result;
 
 /// Mock implementation of the `StoreInterface`.
 pub struct StoreMock {
-    // TODO: implement when needed.
+    pub state: Arc<Mutex<MockState>>,
 }
 
 impl StoreInterface for StoreMock {
@@ -35,9 +43,19 @@ impl StoreInterface for StoreMock {
     }
 
     fn persist(&self) -> PersistImpl {
-        let persist = Persist {};
+        let persist = Persist {
+            state: Arc::clone(&self.state),
+        };
         PersistImpl::new(persist)
     }
+
+    fn shards(&self, cluster_id: String) -> ShardsImpl {
+        let shards = Shards {
+            cluster_id,
+            state: Arc::clone(&self.state),
+        };
+        ShardsImpl::new(shards)
+    }
 }
 
 impl From<StoreMock> for Store {
@@ -56,6 +74,15 @@ impl ActionsInterface for Actions {
         Ok(None)
     }
 
+    fn count_by_state(
+        &self,
+        _: ActionsSearchFilters,
+        _: i64,
+        _: Option<SpanContext>,
+    ) -> Result<Vec<IntervalCount>> {
+        panic!("TODO: MockStore::actions::count_by_state")
+    }
+
     fn finish_history(&self, _: Uuid, _: DateTime<Utc>, _: Option<SpanContext>) -> Result<()> {
         Ok(())
     }
@@ -70,7 +97,7 @@ impl ActionsInterface for Actions {
 }
 
 struct Persist {
-    // TODO: implement when needed.
+    state: Arc<Mutex<MockState>>,
 }
 
 impl PersistInterface for Persist {
@@ -88,4 +115,37 @@ impl PersistInterface for Persist {
         // Noop for now.
         Ok(())
     }
+
+    fn shard_lag_sample(&self, sample: ShardLagSample, _: Option<SpanContext>) -> Result<()> {
+        let mut state = self.state.lock().expect("MockStore state lock is poisoned");
+        state.shards_lag.push(sample);
+        Ok(())
+    }
+}
+
+struct Shards {
+    cluster_id: String,
+    state: Arc<Mutex<MockState>>,
+}
+
+impl ShardsInterface for Shards {
+    fn lag(
+        &self,
+        shard_id: String,
+        from: DateTime<Utc>,
+        until: DateTime<Utc>,
+        _: Option<SpanContext>,
+    ) -> Result<Cursor<ShardLagSample>> {
+        let state = self.state.lock().expect("MockStore state lock is poisoned");
+        let mut samples: Vec<ShardLagSample> = state
+            .shards_lag
+            .iter()
+            .filter(|sample| sample.cluster_id == self.cluster_id && sample.shard_id == shard_id)
+            .filter(|sample| sample.timestamp >= from && sample.timestamp <= until)
+            .cloned()
+            .collect();
+        samples.sort_by_key(|sample| sample.timestamp);
+        let samples: Vec<Result<ShardLagSample>> = samples.into_iter().map(Ok).collect();
+        Ok(Cursor::new(samples.into_iter()))
+    }
 }
@@ -6,6 +6,7 @@ use uuid::Uuid;
 use replicante_models_core::actions::Action;
 use replicante_models_core::actions::ActionHistory;
 
+use super::IntervalCount;
 use crate::backend::ActionsImpl;
 use crate::Cursor;
 use crate::Result;
@@ -37,6 +38,22 @@ impl Actions {
         self.actions.action(action_id, span.into())
     }
 
+    /// Count actions matching the given filters by state and creation time.
+    ///
+    /// Actions are grouped in intervals of `interval_ms` milliseconds starting at `filters.from`.
+    pub fn count_by_state<S>(
+        &self,
+        filters: SearchFilters,
+        interval_ms: i64,
+        span: S,
+    ) -> Result<Vec<IntervalCount>>
+    where
+        S: Into<Option<SpanContext>>,
+    {
+        self.actions
+            .count_by_state(filters, interval_ms, span.into())
+    }
+
     /// Sets the `finished_ts` attribute on an entire action history to allow cleanup.
     pub fn finish_history<S>(
         &self,
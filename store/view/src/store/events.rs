@@ -3,20 +3,25 @@ use chrono::Utc;
 use opentracingrust::SpanContext;
 
 use replicante_models_core::events::Event;
+pub use replicante_models_core::events::EventCodeMatch;
 
+use super::IntervalCount;
 use crate::backend::EventsImpl;
 use crate::Cursor;
 use crate::Result;
 
+/// Tag matching events that do not relate to a cluster.
+pub const SYSTEM_EVENTS_TAG: &str = "System";
+
 /// Filters to apply when iterating over events.
 pub struct EventsFilters {
-    /// Only return cluster-related events if the cluster ID matches.
+    /// Only return cluster-related events if the cluster ID is one of these.
     ///
     /// Non-cluster events will still be returned.
-    pub cluster_id: Option<String>,
+    pub cluster_ids: Vec<String>,
 
-    /// Only return events with a matching event code.
-    pub event: Option<String>,
+    /// Only return events with an event code matching any of these.
+    pub events: Vec<EventCodeMatch>,
 
     /// Exclude snapshot events from the result (on by default).
     pub exclude_snapshots: bool,
@@ -29,18 +34,25 @@ pub struct EventsFilters {
 
     /// Scan events up to the given UTC date and time instead of up to the newest event.
     pub stop_at: Option<DateTime<Utc>>,
+
+    /// Only return events matching all of these tags.
+    ///
+    /// Tags match events by event code or cluster ID.
+    /// The `SYSTEM_EVENTS_TAG` tag matches events that do not relate to a cluster.
+    pub tags: Vec<String>,
 }
 
 impl EventsFilters {
     /// Return all events, don't skip any.
     pub fn all() -> EventsFilters {
         EventsFilters {
-            cluster_id: None,
-            event: None,
+            cluster_ids: Vec::new(),
+            events: Vec::new(),
             exclude_snapshots: false,
             exclude_system_events: false,
             start_from: None,
             stop_at: None,
+            tags: Vec::new(),
         }
     }
 
@@ -53,12 +65,13 @@ impl EventsFilters {
 impl Default for EventsFilters {
     fn default() -> EventsFilters {
         EventsFilters {
-            cluster_id: None,
-            event: None,
+            cluster_ids: Vec::new(),
+            events: Vec::new(),
             exclude_snapshots: true,
             exclude_system_events: false,
             start_from: None,
             stop_at: None,
+            tags: Vec::new(),
         }
     }
 }
@@ -91,6 +104,33 @@ impl Events {
         Events { events }
     }
 
+    /// Count historic events by event code and time.
+    ///
+    /// Events are grouped in intervals of `interval_ms` milliseconds starting at
+    /// `filters.start_from` (or the UNIX epoch if not set).
+    pub fn count_by_code<S>(
+        &self,
+        filters: EventsFilters,
+        interval_ms: i64,
+        span: S,
+    ) -> Result<Vec<IntervalCount>>
+    where
+        S: Into<Option<SpanContext>>,
+    {
+        self.events.count_by_code(filters, interval_ms, span.into())
+    }
+
+    /// Check if a regular expression for event codes is accepted by the store.
+    ///
+    /// Expressions are checked by the engine that runs them as part of `EventsFilters::events`
+    /// since the syntax supported by the store may differ from other regular expression engines.
+    pub fn valid_code_regex<S>(&self, regex: String, span: S) -> Result<bool>
+    where
+        S: Into<Option<SpanContext>>,
+    {
+        self.events.valid_code_regex(regex, span.into())
+    }
+
     /// Query historic events.
     pub fn range<S>(
         &self,
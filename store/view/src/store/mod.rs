@@ -1,5 +1,7 @@
 use std::sync::Arc;
 
+use chrono::DateTime;
+use chrono::Utc;
 use opentracingrust::Tracer;
 use slog::Logger;
 
@@ -13,10 +15,25 @@ use crate::Result;
 pub mod actions;
 pub mod events;
 pub mod persist;
+pub mod shards;
 
 use self::actions::Actions;
 use self::events::Events;
 use self::persist::Persist;
+use self::shards::Shards;
+
+/// Number of records with the same key in a time interval.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct IntervalCount {
+    /// Number of records in the interval.
+    pub count: u64,
+
+    /// Start of the interval the records fall in.
+    pub interval: DateTime<Utc>,
+
+    /// Value the records are grouped by.
+    pub key: String,
+}
 
 /// Interface to Replicante view store layer.
 ///
@@ -78,4 +95,10 @@ impl Store {
         let persist = self.store.persist();
         Persist::new(persist)
     }
+
+    /// Operate on shards.
+    pub fn shards(&self, cluster_id: String) -> Shards {
+        let shards = self.store.shards(cluster_id);
+        Shards::new(shards)
+    }
 }
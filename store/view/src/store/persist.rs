@@ -2,6 +2,7 @@ use opentracingrust::SpanContext;
 
 use replicante_models_core::actions::Action;
 use replicante_models_core::actions::ActionHistory;
+use replicante_models_core::agent::ShardLagSample;
 use replicante_models_core::events::Event;
 
 use crate::backend::PersistImpl;
@@ -40,4 +41,12 @@ impl Persist {
     {
         self.persist.event(event, span.into())
     }
+
+    /// Append a shard commit offset and lag sample.
+    pub fn shard_lag_sample<S>(&self, sample: ShardLagSample, span: S) -> Result<()>
+    where
+        S: Into<Option<SpanContext>>,
+    {
+        self.persist.shard_lag_sample(sample, span.into())
+    }
 }
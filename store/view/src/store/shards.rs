@@ -0,0 +1,78 @@
+use chrono::DateTime;
+use chrono::Utc;
+use opentracingrust::SpanContext;
+
+use replicante_models_core::agent::ShardLagSample;
+
+use crate::backend::ShardsImpl;
+use crate::Cursor;
+use crate::Result;
+
+/// Operate on shards.
+pub struct Shards {
+    shards: ShardsImpl,
+}
+
+impl Shards {
+    pub(crate) fn new(shards: ShardsImpl) -> Shards {
+        Shards { shards }
+    }
+
+    /// Iterate over commit offset and lag samples for a shard in the given time range.
+    ///
+    /// Samples for all nodes the shard is allocated on are returned, oldest first.
+    /// If the range holds more samples than the store returns at once, the most recent
+    /// samples are returned and the oldest ones are dropped.
+    pub fn lag<S>(
+        &self,
+        shard_id: String,
+        from: DateTime<Utc>,
+        until: DateTime<Utc>,
+        span: S,
+    ) -> Result<Cursor<ShardLagSample>>
+    where
+        S: Into<Option<SpanContext>>,
+    {
+        self.shards.lag(shard_id, from, until, span.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+    use chrono::Utc;
+
+    use replicante_models_core::agent::ShardLagSample;
+
+    use crate::mock::Mock;
+
+    fn sample(cluster_id: &str, minute: u32) -> ShardLagSample {
+        ShardLagSample {
+            cluster_id: cluster_id.into(),
+            commit_offset: None,
+            lag: None,
+            node_id: "n1".into(),
+            shard_id: "s1".into(),
+            timestamp: Utc.ymd(2020, 6, 1).and_hms(12, minute, 0),
+        }
+    }
+
+    #[test]
+    fn lag_samples_oldest_first() {
+        let mock = Mock::default();
+        let store = mock.store();
+        for sample in vec![sample("c1", 30), sample("c2", 15), sample("c1", 15)] {
+            store.persist().shard_lag_sample(sample, None).unwrap();
+        }
+
+        let from = Utc.ymd(2020, 6, 1).and_hms(12, 0, 0);
+        let until = Utc.ymd(2020, 6, 1).and_hms(13, 0, 0);
+        let samples: Vec<ShardLagSample> = store
+            .shards("c1".into())
+            .lag("s1".into(), from, until, None)
+            .unwrap()
+            .map(Result::unwrap)
+            .collect();
+        assert_eq!(samples, vec![sample("c1", 15), sample("c1", 30)]);
+    }
+}
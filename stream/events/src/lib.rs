@@ -2,12 +2,16 @@ use std::ops::Deref;
 use std::sync::Arc;
 
 use failure::Fail;
+use opentracingrust::AutoFinishingSpan;
 use opentracingrust::Tracer;
+use sentry::protocol::Breadcrumb;
+use sentry::protocol::Map;
 use slog::Logger;
 
 use replicante_models_core::deserialize_event;
 use replicante_models_core::events::DeserializeResult;
 use replicante_models_core::events::Event;
+use replicante_models_core::events::EventCode;
 use replicante_service_healthcheck::HealthChecks;
 use replicante_stream::EmitMessage as BaseEmitMessage;
 use replicante_stream::Error;
@@ -18,6 +22,9 @@ use replicante_stream::Result;
 use replicante_stream::Stream as BaseStream;
 use replicante_stream::StreamConfig;
 use replicante_stream::StreamOpts;
+use replicante_util_failure::capture_fail;
+use replicante_util_failure::failure_info;
+use replicante_util_tracing::fail_span;
 
 const STREAM_ID: &str = "events";
 
@@ -73,6 +80,88 @@ impl Stream {
         }
     }
 
+    /// Report a message that could not be decoded into an event.
+    ///
+    /// The error is logged, captured with a sentry breadcrumb for the given category
+    /// (for example `events.viewupdater`) and recorded on the span, if any.
+    pub fn report_event_error<C>(
+        logger: &Logger,
+        category: &str,
+        error: Error,
+        code: C,
+        message_id: &str,
+        mut span: Option<AutoFinishingSpan>,
+    ) where
+        C: Into<Option<EventCode>>,
+    {
+        let code = code.into();
+        sentry::with_scope(
+            |_| (),
+            || {
+                sentry::add_breadcrumb(Breadcrumb {
+                    category: Some(category.into()),
+                    message: Some("Unrecognised event".into()),
+                    data: {
+                        let mut map = Map::new();
+                        map.insert("message.id".into(), message_id.into());
+                        if let Some(code) = code.as_ref() {
+                            map.insert("event.category".into(), code.category.clone().into());
+                            map.insert("event.code".into(), code.event.clone().into());
+                        }
+                        map
+                    },
+                    ..Default::default()
+                });
+                let event_code = code
+                    .as_ref()
+                    .map(|code| code.event.as_str())
+                    .unwrap_or("<unknown>");
+                capture_fail!(
+                    &error,
+                    logger,
+                    "Unrecognised event";
+                    "event.code" => event_code,
+                    "message.id" => message_id,
+                    failure_info(&error),
+                );
+            },
+        );
+        if let Some(span) = span.as_deref_mut() {
+            span.tag("message.id", message_id);
+            if let Some(code) = code.as_ref() {
+                span.tag("event.category", code.category.as_str());
+                span.tag("event.code", code.event.as_str());
+            }
+            fail_span(error, span);
+        }
+    }
+
+    /// Start a span to process a message with, if a tracing context was set on the message.
+    pub fn span_for_message(
+        message: &Message,
+        tracer: &Tracer,
+        logger: &Logger,
+        operation: &str,
+    ) -> Option<AutoFinishingSpan> {
+        match message.trace(tracer) {
+            Ok(context) => context.map(|context| {
+                let mut span = tracer.span(operation);
+                span.follows(context);
+                span.auto_finish()
+            }),
+            Err(error) => {
+                let error = failure::SyncFailure::new(error);
+                capture_fail!(
+                    &error,
+                    logger,
+                    "Unable to extract tracing context from message";
+                    failure_info(&error),
+                );
+                None
+            }
+        }
+    }
+
     /// Return a `MockStream` version of the `Event`s stream for tests.
     #[cfg(feature = "with_test_support")]
     pub fn mock() -> Stream {
@@ -0,0 +1,194 @@
+use std::fs::File;
+use std::io::BufRead;
+use std::io::BufReader;
+use std::io::Seek;
+use std::io::SeekFrom;
+use std::marker::PhantomData;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use failure::ResultExt;
+use humthreads::ThreadScope;
+use serde::de::DeserializeOwned;
+
+use super::log::Record;
+use super::log::StreamLog;
+use crate::traits::MessageInterface;
+use crate::Error;
+use crate::ErrorKind;
+use crate::Message;
+use crate::Result;
+
+/// Iterator following the log for a group.
+pub struct EmbeddedIter<'a, T>
+where
+    T: DeserializeOwned + 'static,
+{
+    _enfoce_paylod_type: PhantomData<T>,
+    follow_id: String,
+    log: Arc<StreamLog>,
+    offset: u64,
+    poll_interval: Duration,
+    reader: BufReader<File>,
+    segment: u64,
+    stream_id: &'static str,
+    tail: bool,
+    thread: Option<&'a ThreadScope>,
+}
+
+impl<'a, T> EmbeddedIter<'a, T>
+where
+    T: DeserializeOwned,
+{
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        log: Arc<StreamLog>,
+        follow_id: String,
+        offset: u64,
+        poll_interval: Duration,
+        reader: BufReader<File>,
+        segment: u64,
+        stream_id: &'static str,
+        tail: bool,
+        thread: Option<&'a ThreadScope>,
+    ) -> EmbeddedIter<'a, T> {
+        EmbeddedIter {
+            _enfoce_paylod_type: PhantomData,
+            follow_id,
+            log,
+            offset,
+            poll_interval,
+            reader,
+            segment,
+            stream_id,
+            tail,
+            thread,
+        }
+    }
+}
+
+impl<'a, T> Drop for EmbeddedIter<'a, T>
+where
+    T: DeserializeOwned + 'static,
+{
+    fn drop(&mut self) {
+        self.log.release(&self.follow_id);
+    }
+}
+
+impl<'a, T> Iterator for EmbeddedIter<'a, T>
+where
+    T: DeserializeOwned,
+{
+    type Item = Result<Message<T>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut line = Vec::new();
+        while !self.thread.map(|t| t.should_shutdown()).unwrap_or(false) {
+            line.clear();
+            let read = match self.reader.read_until(b'\n', &mut line) {
+                Ok(read) => read,
+                Err(error) => {
+                    let error = Err(error)
+                        .with_context(|_| ErrorKind::FollowFailed)
+                        .map_err(Error::from);
+                    return Some(error);
+                }
+            };
+            if read > 0 && line.ends_with(b"\n") {
+                let offset = self.offset;
+                self.offset += read as u64;
+                return Some(EmbeddedMessage::decode(
+                    Arc::clone(&self.log),
+                    self.stream_id,
+                    self.follow_id.clone(),
+                    offset,
+                    self.offset,
+                    &line,
+                ));
+            }
+
+            // Reached the end of a segment that is complete: move on to the next one.
+            if read == 0 && self.offset != self.segment && self.log.segment_exists(self.offset) {
+                match self.log.reader(self.offset) {
+                    Ok((segment, reader)) => {
+                        self.reader = reader;
+                        self.segment = segment;
+                        continue;
+                    }
+                    Err(error) => return Some(Err(error)),
+                }
+            }
+
+            // Reached the end of the log, possibly in the middle of a record being written.
+            // Rewind to the start of the incomplete record so it is read in full later.
+            if read > 0 {
+                let position = self.offset - self.segment;
+                if let Err(error) = self.reader.seek(SeekFrom::Start(position)) {
+                    let error = Err(error)
+                        .with_context(|_| ErrorKind::FollowFailed)
+                        .map_err(Error::from);
+                    return Some(error);
+                }
+            }
+            if !self.tail {
+                return None;
+            }
+            thread::sleep(self.poll_interval);
+        }
+        None
+    }
+}
+
+struct EmbeddedMessage {
+    follow_id: String,
+    log: Arc<StreamLog>,
+    log_id: String,
+    next_offset: u64,
+}
+
+impl EmbeddedMessage {
+    fn decode<T>(
+        log: Arc<StreamLog>,
+        stream_id: &'static str,
+        follow_id: String,
+        offset: u64,
+        next_offset: u64,
+        line: &[u8],
+    ) -> Result<Message<T>>
+    where
+        T: DeserializeOwned + 'static,
+    {
+        let log_id = format!("stream={};offset={}", stream_id, offset);
+        let record: Record =
+            serde_json::from_slice(line).with_context(|_| ErrorKind::PayloadDecode)?;
+        let inner = Rc::new(EmbeddedMessage {
+            follow_id: follow_id.clone(),
+            log,
+            log_id,
+            next_offset,
+        });
+        let payload = record.payload.into_bytes();
+        Ok(Message::with_backend(
+            stream_id,
+            follow_id,
+            record.headers,
+            payload,
+            inner,
+        ))
+    }
+}
+
+impl MessageInterface for EmbeddedMessage {
+    fn async_ack(&self) -> Result<()> {
+        // Like kafka, store the offset of the NEXT message to FETCH,
+        // not the offset of the last message processed.
+        self.log.commit(&self.follow_id, self.next_offset)
+    }
+
+    fn id(&self) -> &str {
+        &self.log_id
+    }
+}
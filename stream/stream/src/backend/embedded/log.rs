@@ -0,0 +1,346 @@
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::fs;
+use std::fs::File;
+use std::fs::OpenOptions;
+use std::io;
+use std::io::BufReader;
+use std::io::Read;
+use std::io::Seek;
+use std::io::SeekFrom;
+use std::io::Write;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use failure::ResultExt;
+use serde_derive::Deserialize;
+use serde_derive::Serialize;
+
+use crate::metrics::EMBEDDED_LOG_SIZE;
+use crate::ErrorKind;
+use crate::Result;
+
+const SEGMENT_EXTENSION: &str = "log";
+
+/// Message as stored in the log file, one JSON document per line.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Record {
+    pub headers: HashMap<String, String>,
+    pub id: String,
+    pub payload: String,
+}
+
+/// Segmented append-only log of messages with followers offsets stored alongside it.
+///
+/// Messages are stored in segment files under `<path>/<stream_id>/segments/`, each named after
+/// the offset of its first message, while the offset of the next message to deliver to each
+/// group is stored in its own file under `<path>/<stream_id>/offsets/`.
+///
+/// Offsets are byte positions in the log so followers can resume without scanning it.
+///
+/// Once the last segment grows past the configured size, messages are appended to a new one.
+/// When this happens, segments with only messages before the lowest committed offset are deleted.
+/// Groups that stop following the stream for good prevent segments from being deleted
+/// until their offset file is removed.
+pub struct StreamLog {
+    groups: Mutex<HashSet<String>>,
+    offsets_path: PathBuf,
+    segment_size: u64,
+    segments_path: PathBuf,
+    stream_id: &'static str,
+    writer: Mutex<Option<Segment>>,
+}
+
+impl StreamLog {
+    pub fn open(path: &Path, stream_id: &'static str, segment_size: u64) -> Result<StreamLog> {
+        let root = path.join(stream_id);
+        let offsets_path = root.join("offsets");
+        let segments_path = root.join("segments");
+        fs::create_dir_all(&offsets_path).with_context(|_| ErrorKind::BackendClientCreation)?;
+        fs::create_dir_all(&segments_path).with_context(|_| ErrorKind::BackendClientCreation)?;
+        let log = StreamLog {
+            groups: Mutex::new(HashSet::new()),
+            offsets_path,
+            segment_size,
+            segments_path,
+            stream_id,
+            writer: Mutex::new(None),
+        };
+        let segments = log
+            .segments()
+            .with_context(|_| ErrorKind::BackendClientCreation)?;
+        if segments.is_empty() {
+            OpenOptions::new()
+                .append(true)
+                .create(true)
+                .open(log.segment_path(0))
+                .with_context(|_| ErrorKind::BackendClientCreation)?;
+        }
+        log.observe_size()
+            .with_context(|_| ErrorKind::BackendClientCreation)?;
+        Ok(log)
+    }
+
+    /// Append a record to the log and wait for it to be written to disk.
+    pub fn append(&self, record: &Record) -> Result<()> {
+        let mut line = serde_json::to_vec(record).with_context(|_| ErrorKind::EmitFailed)?;
+        line.push(b'\n');
+        let mut writer = self.writer.lock().expect("StreamLog writer lock poisoned");
+        if writer.is_none() {
+            // The writer is opened lazily so processes that only follow the stream
+            // never modify the log, even to clean up after a crash.
+            let segment = self.open_last().with_context(|_| ErrorKind::EmitFailed)?;
+            *writer = Some(segment);
+            self.observe_size()
+                .with_context(|_| ErrorKind::EmitFailed)?;
+        }
+        let segment = writer.as_mut().expect("StreamLog writer must be open");
+        if segment.len > 0 && segment.len >= self.segment_size {
+            *segment = self
+                .rotate(segment)
+                .with_context(|_| ErrorKind::EmitFailed)?;
+        }
+        segment
+            .file
+            .write_all(&line)
+            .with_context(|_| ErrorKind::EmitFailed)?;
+        segment
+            .file
+            .sync_data()
+            .with_context(|_| ErrorKind::EmitFailed)?;
+        segment.len += line.len() as u64;
+        EMBEDDED_LOG_SIZE
+            .with_label_values(&[self.stream_id])
+            .add(line.len() as f64);
+        Ok(())
+    }
+
+    /// Persist the offset of the next message to deliver to the group.
+    pub fn commit(&self, group: &str, offset: u64) -> Result<()> {
+        let name = encode_group(group);
+        let path = self.offsets_path.join(&name);
+        let temp = self.offsets_path.join(format!("{}.tmp", name));
+        fs::write(&temp, offset.to_string()).with_context(|_| ErrorKind::AckFailed)?;
+        fs::rename(&temp, &path).with_context(|_| ErrorKind::AckFailed)?;
+        Ok(())
+    }
+
+    /// Return the offset of the next message to deliver to the group.
+    ///
+    /// Groups that never acknowledged a message start from the oldest message in the log.
+    pub fn committed(&self, group: &str) -> Result<u64> {
+        let oldest = self
+            .segments()
+            .with_context(|_| ErrorKind::FollowFailed)?
+            .first()
+            .copied()
+            .unwrap_or(0);
+        let path = self.offsets_path.join(encode_group(group));
+        let offset = read_offset(&path).with_context(|_| ErrorKind::FollowFailed)?;
+        Ok(offset.unwrap_or(oldest).max(oldest))
+    }
+
+    /// Open a reader positioned at the given offset in the log.
+    ///
+    /// Returns the offset of the first message in the segment being read along with the reader.
+    pub fn reader(&self, offset: u64) -> Result<(u64, BufReader<File>)> {
+        let segments = self.segments().with_context(|_| ErrorKind::FollowFailed)?;
+        let base = segments
+            .into_iter()
+            .rev()
+            .find(|base| *base <= offset)
+            .unwrap_or(0);
+        let mut file =
+            File::open(self.segment_path(base)).with_context(|_| ErrorKind::FollowFailed)?;
+        file.seek(SeekFrom::Start(offset - base))
+            .with_context(|_| ErrorKind::FollowFailed)?;
+        Ok((base, BufReader::new(file)))
+    }
+
+    /// Mark a group as no longer following the stream in this process.
+    pub fn release(&self, group: &str) {
+        self.groups
+            .lock()
+            .expect("StreamLog groups lock poisoned")
+            .remove(group);
+    }
+
+    /// Mark a group as following the stream in this process.
+    ///
+    /// The log has a single partition so only one follower per group can make progress.
+    pub fn reserve(&self, group: &str) -> Result<()> {
+        let mut groups = self.groups.lock().expect("StreamLog groups lock poisoned");
+        if !groups.insert(group.to_string()) {
+            return Err(ErrorKind::GroupAlreadyFollowed(group.to_string()).into());
+        }
+        Ok(())
+    }
+
+    /// Check if a segment starts at the given offset.
+    ///
+    /// Segments are only created once the previous segment is complete so followers
+    /// that reach the end of a segment can move on to the next one when it exists.
+    pub fn segment_exists(&self, base: u64) -> bool {
+        self.segment_path(base).exists()
+    }
+
+    /// Delete segments with only messages all groups have acknowledged.
+    ///
+    /// The last segment is never deleted, nor are any segments until a group commits an offset.
+    fn compact(&self) -> io::Result<()> {
+        let mut lowest = None;
+        for entry in fs::read_dir(&self.offsets_path)? {
+            let path = entry?.path();
+            if path.extension().is_some() {
+                continue;
+            }
+            if let Some(offset) = read_offset(&path)? {
+                lowest = Some(lowest.map_or(offset, |lowest: u64| lowest.min(offset)));
+            }
+        }
+        let lowest = match lowest {
+            None => return Ok(()),
+            Some(lowest) => lowest,
+        };
+        let segments = self.segments()?;
+        for pair in segments.windows(2) {
+            let (base, next) = (pair[0], pair[1]);
+            if next > lowest {
+                break;
+            }
+            fs::remove_file(self.segment_path(base))?;
+        }
+        Ok(())
+    }
+
+    /// Update the log size metric from the segments on disk.
+    fn observe_size(&self) -> io::Result<()> {
+        let mut size = 0;
+        for base in self.segments()? {
+            size += fs::metadata(self.segment_path(base))?.len();
+        }
+        EMBEDDED_LOG_SIZE
+            .with_label_values(&[self.stream_id])
+            .set(size as f64);
+        Ok(())
+    }
+
+    /// Open the last segment for writing, removing any partial record left by a crash.
+    fn open_last(&self) -> io::Result<Segment> {
+        let base = self.segments()?.last().copied().unwrap_or(0);
+        let mut file = OpenOptions::new()
+            .append(true)
+            .create(true)
+            .read(true)
+            .open(self.segment_path(base))?;
+        truncate_partial_record(&mut file)?;
+        let len = file.metadata()?.len();
+        Ok(Segment { base, file, len })
+    }
+
+    /// Start a new segment after the given one and delete acknowledged segments.
+    fn rotate(&self, segment: &Segment) -> io::Result<Segment> {
+        let base = segment.base + segment.len;
+        let file = OpenOptions::new()
+            .append(true)
+            .create(true)
+            .read(true)
+            .open(self.segment_path(base))?;
+        self.compact()?;
+        self.observe_size()?;
+        Ok(Segment { base, file, len: 0 })
+    }
+
+    /// Return the offset of the first message in each segment, in order.
+    fn segments(&self) -> io::Result<Vec<u64>> {
+        let mut segments = Vec::new();
+        for entry in fs::read_dir(&self.segments_path)? {
+            let path = entry?.path();
+            if path.extension().and_then(|ext| ext.to_str()) != Some(SEGMENT_EXTENSION) {
+                continue;
+            }
+            let base = path
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .and_then(|stem| stem.parse::<u64>().ok());
+            if let Some(base) = base {
+                segments.push(base);
+            }
+        }
+        segments.sort_unstable();
+        Ok(segments)
+    }
+
+    fn segment_path(&self, base: u64) -> PathBuf {
+        self.segments_path
+            .join(format!("{:020}.{}", base, SEGMENT_EXTENSION))
+    }
+}
+
+/// Segment of the log messages are appended to.
+struct Segment {
+    base: u64,
+    file: File,
+    len: u64,
+}
+
+/// Encode a group name into a portable file name.
+fn encode_group(group: &str) -> String {
+    let mut name = String::with_capacity(group.len());
+    for byte in group.bytes() {
+        match byte {
+            b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9' | b'-' | b'_' => name.push(byte as char),
+            byte => name.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    name
+}
+
+/// Read the offset stored in a group offset file, if the file exists.
+fn read_offset(path: &Path) -> io::Result<Option<u64>> {
+    let offset = match fs::read_to_string(path) {
+        Ok(offset) => offset,
+        Err(error) if error.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(error) => return Err(error),
+    };
+    offset
+        .trim()
+        .parse()
+        .map(Some)
+        .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))
+}
+
+/// Truncate a partially written record left behind by a crash while appending.
+fn truncate_partial_record(file: &mut File) -> io::Result<()> {
+    let len = file.metadata()?.len();
+    let mut buffer = [0u8; 4096];
+    let mut end = len;
+    while end > 0 {
+        let start = end.saturating_sub(buffer.len() as u64);
+        let chunk = &mut buffer[..(end - start) as usize];
+        file.seek(SeekFrom::Start(start))?;
+        file.read_exact(chunk)?;
+        if let Some(index) = chunk.iter().rposition(|byte| *byte == b'\n') {
+            let valid = start + index as u64 + 1;
+            if valid < len {
+                file.set_len(valid)?;
+            }
+            return Ok(());
+        }
+        end = start;
+    }
+    file.set_len(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::encode_group;
+
+    #[test]
+    fn encode_group_escapes_special_chars() {
+        assert_eq!(encode_group("events:webhooks"), "events%3Awebhooks");
+        assert_eq!(encode_group("a.b/c"), "a%2Eb%2Fc");
+        assert_eq!(encode_group("view_updater-1"), "view_updater-1");
+    }
+}
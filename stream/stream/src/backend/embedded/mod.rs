@@ -0,0 +1,310 @@
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+use failure::ResultExt;
+use humthreads::ThreadScope;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::config::EmbeddedConfig;
+use crate::iter::Backoff;
+use crate::traits::StreamInterface;
+use crate::EmitMessage;
+use crate::ErrorKind;
+use crate::Iter;
+use crate::Result;
+use crate::StreamOpts;
+
+mod iter;
+mod log;
+
+use self::iter::EmbeddedIter;
+use self::log::Record;
+use self::log::StreamLog;
+
+/// Generic stream backed by an append-only log file.
+///
+/// The log has a single partition so messages are delivered in the order they are emitted
+/// and only one follower per group is allowed in the process.
+/// Only one process should emit messages to the same log at any time.
+pub struct EmbeddedStream {
+    log: Arc<StreamLog>,
+    poll_interval: Duration,
+    stream_id: &'static str,
+}
+
+impl EmbeddedStream {
+    pub fn new(config: EmbeddedConfig, opts: StreamOpts) -> Result<EmbeddedStream> {
+        let stream_id = opts.stream_id;
+        let log = StreamLog::open(Path::new(&config.path), stream_id, config.segment_size)?;
+        let log = Arc::new(log);
+        let poll_interval = Duration::from_millis(config.poll_interval);
+        Ok(EmbeddedStream {
+            log,
+            poll_interval,
+            stream_id,
+        })
+    }
+}
+
+impl<T> StreamInterface<T> for EmbeddedStream
+where
+    T: DeserializeOwned + Serialize + 'static,
+{
+    fn emit(&self, message: EmitMessage<T>) -> Result<()> {
+        let payload = String::from_utf8(message.payload).with_context(|_| ErrorKind::EmitFailed)?;
+        let record = Record {
+            headers: message.headers,
+            id: message.id,
+            payload,
+        };
+        self.log.append(&record)
+    }
+
+    fn follow<'a>(
+        &self,
+        group: String,
+        thread: Option<&'a ThreadScope>,
+        tail: bool,
+    ) -> Result<Iter<'a, T>> {
+        let offset = self.log.committed(&group)?;
+        let (segment, reader) = self.log.reader(offset)?;
+        self.log.reserve(&group)?;
+        let iter = Box::new(EmbeddedIter::new(
+            Arc::clone(&self.log),
+            group.clone(),
+            offset,
+            self.poll_interval,
+            reader,
+            segment,
+            self.stream_id,
+            tail,
+            thread,
+        ));
+        Ok(Iter::with_iter(
+            self.stream_id,
+            group,
+            Backoff::new(),
+            thread,
+            iter,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::fs::OpenOptions;
+    use std::io::Write;
+    use std::path::PathBuf;
+    use std::sync::Arc;
+
+    use humthreads::test_support::MockThreadScope;
+    use tempfile::TempDir;
+
+    use replicante_service_healthcheck::HealthChecks;
+
+    use super::EmbeddedStream;
+    use crate::config::EmbeddedConfig;
+    use crate::EmitMessage;
+    use crate::ErrorKind;
+    use crate::Stream;
+    use crate::StreamOpts;
+
+    const STREAM_ID: &str = "stream_tests";
+
+    fn stream(dir: &TempDir) -> Stream<String> {
+        segmented_stream(dir, 1024 * 1024)
+    }
+
+    fn segmented_stream(dir: &TempDir, segment_size: u64) -> Stream<String> {
+        let config = EmbeddedConfig {
+            path: dir.path().to_string_lossy().into_owned(),
+            poll_interval: 10,
+            segment_size,
+        };
+        let logger = slog::Logger::root(slog::Discard, slog::o!());
+        let mut healthchecks = HealthChecks::new();
+        let opts = StreamOpts::new(STREAM_ID, &mut healthchecks, logger.clone(), None);
+        let backend = EmbeddedStream::new(config, opts).unwrap();
+        Stream::with_backend(STREAM_ID, Arc::new(backend), logger, None)
+    }
+
+    fn emit(stream: &Stream<String>, id: &str, value: &str) {
+        stream
+            .emit(EmitMessage::with(id, value.into()).unwrap())
+            .unwrap();
+    }
+
+    fn segments(dir: &TempDir) -> Vec<PathBuf> {
+        let path = dir.path().join(STREAM_ID).join("segments");
+        let mut segments: Vec<PathBuf> = fs::read_dir(path)
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .collect();
+        segments.sort();
+        segments
+    }
+
+    fn ack(stream: &Stream<String>, group: &str, count: usize) {
+        let mut iter = stream.short_follow(group, None).unwrap();
+        for _ in 0..count {
+            iter.next().unwrap().unwrap().async_ack().unwrap();
+        }
+    }
+
+    fn first_payload(stream: &Stream<String>, group: &str) -> String {
+        let mut iter = stream.short_follow(group, None).unwrap();
+        iter.next().unwrap().unwrap().payload().unwrap()
+    }
+
+    #[test]
+    fn acked_segments_are_deleted_on_rotation() {
+        let dir = tempfile::tempdir().unwrap();
+        let stream = segmented_stream(&dir, 1);
+        emit(&stream, "key", "value");
+        emit(&stream, "a", "b");
+        emit(&stream, "c", "d");
+        assert_eq!(segments(&dir).len(), 3);
+
+        // Segments are deleted once all groups acknowledged their messages.
+        ack(&stream, "fast", 2);
+        ack(&stream, "slow", 1);
+        emit(&stream, "e", "f");
+        assert_eq!(segments(&dir).len(), 3);
+        assert_eq!(first_payload(&stream, "new"), "b");
+
+        ack(&stream, "slow", 1);
+        emit(&stream, "g", "h");
+        assert_eq!(segments(&dir).len(), 3);
+        assert_eq!(first_payload(&stream, "new"), "d");
+    }
+
+    #[test]
+    fn ack_persists_offset() {
+        let dir = tempfile::tempdir().unwrap();
+        let stream = stream(&dir);
+        emit(&stream, "key", "value");
+        emit(&stream, "a", "b");
+        {
+            let mut iter = stream.short_follow("test", None).unwrap();
+            let message = iter.next().unwrap().unwrap();
+            assert_eq!(message.payload().unwrap(), "value");
+            message.async_ack().unwrap();
+        }
+
+        // Use a new stream instance to simulate a process restart.
+        let stream = self::stream(&dir);
+        let mut iter = stream.short_follow("test", None).unwrap();
+        let message = iter.next().unwrap().unwrap();
+        assert_eq!(message.payload().unwrap(), "b");
+        message.async_ack().unwrap();
+        assert!(iter.next().is_none(), "received unexpected message");
+    }
+
+    #[test]
+    fn follow_receives_new_messages() {
+        let dir = tempfile::tempdir().unwrap();
+        let stream = stream(&dir);
+        let scope = MockThreadScope::new().scope();
+        let mut iter = stream.follow("test", &scope).unwrap();
+        emit(&stream, "key", "value");
+        let message = iter.next().unwrap().unwrap();
+        assert_eq!(message.payload().unwrap(), "value");
+        assert_eq!(message.id(), "stream=stream_tests;offset=0");
+        message.async_ack().unwrap();
+    }
+
+    #[test]
+    fn follow_across_segments() {
+        let dir = tempfile::tempdir().unwrap();
+        let stream = segmented_stream(&dir, 1);
+        emit(&stream, "key", "value");
+        emit(&stream, "a", "b");
+        emit(&stream, "c", "d");
+        let mut iter = stream.short_follow("test", None).unwrap();
+        for expected in &["value", "b", "d"] {
+            let message = iter.next().unwrap().unwrap();
+            assert_eq!(message.payload().unwrap(), *expected);
+            message.async_ack().unwrap();
+        }
+        assert!(iter.next().is_none(), "received unexpected message");
+        drop(iter);
+
+        // Resume from a committed offset at the start of a segment.
+        let stream = self::segmented_stream(&dir, 1);
+        emit(&stream, "e", "f");
+        let mut iter = stream.short_follow("test", None).unwrap();
+        let message = iter.next().unwrap().unwrap();
+        assert_eq!(message.payload().unwrap(), "f");
+    }
+
+    #[test]
+    fn group_can_follow_once() {
+        let dir = tempfile::tempdir().unwrap();
+        let stream = stream(&dir);
+        let iter = stream.short_follow("test", None).unwrap();
+        match stream.short_follow("test", None) {
+            Err(error) => match error.kind() {
+                ErrorKind::GroupAlreadyFollowed(group) => assert_eq!(group, "test"),
+                _ => panic!("unexpected error: {:?}", error),
+            },
+            Ok(_) => panic!("group followed twice"),
+        };
+        drop(iter);
+        assert!(stream.short_follow("test", None).is_ok());
+    }
+
+    #[test]
+    fn groups_follow_independently() {
+        let dir = tempfile::tempdir().unwrap();
+        let stream = stream(&dir);
+        emit(&stream, "key", "value");
+        let mut iter = stream.short_follow("group1", None).unwrap();
+        iter.next().unwrap().unwrap().async_ack().unwrap();
+        assert!(iter.next().is_none(), "received unexpected message");
+        let mut iter = stream.short_follow("group2", None).unwrap();
+        let message = iter.next().unwrap().unwrap();
+        assert_eq!(message.payload().unwrap(), "value");
+    }
+
+    #[test]
+    fn partial_record_is_truncated() {
+        let dir = tempfile::tempdir().unwrap();
+        let stream = stream(&dir);
+        emit(&stream, "key", "value");
+        let log = segments(&dir).pop().unwrap();
+        let mut file = OpenOptions::new().append(true).open(&log).unwrap();
+        file.write_all(b"{\"headers\":{},\"id\":\"x\"").unwrap();
+
+        // Logs are repaired when a new stream instance first emits a message.
+        let stream = self::stream(&dir);
+        emit(&stream, "a", "b");
+        let mut iter = stream.short_follow("test", None).unwrap();
+        let message = iter.next().unwrap().unwrap();
+        assert_eq!(message.payload().unwrap(), "value");
+        message.async_ack().unwrap();
+        let message = iter.next().unwrap().unwrap();
+        assert_eq!(message.payload().unwrap(), "b");
+        message.async_ack().unwrap();
+        assert!(iter.next().is_none(), "received unexpected message");
+    }
+
+    #[test]
+    fn unacked_message_is_redelivered_after_restart() {
+        let dir = tempfile::tempdir().unwrap();
+        let stream = stream(&dir);
+        emit(&stream, "key", "value");
+        emit(&stream, "a", "b");
+        {
+            let mut iter = stream.short_follow("test", None).unwrap();
+            let message = iter.next().unwrap().unwrap();
+            message.retry();
+        }
+        let stream = self::stream(&dir);
+        let mut iter = stream.short_follow("test", None).unwrap();
+        let message = iter.next().unwrap().unwrap();
+        assert_eq!(message.payload().unwrap(), "value");
+    }
+}
@@ -3,13 +3,23 @@ use std::sync::Arc;
 use serde::de::DeserializeOwned;
 use serde::Serialize;
 
+use crate::config::EmbeddedConfig;
 use crate::config::KafkaConfig;
 use crate::traits::StreamInterface;
 use crate::Result;
 use crate::StreamOpts;
 
+mod embedded;
 mod kafka;
 
+pub fn embedded<T>(config: EmbeddedConfig, opts: StreamOpts) -> Result<Arc<dyn StreamInterface<T>>>
+where
+    T: DeserializeOwned + Serialize + 'static,
+{
+    let stream = self::embedded::EmbeddedStream::new(config, opts)?;
+    Ok(Arc::new(stream))
+}
+
 pub fn kafka<T>(config: KafkaConfig, opts: StreamOpts) -> Result<Arc<dyn StreamInterface<T>>>
 where
     T: DeserializeOwned + Serialize + 'static,
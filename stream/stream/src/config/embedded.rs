@@ -0,0 +1,29 @@
+use serde_derive::Deserialize;
+use serde_derive::Serialize;
+
+/// Embedded, file-backed, configuration options for a stream.
+#[derive(Clone, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
+pub struct EmbeddedConfig {
+    /// Directory to store stream messages and followers offsets in.
+    pub path: String,
+
+    /// Interval (in milliseconds) between checks for new messages at the end of the stream.
+    #[serde(default = "EmbeddedConfig::default_poll_interval")]
+    pub poll_interval: u64,
+
+    /// Size (in bytes) after which messages are appended to a new log segment.
+    ///
+    /// Segments with only messages acknowledged by all groups are deleted on rotation.
+    #[serde(default = "EmbeddedConfig::default_segment_size")]
+    pub segment_size: u64,
+}
+
+impl EmbeddedConfig {
+    fn default_poll_interval() -> u64 {
+        500
+    }
+
+    fn default_segment_size() -> u64 {
+        64 * 1024 * 1024
+    }
+}
@@ -1,14 +1,23 @@
 use serde_derive::Deserialize;
 use serde_derive::Serialize;
 
+mod embedded;
 mod kafka;
 
+pub use self::embedded::EmbeddedConfig;
 pub use self::kafka::KafkaConfig;
 
 /// Stream configuration options.
 #[derive(Clone, Eq, PartialEq, Hash, Debug, Serialize, Deserialize)]
 #[serde(tag = "backend", content = "options")]
 pub enum StreamConfig {
+    /// Use an embedded, file-backed, log as the stream platform.
+    ///
+    /// Intended for single node deployments and tests: the stream is not replicated
+    /// and only one Replicante Core process can emit messages to it.
+    #[serde(rename = "embedded")]
+    Embedded(EmbeddedConfig),
+
     /// Use kafka as the stream platform (recommended).
     #[serde(rename = "kafka")]
     Kafka(KafkaConfig),
@@ -61,6 +61,9 @@ pub enum ErrorKind {
     #[fail(display = "unable to follow stream")]
     FollowFailed,
 
+    #[fail(display = "group '{}' is already following the stream", _0)]
+    GroupAlreadyFollowed(String),
+
     #[fail(display = "unable to decode value for header '{}'", _0)]
     MessageInvalidHeader(String),
 
@@ -81,6 +84,7 @@ impl ErrorKind {
             ErrorKind::BackendClientCreation => "BackendClientCreation",
             ErrorKind::EmitFailed => "EmitFailed",
             ErrorKind::FollowFailed => "FollowFailed",
+            ErrorKind::GroupAlreadyFollowed(_) => "GroupAlreadyFollowed",
             ErrorKind::MessageInvalidHeader(_) => "MessageInvalidHeader",
             ErrorKind::MessageNoPayload => "MessageNoPayload",
             ErrorKind::PayloadEncode => "PayloadEncode",
@@ -1,5 +1,6 @@
 use lazy_static::lazy_static;
 use prometheus::CounterVec;
+use prometheus::GaugeVec;
 use prometheus::HistogramOpts;
 use prometheus::HistogramVec;
 use prometheus::Opts;
@@ -81,6 +82,14 @@ lazy_static! {
         &["stream", "group"]
     )
     .expect("Failed to create DELIVERED_TOTAL");
+    pub static ref EMBEDDED_LOG_SIZE: GaugeVec = GaugeVec::new(
+        Opts::new(
+            "replicore_stream_embedded_log_size",
+            "Size, in bytes, of the embedded stream log segments on disk",
+        ),
+        &["stream"]
+    )
+    .expect("Failed to create EMBEDDED_LOG_SIZE");
     pub static ref EMIT_ERROR: CounterVec = CounterVec::new(
         Opts::new(
             "replicore_stream_emit_error",
@@ -127,6 +136,9 @@ pub fn register_metrics(logger: &Logger, registry: &Registry) {
     if let Err(error) = registry.register(Box::new(DELIVERED_TOTAL.clone())) {
         debug!(logger, "Failed to register DELIVERED_TOTAL"; "error" => ?error);
     }
+    if let Err(error) = registry.register(Box::new(EMBEDDED_LOG_SIZE.clone())) {
+        debug!(logger, "Failed to register EMBEDDED_LOG_SIZE"; "error" => ?error);
+    }
     if let Err(error) = registry.register(Box::new(EMIT_ERROR.clone())) {
         debug!(logger, "Failed to register EMIT_ERROR"; "error" => ?error);
     }
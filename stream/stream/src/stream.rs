@@ -49,6 +49,7 @@ where
         let stream_id = opts.stream_id;
         let tracer = opts.tracer.clone();
         let backend = match config {
+            StreamConfig::Embedded(config) => backend::embedded(config, opts)?,
             StreamConfig::Kafka(config) => backend::kafka(config, opts)?,
         };
         Ok(Stream::with_backend(stream_id, backend, logger, tracer))